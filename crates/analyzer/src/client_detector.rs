@@ -102,13 +102,13 @@ fn find_client_in_source(source: &str, pkg_name: &str) -> Option<String> {
 /// Extract client type from AST item
 fn extract_client_from_item(item: &Item, pkg_name: &str) -> Option<String> {
     match item {
-        Item::Struct(ItemStruct { ident, vis, .. }) => {
-            // Check for pub struct Client
-            if ident == "Client" && matches!(vis, syn::Visibility::Public(_)) {
-                // Convert crate name to module path (aws-sdk-s3 → aws_sdk_s3)
-                let module = pkg_name.replace('-', "_");
-                return Some(format!("{module}::Client"));
-            }
+        // Check for pub struct Client
+        Item::Struct(ItemStruct { ident, vis, .. })
+            if ident == "Client" && matches!(vis, syn::Visibility::Public(_)) =>
+        {
+            // Convert crate name to module path (aws-sdk-s3 → aws_sdk_s3)
+            let module = pkg_name.replace('-', "_");
+            return Some(format!("{module}::Client"));
         },
         Item::Mod(ItemMod {
             content: Some((_, items)),
@@ -6,10 +6,14 @@ use anyhow::{Context, Result};
 use clap::{Parser, Subcommand, ValueEnum};
 use colored::*;
 use hemmer_provider_generator_analyzer::SdkAnalyzer;
-use hemmer_provider_generator_common::sanitize_rust_identifier;
+use hemmer_provider_generator_common::overlay::Overlay;
+use hemmer_provider_generator_common::{sanitize_rust_identifier, Provider, ServiceDefinition};
 use hemmer_provider_generator_generator::{ProviderGenerator, UnifiedProviderGenerator};
+use hemmer_provider_generator_parser::crd::is_crd_manifest;
+use hemmer_provider_generator_parser::openapi::{is_azure_spec, is_openapi_spec, ProviderHint};
 use hemmer_provider_generator_parser::{
-    DiscoveryParser, OpenApiParser, ProtobufParser, SmithyParser,
+    CrdParser, CrudRules, DiscoveryParser, OpenApiParser, ProtobufParser, SmithyModelPath,
+    SmithyParser,
 };
 use std::path::{Path, PathBuf};
 
@@ -34,7 +38,9 @@ enum Commands {
         # Parse GCP Discovery document\n  \
         hemmer-provider-generator parse --spec storage-v1.json --format discovery\n\n  \
         # Auto-detect format\n  \
-        hemmer-provider-generator parse --spec kubernetes-api.json")]
+        hemmer-provider-generator parse --spec kubernetes-api.json\n\n  \
+        # Parse an Azure Swagger spec\n  \
+        hemmer-provider-generator parse --spec storage.json --provider azure")]
     Parse {
         /// Path to the spec file
         #[arg(short, long)]
@@ -48,9 +54,28 @@ enum Commands {
         #[arg(long)]
         service: Option<String>,
 
+        /// Provider name (e.g., "aws", "gcp", "azure"); Azure is inferred for
+        /// OpenAPI specs with `x-ms-*` extensions or Resource Manager paths
+        #[arg(short, long)]
+        provider: Option<String>,
+
         /// API version
         #[arg(long, default_value = "v1")]
         version: String,
+
+        /// Import roots for .proto specs (comma-separated, like protoc's -I)
+        #[arg(short = 'I', long, value_delimiter = ',')]
+        proto_path: Vec<PathBuf>,
+
+        /// Smithy IDL files, or directories of them, whose shapes .smithy specs
+        /// may reference (comma-separated)
+        #[arg(long, value_delimiter = ',')]
+        model_path: Vec<PathBuf>,
+
+        /// CRUD classification rules file (YAML), checked before the built-in rules
+        /// (CRDs and Kubernetes specs with `x-kubernetes-action` are not classified by rules)
+        #[arg(long)]
+        crud_rules: Option<PathBuf>,
     },
 
     /// Generate a provider from a single spec file
@@ -72,7 +97,13 @@ enum Commands {
         --spec service.pb \\\n    \
         --format protobuf \\\n    \
         --service storage \\\n    \
-        --output ./providers/grpc-storage")]
+        --output ./providers/grpc-storage\n\n  \
+        # Generate from .proto sources (no protoc needed)\n  \
+        hemmer-provider-generator generate \\\n    \
+        --spec googleapis/google/storage/v2/storage.proto \\\n    \
+        --proto-path googleapis \\\n    \
+        --service storage \\\n    \
+        --output ./providers/gcp-storage")]
     Generate {
         /// Path to the spec file
         #[arg(short, long)]
@@ -86,6 +117,11 @@ enum Commands {
         #[arg(long)]
         service: String,
 
+        /// Provider name (e.g., "aws", "gcp", "azure"); Azure is inferred for
+        /// OpenAPI specs with `x-ms-*` extensions or Resource Manager paths
+        #[arg(short, long)]
+        provider: Option<String>,
+
         /// API version
         #[arg(long, default_value = "v1")]
         version: String,
@@ -93,6 +129,24 @@ enum Commands {
         /// Output directory
         #[arg(short, long, default_value = "./output")]
         output: PathBuf,
+
+        /// Import roots for .proto specs (comma-separated, like protoc's -I)
+        #[arg(short = 'I', long, value_delimiter = ',')]
+        proto_path: Vec<PathBuf>,
+
+        /// Smithy IDL files, or directories of them, whose shapes .smithy specs
+        /// may reference (comma-separated)
+        #[arg(long, value_delimiter = ',')]
+        model_path: Vec<PathBuf>,
+
+        /// CRUD classification rules file (YAML), checked before the built-in rules
+        /// (CRDs and Kubernetes specs with `x-kubernetes-action` are not classified by rules)
+        #[arg(long)]
+        crud_rules: Option<PathBuf>,
+
+        /// Overlay files (YAML) patching the parsed definitions, applied in order
+        #[arg(long, value_delimiter = ',')]
+        overlay: Vec<PathBuf>,
     },
 
     /// Generate a unified provider from multiple spec files
@@ -114,7 +168,12 @@ enum Commands {
         --provider aws \\\n    \
         --spec-dir ./aws-sdk/models/ \\\n    \
         --filter s3,dynamodb,ec2 \\\n    \
-        --output ./provider-aws")]
+        --output ./provider-aws\n\n  \
+        # Generate Kubernetes provider from operator CRDs\n  \
+        hemmer-provider-generator generate-unified \\\n    \
+        --provider kubernetes \\\n    \
+        --spec-dir ./crds/ \\\n    \
+        --output ./provider-kubernetes")]
     GenerateUnified {
         /// Provider name (e.g., "aws", "gcp", "azure")
         #[arg(short, long)]
@@ -147,6 +206,24 @@ enum Commands {
         /// Output directory
         #[arg(short, long, default_value = "./output")]
         output: PathBuf,
+
+        /// Import roots for .proto specs (comma-separated, like protoc's -I)
+        #[arg(short = 'I', long, value_delimiter = ',')]
+        proto_path: Vec<PathBuf>,
+
+        /// Smithy IDL files, or directories of them, whose shapes .smithy specs
+        /// may reference (comma-separated)
+        #[arg(long, value_delimiter = ',')]
+        model_path: Vec<PathBuf>,
+
+        /// CRUD classification rules file (YAML), checked before the built-in rules
+        /// (CRDs and Kubernetes specs with `x-kubernetes-action` are not classified by rules)
+        #[arg(long)]
+        crud_rules: Option<PathBuf>,
+
+        /// Overlay files (YAML) patching the parsed definitions, applied in order
+        #[arg(long, value_delimiter = ',')]
+        overlay: Vec<PathBuf>,
     },
 
     /// Analyze an SDK repository and generate provider metadata YAML
@@ -207,14 +284,16 @@ enum Commands {
 
 #[derive(Debug, Clone, Copy, ValueEnum)]
 enum SpecFormat {
-    /// AWS Smithy JSON AST
+    /// AWS Smithy JSON AST or Smithy IDL (.smithy)
     Smithy,
     /// OpenAPI 3.0 (Kubernetes, Azure)
     Openapi,
     /// Google Discovery Document
     Discovery,
-    /// Protocol Buffer FileDescriptorSet
+    /// Protocol Buffer FileDescriptorSet (.pb) or .proto sources
     Protobuf,
+    /// Kubernetes CustomResourceDefinition manifests (YAML)
+    Crd,
 }
 
 impl std::fmt::Display for SpecFormat {
@@ -224,6 +303,7 @@ impl std::fmt::Display for SpecFormat {
             SpecFormat::Openapi => write!(f, "OpenAPI"),
             SpecFormat::Discovery => write!(f, "Discovery"),
             SpecFormat::Protobuf => write!(f, "Protobuf"),
+            SpecFormat::Crd => write!(f, "CRD"),
         }
     }
 }
@@ -240,13 +320,26 @@ fn main() -> Result<()> {
             spec,
             format,
             service,
+            provider,
             version,
+            proto_path,
+            model_path,
+            crud_rules,
         } => {
+            let provider = provider.as_deref().map(parse_provider).transpose()?;
+            let model_path = load_model_path(&model_path)?;
+            let crud_rules = load_crud_rules(crud_rules.as_deref())?;
             parse_command(
                 spec.as_path(),
                 format,
                 service.as_deref(),
                 &version,
+                &ParserOptions {
+                    provider,
+                    proto_paths: &proto_path,
+                    model_path: &model_path,
+                    crud_rules: &crud_rules,
+                },
                 cli.verbose,
             )?;
         },
@@ -254,15 +347,31 @@ fn main() -> Result<()> {
             spec,
             format,
             service,
+            provider,
             version,
             output,
+            proto_path,
+            model_path,
+            crud_rules,
+            overlay,
         } => {
+            let provider = provider.as_deref().map(parse_provider).transpose()?;
+            let model_path = load_model_path(&model_path)?;
+            let crud_rules = load_crud_rules(crud_rules.as_deref())?;
+            let overlays = load_overlays(&overlay)?;
             generate_command(
                 spec.as_path(),
                 format,
                 &service,
                 &version,
                 output.as_path(),
+                &ParserOptions {
+                    provider,
+                    proto_paths: &proto_path,
+                    model_path: &model_path,
+                    crud_rules: &crud_rules,
+                },
+                &overlays,
                 cli.verbose,
             )?;
         },
@@ -276,7 +385,14 @@ fn main() -> Result<()> {
             services,
             version,
             output,
+            proto_path,
+            model_path,
+            crud_rules,
+            overlay,
         } => {
+            let model_path = load_model_path(&model_path)?;
+            let crud_rules = load_crud_rules(crud_rules.as_deref())?;
+            let overlays = load_overlays(&overlay)?;
             generate_unified_command(UnifiedConfig {
                 provider_name: &provider,
                 spec_paths: specs.as_deref(),
@@ -286,6 +402,10 @@ fn main() -> Result<()> {
                 service_names: services.as_deref(),
                 version: &version,
                 output: output.as_path(),
+                proto_paths: &proto_path,
+                model_path: &model_path,
+                crud_rules: &crud_rules,
+                overlays: &overlays,
                 verbose: cli.verbose,
             })?;
         },
@@ -320,20 +440,29 @@ fn parse_command(
     format: Option<SpecFormat>,
     service_name: Option<&str>,
     version: &str,
+    options: &ParserOptions,
     verbose: bool,
 ) -> Result<()> {
     println!("{} Parsing spec file: {}", "→".cyan(), spec_path.display());
 
     // Detect format if not specified
-    let detected_format = format.unwrap_or_else(|| {
-        let detected = detect_format(spec_path);
-        println!(
-            "{} Auto-detected format: {}",
-            "→".cyan(),
-            detected.to_string().yellow()
-        );
-        detected
-    });
+    let detected_format = match format {
+        Some(format) => format,
+        None => {
+            let detected = detect_format(spec_path).with_context(|| {
+                format!(
+                    "Could not detect the format of {}; pass --format",
+                    spec_path.display()
+                )
+            })?;
+            println!(
+                "{} Auto-detected format: {}",
+                "→".cyan(),
+                detected.to_string().yellow()
+            );
+            detected
+        },
+    };
 
     // Infer service name from filename if not provided
     let service = service_name
@@ -351,29 +480,56 @@ fn parse_command(
     let service_def = match detected_format {
         SpecFormat::Smithy => {
             println!("{} Using Smithy parser", "→".cyan());
-            let parser = SmithyParser::from_file(spec_path, &service, version)
-                .context("Failed to load Smithy spec")?;
+            let mut parser = SmithyParser::from_file_with_model_path(
+                spec_path,
+                options.model_path,
+                &service,
+                version,
+            )
+            .context("Failed to load Smithy spec")?
+            .with_crud_rules(options.crud_rules.clone());
+            if let Some(provider) = &options.provider {
+                parser = parser.with_provider(provider.clone());
+            }
             parser.parse().context("Failed to parse Smithy spec")?
         },
         SpecFormat::Openapi => {
             println!("{} Using OpenAPI parser", "→".cyan());
-            let parser = OpenApiParser::from_file(spec_path, &service, version)
-                .context("Failed to load OpenAPI spec")?;
+            let mut parser = OpenApiParser::from_file(spec_path, &service, version)
+                .context("Failed to load OpenAPI spec")?
+                .with_crud_rules(options.crud_rules.clone());
+            if let Some(hint) = openapi_provider_hint(spec_path, options.provider.as_ref()) {
+                parser = parser.with_provider_hint(hint);
+            }
             parser.parse().context("Failed to parse OpenAPI spec")?
         },
         SpecFormat::Discovery => {
             println!("{} Using Discovery parser", "→".cyan());
             let parser = DiscoveryParser::from_file(spec_path, &service, version)
-                .context("Failed to load Discovery doc")?;
+                .context("Failed to load Discovery doc")?
+                .with_crud_rules(options.crud_rules.clone());
             parser.parse().context("Failed to parse Discovery doc")?
         },
         SpecFormat::Protobuf => {
             println!("{} Using Protobuf parser", "→".cyan());
-            let parser = ProtobufParser::from_file(spec_path, &service, version)
-                .context("Failed to load Protobuf FileDescriptorSet")?;
-            parser
-                .parse()
-                .context("Failed to parse Protobuf FileDescriptorSet")?
+            let mut parser = ProtobufParser::from_file_with_import_roots(
+                spec_path,
+                options.proto_paths,
+                &service,
+                version,
+            )
+            .context("Failed to load Protobuf spec")?
+            .with_crud_rules(options.crud_rules.clone());
+            if let Some(provider) = &options.provider {
+                parser = parser.with_provider(provider.clone());
+            }
+            parser.parse().context("Failed to parse Protobuf spec")?
+        },
+        SpecFormat::Crd => {
+            println!("{} Using CRD parser", "→".cyan());
+            let parser = CrdParser::from_file(spec_path, &service, version)
+                .context("Failed to load CRD manifests")?;
+            parser.parse().context("Failed to parse CRD manifests")?
         },
     };
 
@@ -412,12 +568,15 @@ fn parse_command(
     Ok(())
 }
 
+#[allow(clippy::too_many_arguments)]
 fn generate_command(
     spec_path: &Path,
     format: Option<SpecFormat>,
     service_name: &str,
     version: &str,
     output: &Path,
+    options: &ParserOptions,
+    overlays: &[(PathBuf, Overlay)],
     verbose: bool,
 ) -> Result<()> {
     println!(
@@ -427,15 +586,23 @@ fn generate_command(
     );
 
     // Detect format if not specified
-    let detected_format = format.unwrap_or_else(|| {
-        let detected = detect_format(spec_path);
-        println!(
-            "{} Auto-detected format: {}",
-            "→".cyan(),
-            detected.to_string().yellow()
-        );
-        detected
-    });
+    let detected_format = match format {
+        Some(format) => format,
+        None => {
+            let detected = detect_format(spec_path).with_context(|| {
+                format!(
+                    "Could not detect the format of {}; pass --format",
+                    spec_path.display()
+                )
+            })?;
+            println!(
+                "{} Auto-detected format: {}",
+                "→".cyan(),
+                detected.to_string().yellow()
+            );
+            detected
+        },
+    };
 
     if verbose {
         println!("  Format: {}", detected_format);
@@ -446,31 +613,59 @@ fn generate_command(
 
     // Parse based on format
     println!("{} Parsing spec...", "→".cyan());
-    let service_def = match detected_format {
+    let mut service_def = match detected_format {
         SpecFormat::Smithy => {
-            let parser = SmithyParser::from_file(spec_path, service_name, version)
-                .context("Failed to load Smithy spec")?;
+            let mut parser = SmithyParser::from_file_with_model_path(
+                spec_path,
+                options.model_path,
+                service_name,
+                version,
+            )
+            .context("Failed to load Smithy spec")?
+            .with_crud_rules(options.crud_rules.clone());
+            if let Some(provider) = &options.provider {
+                parser = parser.with_provider(provider.clone());
+            }
             parser.parse().context("Failed to parse Smithy spec")?
         },
         SpecFormat::Openapi => {
-            let parser = OpenApiParser::from_file(spec_path, service_name, version)
-                .context("Failed to load OpenAPI spec")?;
+            let mut parser = OpenApiParser::from_file(spec_path, service_name, version)
+                .context("Failed to load OpenAPI spec")?
+                .with_crud_rules(options.crud_rules.clone());
+            if let Some(hint) = openapi_provider_hint(spec_path, options.provider.as_ref()) {
+                parser = parser.with_provider_hint(hint);
+            }
             parser.parse().context("Failed to parse OpenAPI spec")?
         },
         SpecFormat::Discovery => {
             let parser = DiscoveryParser::from_file(spec_path, service_name, version)
-                .context("Failed to load Discovery doc")?;
+                .context("Failed to load Discovery doc")?
+                .with_crud_rules(options.crud_rules.clone());
             parser.parse().context("Failed to parse Discovery doc")?
         },
         SpecFormat::Protobuf => {
-            let parser = ProtobufParser::from_file(spec_path, service_name, version)
-                .context("Failed to load Protobuf FileDescriptorSet")?;
-            parser
-                .parse()
-                .context("Failed to parse Protobuf FileDescriptorSet")?
+            let mut parser = ProtobufParser::from_file_with_import_roots(
+                spec_path,
+                options.proto_paths,
+                service_name,
+                version,
+            )
+            .context("Failed to load Protobuf spec")?
+            .with_crud_rules(options.crud_rules.clone());
+            if let Some(provider) = &options.provider {
+                parser = parser.with_provider(provider.clone());
+            }
+            parser.parse().context("Failed to parse Protobuf spec")?
+        },
+        SpecFormat::Crd => {
+            let parser = CrdParser::from_file(spec_path, service_name, version)
+                .context("Failed to load CRD manifests")?;
+            parser.parse().context("Failed to parse CRD manifests")?
         },
     };
 
+    apply_overlays(overlays, std::slice::from_mut(&mut service_def));
+
     println!(
         "{} Parsed {} resources",
         "✓".green(),
@@ -500,6 +695,81 @@ fn generate_command(
     Ok(())
 }
 
+/// Spec parser settings shared by `parse` and `generate`
+struct ParserOptions<'a> {
+    /// Provider from `--provider`, if given
+    provider: Option<Provider>,
+    /// Import roots for .proto specs
+    proto_paths: &'a [PathBuf],
+    /// Shapes .smithy specs may reference
+    model_path: &'a SmithyModelPath,
+    crud_rules: &'a CrudRules,
+}
+
+/// Parse a provider name as given to `--provider`
+fn parse_provider(name: &str) -> Result<Provider> {
+    match name.to_lowercase().as_str() {
+        "aws" => Ok(Provider::Aws),
+        "gcp" => Ok(Provider::Gcp),
+        "azure" => Ok(Provider::Azure),
+        "kubernetes" | "k8s" => Ok(Provider::Kubernetes),
+        _ => anyhow::bail!("Unknown provider: {}", name),
+    }
+}
+
+/// Provider hint for a single OpenAPI spec
+///
+/// Without `--provider`, Azure specs are recognized by their `x-ms-*`
+/// extensions or Resource Manager paths.
+fn openapi_provider_hint(spec_path: &Path, provider: Option<&Provider>) -> Option<ProviderHint> {
+    let azure = match provider {
+        Some(provider) => *provider == Provider::Azure,
+        None => std::fs::read_to_string(spec_path).is_ok_and(|content| is_azure_spec(&content)),
+    };
+    azure.then_some(ProviderHint::Azure)
+}
+
+/// Load the `--crud-rules` file, or the built-in rules without one
+fn load_crud_rules(path: Option<&Path>) -> Result<CrudRules> {
+    match path {
+        Some(path) => CrudRules::from_file(path)
+            .with_context(|| format!("Failed to load CRUD rules: {}", path.display())),
+        None => Ok(CrudRules::default()),
+    }
+}
+
+/// Load the `--model-path` Smithy IDL files
+fn load_model_path(paths: &[PathBuf]) -> Result<SmithyModelPath> {
+    SmithyModelPath::load(paths).context("Failed to load Smithy model path")
+}
+
+/// Load `--overlay` files
+fn load_overlays(paths: &[PathBuf]) -> Result<Vec<(PathBuf, Overlay)>> {
+    paths
+        .iter()
+        .map(|path| {
+            let overlay = Overlay::load(path)
+                .with_context(|| format!("Failed to load overlay: {}", path.display()))?;
+            Ok((path.clone(), overlay))
+        })
+        .collect()
+}
+
+/// Apply overlays in order, warning about entries that no longer match anything
+fn apply_overlays(overlays: &[(PathBuf, Overlay)], services: &mut [ServiceDefinition]) {
+    for (path, overlay) in overlays {
+        println!("{} Applying overlay: {}", "→".cyan(), path.display());
+        for entry in overlay.apply(services) {
+            eprintln!(
+                "{} Overlay entry {} in {} matches nothing",
+                "⚠".yellow(),
+                entry.yellow(),
+                path.display()
+            );
+        }
+    }
+}
+
 /// Configuration for unified provider generation
 struct UnifiedConfig<'a> {
     provider_name: &'a str,
@@ -510,11 +780,18 @@ struct UnifiedConfig<'a> {
     service_names: Option<&'a [String]>,
     version: &'a str,
     output: &'a Path,
+    /// Import roots for .proto specs (the spec directory is always one)
+    proto_paths: &'a [PathBuf],
+    /// Shapes .smithy specs may reference, parsed once for all specs
+    model_path: &'a SmithyModelPath,
+    crud_rules: &'a CrudRules,
+    /// Overlays applied to the parsed services, with their file paths
+    overlays: &'a [(PathBuf, Overlay)],
     verbose: bool,
 }
 
 fn generate_unified_command(config: UnifiedConfig) -> Result<()> {
-    use hemmer_provider_generator_common::{Provider, ProviderDefinition, ServiceDefinition};
+    use hemmer_provider_generator_common::ProviderDefinition;
 
     // Discover spec files
     let discovered_specs: Vec<PathBuf> = if let Some(dir) = config.spec_dir {
@@ -534,6 +811,12 @@ fn generate_unified_command(config: UnifiedConfig) -> Result<()> {
         anyhow::bail!("No spec files found");
     }
 
+    // .proto imports are relative to the scanned directory, as with `protoc -I`
+    let mut proto_paths = config.proto_paths.to_vec();
+    if let Some(dir) = config.spec_dir {
+        proto_paths.push(dir.to_path_buf());
+    }
+
     println!(
         "{} Generating unified {} provider from {} specs",
         "→".cyan(),
@@ -542,13 +825,7 @@ fn generate_unified_command(config: UnifiedConfig) -> Result<()> {
     );
 
     // Parse provider enum from string
-    let provider = match config.provider_name.to_lowercase().as_str() {
-        "aws" => Provider::Aws,
-        "gcp" => Provider::Gcp,
-        "azure" => Provider::Azure,
-        "kubernetes" | "k8s" => Provider::Kubernetes,
-        _ => anyhow::bail!("Unknown provider: {}", config.provider_name),
-    };
+    let provider = parse_provider(config.provider_name)?;
 
     // Parse all specs
     let mut services = Vec::new();
@@ -563,7 +840,15 @@ fn generate_unified_command(config: UnifiedConfig) -> Result<()> {
         );
 
         // Detect format if not specified
-        let detected_format = config.format.unwrap_or_else(|| detect_format(spec_path));
+        let Some(detected_format) = config.format.or_else(|| detect_format(spec_path)) else {
+            eprintln!(
+                "{} Skipping {}: not an OpenAPI spec or CRD manifest",
+                "⚠".yellow(),
+                spec_path.display()
+            );
+            skipped += 1;
+            continue;
+        };
 
         // Get service name
         let inferred_name = infer_service_name(spec_path);
@@ -582,17 +867,31 @@ fn generate_unified_command(config: UnifiedConfig) -> Result<()> {
         let service_def_result: Result<_> = (|| {
             let service_def = match detected_format {
                 SpecFormat::Smithy => {
-                    let parser =
-                        SmithyParser::from_file(spec_path, service_name, config.version).context(
-                            format!("Failed to load Smithy spec: {}", spec_path.display()),
-                        )?;
+                    let parser = SmithyParser::from_file_with_model_path(
+                        spec_path,
+                        config.model_path,
+                        service_name,
+                        config.version,
+                    )
+                    .context(format!(
+                        "Failed to load Smithy spec: {}",
+                        spec_path.display()
+                    ))?
+                    .with_crud_rules(config.crud_rules.clone())
+                    .with_provider(provider.clone());
                     parser.parse().context("Failed to parse Smithy spec")?
                 },
                 SpecFormat::Openapi => {
-                    let parser =
-                        OpenApiParser::from_file(spec_path, service_name, config.version).context(
-                            format!("Failed to load OpenAPI spec: {}", spec_path.display()),
-                        )?;
+                    let mut parser =
+                        OpenApiParser::from_file(spec_path, service_name, config.version)
+                            .context(format!(
+                                "Failed to load OpenAPI spec: {}",
+                                spec_path.display()
+                            ))?
+                            .with_crud_rules(config.crud_rules.clone());
+                    if provider == Provider::Azure {
+                        parser = parser.with_provider_hint(ProviderHint::Azure);
+                    }
                     parser.parse().context("Failed to parse OpenAPI spec")?
                 },
                 SpecFormat::Discovery => {
@@ -601,18 +900,31 @@ fn generate_unified_command(config: UnifiedConfig) -> Result<()> {
                             .context(format!(
                                 "Failed to load Discovery doc: {}",
                                 spec_path.display()
-                            ))?;
+                            ))?
+                            .with_crud_rules(config.crud_rules.clone());
                     parser.parse().context("Failed to parse Discovery doc")?
                 },
                 SpecFormat::Protobuf => {
-                    let parser = ProtobufParser::from_file(spec_path, service_name, config.version)
-                        .context(format!(
-                            "Failed to load Protobuf FileDescriptorSet: {}",
-                            spec_path.display()
-                        ))?;
-                    parser
-                        .parse()
-                        .context("Failed to parse Protobuf FileDescriptorSet")?
+                    let parser = ProtobufParser::from_file_with_import_roots(
+                        spec_path,
+                        &proto_paths,
+                        service_name,
+                        config.version,
+                    )
+                    .context(format!(
+                        "Failed to load Protobuf spec: {}",
+                        spec_path.display()
+                    ))?
+                    .with_crud_rules(config.crud_rules.clone())
+                    .with_provider(provider.clone());
+                    parser.parse().context("Failed to parse Protobuf spec")?
+                },
+                SpecFormat::Crd => {
+                    let parser =
+                        CrdParser::from_file(spec_path, service_name, config.version).context(
+                            format!("Failed to load CRD manifests: {}", spec_path.display()),
+                        )?;
+                    parser.parse().context("Failed to parse CRD manifests")?
                 },
             };
             Ok(service_def)
@@ -673,12 +985,13 @@ fn generate_unified_command(config: UnifiedConfig) -> Result<()> {
     };
 
     // Create unified provider definition
-    let provider_def = ProviderDefinition {
+    let mut provider_def = ProviderDefinition {
         provider,
         provider_name: config.provider_name.to_string(),
         sdk_version: config.version.to_string(),
         services: deduplicated_services,
     };
+    apply_overlays(config.overlays, &mut provider_def.services);
 
     let total_resources: usize = provider_def
         .services
@@ -724,24 +1037,39 @@ fn generate_unified_command(config: UnifiedConfig) -> Result<()> {
 }
 
 /// Detect spec format from file extension and content
-fn detect_format(path: &Path) -> SpecFormat {
+///
+/// Returns `None` for YAML files that are neither OpenAPI specs nor CRD manifests.
+fn detect_format(path: &Path) -> Option<SpecFormat> {
     // Try extension first
     if let Some(ext) = path.extension() {
-        if let Some("pb") = ext.to_str() {
-            return SpecFormat::Protobuf;
+        match ext.to_str() {
+            Some("pb") | Some("proto") => return Some(SpecFormat::Protobuf),
+            Some("smithy") => return Some(SpecFormat::Smithy),
+            // YAML files are only specs when they say so
+            Some("yaml") | Some("yml") => {
+                let content = std::fs::read_to_string(path).ok()?;
+                return if is_crd_manifest(&content) {
+                    Some(SpecFormat::Crd)
+                } else if is_openapi_spec(&content) {
+                    Some(SpecFormat::Openapi)
+                } else {
+                    None
+                };
+            },
+            _ => {},
         }
     }
 
     // Try filename patterns
     if let Some(filename) = path.file_name().and_then(|s| s.to_str()) {
         if filename.contains("smithy") || filename.contains("model") {
-            return SpecFormat::Smithy;
+            return Some(SpecFormat::Smithy);
         }
         if filename.contains("openapi") || filename.contains("swagger") {
-            return SpecFormat::Openapi;
+            return Some(SpecFormat::Openapi);
         }
         if filename.contains("discovery") {
-            return SpecFormat::Discovery;
+            return Some(SpecFormat::Discovery);
         }
     }
 
@@ -749,18 +1077,20 @@ fn detect_format(path: &Path) -> SpecFormat {
     if let Ok(content) = std::fs::read_to_string(path) {
         // Check for format-specific markers
         if content.contains("\"smithy\"") && content.contains("\"shapes\"") {
-            return SpecFormat::Smithy;
+            return Some(SpecFormat::Smithy);
         }
-        if content.contains("\"openapi\"") && content.contains("\"paths\"") {
-            return SpecFormat::Openapi;
+        if (content.contains("\"openapi\"") || content.contains("\"swagger\""))
+            && content.contains("\"paths\"")
+        {
+            return Some(SpecFormat::Openapi);
         }
         if content.contains("\"discoveryVersion\"") && content.contains("\"resources\"") {
-            return SpecFormat::Discovery;
+            return Some(SpecFormat::Discovery);
         }
     }
 
     // Default to Smithy (most common for AWS)
-    SpecFormat::Smithy
+    Some(SpecFormat::Smithy)
 }
 
 /// Infer service name from filename
@@ -930,7 +1260,7 @@ fn discover_specs(
             } else if path.is_file() {
                 // Skip files with non-spec extensions
                 if let Some(ext) = path.extension().and_then(|e| e.to_str()) {
-                    if !matches!(ext, "json" | "pb") {
+                    if !matches!(ext, "json" | "pb" | "proto" | "smithy" | "yaml" | "yml") {
                         continue;
                     }
                 } else {
@@ -939,7 +1269,12 @@ fn discover_specs(
                 }
 
                 // Check if file matches format
-                let detected_format = detect_format(&path);
+                let Some(detected_format) = detect_format(&path) else {
+                    if verbose {
+                        println!("  Skipping {} (not a spec)", path.display());
+                    }
+                    continue;
+                };
 
                 // If format specified, skip non-matching files
                 if let Some(expected_format) = format {
@@ -1108,5 +1443,6 @@ fn matches_format(detected: &SpecFormat, expected: &SpecFormat) -> bool {
             | (SpecFormat::Openapi, SpecFormat::Openapi)
             | (SpecFormat::Discovery, SpecFormat::Discovery)
             | (SpecFormat::Protobuf, SpecFormat::Protobuf)
+            | (SpecFormat::Crd, SpecFormat::Crd)
     )
 }
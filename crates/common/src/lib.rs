@@ -10,6 +10,7 @@
 //! 2. **Generator**: ServiceDefinition → Generated code (provider.k + Rust)
 //! 3. **Output**: Generated provider implementing ProviderExecutor trait
 
+pub mod overlay;
 pub mod sdk_metadata;
 
 use serde::{Deserialize, Serialize};
@@ -330,6 +331,10 @@ pub struct BlockDefinition {
     /// Used in request builders: `request.set_lifecycle_rules(rules)`
     #[serde(default)]
     pub sdk_accessor_method: Option<String>,
+
+    /// Whether at most one of the block's attributes/blocks may be set (unions, oneOf)
+    #[serde(default)]
+    pub one_of: bool,
 }
 
 /// Definition of a single resource type (e.g., S3 Bucket, EC2 Instance)
@@ -349,6 +354,34 @@ pub struct ResourceDefinition {
     pub operations: Operations,
     /// Primary identifier field name (e.g., "bucket_name", "id")
     pub id_field: Option<String>,
+    /// Set when the resource type is deprecated
+    #[serde(default)]
+    pub deprecated: Option<Deprecation>,
+    /// Stability level of the API the resource belongs to
+    #[serde(default)]
+    pub stability: Stability,
+}
+
+impl ResourceDefinition {
+    /// Infer computed and write-only inputs from the outputs
+    ///
+    /// Optional inputs that the read response also returns are computed by
+    /// the service when not set. Sensitive inputs it never returns are
+    /// write-only. Nothing is inferred for resources without outputs.
+    pub fn infer_computed_fields(&mut self) {
+        if self.outputs.is_empty() {
+            return;
+        }
+
+        for field in &mut self.fields {
+            let returned = self.outputs.iter().any(|o| o.name == field.name);
+            if returned && !field.required && !field.write_only {
+                field.computed = true;
+            } else if !returned && field.sensitive {
+                field.write_only = true;
+            }
+        }
+    }
 }
 
 /// Definition of a data source (read-only resource lookup)
@@ -364,6 +397,11 @@ pub struct DataSourceDefinition {
     pub attributes: Vec<FieldDefinition>,
     /// SDK operation to fetch the data
     pub read_operation: OperationMapping,
+    /// Response member holding the matched items, for lookups through a list
+    /// operation; attributes are then read from the first item
+    /// e.g., "vpcs" for DescribeVpcs
+    #[serde(default)]
+    pub items_accessor: Option<String>,
 }
 
 /// CRUD operations mapped from SDK operations
@@ -382,16 +420,121 @@ pub struct Operations {
 }
 
 /// Mapping of a CRUD operation to SDK operation(s)
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct OperationMapping {
     /// SDK operation name (e.g., "create_bucket")
     pub sdk_operation: String,
     /// Additional operations that might be needed (e.g., for composite updates)
+    #[serde(default)]
     pub additional_operations: Vec<String>,
+    /// Pagination tokens if the operation returns results in pages
+    #[serde(default)]
+    pub pagination: Option<PaginationConfig>,
+    /// Request member that takes a client-generated idempotency token (snake_case)
+    /// e.g., "client_token" - generated code fills it in automatically
+    #[serde(default)]
+    pub idempotency_token: Option<String>,
+    /// Whether the operation has no side effects
+    #[serde(default)]
+    pub readonly: bool,
+    /// Whether repeating the operation has the same effect as calling it once
+    #[serde(default)]
+    pub idempotent: bool,
+    /// Waiter to run after this operation until the resource settles
+    /// e.g., "bucket_exists" after create, "bucket_not_exists" after delete
+    #[serde(default)]
+    pub waiter: Option<WaiterDefinition>,
+    /// Set when the operation completes asynchronously and has to be polled
+    #[serde(default)]
+    pub long_running: Option<LongRunningConfig>,
+    /// Set when the operation is deprecated
+    #[serde(default)]
+    pub deprecated: Option<Deprecation>,
 }
 
-/// Definition of a field in a resource
+/// Polling details of a long-running (asynchronous) operation
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct LongRunningConfig {
+    /// How the final resource state is fetched once the operation finishes
+    /// e.g., "azure-async-operation", "location", "original-uri"
+    pub final_state_via: Option<String>,
+    /// Type of the operation's result once it completes
+    /// e.g., "google.cloud.functions.v2.Function" for a `google.longrunning.Operation`
+    #[serde(default)]
+    pub response_type: Option<String>,
+    /// Type of the progress metadata reported while the operation runs
+    #[serde(default)]
+    pub metadata_type: Option<String>,
+    /// Maximum time to wait for completion in seconds (None = generator default)
+    #[serde(default)]
+    pub timeout_secs: Option<u64>,
+    /// For APIs returning an `Operation` resource: the SDK resource it is
+    /// polled through, e.g., "zone_operations" for Compute Engine
+    #[serde(default)]
+    pub operations_resource: Option<String>,
+    /// Parameters of the polling call besides the operation name, filled in
+    /// from the resource's fields, e.g., ["project", "zone"]
+    #[serde(default)]
+    pub operation_scope: Vec<String>,
+}
+
+/// Pagination tokens of a paginated operation
+///
+/// Member names are snake_case; output members may be dotted paths
+/// (e.g., "result.next_token").
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PaginationConfig {
+    /// Request member carrying the continuation token
+    pub input_token: Option<String>,
+    /// Response member holding the token for the next page
+    pub output_token: Option<String>,
+    /// Response member holding the items of a page
+    pub items: Option<String>,
+    /// Request member limiting the number of items per page
+    pub page_size: Option<String>,
+}
+
+/// Waiter that polls an operation until the resource reaches a terminal state
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WaiterDefinition {
+    /// Waiter name (snake_case, e.g., "bucket_exists")
+    pub name: String,
+    /// Human-readable description
+    pub description: Option<String>,
+    /// SDK operation polled by the waiter (e.g., "head_bucket")
+    pub operation: String,
+    /// Acceptors evaluated in order after each attempt
+    pub acceptors: Vec<WaiterAcceptor>,
+    /// Minimum delay between attempts in seconds
+    pub min_delay: Option<u64>,
+    /// Maximum delay between attempts in seconds
+    pub max_delay: Option<u64>,
+}
+
+/// A waiter acceptor: the state to transition to when its matcher matches
 #[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WaiterAcceptor {
+    /// State the waiter transitions to
+    pub state: WaiterState,
+    /// Matcher as defined by the spec (e.g., `{"success": true}`,
+    /// `{"errorType": "NotFound"}` or `{"output": {"path": ..., "expected": ...}}`)
+    pub matcher: serde_json::Value,
+}
+
+/// Waiter state reached when an acceptor matches
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum WaiterState {
+    /// The resource reached the desired state
+    Success,
+    /// The resource can no longer reach the desired state
+    Failure,
+    /// Keep polling
+    Retry,
+}
+
+/// Definition of a field in a resource
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct FieldDefinition {
     /// Field name (snake_case)
     pub name: String,
@@ -410,17 +553,190 @@ pub struct FieldDefinition {
     /// If None, defaults to the field name
     #[serde(default)]
     pub response_accessor: Option<String>,
+    /// For input fields: the SDK request setter method name (snake_case)
+    /// e.g., "bucket" for request.bucket() after an overlay renamed the field
+    /// If None, defaults to the field name
+    #[serde(default)]
+    pub request_accessor: Option<String>,
+    /// SDK type name for named types such as enums (e.g., "SkuName")
+    #[serde(default)]
+    pub sdk_type_name: Option<String>,
+    /// For inputs sent as request parameters rather than in the body:
+    /// where they go, e.g., "path" or "query"
+    #[serde(default)]
+    pub location: Option<String>,
+    /// Validation constraints on the field's value
+    #[serde(default)]
+    pub constraints: FieldConstraints,
+    /// Value used when the field is not set
+    #[serde(default)]
+    pub default_value: Option<serde_json::Value>,
+    /// For inputs: whether the service computes the value when it is not set
+    /// (outputs are always computed)
+    #[serde(default)]
+    pub computed: bool,
+    /// Whether the value is only ever sent, never returned by reads (e.g., passwords)
+    #[serde(default)]
+    pub write_only: bool,
+    /// Set when the field is deprecated
+    #[serde(default)]
+    pub deprecated: Option<Deprecation>,
+}
+
+impl FieldDefinition {
+    /// Whether the field is an optional input the service computes when not set
+    pub fn is_optional_computed(&self) -> bool {
+        self.computed && !self.required
+    }
+}
+
+/// Deprecation notice of a resource, field or operation
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Deprecation {
+    /// Why it is deprecated or what to use instead
+    #[serde(default)]
+    pub message: Option<String>,
+}
+
+impl Deprecation {
+    /// Deprecation notice with the given message, if any
+    pub fn new(message: Option<&str>) -> Self {
+        Self {
+            message: message.map(|m| m.to_string()).filter(|m| !m.is_empty()),
+        }
+    }
+}
+
+/// Stability level of an API
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Stability {
+    /// Generally available
+    #[default]
+    Ga,
+    /// Beta or preview: may still change
+    Beta,
+    /// Alpha: may change or go away at any time
+    Alpha,
+}
+
+impl Stability {
+    /// Stability level from an API version or package name
+    ///
+    /// Only the version itself is looked at: the `vN` segment of a package name
+    /// or path, the suffix of a date version, or the pre-release of a semantic
+    /// version. e.g., "v1beta1", "v1p1beta1", "2023-01-01-preview" and
+    /// "1.2.0-rc.1" → beta, "google.cloud.foo.v2alpha" → alpha,
+    /// "google.cloud.betaanalytics.v1" → GA
+    pub fn from_version(version: &str) -> Self {
+        let version = version.trim().to_ascii_lowercase();
+        let label = date_version_label(&version)
+            .or_else(|| semantic_version_label(&version))
+            .or_else(|| {
+                version
+                    .rsplit(['.', '/'])
+                    .find_map(|segment| api_version_label(segment))
+            })
+            .unwrap_or_default();
+
+        match label.trim_end_matches(|c: char| c.is_ascii_digit()) {
+            "alpha" => Stability::Alpha,
+            "beta" | "preview" | "privatepreview" | "rc" => Stability::Beta,
+            _ => Stability::Ga,
+        }
+    }
+}
+
+/// Suffix of a date version: "2023-01-01-preview" → "preview", "2016-11-15" → ""
+fn date_version_label(version: &str) -> Option<&str> {
+    let date = version.get(..10)?;
+    let is_date = date.char_indices().all(|(i, c)| match i {
+        4 | 7 => c == '-',
+        _ => c.is_ascii_digit(),
+    });
+    let rest = &version[10..];
+    (is_date && (rest.is_empty() || rest.starts_with('-'))).then(|| rest.trim_start_matches('-'))
+}
+
+/// Pre-release of a semantic version: "1.2.0-beta.1" → "beta", "v1.27.0" → ""
+fn semantic_version_label(version: &str) -> Option<&str> {
+    let (core, pre_release) = version.split_once('-').unwrap_or((version, ""));
+    let core = core.strip_prefix('v').unwrap_or(core);
+    let is_semantic = core.contains('.')
+        && core
+            .split('.')
+            .all(|part| !part.is_empty() && part.bytes().all(|b| b.is_ascii_digit()));
+    is_semantic.then(|| pre_release.split('.').next().unwrap_or_default())
+}
+
+/// Label of an API version segment: "v1beta1" → "beta1", "v1p1alpha" → "alpha", "v2" → ""
+fn api_version_label(segment: &str) -> Option<&str> {
+    let rest = segment.strip_prefix('v')?;
+    let label = rest.trim_start_matches(|c: char| c.is_ascii_digit());
+    if label.len() == rest.len() {
+        return None;
+    }
+    // Google point releases, e.g. "v1p1beta1"
+    let label = match label.strip_prefix('p') {
+        Some(point) if point.starts_with(|c: char| c.is_ascii_digit()) => {
+            point.trim_start_matches(|c: char| c.is_ascii_digit())
+        },
+        _ => label,
+    };
+    label
+        .bytes()
+        .all(|b| b.is_ascii_alphanumeric())
+        .then_some(label)
+}
+
+/// Validation constraints on a field's value
+///
+/// The allowed values of enums are the variants of [`FieldType::Enum`].
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct FieldConstraints {
+    /// Smallest allowed number (inclusive)
+    #[serde(default)]
+    pub minimum: Option<f64>,
+    /// Largest allowed number (inclusive)
+    #[serde(default)]
+    pub maximum: Option<f64>,
+    /// Minimum length of a string, or number of entries of a list or map
+    #[serde(default)]
+    pub min_length: Option<u64>,
+    /// Maximum length of a string, or number of entries of a list or map
+    #[serde(default)]
+    pub max_length: Option<u64>,
+    /// Regular expression string values have to match
+    #[serde(default)]
+    pub pattern: Option<String>,
+}
+
+impl FieldConstraints {
+    /// Whether no constraint is set
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
 }
 
 /// Represents a field type in the intermediate representation
 ///
 /// Maps SDK types → IR types → KCL types → Generated Rust types
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub enum FieldType {
     /// String type
+    #[default]
     String,
     /// Integer type (i64)
     Integer,
+    /// 8-bit signed integer (i8)
+    Int8,
+    /// 16-bit signed integer (i16)
+    Int16,
+    /// 32-bit signed integer (i32)
+    Int32,
+    /// 32-bit unsigned integer (u32)
+    UInt32,
+    /// 64-bit unsigned integer (u64)
+    UInt64,
     /// Boolean type
     Boolean,
     /// Float type (f64)
@@ -435,6 +751,13 @@ pub enum FieldType {
     DateTime,
     /// Nested object (represented as map)
     Object(HashMap<String, Box<FieldType>>),
+    /// Binary data (base64 encoded string in state)
+    Bytes,
+    /// Arbitrary JSON document
+    Document,
+    /// Tagged union: exactly one of the members is set
+    /// (a single-entry object keyed by the member name in state)
+    Union(HashMap<String, Box<FieldType>>),
 }
 
 impl FieldType {
@@ -447,9 +770,23 @@ impl FieldType {
             FieldType::Float => "Float".to_string(),
             FieldType::List(inner) => format!("List<{}>", inner.to_kcl_type()),
             FieldType::Map(k, v) => format!("Map<{},{}>", k.to_kcl_type(), v.to_kcl_type()),
-            FieldType::Enum(_) => "String".to_string(), // Enums become strings
+            FieldType::Enum(values) if values.is_empty() => "String".to_string(),
+            // Literal union of the variants: "a" | "b"
+            FieldType::Enum(values) => values
+                .iter()
+                .map(|v| serde_json::Value::String(v.clone()).to_string())
+                .collect::<Vec<_>>()
+                .join(" | "),
             FieldType::DateTime => "String".to_string(), // ISO 8601
             FieldType::Object(_) => "Map<String,Any>".to_string(),
+            FieldType::Int8
+            | FieldType::Int16
+            | FieldType::Int32
+            | FieldType::UInt32
+            | FieldType::UInt64 => "Integer".to_string(),
+            FieldType::Bytes => "String".to_string(), // Base64
+            FieldType::Document => "Any".to_string(),
+            FieldType::Union(_) => "Map<String,Any>".to_string(),
         }
     }
 
@@ -464,9 +801,19 @@ impl FieldType {
             FieldType::Map(k, v) => {
                 format!("HashMap<{}, {}>", k.to_rust_type(), v.to_rust_type())
             },
+            // Variant name in state, converted with the SDK enum's `From<&str>`
+            // (see `FieldDefinition::sdk_type_name`)
             FieldType::Enum(_) => "String".to_string(),
             FieldType::DateTime => "String".to_string(),
             FieldType::Object(_) => "HashMap<String, serde_json::Value>".to_string(),
+            FieldType::Int8 => "i8".to_string(),
+            FieldType::Int16 => "i16".to_string(),
+            FieldType::Int32 => "i32".to_string(),
+            FieldType::UInt32 => "u32".to_string(),
+            FieldType::UInt64 => "u64".to_string(),
+            FieldType::Bytes => "Vec<u8>".to_string(),
+            FieldType::Document => "serde_json::Value".to_string(),
+            FieldType::Union(_) => "HashMap<String, serde_json::Value>".to_string(),
         }
     }
 }
@@ -812,6 +1159,13 @@ mod tests {
             FieldType::Map(Box::new(FieldType::String), Box::new(FieldType::Integer)).to_kcl_type(),
             "Map<String,Integer>"
         );
+        assert_eq!(FieldType::UInt64.to_kcl_type(), "Integer");
+        assert_eq!(FieldType::Bytes.to_kcl_type(), "String");
+        assert_eq!(FieldType::Document.to_kcl_type(), "Any");
+        assert_eq!(
+            FieldType::Enum(vec!["standard".to_string(), "archive".to_string()]).to_kcl_type(),
+            r#""standard" | "archive""#
+        );
     }
 
     #[test]
@@ -827,6 +1181,10 @@ mod tests {
                 .to_rust_type(),
             "HashMap<String, i64>"
         );
+        assert_eq!(FieldType::Int32.to_rust_type(), "i32");
+        assert_eq!(FieldType::UInt64.to_rust_type(), "u64");
+        assert_eq!(FieldType::Bytes.to_rust_type(), "Vec<u8>");
+        assert_eq!(FieldType::Document.to_rust_type(), "serde_json::Value");
     }
 
     #[test]
@@ -843,6 +1201,87 @@ mod tests {
         assert_eq!(service.name, "s3");
     }
 
+    #[test]
+    fn test_infer_computed_fields() {
+        let field = |name: &str, required: bool, sensitive: bool| FieldDefinition {
+            name: name.to_string(),
+            required,
+            sensitive,
+            ..Default::default()
+        };
+        let mut resource = ResourceDefinition {
+            name: "database".to_string(),
+            description: None,
+            fields: vec![
+                field("name", true, false),
+                field("storage_class", false, false),
+                field("password", false, true),
+                field("labels", false, false),
+            ],
+            outputs: vec![
+                field("name", false, false),
+                field("storage_class", false, false),
+            ],
+            blocks: vec![],
+            operations: Operations {
+                create: None,
+                read: None,
+                update: None,
+                delete: None,
+                import: None,
+            },
+            id_field: None,
+            deprecated: None,
+            stability: Stability::Ga,
+        };
+
+        resource.infer_computed_fields();
+        let inferred: Vec<(bool, bool)> = resource
+            .fields
+            .iter()
+            .map(|f| (f.is_optional_computed(), f.write_only))
+            .collect();
+        assert_eq!(
+            inferred,
+            vec![(false, false), (true, false), (false, true), (false, false)]
+        );
+    }
+
+    #[test]
+    fn test_stability_from_version() {
+        assert_eq!(Stability::from_version("v1"), Stability::Ga);
+        assert_eq!(Stability::from_version("2016-11-15"), Stability::Ga);
+        assert_eq!(Stability::from_version("v1beta1"), Stability::Beta);
+        assert_eq!(
+            Stability::from_version("2023-01-01-preview"),
+            Stability::Beta
+        );
+        assert_eq!(
+            Stability::from_version("google.cloud.foo.v2alpha"),
+            Stability::Alpha
+        );
+        assert_eq!(Stability::from_version("v1p1beta1"), Stability::Beta);
+        assert_eq!(Stability::from_version("v1alpha2"), Stability::Alpha);
+        assert_eq!(
+            Stability::from_version("2024-05-01-privatepreview"),
+            Stability::Beta
+        );
+        assert_eq!(Stability::from_version("1.2.0-rc.1"), Stability::Beta);
+        assert_eq!(Stability::from_version("v1.27.0"), Stability::Ga);
+        assert_eq!(Stability::from_version("0.9.0-alpha"), Stability::Alpha);
+
+        // Words elsewhere in the name don't count
+        assert_eq!(
+            Stability::from_version("google.cloud.betaanalytics.v1"),
+            Stability::Ga
+        );
+        assert_eq!(
+            Stability::from_version("example.alphabet.v2"),
+            Stability::Ga
+        );
+        assert_eq!(Stability::from_version("previewer"), Stability::Ga);
+    }
+
     // Mock parser for testing
     struct MockParser {
         service_name: String,
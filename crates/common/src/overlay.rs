@@ -0,0 +1,709 @@
+//! Overlay files patching parsed service definitions before generation
+//!
+//! Overlays fix what parsers get wrong without touching the spec, so the fixes
+//! survive regeneration. Entries are keyed by the names the parser produced:
+//!
+//! ```yaml
+//! services:
+//!   s3:
+//!     resources:
+//!       bucket:
+//!         rename: s3_bucket
+//!         id_field: bucket
+//!         stability: Beta
+//!         fields:
+//!           bucket: { required: true, immutable: true }
+//!           policy: { sensitive: true, write_only: true }
+//!           grant_full_control: { drop: true }
+//!           acl: { deprecated: { message: "Use grants instead" } }
+//!           tags: { add: true, field_type: !Map [String, String] }
+//!         outputs:
+//!           location: { rename: region }
+//!         blocks:
+//!           cors_configuration:
+//!             attributes:
+//!               max_age_seconds: { field_type: Integer }
+//!           logging: { add: true, nesting_mode: Single }
+//!         operations:
+//!           read: { sdk_operation: head_bucket }
+//!           update: null
+//!       bucket_analytics_configuration: { drop: true }
+//!       bucket_website: { add: true, operations: { create: { sdk_operation: put_bucket_website } } }
+//!   compute:
+//!     resources:
+//!       disk: { lro_timeout_secs: 3600 }
+//! ```
+//!
+//! Entries that match nothing (unless `add` is set) are reported by
+//! [`Overlay::apply`], so stale overlays are noticed when the specs change.
+
+use crate::{
+    BlockDefinition, Deprecation, FieldDefinition, FieldType, GeneratorError, NestingMode,
+    OperationMapping, Operations, ResourceDefinition, Result, ServiceDefinition, Stability,
+};
+use serde::{Deserialize, Deserializer};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+/// Patches to apply to parsed services, keyed by service name
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Overlay {
+    #[serde(default)]
+    pub services: BTreeMap<String, ServicePatch>,
+}
+
+/// Patches to the resources of one service
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ServicePatch {
+    #[serde(default)]
+    pub resources: BTreeMap<String, ResourcePatch>,
+}
+
+/// Patch to a resource
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ResourcePatch {
+    /// Create the resource if the parser didn't produce it
+    #[serde(default)]
+    pub add: bool,
+    /// Remove the resource
+    #[serde(default)]
+    pub drop: bool,
+    pub rename: Option<String>,
+    pub description: Option<String>,
+    pub id_field: Option<String>,
+    pub deprecated: Option<Deprecation>,
+    pub stability: Option<Stability>,
+    /// Seconds to wait for the resource's long-running operations to finish
+    pub lro_timeout_secs: Option<u64>,
+    #[serde(default)]
+    pub fields: BTreeMap<String, FieldPatch>,
+    #[serde(default)]
+    pub outputs: BTreeMap<String, FieldPatch>,
+    #[serde(default)]
+    pub blocks: BTreeMap<String, BlockPatch>,
+    #[serde(default)]
+    pub operations: OperationsPatch,
+}
+
+/// Patch to a field, output or block attribute
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct FieldPatch {
+    /// Create the field if the parser didn't produce it
+    #[serde(default)]
+    pub add: bool,
+    /// Remove the field
+    #[serde(default)]
+    pub drop: bool,
+    pub rename: Option<String>,
+    pub field_type: Option<FieldType>,
+    pub required: Option<bool>,
+    pub sensitive: Option<bool>,
+    pub immutable: Option<bool>,
+    pub computed: Option<bool>,
+    pub write_only: Option<bool>,
+    pub deprecated: Option<Deprecation>,
+    pub description: Option<String>,
+}
+
+/// Patch to a nested block
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct BlockPatch {
+    /// Create the block if the parser didn't produce it
+    #[serde(default)]
+    pub add: bool,
+    /// Remove the block
+    #[serde(default)]
+    pub drop: bool,
+    pub rename: Option<String>,
+    pub description: Option<String>,
+    pub nesting_mode: Option<NestingMode>,
+    pub min_items: Option<u32>,
+    pub max_items: Option<u32>,
+    #[serde(default)]
+    pub attributes: BTreeMap<String, FieldPatch>,
+    #[serde(default)]
+    pub blocks: BTreeMap<String, BlockPatch>,
+}
+
+/// Replacement operation mappings; `null` removes an operation
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct OperationsPatch {
+    #[serde(default, deserialize_with = "present")]
+    pub create: Option<Option<OperationMapping>>,
+    #[serde(default, deserialize_with = "present")]
+    pub read: Option<Option<OperationMapping>>,
+    #[serde(default, deserialize_with = "present")]
+    pub update: Option<Option<OperationMapping>>,
+    #[serde(default, deserialize_with = "present")]
+    pub delete: Option<Option<OperationMapping>>,
+    #[serde(default, deserialize_with = "present")]
+    pub import: Option<Option<OperationMapping>>,
+}
+
+/// Tell a `null` value (`Some(None)`) apart from a missing key (`None`)
+fn present<'de, D, T>(deserializer: D) -> std::result::Result<Option<Option<T>>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    Option::<T>::deserialize(deserializer).map(Some)
+}
+
+impl Overlay {
+    /// Load an overlay from a YAML file
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = fs::read_to_string(path).map_err(|e| {
+            GeneratorError::Parse(format!("Failed to read overlay file {:?}: {}", path, e))
+        })?;
+
+        Self::from_yaml(&content).map_err(|e| {
+            GeneratorError::Parse(format!("Failed to parse overlay from {:?}: {}", path, e))
+        })
+    }
+
+    /// Parse an overlay from YAML
+    pub fn from_yaml(yaml: &str) -> Result<Self> {
+        serde_yaml::from_str(yaml).map_err(|e| GeneratorError::Parse(e.to_string()))
+    }
+
+    /// Apply the overlay to `services`
+    ///
+    /// Returns the entries that matched nothing, as dotted paths
+    /// (e.g. "services.s3.resources.bucket.fields.acl").
+    pub fn apply(&self, services: &mut [ServiceDefinition]) -> Vec<String> {
+        let mut unmatched = Vec::new();
+
+        for (name, patch) in &self.services {
+            let path = format!("services.{}", name);
+            match services.iter_mut().find(|service| &service.name == name) {
+                Some(service) => apply_patches(
+                    &mut service.resources,
+                    &patch.resources,
+                    &format!("{}.resources", path),
+                    &mut unmatched,
+                ),
+                None => unmatched.push(path),
+            }
+        }
+
+        unmatched
+    }
+}
+
+/// A patch to a named IR item
+trait Patch<T> {
+    fn adds(&self) -> bool;
+    fn drops(&self) -> bool;
+    fn name(item: &T) -> &str;
+    /// An empty item, for patches that add one
+    fn create(&self, name: &str) -> T;
+    fn apply(&self, item: &mut T, path: &str, unmatched: &mut Vec<String>);
+}
+
+/// Apply patches keyed by item name, recording entries that match nothing
+fn apply_patches<T, P: Patch<T>>(
+    items: &mut Vec<T>,
+    patches: &BTreeMap<String, P>,
+    path: &str,
+    unmatched: &mut Vec<String>,
+) {
+    for (name, patch) in patches {
+        let path = format!("{}.{}", path, name);
+        let index = match items.iter().position(|item| P::name(item) == name) {
+            Some(index) => index,
+            None if patch.adds() => {
+                items.push(patch.create(name));
+                items.len() - 1
+            },
+            None => {
+                unmatched.push(path);
+                continue;
+            },
+        };
+
+        if patch.drops() {
+            items.remove(index);
+        } else {
+            patch.apply(&mut items[index], &path, unmatched);
+        }
+    }
+}
+
+impl Patch<ResourceDefinition> for ResourcePatch {
+    fn adds(&self) -> bool {
+        self.add
+    }
+
+    fn drops(&self) -> bool {
+        self.drop
+    }
+
+    fn name(item: &ResourceDefinition) -> &str {
+        &item.name
+    }
+
+    fn create(&self, name: &str) -> ResourceDefinition {
+        ResourceDefinition {
+            name: name.to_string(),
+            description: None,
+            fields: Vec::new(),
+            outputs: Vec::new(),
+            blocks: Vec::new(),
+            operations: Operations {
+                create: None,
+                read: None,
+                update: None,
+                delete: None,
+                import: None,
+            },
+            id_field: None,
+            deprecated: None,
+            stability: Stability::Ga,
+        }
+    }
+
+    fn apply(&self, resource: &mut ResourceDefinition, path: &str, unmatched: &mut Vec<String>) {
+        if let Some(name) = &self.rename {
+            resource.name = name.clone();
+        }
+        if let Some(description) = &self.description {
+            resource.description = Some(description.clone());
+        }
+
+        // Renamed fields keep identifying the resource, and renamed outputs
+        // keep reading the SDK accessor of their old name
+        for (name, patch) in self.fields.iter().chain(&self.outputs) {
+            if let (Some(rename), Some(id_field)) = (&patch.rename, &mut resource.id_field) {
+                if id_field == name {
+                    *id_field = rename.clone();
+                }
+            }
+        }
+        for (name, _) in self
+            .outputs
+            .iter()
+            .filter(|(_, patch)| patch.rename.is_some())
+        {
+            if let Some(output) = resource.outputs.iter_mut().find(|o| &o.name == name) {
+                output.response_accessor.get_or_insert_with(|| name.clone());
+            }
+        }
+        if let Some(id_field) = &self.id_field {
+            resource.id_field = Some(id_field.clone());
+        }
+        if let Some(deprecated) = &self.deprecated {
+            resource.deprecated = Some(deprecated.clone());
+        }
+        if let Some(stability) = self.stability {
+            resource.stability = stability;
+        }
+
+        apply_patches(
+            &mut resource.fields,
+            &self.fields,
+            &format!("{}.fields", path),
+            unmatched,
+        );
+        apply_patches(
+            &mut resource.outputs,
+            &self.outputs,
+            &format!("{}.outputs", path),
+            unmatched,
+        );
+        apply_patches(
+            &mut resource.blocks,
+            &self.blocks,
+            &format!("{}.blocks", path),
+            unmatched,
+        );
+
+        let operations = &mut resource.operations;
+        for (patch, operation) in [
+            (&self.operations.create, &mut operations.create),
+            (&self.operations.read, &mut operations.read),
+            (&self.operations.update, &mut operations.update),
+            (&self.operations.delete, &mut operations.delete),
+            (&self.operations.import, &mut operations.import),
+        ] {
+            if let Some(mapping) = patch {
+                *operation = mapping.clone();
+            }
+        }
+
+        if let Some(secs) = self.lro_timeout_secs {
+            let mut long_running = [
+                &mut operations.create,
+                &mut operations.update,
+                &mut operations.delete,
+            ]
+            .into_iter()
+            .filter_map(|operation| operation.as_mut()?.long_running.as_mut())
+            .peekable();
+            if long_running.peek().is_none() {
+                unmatched.push(format!("{}.lro_timeout_secs", path));
+            }
+            for config in long_running {
+                config.timeout_secs = Some(secs);
+            }
+        }
+    }
+}
+
+impl Patch<FieldDefinition> for FieldPatch {
+    fn adds(&self) -> bool {
+        self.add
+    }
+
+    fn drops(&self) -> bool {
+        self.drop
+    }
+
+    fn name(item: &FieldDefinition) -> &str {
+        &item.name
+    }
+
+    fn create(&self, name: &str) -> FieldDefinition {
+        FieldDefinition {
+            name: name.to_string(),
+            ..Default::default()
+        }
+    }
+
+    fn apply(&self, field: &mut FieldDefinition, _path: &str, _unmatched: &mut Vec<String>) {
+        // Renamed fields keep calling the SDK setter of their old name
+        if let Some(name) = &self.rename {
+            field
+                .request_accessor
+                .get_or_insert_with(|| field.name.clone());
+            field.name = name.clone();
+        }
+        if let Some(field_type) = &self.field_type {
+            field.field_type = field_type.clone();
+        }
+        if let Some(required) = self.required {
+            field.required = required;
+        }
+        if let Some(sensitive) = self.sensitive {
+            field.sensitive = sensitive;
+        }
+        if let Some(immutable) = self.immutable {
+            field.immutable = immutable;
+        }
+        if let Some(computed) = self.computed {
+            field.computed = computed;
+        }
+        if let Some(write_only) = self.write_only {
+            field.write_only = write_only;
+        }
+        if let Some(deprecated) = &self.deprecated {
+            field.deprecated = Some(deprecated.clone());
+        }
+        if let Some(description) = &self.description {
+            field.description = Some(description.clone());
+        }
+    }
+}
+
+impl Patch<BlockDefinition> for BlockPatch {
+    fn adds(&self) -> bool {
+        self.add
+    }
+
+    fn drops(&self) -> bool {
+        self.drop
+    }
+
+    fn name(item: &BlockDefinition) -> &str {
+        &item.name
+    }
+
+    fn create(&self, name: &str) -> BlockDefinition {
+        let nesting_mode = self.nesting_mode.clone().unwrap_or(NestingMode::Single);
+        BlockDefinition {
+            name: name.to_string(),
+            description: None,
+            attributes: Vec::new(),
+            blocks: Vec::new(),
+            max_items: if nesting_mode == NestingMode::Single {
+                1
+            } else {
+                0
+            },
+            nesting_mode,
+            min_items: 0,
+            sdk_type_name: None,
+            sdk_accessor_method: None,
+            one_of: false,
+        }
+    }
+
+    fn apply(&self, block: &mut BlockDefinition, path: &str, unmatched: &mut Vec<String>) {
+        if let Some(name) = &self.rename {
+            block
+                .sdk_accessor_method
+                .get_or_insert_with(|| block.name.clone());
+            block.name = name.clone();
+        }
+        if let Some(description) = &self.description {
+            block.description = Some(description.clone());
+        }
+        if let Some(nesting_mode) = &self.nesting_mode {
+            block.nesting_mode = nesting_mode.clone();
+        }
+        if let Some(min_items) = self.min_items {
+            block.min_items = min_items;
+        }
+        if let Some(max_items) = self.max_items {
+            block.max_items = max_items;
+        }
+
+        apply_patches(
+            &mut block.attributes,
+            &self.attributes,
+            &format!("{}.attributes", path),
+            unmatched,
+        );
+        apply_patches(
+            &mut block.blocks,
+            &self.blocks,
+            &format!("{}.blocks", path),
+            unmatched,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{LongRunningConfig, Provider};
+
+    fn service() -> ServiceDefinition {
+        let field = |name: &str| FieldDefinition {
+            name: name.to_string(),
+            ..Default::default()
+        };
+        let bucket = ResourceDefinition {
+            name: "bucket".to_string(),
+            description: None,
+            fields: vec![field("bucket"), field("policy"), field("acl")],
+            outputs: vec![field("location")],
+            blocks: vec![BlockDefinition {
+                name: "cors_rule".to_string(),
+                description: None,
+                attributes: vec![field("max_age_seconds")],
+                blocks: vec![],
+                nesting_mode: NestingMode::List,
+                min_items: 0,
+                max_items: 0,
+                sdk_type_name: None,
+                sdk_accessor_method: None,
+                one_of: false,
+            }],
+            operations: Operations {
+                create: Some(OperationMapping {
+                    sdk_operation: "create_bucket".to_string(),
+                    ..Default::default()
+                }),
+                read: None,
+                update: Some(OperationMapping {
+                    sdk_operation: "put_bucket_acl".to_string(),
+                    ..Default::default()
+                }),
+                delete: None,
+                import: None,
+            },
+            id_field: Some("bucket".to_string()),
+            deprecated: None,
+            stability: Stability::Ga,
+        };
+        let mut analytics = Patch::create(&ResourcePatch::default(), "bucket_analytics");
+        analytics.fields.push(field("id"));
+
+        ServiceDefinition {
+            provider: Provider::Aws,
+            name: "s3".to_string(),
+            sdk_version: "1.0.0".to_string(),
+            resources: vec![bucket, analytics],
+            data_sources: vec![],
+        }
+    }
+
+    #[test]
+    fn test_apply_overlay() {
+        let overlay = Overlay::from_yaml(
+            r#"
+services:
+  s3:
+    resources:
+      bucket:
+        rename: s3_bucket
+        fields:
+          bucket: { rename: name, required: true, immutable: true }
+          policy: { sensitive: true }
+          acl: { drop: true }
+          tags: { add: true, field_type: !Map [String, String] }
+        outputs:
+          location: { rename: region }
+        blocks:
+          cors_rule:
+            rename: cors
+            attributes:
+              max_age_seconds: { field_type: Integer }
+          logging: { add: true }
+        operations:
+          read: { sdk_operation: head_bucket }
+          update: null
+      bucket_analytics: { drop: true }
+      bucket_website:
+        add: true
+        id_field: bucket
+        operations:
+          create: { sdk_operation: put_bucket_website }
+"#,
+        )
+        .unwrap();
+
+        let mut services = vec![service()];
+        let unmatched = overlay.apply(&mut services);
+        assert!(unmatched.is_empty(), "unexpected: {:?}", unmatched);
+
+        let resources = &services[0].resources;
+        let names: Vec<&str> = resources.iter().map(|r| r.name.as_str()).collect();
+        assert_eq!(names, vec!["s3_bucket", "bucket_website"]);
+
+        let bucket = &resources[0];
+        assert_eq!(bucket.id_field.as_deref(), Some("name"));
+        let fields: Vec<&str> = bucket.fields.iter().map(|f| f.name.as_str()).collect();
+        assert_eq!(fields, vec!["name", "policy", "tags"]);
+        assert_eq!(bucket.fields[0].request_accessor.as_deref(), Some("bucket"));
+        assert!(bucket.fields[0].required && bucket.fields[0].immutable);
+        assert!(bucket.fields[1].sensitive);
+        assert_eq!(
+            bucket.fields[2].field_type,
+            FieldType::Map(Box::new(FieldType::String), Box::new(FieldType::String))
+        );
+
+        assert_eq!(bucket.outputs[0].name, "region");
+        assert_eq!(
+            bucket.outputs[0].response_accessor.as_deref(),
+            Some("location")
+        );
+
+        assert_eq!(bucket.blocks[0].name, "cors");
+        assert_eq!(
+            bucket.blocks[0].sdk_accessor_method.as_deref(),
+            Some("cors_rule")
+        );
+        assert_eq!(
+            bucket.blocks[0].attributes[0].field_type,
+            FieldType::Integer
+        );
+        assert_eq!(bucket.blocks[1].name, "logging");
+        assert_eq!(bucket.blocks[1].nesting_mode, NestingMode::Single);
+        assert_eq!(bucket.blocks[1].max_items, 1);
+
+        assert_eq!(
+            bucket
+                .operations
+                .read
+                .as_ref()
+                .map(|op| op.sdk_operation.as_str()),
+            Some("head_bucket")
+        );
+        assert!(bucket.operations.create.is_some());
+        assert!(bucket.operations.update.is_none());
+
+        let website = &resources[1];
+        assert_eq!(website.id_field.as_deref(), Some("bucket"));
+        assert!(website.operations.create.is_some());
+    }
+
+    #[test]
+    fn test_report_unmatched_entries() {
+        let overlay = Overlay::from_yaml(
+            r#"
+services:
+  s3:
+    resources:
+      bucket:
+        fields:
+          grant_read: { sensitive: true }
+        blocks:
+          cors_rule:
+            attributes:
+              allowed_origin: { required: true }
+      object: { drop: true }
+  ec2:
+    resources: {}
+"#,
+        )
+        .unwrap();
+
+        let mut services = vec![service()];
+        let unmatched = overlay.apply(&mut services);
+        assert_eq!(
+            unmatched,
+            vec![
+                "services.ec2",
+                "services.s3.resources.bucket.fields.grant_read",
+                "services.s3.resources.bucket.blocks.cors_rule.attributes.allowed_origin",
+                "services.s3.resources.object",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_set_lro_timeout() {
+        let overlay = Overlay::from_yaml(
+            r#"
+services:
+  s3:
+    resources:
+      bucket: { lro_timeout_secs: 3600 }
+      bucket_analytics: { lro_timeout_secs: 60 }
+"#,
+        )
+        .unwrap();
+
+        let mut services = vec![service()];
+        services[0].resources[0].operations.create = Some(OperationMapping {
+            sdk_operation: "create_bucket".to_string(),
+            long_running: Some(LongRunningConfig::default()),
+            ..Default::default()
+        });
+        let unmatched = overlay.apply(&mut services);
+
+        let operations = &services[0].resources[0].operations;
+        assert_eq!(
+            operations
+                .create
+                .as_ref()
+                .and_then(|op| op.long_running.as_ref())
+                .and_then(|lro| lro.timeout_secs),
+            Some(3600)
+        );
+        // Operations that return right away are left alone
+        assert!(operations.update.as_ref().unwrap().long_running.is_none());
+        // A resource without long-running operations has nothing to apply it to
+        assert_eq!(
+            unmatched,
+            vec!["services.s3.resources.bucket_analytics.lro_timeout_secs"]
+        );
+    }
+
+    #[test]
+    fn test_reject_unknown_keys() {
+        assert!(Overlay::from_yaml(
+            "services:\n  s3:\n    resources:\n      bucket: { sensitve: true }\n"
+        )
+        .is_err());
+        assert!(Overlay::from_yaml("services:\n  s3:\n    resources:\n      bucket:\n        fields:\n          acl: { sensitve: true }\n").is_err());
+    }
+}
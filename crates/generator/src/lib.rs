@@ -220,6 +220,7 @@ pub fn generate_provider(service_def: ServiceDefinition, output_path: &str) -> R
 /// - src/lib.rs (ProviderService implementation)
 /// - src/{service}/mod.rs (service handlers)
 /// - src/{service}/resources/*.rs (resource handlers)
+/// - src/{service}/data_sources/*.rs (data source lookups)
 /// - Cargo.toml
 /// - README.md
 pub struct UnifiedProviderGenerator {
@@ -273,8 +274,17 @@ impl UnifiedProviderGenerator {
                 ))
             })?;
 
+            let data_sources_dir = service_dir.join("data_sources");
+            fs::create_dir_all(&data_sources_dir).map_err(|e| {
+                GeneratorError::Generation(format!(
+                    "Failed to create data_sources directory for {}: {}",
+                    service.name, e
+                ))
+            })?;
+
             self.generate_service_mod(&service_dir, service)?;
             self.generate_service_resources(&resources_dir, service)?;
+            self.generate_service_data_sources(&data_sources_dir, service)?;
         }
 
         Ok(())
@@ -409,6 +419,60 @@ impl UnifiedProviderGenerator {
         Ok(())
     }
 
+    /// Generate service data sources
+    fn generate_service_data_sources(
+        &self,
+        data_sources_dir: &Path,
+        service: &ServiceDefinition,
+    ) -> Result<()> {
+        // Generate individual data source files
+        for data_source in &service.data_sources {
+            let mut context = self.create_unified_context();
+            context.insert("service", service);
+            context.insert("service_name", &service.name);
+            context.insert("data_source", data_source);
+
+            let rendered = self
+                .tera
+                .render("unified_data_source.rs", &context)
+                .map_err(|e| GeneratorError::Generation(format!("Template error: {}", e)))?;
+
+            let output_path = data_sources_dir.join(format!("{}.rs", data_source.name));
+            fs::write(output_path, rendered).map_err(|e| {
+                GeneratorError::Generation(format!(
+                    "Failed to write data source {}.rs: {}",
+                    data_source.name, e
+                ))
+            })?;
+        }
+
+        // Generate data_sources/mod.rs
+        let mut context = self.create_unified_context();
+        let data_source_names: Vec<&str> = service
+            .data_sources
+            .iter()
+            .map(|ds| ds.name.as_str())
+            .collect();
+        context.insert("resource_names", &data_source_names);
+        context.insert("is_unified", &true);
+        context.insert("module_doc", "Data source modules");
+
+        let rendered = self
+            .tera
+            .render("resources_mod.rs", &context)
+            .map_err(|e| GeneratorError::Generation(format!("Template error: {}", e)))?;
+
+        let output_path = data_sources_dir.join("mod.rs");
+        fs::write(output_path, rendered).map_err(|e| {
+            GeneratorError::Generation(format!(
+                "Failed to write {}/data_sources/mod.rs: {}",
+                service.name, e
+            ))
+        })?;
+
+        Ok(())
+    }
+
     /// Generate README.md
     fn generate_unified_readme(&self, output_dir: &Path) -> Result<()> {
         let context = self.create_unified_context();
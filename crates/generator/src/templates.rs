@@ -26,6 +26,14 @@ pub fn load_templates() -> Result<Tera> {
     tera.register_filter("to_camel_case", to_camel_case_filter);
     tera.register_filter("json_extractor", json_extractor_filter);
     tera.register_filter("nesting_mode", nesting_mode_filter);
+    tera.register_filter("attribute_constraints", attribute_constraints_filter);
+    tera.register_filter("block_constraints", block_constraints_filter);
+    tera.register_filter("json_conversion", json_conversion_filter);
+    tera.register_filter("json_value", json_value_filter);
+    tera.register_filter("sdk_member", sdk_member_filter);
+    tera.register_filter("deprecation_message", deprecation_message_filter);
+
+    add_macros(&mut tera)?;
 
     // Add templates inline for now (Phase 3 MVP)
     // In production, these could be loaded from files
@@ -100,6 +108,14 @@ pub fn load_unified_templates() -> Result<Tera> {
     tera.register_filter("to_camel_case", to_camel_case_filter);
     tera.register_filter("json_extractor", json_extractor_filter);
     tera.register_filter("nesting_mode", nesting_mode_filter);
+    tera.register_filter("attribute_constraints", attribute_constraints_filter);
+    tera.register_filter("block_constraints", block_constraints_filter);
+    tera.register_filter("json_conversion", json_conversion_filter);
+    tera.register_filter("json_value", json_value_filter);
+    tera.register_filter("sdk_member", sdk_member_filter);
+    tera.register_filter("deprecation_message", deprecation_message_filter);
+
+    add_macros(&mut tera)?;
 
     // Add unified templates
     tera.add_raw_template(
@@ -156,6 +172,17 @@ pub fn load_unified_templates() -> Result<Tera> {
         ))
     })?;
 
+    tera.add_raw_template(
+        "unified_data_source.rs",
+        include_str!("../templates/unified_data_source.rs.tera"),
+    )
+    .map_err(|e| {
+        GeneratorError::Generation(format!(
+            "Failed to load unified_data_source.rs template: {}",
+            e
+        ))
+    })?;
+
     tera.add_raw_template(
         "resources_mod.rs",
         include_str!("../templates/resources_mod.rs.tera"),
@@ -211,6 +238,14 @@ pub fn load_unified_templates() -> Result<Tera> {
     Ok(tera)
 }
 
+/// Add the macros shared by the resource templates, which import them
+fn add_macros(tera: &mut Tera) -> Result<()> {
+    tera.add_raw_template("macros.rs", include_str!("../templates/macros.rs.tera"))
+        .map_err(|e| {
+            GeneratorError::Generation(format!("Failed to load macros.rs template: {}", e))
+        })
+}
+
 /// Filter to convert FieldType to KCL type
 fn kcl_type_filter(value: &Value, _args: &HashMap<String, Value>) -> tera::Result<Value> {
     use hemmer_provider_generator_common::FieldType;
@@ -228,6 +263,9 @@ fn kcl_type_filter(value: &Value, _args: &HashMap<String, Value>) -> tera::Resul
         "Integer" => "int".to_string(),
         "Boolean" => "bool".to_string(),
         "Float" => "float".to_string(),
+        "Any" => "any".to_string(),
+        // Enum variants are already a KCL literal union
+        _ if kcl_type.starts_with('"') => kcl_type,
         _ if kcl_type.starts_with("List<") => {
             // Extract inner type and convert: List<String> -> [str]
             let inner = kcl_type
@@ -297,6 +335,7 @@ fn lower_filter(value: &Value, _args: &HashMap<String, Value>) -> tera::Result<V
 ///   - Aws + "configservice" -> "aws-sdk-config" (special mapping)
 ///   - Gcp + "storage" -> "google-storage"
 ///   - Azure + "compute" -> "azure-compute"
+///   - Kubernetes + any -> "" (shared `kube` client, see provider dependencies)
 fn sdk_dependency_filter(value: &Value, args: &HashMap<String, Value>) -> tera::Result<Value> {
     let provider = value
         .as_str()
@@ -338,6 +377,8 @@ fn sdk_dependency_filter(value: &Value, args: &HashMap<String, Value>) -> tera::
         },
         "Gcp" => format!("google-{}", service_name),
         "Azure" => format!("azure-{}", service_name),
+        // One shared client crate for all API groups, listed in the provider's dependencies
+        "Kubernetes" => String::new(),
         _ => {
             return Err(tera::Error::msg(format!(
                 "Unsupported provider for sdk_dependency: {}",
@@ -433,6 +474,13 @@ fn field_type_to_jcl(field_type: &hemmer_provider_generator_common::FieldType) -
         },
         FieldType::Enum(_) => "string".to_string(), // Enums represented as strings in JCL
         FieldType::Object(_) => "object".to_string(), // Complex objects
+        FieldType::Int8
+        | FieldType::Int16
+        | FieldType::Int32
+        | FieldType::UInt32
+        | FieldType::UInt64 => "int".to_string(),
+        FieldType::Bytes => "string".to_string(), // Base64 encoded
+        FieldType::Document | FieldType::Union(_) => "object".to_string(),
     }
 }
 
@@ -474,6 +522,13 @@ fn field_type_to_sdk_attr(field_type: &hemmer_provider_generator_common::FieldTy
         },
         FieldType::Enum(_) => "AttributeType::String".to_string(), // Enums as strings
         FieldType::Object(_) => "AttributeType::Dynamic".to_string(), // Complex objects as dynamic
+        FieldType::Int8
+        | FieldType::Int16
+        | FieldType::Int32
+        | FieldType::UInt32
+        | FieldType::UInt64 => "AttributeType::Int64".to_string(),
+        FieldType::Bytes => "AttributeType::String".to_string(), // Base64 encoded
+        FieldType::Document | FieldType::Union(_) => "AttributeType::Dynamic".to_string(),
     }
 }
 
@@ -667,6 +722,15 @@ fn json_extractor_filter(value: &Value, _args: &HashMap<String, Value>) -> tera:
         FieldType::Map(_, _) => "as_object().cloned()",
         FieldType::Enum(_) => "as_str().map(|s| s.to_string())",
         FieldType::Object(_) => "clone()",
+        // Out of range values are rejected by the generated constraint checks
+        FieldType::Int8 => "as_i64().and_then(|n| i8::try_from(n).ok())",
+        FieldType::Int16 => "as_i64().and_then(|n| i16::try_from(n).ok())",
+        FieldType::Int32 => "as_i64().and_then(|n| i32::try_from(n).ok())",
+        FieldType::UInt32 => "as_u64().and_then(|n| u32::try_from(n).ok())",
+        FieldType::UInt64 => "as_u64()",
+        FieldType::Bytes => "as_str().and_then(|s| base64::Engine::decode(&base64::engine::general_purpose::STANDARD, s).ok())",
+        FieldType::Document => "clone()",
+        FieldType::Union(_) => "as_object().cloned()",
     };
 
     Ok(Value::String(extractor.to_string()))
@@ -689,3 +753,247 @@ fn nesting_mode_filter(value: &Value, _args: &HashMap<String, Value>) -> tera::R
 
     Ok(Value::String(sdk_mode.to_string()))
 }
+
+/// Filter to convert a FieldDefinition to an `AttributeConstraints` literal
+/// Usage: {{ field | attribute_constraints }} -> "" when there is nothing to enforce
+fn attribute_constraints_filter(
+    value: &Value,
+    _args: &HashMap<String, Value>,
+) -> tera::Result<Value> {
+    use hemmer_provider_generator_common::{FieldDefinition, FieldType};
+
+    let field: FieldDefinition = serde_json::from_value(value.clone())
+        .map_err(|e| tera::Error::msg(format!("Failed to deserialize FieldDefinition: {}", e)))?;
+
+    let allowed_values = match &field.field_type {
+        FieldType::Enum(values) => values.clone(),
+        _ => Vec::new(),
+    };
+    let mut one_of: Vec<String> = match &field.field_type {
+        FieldType::Union(variants) => variants.keys().cloned().collect(),
+        _ => Vec::new(),
+    };
+    one_of.sort();
+
+    // Sized integers can't hold values outside of their type's range
+    let (type_minimum, type_maximum) = match field.field_type {
+        FieldType::Int8 => (Some(i8::MIN as f64), Some(i8::MAX as f64)),
+        FieldType::Int16 => (Some(i16::MIN as f64), Some(i16::MAX as f64)),
+        FieldType::Int32 => (Some(i32::MIN as f64), Some(i32::MAX as f64)),
+        FieldType::UInt32 => (Some(0.0), Some(u32::MAX as f64)),
+        FieldType::UInt64 => (Some(0.0), None),
+        _ => (None, None),
+    };
+    let mut constraints = field.constraints.clone();
+    constraints.minimum = constraints.minimum.or(type_minimum);
+    constraints.maximum = constraints.maximum.or(type_maximum);
+
+    if constraints.is_empty()
+        && field.default_value.is_none()
+        && allowed_values.is_empty()
+        && one_of.is_empty()
+    {
+        return Ok(Value::String(String::new()));
+    }
+
+    Ok(Value::String(constraints_literal(
+        &field.name,
+        &constraints,
+        &allowed_values,
+        &one_of,
+        field.default_value.as_ref(),
+    )))
+}
+
+/// Filter to convert a one-of BlockDefinition to an `AttributeConstraints` literal
+/// Usage: {{ block | block_constraints }} -> "" for blocks without constraints
+fn block_constraints_filter(value: &Value, _args: &HashMap<String, Value>) -> tera::Result<Value> {
+    use hemmer_provider_generator_common::{BlockDefinition, FieldConstraints};
+
+    let block: BlockDefinition = serde_json::from_value(value.clone())
+        .map_err(|e| tera::Error::msg(format!("Failed to deserialize BlockDefinition: {}", e)))?;
+
+    if !block.one_of {
+        return Ok(Value::String(String::new()));
+    }
+
+    // Members of a union are its attributes and nested blocks
+    let mut one_of: Vec<String> = block
+        .attributes
+        .iter()
+        .map(|a| a.name.clone())
+        .chain(block.blocks.iter().map(|b| b.name.clone()))
+        .collect();
+    one_of.sort();
+
+    Ok(Value::String(constraints_literal(
+        &block.name,
+        &FieldConstraints::default(),
+        &[],
+        &one_of,
+        None,
+    )))
+}
+
+/// Build an `AttributeConstraints` literal of the generated provider
+fn constraints_literal(
+    name: &str,
+    constraints: &hemmer_provider_generator_common::FieldConstraints,
+    allowed_values: &[String],
+    one_of: &[String],
+    default_value: Option<&serde_json::Value>,
+) -> String {
+    let option = |v: Option<String>| v.map_or("None".to_string(), |v| format!("Some({})", v));
+    let list = |values: &[String]| {
+        values
+            .iter()
+            .map(|v| rust_string_literal(v))
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+
+    format!(
+        "AttributeConstraints {{ name: {}, minimum: {}, maximum: {}, min_length: {}, max_length: {}, pattern: {}, allowed_values: &[{}], one_of: &[{}], default_value: {} }}",
+        rust_string_literal(name),
+        option(constraints.minimum.map(|n| format!("{:?}", n))),
+        option(constraints.maximum.map(|n| format!("{:?}", n))),
+        option(constraints.min_length.map(|n| n.to_string())),
+        option(constraints.max_length.map(|n| n.to_string())),
+        option(constraints.pattern.as_deref().map(rust_string_literal)),
+        list(allowed_values),
+        list(one_of),
+        option(default_value.map(|v| rust_string_literal(&v.to_string()))),
+    )
+}
+
+/// Filter to convert a FieldDefinition to an expression reading its value from the JSON `val`
+/// Usage: {{ attr | json_conversion(provider=provider, service_name=service_name) }}
+///   -> "val.as_bool()", or "" when the value can't be converted
+///
+/// The expression evaluates to an `Option` of the type the SDK expects for the field.
+fn json_conversion_filter(value: &Value, args: &HashMap<String, Value>) -> tera::Result<Value> {
+    use hemmer_provider_generator_common::{FieldDefinition, FieldType};
+
+    let field: FieldDefinition = serde_json::from_value(value.clone())
+        .map_err(|e| tera::Error::msg(format!("Failed to deserialize FieldDefinition: {}", e)))?;
+    let provider = args.get("provider").and_then(|v| v.as_str()).unwrap_or("");
+
+    let conversion = match &field.field_type {
+        FieldType::Enum(_) if field.sdk_type_name.is_some() => {
+            let module = sdk_crate_module_filter(&Value::String(provider.to_string()), args)?;
+            format!(
+                "val.as_str().map({}::types::{}::from)",
+                module.as_str().unwrap_or_default(),
+                field.sdk_type_name.as_deref().unwrap_or_default()
+            )
+        },
+        FieldType::String | FieldType::DateTime | FieldType::Enum(_) => {
+            "val.as_str().map(|s| s.to_string())".to_string()
+        },
+        FieldType::Integer
+        | FieldType::Int8
+        | FieldType::Int16
+        | FieldType::Int32
+        | FieldType::UInt32
+        | FieldType::UInt64
+        | FieldType::Float
+        | FieldType::Boolean => {
+            let extractor = json_extractor_filter(&serde_json::to_value(&field.field_type)?, args)?;
+            format!("val.{}", extractor.as_str().unwrap_or_default())
+        },
+        FieldType::Bytes => format!(
+            "val.{}.map(Into::into)",
+            json_extractor_filter(&serde_json::to_value(&field.field_type)?, args)?
+                .as_str()
+                .unwrap_or_default()
+        ),
+        // A null document leaves the member unset
+        FieldType::Document if provider == "Aws" => {
+            "(!val.is_null()).then(|| crate::json_to_document(val))".to_string()
+        },
+        FieldType::Document => "(!val.is_null()).then(|| val.clone())".to_string(),
+        // Collections and nested structures need builders of their own
+        FieldType::List(_) | FieldType::Map(_, _) | FieldType::Object(_) | FieldType::Union(_) => {
+            String::new()
+        },
+    };
+
+    Ok(Value::String(conversion))
+}
+
+/// Filter to convert a FieldDefinition to an expression turning the SDK value `val` into JSON
+/// Usage: {{ attr | json_value(provider=provider) }} -> "Some(serde_json::Value::Bool(*val))"
+///
+/// `val` is a reference as returned by SDK accessors. The expression evaluates
+/// to an `Option<serde_json::Value>`.
+fn json_value_filter(value: &Value, args: &HashMap<String, Value>) -> tera::Result<Value> {
+    use hemmer_provider_generator_common::{FieldDefinition, FieldType};
+
+    let field: FieldDefinition = serde_json::from_value(value.clone())
+        .map_err(|e| tera::Error::msg(format!("Failed to deserialize FieldDefinition: {}", e)))?;
+    let provider = args.get("provider").and_then(|v| v.as_str()).unwrap_or("");
+
+    let conversion = match &field.field_type {
+        FieldType::String => "Some(serde_json::Value::String(val.to_string()))",
+        FieldType::Enum(_) if field.sdk_type_name.is_some() => {
+            "Some(serde_json::Value::String(val.as_str().to_string()))"
+        },
+        FieldType::Enum(_) => "Some(serde_json::Value::String(val.to_string()))",
+        FieldType::Integer
+        | FieldType::Int8
+        | FieldType::Int16
+        | FieldType::Int32
+        | FieldType::UInt32
+        | FieldType::UInt64 => "Some(serde_json::Value::Number(serde_json::Number::from(*val)))",
+        FieldType::Float => "serde_json::Number::from_f64(*val).map(serde_json::Value::Number)",
+        FieldType::Boolean => "Some(serde_json::Value::Bool(*val))",
+        FieldType::Bytes => "Some(serde_json::Value::String(base64::Engine::encode(&base64::engine::general_purpose::STANDARD, val)))",
+        FieldType::Document if provider == "Aws" => "Some(crate::document_to_json(val))",
+        FieldType::Document => "Some(val.clone())",
+        // Timestamps and complex types are kept as their debug representation
+        FieldType::DateTime
+        | FieldType::List(_)
+        | FieldType::Map(_, _)
+        | FieldType::Object(_)
+        | FieldType::Union(_) => {
+            "Some(serde_json::Value::String(format!(\"{:?}\", val)))"
+        },
+    };
+
+    Ok(Value::String(conversion.to_string()))
+}
+
+/// Filter to get the SDK member name of a FieldDefinition, used for its setter and getter
+/// Usage: {{ field | sdk_member }} -> "bucket", or the field name when it has no request accessor
+fn sdk_member_filter(value: &Value, _args: &HashMap<String, Value>) -> tera::Result<Value> {
+    use hemmer_provider_generator_common::FieldDefinition;
+
+    let field: FieldDefinition = serde_json::from_value(value.clone())
+        .map_err(|e| tera::Error::msg(format!("Failed to deserialize FieldDefinition: {}", e)))?;
+
+    Ok(Value::String(field.request_accessor.unwrap_or(field.name)))
+}
+
+/// Filter to convert a Deprecation to a string literal of its message
+/// Usage: {{ field.deprecated | deprecation_message }} -> "Use x instead", or "" without a message
+fn deprecation_message_filter(
+    value: &Value,
+    _args: &HashMap<String, Value>,
+) -> tera::Result<Value> {
+    use hemmer_provider_generator_common::Deprecation;
+
+    let deprecation: Deprecation = serde_json::from_value(value.clone())
+        .map_err(|e| tera::Error::msg(format!("Failed to deserialize Deprecation: {}", e)))?;
+
+    Ok(Value::String(rust_string_literal(
+        deprecation.message.as_deref().unwrap_or_default(),
+    )))
+}
+
+/// Quote a string as a Rust string literal
+///
+/// `Debug` escapes quotes, backslashes and control characters with escapes
+/// Rust accepts (`\u{1f}`), unlike JSON's `\b`, `\f` and `\u001f`.
+fn rust_string_literal(s: &str) -> String {
+    format!("{:?}", s)
+}
@@ -2,7 +2,7 @@
 
 use hemmer_provider_generator_common::{
     BlockDefinition, FieldDefinition, FieldType, NestingMode, OperationMapping, Operations,
-    Provider, ResourceDefinition, ServiceDefinition,
+    Provider, ResourceDefinition, ServiceDefinition, Stability,
 };
 use hemmer_provider_generator_generator::ProviderGenerator;
 use tempfile::TempDir;
@@ -27,6 +27,7 @@ fn test_generate_s3_provider() {
                     immutable: true,
                     description: Some("Bucket name".to_string()),
                     response_accessor: None,
+                    ..Default::default()
                 },
                 FieldDefinition {
                     name: "acl".to_string(),
@@ -36,6 +37,7 @@ fn test_generate_s3_provider() {
                     immutable: false,
                     description: Some("Access control list".to_string()),
                     response_accessor: None,
+                    ..Default::default()
                 },
             ],
             outputs: vec![FieldDefinition {
@@ -46,6 +48,7 @@ fn test_generate_s3_provider() {
                 immutable: true,
                 description: Some("Amazon Resource Name".to_string()),
                 response_accessor: Some("arn".to_string()),
+                ..Default::default()
             }],
             blocks: vec![BlockDefinition {
                 name: "lifecycle_rule".to_string(),
@@ -59,6 +62,7 @@ fn test_generate_s3_provider() {
                         immutable: false,
                         description: Some("Unique identifier for the rule".to_string()),
                         response_accessor: None,
+                        ..Default::default()
                     },
                     FieldDefinition {
                         name: "enabled".to_string(),
@@ -68,6 +72,7 @@ fn test_generate_s3_provider() {
                         immutable: false,
                         description: Some("Whether the rule is enabled".to_string()),
                         response_accessor: None,
+                        ..Default::default()
                     },
                     FieldDefinition {
                         name: "prefix".to_string(),
@@ -77,6 +82,7 @@ fn test_generate_s3_provider() {
                         immutable: false,
                         description: Some("Object key prefix filter".to_string()),
                         response_accessor: None,
+                        ..Default::default()
                     },
                     FieldDefinition {
                         name: "expiration_days".to_string(),
@@ -86,6 +92,7 @@ fn test_generate_s3_provider() {
                         immutable: false,
                         description: Some("Number of days until objects expire".to_string()),
                         response_accessor: None,
+                        ..Default::default()
                     },
                 ],
                 blocks: vec![BlockDefinition {
@@ -100,6 +107,7 @@ fn test_generate_s3_provider() {
                             immutable: false,
                             description: Some("Days until transition".to_string()),
                             response_accessor: None,
+                            ..Default::default()
                         },
                         FieldDefinition {
                             name: "storage_class".to_string(),
@@ -109,6 +117,7 @@ fn test_generate_s3_provider() {
                             immutable: false,
                             description: Some("Target storage class".to_string()),
                             response_accessor: None,
+                            ..Default::default()
                         },
                     ],
                     blocks: vec![], // Could nest even further
@@ -117,33 +126,41 @@ fn test_generate_s3_provider() {
                     max_items: 0,
                     sdk_type_name: Some("Transition".to_string()),
                     sdk_accessor_method: Some("set_transitions".to_string()),
+                    one_of: false,
                 }],
                 nesting_mode: NestingMode::List,
                 min_items: 0,
                 max_items: 0, // 0 = unlimited
                 sdk_type_name: Some("LifecycleRule".to_string()),
                 sdk_accessor_method: Some("set_lifecycle_rules".to_string()),
+                one_of: false,
             }],
             id_field: None, // Will implement ID detection later
             operations: Operations {
                 create: Some(OperationMapping {
                     sdk_operation: "create_bucket".to_string(),
                     additional_operations: vec![],
+                    ..Default::default()
                 }),
                 read: Some(OperationMapping {
                     sdk_operation: "head_bucket".to_string(),
                     additional_operations: vec![],
+                    ..Default::default()
                 }),
                 update: Some(OperationMapping {
                     sdk_operation: "put_bucket_acl".to_string(),
                     additional_operations: vec![],
+                    ..Default::default()
                 }),
                 delete: Some(OperationMapping {
                     sdk_operation: "delete_bucket".to_string(),
                     additional_operations: vec![],
+                    ..Default::default()
                 }),
                 import: None, // Will implement later
             },
+            deprecated: None,
+            stability: Stability::Ga,
         }],
     };
 
@@ -313,6 +330,7 @@ fn test_generated_provider_compiles() {
                 immutable: true,
                 description: Some("Bucket name".to_string()),
                 response_accessor: None,
+                ..Default::default()
             }],
             outputs: vec![], // Simplified - no outputs for basic compilation test
             blocks: vec![],
@@ -321,18 +339,23 @@ fn test_generated_provider_compiles() {
                 create: Some(OperationMapping {
                     sdk_operation: "create_bucket".to_string(),
                     additional_operations: vec![],
+                    ..Default::default()
                 }),
                 read: Some(OperationMapping {
                     sdk_operation: "head_bucket".to_string(),
                     additional_operations: vec![],
+                    ..Default::default()
                 }),
                 update: None, // S3 buckets don't have a simple update operation
                 delete: Some(OperationMapping {
                     sdk_operation: "delete_bucket".to_string(),
                     additional_operations: vec![],
+                    ..Default::default()
                 }),
                 import: None,
             },
+            deprecated: None,
+            stability: Stability::Ga,
         }],
     };
 
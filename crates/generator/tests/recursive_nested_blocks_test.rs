@@ -5,7 +5,7 @@
 
 use hemmer_provider_generator_common::{
     BlockDefinition, FieldDefinition, FieldType, NestingMode, OperationMapping, Operations,
-    Provider, ResourceDefinition, ServiceDefinition,
+    Provider, ResourceDefinition, ServiceDefinition, Stability,
 };
 use hemmer_provider_generator_generator::ProviderGenerator;
 use tempfile::TempDir;
@@ -30,6 +30,7 @@ fn test_generate_provider_with_recursive_nested_blocks() {
                 immutable: false,
                 description: Some("Bucket name".to_string()),
                 response_accessor: None,
+                ..Default::default()
             }],
             outputs: vec![],
             blocks: vec![BlockDefinition {
@@ -48,6 +49,7 @@ fn test_generate_provider_with_recursive_nested_blocks() {
                             immutable: false,
                             description: Some("Rule ID".to_string()),
                             response_accessor: None,
+                            ..Default::default()
                         },
                         FieldDefinition {
                             name: "status".to_string(),
@@ -57,6 +59,7 @@ fn test_generate_provider_with_recursive_nested_blocks() {
                             immutable: false,
                             description: Some("Rule status".to_string()),
                             response_accessor: None,
+                            ..Default::default()
                         },
                     ],
                     blocks: vec![],
@@ -65,33 +68,41 @@ fn test_generate_provider_with_recursive_nested_blocks() {
                     max_items: 0,
                     sdk_type_name: Some("LifecycleRule".to_string()),
                     sdk_accessor_method: Some("set_rules".to_string()),
+                    one_of: false,
                 }],
                 nesting_mode: NestingMode::Single,
                 min_items: 1,
                 max_items: 1,
                 sdk_type_name: Some("BucketLifecycleConfiguration".to_string()),
                 sdk_accessor_method: Some("set_lifecycle_configuration".to_string()),
+                one_of: false,
             }],
             id_field: None,
             operations: Operations {
                 create: Some(OperationMapping {
                     sdk_operation: "put_bucket_lifecycle_configuration".to_string(),
                     additional_operations: vec![],
+                    ..Default::default()
                 }),
                 read: Some(OperationMapping {
                     sdk_operation: "get_bucket_lifecycle_configuration".to_string(),
                     additional_operations: vec![],
+                    ..Default::default()
                 }),
                 update: Some(OperationMapping {
                     sdk_operation: "put_bucket_lifecycle_configuration".to_string(),
                     additional_operations: vec![],
+                    ..Default::default()
                 }),
                 delete: Some(OperationMapping {
                     sdk_operation: "delete_bucket_lifecycle".to_string(),
                     additional_operations: vec![],
+                    ..Default::default()
                 }),
                 import: None,
             },
+            deprecated: None,
+            stability: Stability::Ga,
         }],
     };
 
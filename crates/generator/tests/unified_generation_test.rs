@@ -1,8 +1,11 @@
 //! Integration test for unified multi-service provider generation
 
+use hemmer_provider_generator_common::overlay::Overlay;
 use hemmer_provider_generator_common::{
-    BlockDefinition, FieldDefinition, FieldType, NestingMode, OperationMapping, Operations,
-    Provider, ProviderDefinition, ResourceDefinition, ServiceDefinition,
+    BlockDefinition, DataSourceDefinition, Deprecation, FieldConstraints, FieldDefinition,
+    FieldType, LongRunningConfig, NestingMode, OperationMapping, Operations, Provider,
+    ProviderDefinition, ResourceDefinition, ServiceDefinition, Stability, WaiterAcceptor,
+    WaiterDefinition, WaiterState,
 };
 use hemmer_provider_generator_generator::UnifiedProviderGenerator;
 use std::fs;
@@ -28,6 +31,7 @@ fn test_generate_unified_aws_provider() {
                     immutable: true,
                     description: Some("The name of the bucket".to_string()),
                     response_accessor: None,
+                    ..Default::default()
                 },
                 FieldDefinition {
                     name: "region".to_string(),
@@ -37,6 +41,7 @@ fn test_generate_unified_aws_provider() {
                     immutable: true,
                     description: Some("The AWS region".to_string()),
                     response_accessor: None,
+                    ..Default::default()
                 },
             ],
             outputs: vec![FieldDefinition {
@@ -47,6 +52,7 @@ fn test_generate_unified_aws_provider() {
                 immutable: false,
                 description: Some("The ARN of the bucket".to_string()),
                 response_accessor: Some("arn".to_string()),
+                ..Default::default()
             }],
             blocks: vec![],
             id_field: None, // Will implement ID detection later
@@ -54,18 +60,23 @@ fn test_generate_unified_aws_provider() {
                 create: Some(OperationMapping {
                     sdk_operation: "create_bucket".to_string(),
                     additional_operations: vec![],
+                    ..Default::default()
                 }),
                 read: Some(OperationMapping {
                     sdk_operation: "head_bucket".to_string(),
                     additional_operations: vec![],
+                    ..Default::default()
                 }),
                 update: None,
                 delete: Some(OperationMapping {
                     sdk_operation: "delete_bucket".to_string(),
                     additional_operations: vec![],
+                    ..Default::default()
                 }),
                 import: None, // Will implement later
             },
+            deprecated: None,
+            stability: Stability::Ga,
         }],
     };
 
@@ -86,6 +97,7 @@ fn test_generate_unified_aws_provider() {
                     immutable: true,
                     description: Some("The name of the table".to_string()),
                     response_accessor: None,
+                    ..Default::default()
                 },
                 FieldDefinition {
                     name: "read_capacity".to_string(),
@@ -95,6 +107,7 @@ fn test_generate_unified_aws_provider() {
                     immutable: false,
                     description: Some("Read capacity units".to_string()),
                     response_accessor: None,
+                    ..Default::default()
                 },
             ],
             outputs: vec![FieldDefinition {
@@ -105,6 +118,7 @@ fn test_generate_unified_aws_provider() {
                 immutable: false,
                 description: Some("The ARN of the table".to_string()),
                 response_accessor: Some("table_arn".to_string()),
+                ..Default::default()
             }],
             blocks: vec![BlockDefinition {
                 name: "global_secondary_index".to_string(),
@@ -118,6 +132,7 @@ fn test_generate_unified_aws_provider() {
                         immutable: false,
                         description: Some("Name of the index".to_string()),
                         response_accessor: None,
+                        ..Default::default()
                     },
                     FieldDefinition {
                         name: "hash_key".to_string(),
@@ -127,6 +142,7 @@ fn test_generate_unified_aws_provider() {
                         immutable: false,
                         description: Some("Hash key attribute name".to_string()),
                         response_accessor: None,
+                        ..Default::default()
                     },
                     FieldDefinition {
                         name: "range_key".to_string(),
@@ -136,6 +152,7 @@ fn test_generate_unified_aws_provider() {
                         immutable: false,
                         description: Some("Range key attribute name".to_string()),
                         response_accessor: None,
+                        ..Default::default()
                     },
                 ],
                 blocks: vec![], // Could have nested projection blocks
@@ -144,27 +161,34 @@ fn test_generate_unified_aws_provider() {
                 max_items: 20, // DynamoDB limit
                 sdk_type_name: Some("GlobalSecondaryIndex".to_string()),
                 sdk_accessor_method: Some("set_global_secondary_indexes".to_string()),
+                one_of: false,
             }],
             id_field: None, // Will implement ID detection later
             operations: Operations {
                 create: Some(OperationMapping {
                     sdk_operation: "create_table".to_string(),
                     additional_operations: vec![],
+                    ..Default::default()
                 }),
                 read: Some(OperationMapping {
                     sdk_operation: "describe_table".to_string(),
                     additional_operations: vec![],
+                    ..Default::default()
                 }),
                 update: Some(OperationMapping {
                     sdk_operation: "update_table".to_string(),
                     additional_operations: vec![],
+                    ..Default::default()
                 }),
                 delete: Some(OperationMapping {
                     sdk_operation: "delete_table".to_string(),
                     additional_operations: vec![],
+                    ..Default::default()
                 }),
                 import: None, // Will implement later
             },
+            deprecated: None,
+            stability: Stability::Ga,
         }],
     };
 
@@ -289,6 +313,1189 @@ fn test_generate_unified_provider_with_empty_services() {
     fs::remove_dir_all(&output_dir).expect("Failed to clean up test directory");
 }
 
+#[test]
+fn test_generate_unified_provider_with_waiters_and_idempotency_tokens() {
+    use tempfile::TempDir;
+
+    let exists_waiter = WaiterDefinition {
+        name: "table_exists".to_string(),
+        description: None,
+        operation: "describe_table".to_string(),
+        acceptors: vec![WaiterAcceptor {
+            state: WaiterState::Success,
+            matcher: serde_json::json!({ "success": true }),
+        }],
+        min_delay: Some(20),
+        max_delay: None,
+    };
+    let not_exists_waiter = WaiterDefinition {
+        name: "table_not_exists".to_string(),
+        ..exists_waiter.clone()
+    };
+
+    let dynamodb_service = ServiceDefinition {
+        provider: Provider::Aws,
+        name: "dynamodb".to_string(),
+        sdk_version: "1.0.0".to_string(),
+        data_sources: vec![],
+        resources: vec![ResourceDefinition {
+            name: "table".to_string(),
+            description: Some("DynamoDB table resource".to_string()),
+            fields: vec![FieldDefinition {
+                name: "table_name".to_string(),
+                field_type: FieldType::String,
+                required: true,
+                sensitive: false,
+                immutable: true,
+                description: Some("The name of the table".to_string()),
+                response_accessor: None,
+                ..Default::default()
+            }],
+            outputs: vec![],
+            blocks: vec![],
+            id_field: Some("table_name".to_string()),
+            operations: Operations {
+                create: Some(OperationMapping {
+                    sdk_operation: "create_table".to_string(),
+                    additional_operations: vec![],
+                    idempotency_token: Some("client_token".to_string()),
+                    waiter: Some(exists_waiter),
+                    ..Default::default()
+                }),
+                read: Some(OperationMapping {
+                    sdk_operation: "describe_table".to_string(),
+                    additional_operations: vec![],
+                    readonly: true,
+                    idempotent: true,
+                    ..Default::default()
+                }),
+                update: None,
+                delete: Some(OperationMapping {
+                    sdk_operation: "delete_table".to_string(),
+                    additional_operations: vec![],
+                    waiter: Some(not_exists_waiter),
+                    ..Default::default()
+                }),
+                import: None,
+            },
+            deprecated: None,
+            stability: Stability::Ga,
+        }],
+    };
+
+    let provider_def = ProviderDefinition {
+        provider: Provider::Aws,
+        provider_name: "aws".to_string(),
+        sdk_version: "1.0.0".to_string(),
+        services: vec![dynamodb_service],
+    };
+
+    let temp_dir = TempDir::new().unwrap();
+    let generator = UnifiedProviderGenerator::new(provider_def).unwrap();
+    generator
+        .generate_to_directory(temp_dir.path())
+        .expect("Failed to generate unified provider");
+
+    let table_rs = fs::read_to_string(temp_dir.path().join("src/dynamodb/resources/table.rs"))
+        .expect("Failed to read table.rs");
+
+    // Creates fill in the idempotency token themselves
+    assert!(table_rs.contains("request = request.client_token(uuid::Uuid::new_v4().to_string());"));
+
+    // Create and delete wait for the table to settle
+    assert!(table_rs.contains("use aws_sdk_dynamodb::client::Waiters as _;"));
+    assert!(table_rs.contains("client.wait_until_table_exists()"));
+    assert!(table_rs.contains("client.wait_until_table_not_exists()"));
+    assert!(table_rs.contains("waiter = waiter.table_name(s);"));
+    assert!(
+        table_rs.contains("ProviderError::Sdk(format!(\"table did not reach the expected state")
+    );
+    assert!(!table_rs.contains("ProviderError::SdkError"));
+
+    let cargo_toml = fs::read_to_string(temp_dir.path().join("Cargo.toml")).unwrap();
+    assert!(cargo_toml.contains("uuid = { version = \"1\", features = [\"v4\"] }"));
+
+    println!("✅ Generated waiters and idempotency tokens verified");
+}
+
+#[test]
+fn test_generate_unified_provider_with_long_running_operations() {
+    use tempfile::TempDir;
+
+    let mut compute_service = ServiceDefinition {
+        provider: Provider::Azure,
+        name: "compute".to_string(),
+        sdk_version: "1.0.0".to_string(),
+        data_sources: vec![],
+        resources: vec![
+            ResourceDefinition {
+                name: "disk".to_string(),
+                description: Some("Managed disk".to_string()),
+                fields: vec![FieldDefinition {
+                    name: "disk_name".to_string(),
+                    field_type: FieldType::String,
+                    required: true,
+                    immutable: true,
+                    ..Default::default()
+                }],
+                outputs: vec![],
+                blocks: vec![],
+                id_field: Some("disk_name".to_string()),
+                operations: Operations {
+                    create: Some(OperationMapping {
+                        sdk_operation: "create_or_update".to_string(),
+                        long_running: Some(LongRunningConfig {
+                            timeout_secs: Some(900),
+                            ..Default::default()
+                        }),
+                        ..Default::default()
+                    }),
+                    read: Some(OperationMapping {
+                        sdk_operation: "get".to_string(),
+                        ..Default::default()
+                    }),
+                    update: None,
+                    delete: Some(OperationMapping {
+                        sdk_operation: "delete".to_string(),
+                        long_running: Some(LongRunningConfig::default()),
+                        ..Default::default()
+                    }),
+                    import: None,
+                },
+                deprecated: None,
+                stability: Stability::Ga,
+            },
+            ResourceDefinition {
+                name: "snapshot".to_string(),
+                description: None,
+                fields: vec![],
+                outputs: vec![],
+                blocks: vec![],
+                id_field: None,
+                operations: Operations {
+                    create: Some(OperationMapping {
+                        sdk_operation: "insert".to_string(),
+                        long_running: Some(LongRunningConfig {
+                            operations_resource: Some("zone_operations".to_string()),
+                            operation_scope: vec!["project".to_string(), "zone".to_string()],
+                            ..Default::default()
+                        }),
+                        ..Default::default()
+                    }),
+                    read: None,
+                    update: None,
+                    delete: None,
+                    import: None,
+                },
+                deprecated: None,
+                stability: Stability::Ga,
+            },
+        ],
+    };
+
+    // Overlays set the timeout of a resource's long-running operations
+    let overlay = Overlay::from_yaml(
+        "services:\n  compute:\n    resources:\n      snapshot: { lro_timeout_secs: 3600 }\n",
+    )
+    .unwrap();
+    let unmatched = overlay.apply(std::slice::from_mut(&mut compute_service));
+    assert!(unmatched.is_empty());
+
+    let provider_def = ProviderDefinition {
+        provider: Provider::Azure,
+        provider_name: "azure".to_string(),
+        sdk_version: "1.0.0".to_string(),
+        services: vec![compute_service],
+    };
+
+    let temp_dir = TempDir::new().unwrap();
+    let generator = UnifiedProviderGenerator::new(provider_def).unwrap();
+    generator
+        .generate_to_directory(temp_dir.path())
+        .expect("Failed to generate unified provider");
+
+    let disk_rs = fs::read_to_string(temp_dir.path().join("src/compute/resources/disk.rs"))
+        .expect("Failed to read disk.rs");
+
+    // Long-running operations are polled until done, with the configured timeout
+    assert_eq!(disk_rs.matches("request.poller().until_done(),").count(), 2);
+    assert!(disk_rs.contains("900,"));
+    assert!(disk_rs.contains("LRO_TIMEOUT_SECS,"));
+    // The read operation is sent as usual
+    assert!(disk_rs.contains("request.send().await"));
+
+    // Operation resources are polled through their operations resource until DONE
+    let snapshot_rs = fs::read_to_string(temp_dir.path().join("src/compute/resources/snapshot.rs"))
+        .expect("Failed to read snapshot.rs");
+    assert!(snapshot_rs.contains("wait_for_operation(operation, |name| {"));
+    assert!(snapshot_rs.contains("let mut poll = client.zone_operations().get();"));
+    assert!(snapshot_rs.contains("poll = poll.project(s);"));
+    assert!(snapshot_rs.contains("poll = poll.zone(s);"));
+    assert!(snapshot_rs.contains("poll.operation(name).send()"));
+    assert!(!snapshot_rs.contains("request.poller()"));
+    assert!(snapshot_rs.contains("3600,"));
+    assert!(!snapshot_rs.contains("LRO_TIMEOUT_SECS,"));
+
+    println!("✅ Generated long-running operation polling verified");
+}
+
+#[test]
+fn test_generate_unified_provider_with_data_sources() {
+    use tempfile::TempDir;
+
+    let ec2_service = ServiceDefinition {
+        provider: Provider::Aws,
+        name: "ec2".to_string(),
+        sdk_version: "1.0.0".to_string(),
+        resources: vec![ResourceDefinition {
+            name: "vpc".to_string(),
+            description: None,
+            fields: vec![],
+            outputs: vec![],
+            blocks: vec![],
+            id_field: None,
+            operations: Operations {
+                create: Some(OperationMapping {
+                    sdk_operation: "create_vpc".to_string(),
+                    ..Default::default()
+                }),
+                read: None,
+                update: None,
+                delete: None,
+                import: None,
+            },
+            deprecated: None,
+            stability: Stability::Ga,
+        }],
+        data_sources: vec![DataSourceDefinition {
+            name: "vpc".to_string(),
+            description: Some("Describes VPCs".to_string()),
+            arguments: vec![FieldDefinition {
+                name: "vpc_ids".to_string(),
+                field_type: FieldType::String,
+                ..Default::default()
+            }],
+            attributes: vec![FieldDefinition {
+                name: "cidr_block".to_string(),
+                field_type: FieldType::String,
+                response_accessor: Some("cidr_block".to_string()),
+                ..Default::default()
+            }],
+            read_operation: OperationMapping {
+                sdk_operation: "describe_vpcs".to_string(),
+                readonly: true,
+                ..Default::default()
+            },
+            items_accessor: Some("vpcs".to_string()),
+        }],
+    };
+
+    let provider_def = ProviderDefinition {
+        provider: Provider::Aws,
+        provider_name: "aws".to_string(),
+        sdk_version: "1.0.0".to_string(),
+        services: vec![ec2_service],
+    };
+
+    let temp_dir = TempDir::new().unwrap();
+    let generator = UnifiedProviderGenerator::new(provider_def).unwrap();
+    generator
+        .generate_to_directory(temp_dir.path())
+        .expect("Failed to generate unified provider");
+
+    // Data sources live next to resources of the same name, in their own module
+    let service_rs = fs::read_to_string(temp_dir.path().join("src/ec2/mod.rs"))
+        .expect("Failed to read ec2/mod.rs");
+    assert!(service_rs.contains("pub mod data_sources;"));
+    assert!(service_rs.contains("data_sources::vpc::read_data_source(client, config)"));
+    let mod_rs = fs::read_to_string(temp_dir.path().join("src/ec2/data_sources/mod.rs"))
+        .expect("Failed to read data_sources/mod.rs");
+    assert!(mod_rs.contains("pub mod vpc;"));
+    assert!(temp_dir.path().join("src/ec2/resources/vpc.rs").exists());
+
+    // The lookup reads attributes from the first listed item
+    let vpc_rs = fs::read_to_string(temp_dir.path().join("src/ec2/data_sources/vpc.rs"))
+        .expect("Failed to read data_sources/vpc.rs");
+    assert!(vpc_rs.contains("let mut request = client.describe_vpcs();"));
+    assert!(vpc_rs.contains("request = request.vpc_ids(v);"));
+    assert!(vpc_rs.contains("response.vpcs().and_then(|items| items.first())"));
+    assert!(vpc_rs.contains("ProviderError::NotFound"));
+    assert!(vpc_rs.contains("response.cidr_block()"));
+
+    println!("✅ Generated data source lookups verified");
+}
+
+#[test]
+fn test_generate_unified_provider_with_field_constraints() {
+    use tempfile::TempDir;
+
+    let s3_service = ServiceDefinition {
+        provider: Provider::Aws,
+        name: "s3".to_string(),
+        sdk_version: "1.0.0".to_string(),
+        data_sources: vec![],
+        resources: vec![ResourceDefinition {
+            name: "bucket".to_string(),
+            description: None,
+            fields: vec![
+                FieldDefinition {
+                    name: "bucket".to_string(),
+                    field_type: FieldType::String,
+                    required: true,
+                    constraints: FieldConstraints {
+                        min_length: Some(3),
+                        max_length: Some(63),
+                        pattern: Some(r"^[a-z0-9.\-]+$".to_string()),
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                },
+                FieldDefinition {
+                    name: "retention_days".to_string(),
+                    field_type: FieldType::Integer,
+                    constraints: FieldConstraints {
+                        minimum: Some(1.0),
+                        maximum: Some(365.0),
+                        ..Default::default()
+                    },
+                    default_value: Some(serde_json::json!(7)),
+                    ..Default::default()
+                },
+                FieldDefinition {
+                    name: "storage_class".to_string(),
+                    field_type: FieldType::Enum(vec![
+                        "STANDARD".to_string(),
+                        "GLACIER".to_string(),
+                    ]),
+                    ..Default::default()
+                },
+                FieldDefinition {
+                    name: "delimiter".to_string(),
+                    field_type: FieldType::String,
+                    constraints: FieldConstraints {
+                        pattern: Some("^[^\u{8}\u{c}\u{1f}]*$".to_string()),
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                },
+                FieldDefinition {
+                    name: "tags".to_string(),
+                    field_type: FieldType::String,
+                    ..Default::default()
+                },
+            ],
+            outputs: vec![],
+            blocks: vec![],
+            id_field: None,
+            operations: Operations {
+                create: Some(OperationMapping {
+                    sdk_operation: "create_bucket".to_string(),
+                    ..Default::default()
+                }),
+                read: None,
+                update: None,
+                delete: None,
+                import: None,
+            },
+            deprecated: None,
+            stability: Stability::Ga,
+        }],
+    };
+
+    let provider_def = ProviderDefinition {
+        provider: Provider::Aws,
+        provider_name: "aws".to_string(),
+        sdk_version: "1.0.0".to_string(),
+        services: vec![s3_service],
+    };
+
+    let temp_dir = TempDir::new().unwrap();
+    let generator = UnifiedProviderGenerator::new(provider_def).unwrap();
+    generator
+        .generate_to_directory(temp_dir.path())
+        .expect("Failed to generate unified provider");
+
+    let lib_rs =
+        fs::read_to_string(temp_dir.path().join("src/lib.rs")).expect("Failed to read lib.rs");
+
+    // Only constrained fields are listed, with Rust literals for each constraint
+    assert!(lib_rs.contains(r#"AttributeConstraints { name: "bucket", minimum: None, maximum: None, min_length: Some(3), max_length: Some(63), pattern: Some("^[a-z0-9.\\-]+$"), allowed_values: &[], one_of: &[], default_value: None }"#));
+    assert!(lib_rs.contains(r#"AttributeConstraints { name: "retention_days", minimum: Some(1.0), maximum: Some(365.0), min_length: None, max_length: None, pattern: None, allowed_values: &[], one_of: &[], default_value: Some("7") }"#));
+    assert!(lib_rs.contains(r#"allowed_values: &["STANDARD", "GLACIER"]"#));
+    // Control characters get Rust escapes, not JSON ones like \b or \u001f
+    assert!(lib_rs.contains(r#"pattern: Some("^[^\u{8}\u{c}\u{1f}]*$")"#));
+    assert!(!lib_rs.contains(r#"name: "tags""#));
+
+    // Defaults are filled in and constraints checked when planning, creating and updating
+    assert_eq!(
+        lib_rs
+            .matches("validate_attributes(resource_type, &")
+            .count(),
+        3
+    );
+    assert!(lib_rs.contains("apply_attribute_defaults(resource_type, &mut proposed_state);"));
+    assert!(lib_rs.contains("regex::Regex::new(pattern)"));
+
+    let cargo_toml =
+        fs::read_to_string(temp_dir.path().join("Cargo.toml")).expect("Failed to read Cargo.toml");
+    assert!(cargo_toml.contains("regex = \"1\""));
+
+    println!("✅ Generated field constraint checks verified");
+}
+
+#[test]
+fn test_generate_unified_provider_with_computed_and_write_only_fields() {
+    use tempfile::TempDir;
+
+    let rds_service = ServiceDefinition {
+        provider: Provider::Aws,
+        name: "rds".to_string(),
+        sdk_version: "1.0.0".to_string(),
+        data_sources: vec![],
+        resources: vec![ResourceDefinition {
+            name: "cluster".to_string(),
+            description: None,
+            fields: vec![
+                FieldDefinition {
+                    name: "cluster_id".to_string(),
+                    field_type: FieldType::String,
+                    required: true,
+                    immutable: true,
+                    ..Default::default()
+                },
+                FieldDefinition {
+                    name: "engine_version".to_string(),
+                    field_type: FieldType::String,
+                    computed: true,
+                    ..Default::default()
+                },
+                FieldDefinition {
+                    name: "master_password".to_string(),
+                    field_type: FieldType::String,
+                    sensitive: true,
+                    write_only: true,
+                    ..Default::default()
+                },
+            ],
+            outputs: vec![
+                FieldDefinition {
+                    name: "engine_version".to_string(),
+                    field_type: FieldType::String,
+                    response_accessor: Some("engine_version".to_string()),
+                    ..Default::default()
+                },
+                FieldDefinition {
+                    name: "endpoint".to_string(),
+                    field_type: FieldType::String,
+                    response_accessor: Some("endpoint".to_string()),
+                    ..Default::default()
+                },
+            ],
+            blocks: vec![],
+            id_field: None,
+            operations: Operations {
+                create: Some(OperationMapping {
+                    sdk_operation: "create_db_cluster".to_string(),
+                    ..Default::default()
+                }),
+                read: None,
+                update: None,
+                delete: None,
+                import: None,
+            },
+            deprecated: None,
+            stability: Stability::Ga,
+        }],
+    };
+
+    let provider_def = ProviderDefinition {
+        provider: Provider::Aws,
+        provider_name: "aws".to_string(),
+        sdk_version: "1.0.0".to_string(),
+        services: vec![rds_service],
+    };
+
+    let temp_dir = TempDir::new().unwrap();
+    let generator = UnifiedProviderGenerator::new(provider_def).unwrap();
+    generator
+        .generate_to_directory(temp_dir.path())
+        .expect("Failed to generate unified provider");
+
+    let lib_rs =
+        fs::read_to_string(temp_dir.path().join("src/lib.rs")).expect("Failed to read lib.rs");
+
+    // Outputs don't replace the optional input attribute of the same name
+    assert_eq!(
+        lib_rs.matches(r#""engine_version".to_string(),"#).count(),
+        1
+    );
+    assert_eq!(lib_rs.matches(r#""endpoint".to_string(),"#).count(), 1);
+
+    // Planning keeps computed values the configuration leaves unset
+    let carried = lib_rs
+        .split("fn carry_computed_values(")
+        .nth(1)
+        .expect("Should generate carry_computed_values");
+    let carried = &carried[..carried.find("_ => &[]").unwrap()];
+    assert!(carried.contains(r#""engine_version","#));
+    assert!(carried.contains(r#""endpoint","#));
+    assert!(!carried.contains(r#""master_password","#));
+    assert!(!carried.contains(r#""cluster_id","#));
+    assert!(
+        lib_rs.contains("self.carry_computed_values(resource_type, prior, &mut proposed_state);")
+    );
+
+    println!("✅ Generated computed value handling verified");
+}
+
+#[test]
+fn test_generate_unified_provider_with_sized_bytes_and_document_fields() {
+    use std::collections::HashMap;
+    use tempfile::TempDir;
+
+    let mut variants = HashMap::new();
+    variants.insert("url".to_string(), Box::new(FieldType::String));
+    variants.insert("checksum".to_string(), Box::new(FieldType::Bytes));
+
+    let field = |name: &str, field_type: FieldType| FieldDefinition {
+        name: name.to_string(),
+        field_type,
+        ..Default::default()
+    };
+
+    let media_service = ServiceDefinition {
+        provider: Provider::Aws,
+        name: "media".to_string(),
+        sdk_version: "1.0.0".to_string(),
+        data_sources: vec![],
+        resources: vec![ResourceDefinition {
+            name: "asset".to_string(),
+            description: None,
+            fields: vec![
+                field("channels", FieldType::Int8),
+                field("sample_bits", FieldType::Int16),
+                field("duration", FieldType::Int32),
+                field("frame_count", FieldType::UInt32),
+                field("byte_count", FieldType::UInt64),
+                field("size", FieldType::Integer),
+                field("thumbnail", FieldType::Bytes),
+                field("metadata", FieldType::Document),
+                field("source", FieldType::Union(variants)),
+            ],
+            outputs: vec![FieldDefinition {
+                name: "fingerprint".to_string(),
+                field_type: FieldType::Bytes,
+                response_accessor: Some("fingerprint".to_string()),
+                ..Default::default()
+            }],
+            blocks: vec![],
+            id_field: None,
+            operations: Operations {
+                create: Some(OperationMapping {
+                    sdk_operation: "create_asset".to_string(),
+                    ..Default::default()
+                }),
+                read: None,
+                update: None,
+                delete: None,
+                import: None,
+            },
+            deprecated: None,
+            stability: Stability::Ga,
+        }],
+    };
+
+    let provider_def = ProviderDefinition {
+        provider: Provider::Aws,
+        provider_name: "aws".to_string(),
+        sdk_version: "1.0.0".to_string(),
+        services: vec![media_service],
+    };
+
+    let temp_dir = TempDir::new().unwrap();
+    let generator = UnifiedProviderGenerator::new(provider_def).unwrap();
+    generator
+        .generate_to_directory(temp_dir.path())
+        .expect("Failed to generate unified provider");
+
+    let asset_rs = fs::read_to_string(temp_dir.path().join("src/media/resources/asset.rs"))
+        .expect("Failed to read asset.rs");
+
+    // Integer setters convert to the field's width instead of truncating
+    assert!(!asset_rs.contains(" as i32"));
+    assert!(asset_rs.contains("val.as_i64().and_then(|n| i8::try_from(n).ok())"));
+    assert!(asset_rs.contains("val.as_i64().and_then(|n| i16::try_from(n).ok())"));
+    assert!(asset_rs.contains("val.as_i64().and_then(|n| i32::try_from(n).ok())"));
+    assert!(asset_rs.contains("val.as_u64().and_then(|n| u32::try_from(n).ok())"));
+    assert!(asset_rs.contains(".duration(v)"));
+    assert!(asset_rs.contains(".size(v)"));
+
+    // Bytes are base64 in state, documents pass through as JSON
+    assert!(asset_rs.contains("base64::engine::general_purpose::STANDARD"));
+    assert!(asset_rs.contains(".ok()).map(Into::into)"));
+    assert!(asset_rs.contains(".thumbnail(v)"));
+    assert!(asset_rs.contains("(!val.is_null()).then(|| crate::json_to_document(val))"));
+    assert!(asset_rs.contains(".metadata(v)"));
+    assert!(
+        asset_rs.contains(r#"ProviderError::Validation("Setting union source is not supported""#)
+    );
+    assert!(!asset_rs.contains("ignoring it"));
+
+    // Out-of-range values are rejected at plan time
+    let lib_rs =
+        fs::read_to_string(temp_dir.path().join("src/lib.rs")).expect("Failed to read lib.rs");
+    assert!(lib_rs.contains(r#"name: "channels", minimum: Some(-128.0), maximum: Some(127.0)"#));
+    assert!(
+        lib_rs.contains(r#"name: "frame_count", minimum: Some(0.0), maximum: Some(4294967295.0)"#)
+    );
+
+    let cargo_toml =
+        fs::read_to_string(temp_dir.path().join("Cargo.toml")).expect("Failed to read Cargo.toml");
+    assert!(cargo_toml.contains("base64 = "));
+
+    println!("✅ Generated sized integer, bytes and document handling verified");
+}
+
+#[test]
+fn test_generate_unified_provider_with_document_fields() {
+    use tempfile::TempDir;
+
+    let events_service = ServiceDefinition {
+        provider: Provider::Aws,
+        name: "events".to_string(),
+        sdk_version: "1.0.0".to_string(),
+        data_sources: vec![],
+        resources: vec![ResourceDefinition {
+            name: "rule".to_string(),
+            description: None,
+            fields: vec![
+                FieldDefinition {
+                    name: "name".to_string(),
+                    field_type: FieldType::String,
+                    required: true,
+                    ..Default::default()
+                },
+                FieldDefinition {
+                    name: "event_pattern".to_string(),
+                    field_type: FieldType::Document,
+                    ..Default::default()
+                },
+            ],
+            outputs: vec![FieldDefinition {
+                name: "event_pattern".to_string(),
+                field_type: FieldType::Document,
+                response_accessor: Some("event_pattern".to_string()),
+                ..Default::default()
+            }],
+            blocks: vec![BlockDefinition {
+                name: "target".to_string(),
+                description: None,
+                attributes: vec![
+                    FieldDefinition {
+                        name: "arn".to_string(),
+                        field_type: FieldType::String,
+                        required: true,
+                        ..Default::default()
+                    },
+                    FieldDefinition {
+                        name: "input".to_string(),
+                        field_type: FieldType::Document,
+                        ..Default::default()
+                    },
+                ],
+                blocks: vec![],
+                nesting_mode: NestingMode::Single,
+                min_items: 0,
+                max_items: 1,
+                sdk_type_name: Some("Target".to_string()),
+                sdk_accessor_method: Some("target".to_string()),
+                one_of: false,
+            }],
+            id_field: None,
+            operations: Operations {
+                create: Some(OperationMapping {
+                    sdk_operation: "put_rule".to_string(),
+                    ..Default::default()
+                }),
+                read: None,
+                update: None,
+                delete: None,
+                import: None,
+            },
+            deprecated: None,
+            stability: Stability::Ga,
+        }],
+    };
+
+    let provider_def = ProviderDefinition {
+        provider: Provider::Aws,
+        provider_name: "aws".to_string(),
+        sdk_version: "1.0.0".to_string(),
+        services: vec![events_service],
+    };
+
+    let temp_dir = TempDir::new().unwrap();
+    let generator = UnifiedProviderGenerator::new(provider_def).unwrap();
+    generator
+        .generate_to_directory(temp_dir.path())
+        .expect("Failed to generate unified provider");
+
+    // The generated lib converts between JSON and Smithy documents
+    let lib_rs =
+        fs::read_to_string(temp_dir.path().join("src/lib.rs")).expect("Failed to read lib.rs");
+    assert!(lib_rs.contains(
+        "pub(crate) fn json_to_document(value: &serde_json::Value) -> aws_smithy_types::Document {"
+    ));
+    assert!(lib_rs.contains(
+        "pub(crate) fn document_to_json(document: &aws_smithy_types::Document) -> serde_json::Value {"
+    ));
+
+    // Document members are converted instead of passing JSON to the SDK
+    let rule_rs = fs::read_to_string(temp_dir.path().join("src/events/resources/rule.rs"))
+        .expect("Failed to read rule.rs");
+    assert!(!rule_rs.contains("(val.clone())"));
+    assert!(rule_rs.contains("(!val.is_null()).then(|| crate::json_to_document(val))"));
+    assert!(rule_rs.contains("request = request.event_pattern(v);"));
+    assert!(rule_rs.contains("builder = builder.input(v);"));
+    assert!(rule_rs.contains("Some(crate::document_to_json(val))"));
+
+    let cargo_toml =
+        fs::read_to_string(temp_dir.path().join("Cargo.toml")).expect("Failed to read Cargo.toml");
+    assert!(cargo_toml.contains("aws-smithy-types = "));
+
+    println!("✅ Generated document conversion verified");
+}
+
+#[test]
+fn test_generate_unified_provider_with_union_blocks() {
+    use std::collections::HashMap;
+    use tempfile::TempDir;
+
+    let mut upload_members = HashMap::new();
+    upload_members.insert("bucket".to_string(), Box::new(FieldType::String));
+    upload_members.insert("key".to_string(), Box::new(FieldType::String));
+    let mut variants = HashMap::new();
+    variants.insert("url".to_string(), Box::new(FieldType::String));
+    variants.insert(
+        "upload".to_string(),
+        Box::new(FieldType::Object(upload_members)),
+    );
+
+    let attribute = |name: &str, field_type: FieldType| FieldDefinition {
+        name: name.to_string(),
+        field_type,
+        ..Default::default()
+    };
+
+    let media_service = ServiceDefinition {
+        provider: Provider::Aws,
+        name: "media".to_string(),
+        sdk_version: "1.0.0".to_string(),
+        data_sources: vec![],
+        resources: vec![ResourceDefinition {
+            name: "asset".to_string(),
+            description: None,
+            fields: vec![attribute("source", FieldType::Union(variants))],
+            outputs: vec![],
+            blocks: vec![BlockDefinition {
+                name: "source".to_string(),
+                description: None,
+                attributes: vec![attribute("url", FieldType::String)],
+                blocks: vec![BlockDefinition {
+                    name: "upload".to_string(),
+                    description: None,
+                    attributes: vec![
+                        attribute("bucket", FieldType::String),
+                        attribute("key", FieldType::String),
+                    ],
+                    blocks: vec![],
+                    nesting_mode: NestingMode::Single,
+                    min_items: 0,
+                    max_items: 1,
+                    sdk_type_name: Some("UploadSource".to_string()),
+                    sdk_accessor_method: Some("set_upload".to_string()),
+                    one_of: false,
+                }],
+                nesting_mode: NestingMode::Single,
+                min_items: 0,
+                max_items: 1,
+                sdk_type_name: Some("AssetSource".to_string()),
+                sdk_accessor_method: Some("source".to_string()),
+                one_of: true,
+            }],
+            id_field: None,
+            operations: Operations {
+                create: Some(OperationMapping {
+                    sdk_operation: "create_asset".to_string(),
+                    ..Default::default()
+                }),
+                read: None,
+                update: None,
+                delete: None,
+                import: None,
+            },
+            deprecated: None,
+            stability: Stability::Ga,
+        }],
+    };
+
+    let provider_def = ProviderDefinition {
+        provider: Provider::Aws,
+        provider_name: "aws".to_string(),
+        sdk_version: "1.0.0".to_string(),
+        services: vec![media_service],
+    };
+
+    let temp_dir = TempDir::new().unwrap();
+    let generator = UnifiedProviderGenerator::new(provider_def).unwrap();
+    generator
+        .generate_to_directory(temp_dir.path())
+        .expect("Failed to generate unified provider");
+
+    // Plan requires exactly one member of the union
+    let lib_rs =
+        fs::read_to_string(temp_dir.path().join("src/lib.rs")).expect("Failed to read lib.rs");
+    assert!(lib_rs.contains(r#"name: "source", "#));
+    assert!(lib_rs.contains(r#"one_of: &["upload", "url"]"#));
+    assert!(lib_rs.contains("must set exactly one of"));
+
+    // Create builds the SDK union variant from the single-key object
+    let asset_rs = fs::read_to_string(temp_dir.path().join("src/media/resources/asset.rs"))
+        .expect("Failed to read asset.rs");
+    assert!(!asset_rs.contains("ignoring it"));
+    assert!(asset_rs.contains("// Built from the source one-of block below"));
+    assert!(asset_rs.contains("\"source must set exactly one member\""));
+    assert!(asset_rs.contains(
+        r#""url" => val.as_str().map(|s| s.to_string()).map(aws_sdk_media::types::AssetSource::Url),"#
+    ));
+    assert!(asset_rs
+        .contains("let mut nested_builder = aws_sdk_media::types::UploadSource::builder();"));
+    assert!(asset_rs.contains(
+        r#"if let Some(v) = val.get("bucket").and_then(|val| val.as_str().map(|s| s.to_string())) {"#
+    ));
+    assert!(asset_rs
+        .contains("nested_builder.build().ok().map(aws_sdk_media::types::AssetSource::Upload)"));
+    assert!(asset_rs.contains("Some(value) => request = request.source(value),"));
+
+    println!("✅ Generated union handling verified");
+}
+
+#[test]
+fn test_generate_unified_provider_with_renamed_fields() {
+    use tempfile::TempDir;
+
+    let field = |name: &str, field_type: FieldType| FieldDefinition {
+        name: name.to_string(),
+        field_type,
+        ..Default::default()
+    };
+
+    let mut s3_service = ServiceDefinition {
+        provider: Provider::Aws,
+        name: "s3".to_string(),
+        sdk_version: "1.0.0".to_string(),
+        data_sources: vec![],
+        resources: vec![ResourceDefinition {
+            name: "bucket".to_string(),
+            description: None,
+            fields: vec![
+                field("bucket", FieldType::String),
+                field("object_lock_enabled", FieldType::Boolean),
+            ],
+            outputs: vec![],
+            blocks: vec![BlockDefinition {
+                name: "cors_rule".to_string(),
+                description: None,
+                attributes: vec![field("max_age_seconds", FieldType::Integer)],
+                blocks: vec![],
+                nesting_mode: NestingMode::List,
+                min_items: 0,
+                max_items: 0,
+                sdk_type_name: Some("CorsRule".to_string()),
+                sdk_accessor_method: None,
+                one_of: false,
+            }],
+            id_field: Some("bucket".to_string()),
+            operations: Operations {
+                create: Some(OperationMapping {
+                    sdk_operation: "create_bucket".to_string(),
+                    ..Default::default()
+                }),
+                read: Some(OperationMapping {
+                    sdk_operation: "head_bucket".to_string(),
+                    ..Default::default()
+                }),
+                update: None,
+                delete: None,
+                import: None,
+            },
+            deprecated: None,
+            stability: Stability::Ga,
+        }],
+    };
+
+    // Overlays rename the schema attributes, not the SDK members behind them
+    let overlay = Overlay::from_yaml(
+        r#"
+services:
+  s3:
+    resources:
+      bucket:
+        fields:
+          bucket: { rename: name, required: true, immutable: true }
+          object_lock_enabled: { rename: object_lock }
+        blocks:
+          cors_rule:
+            rename: cors
+            attributes:
+              max_age_seconds: { rename: max_age }
+"#,
+    )
+    .unwrap();
+    let unmatched = overlay.apply(std::slice::from_mut(&mut s3_service));
+    assert!(unmatched.is_empty());
+
+    let provider_def = ProviderDefinition {
+        provider: Provider::Aws,
+        provider_name: "aws".to_string(),
+        sdk_version: "1.0.0".to_string(),
+        services: vec![s3_service],
+    };
+
+    let temp_dir = TempDir::new().unwrap();
+    let generator = UnifiedProviderGenerator::new(provider_def).unwrap();
+    generator
+        .generate_to_directory(temp_dir.path())
+        .expect("Failed to generate unified provider");
+
+    let bucket_rs = fs::read_to_string(temp_dir.path().join("src/s3/resources/bucket.rs"))
+        .expect("Failed to read bucket.rs");
+
+    // Inputs are read under the new names and passed to the SDK setters of the old ones
+    assert!(bucket_rs.contains(r#"input.get("name")"#));
+    assert!(bucket_rs.contains("request = request.bucket(v);"));
+    assert!(bucket_rs.contains("request = request.object_lock_enabled(v);"));
+    assert!(bucket_rs.contains("request = request.bucket(s);"));
+    assert!(bucket_rs.contains("request = request.bucket(id);"));
+    assert!(!bucket_rs.contains("request.name("));
+    assert!(!bucket_rs.contains("request.object_lock("));
+
+    // Renamed blocks and their attributes keep their SDK members too
+    assert!(bucket_rs.contains(r#"input.get("cors")"#));
+    assert!(bucket_rs.contains("request = request.cors_rule(block_items);"));
+    assert!(bucket_rs.contains("builder = builder.max_age_seconds(v);"));
+    assert!(!bucket_rs.contains("request.cors("));
+
+    println!("✅ Generated SDK setters of renamed fields verified");
+}
+
+#[test]
+fn test_generate_unified_provider_with_enum_fields() {
+    use tempfile::TempDir;
+
+    let storage_class = FieldType::Enum(vec!["STANDARD".to_string(), "GLACIER".to_string()]);
+    let s3_service = ServiceDefinition {
+        provider: Provider::Aws,
+        name: "s3".to_string(),
+        sdk_version: "1.0.0".to_string(),
+        data_sources: vec![],
+        resources: vec![ResourceDefinition {
+            name: "object".to_string(),
+            description: None,
+            fields: vec![
+                FieldDefinition {
+                    name: "key".to_string(),
+                    field_type: FieldType::String,
+                    required: true,
+                    ..Default::default()
+                },
+                FieldDefinition {
+                    name: "storage_class".to_string(),
+                    field_type: storage_class.clone(),
+                    sdk_type_name: Some("StorageClass".to_string()),
+                    ..Default::default()
+                },
+                FieldDefinition {
+                    name: "acl".to_string(),
+                    field_type: FieldType::Enum(vec![
+                        "private".to_string(),
+                        "public-read".to_string(),
+                    ]),
+                    ..Default::default()
+                },
+            ],
+            outputs: vec![FieldDefinition {
+                name: "storage_class".to_string(),
+                field_type: storage_class,
+                sdk_type_name: Some("StorageClass".to_string()),
+                response_accessor: Some("storage_class".to_string()),
+                ..Default::default()
+            }],
+            blocks: vec![],
+            id_field: None,
+            operations: Operations {
+                create: Some(OperationMapping {
+                    sdk_operation: "put_object".to_string(),
+                    ..Default::default()
+                }),
+                read: None,
+                update: None,
+                delete: None,
+                import: None,
+            },
+            deprecated: None,
+            stability: Stability::Ga,
+        }],
+    };
+
+    let provider_def = ProviderDefinition {
+        provider: Provider::Aws,
+        provider_name: "aws".to_string(),
+        sdk_version: "1.0.0".to_string(),
+        services: vec![s3_service],
+    };
+
+    let temp_dir = TempDir::new().unwrap();
+    let generator = UnifiedProviderGenerator::new(provider_def).unwrap();
+    generator
+        .generate_to_directory(temp_dir.path())
+        .expect("Failed to generate unified provider");
+
+    // The manifest lists the variants
+    let provider_jcf = fs::read_to_string(temp_dir.path().join("provider.jcf"))
+        .expect("Failed to read provider.jcf");
+    assert!(provider_jcf.contains(r#"allowed_values: ["STANDARD","GLACIER"],"#));
+
+    // Values are validated against the variants
+    let lib_rs =
+        fs::read_to_string(temp_dir.path().join("src/lib.rs")).expect("Failed to read lib.rs");
+    assert!(lib_rs.contains(r#"allowed_values: &["STANDARD", "GLACIER"]"#));
+
+    // Setters convert to the SDK enum, outputs read back the variant value
+    let object_rs = fs::read_to_string(temp_dir.path().join("src/s3/resources/object.rs"))
+        .expect("Failed to read object.rs");
+    assert!(object_rs.contains("val.as_str().map(aws_sdk_s3::types::StorageClass::from)"));
+    assert!(object_rs.contains("request = request.storage_class(v);"));
+    assert!(object_rs.contains("serde_json::Value::String(val.as_str().to_string())"));
+
+    // Without an SDK enum type the value is passed as a plain string
+    assert!(object_rs.contains(
+        "if let Some(v) = val.as_str().map(|s| s.to_string()) {\n            request = request.acl(v);"
+    ));
+
+    println!("✅ Generated enum handling verified");
+}
+
+#[test]
+fn test_generate_unified_provider_with_deprecations() {
+    use tempfile::TempDir;
+
+    let sqs_service = ServiceDefinition {
+        provider: Provider::Aws,
+        name: "sqs".to_string(),
+        sdk_version: "1.0.0".to_string(),
+        data_sources: vec![],
+        resources: vec![ResourceDefinition {
+            name: "queue".to_string(),
+            description: Some("Message queue".to_string()),
+            fields: vec![
+                FieldDefinition {
+                    name: "queue_name".to_string(),
+                    field_type: FieldType::String,
+                    required: true,
+                    ..Default::default()
+                },
+                FieldDefinition {
+                    name: "visibility".to_string(),
+                    field_type: FieldType::Integer,
+                    description: Some("Visibility timeout".to_string()),
+                    deprecated: Some(Deprecation {
+                        message: Some("Use visibility_timeout instead".to_string()),
+                    }),
+                    ..Default::default()
+                },
+                FieldDefinition {
+                    name: "receive_wait".to_string(),
+                    field_type: FieldType::Integer,
+                    // Messages copied from specs may contain control characters
+                    deprecated: Some(Deprecation {
+                        message: Some("Use \"wait_time\"\u{c}instead".to_string()),
+                    }),
+                    ..Default::default()
+                },
+            ],
+            outputs: vec![],
+            blocks: vec![],
+            id_field: None,
+            operations: Operations {
+                create: Some(OperationMapping {
+                    sdk_operation: "create_queue".to_string(),
+                    ..Default::default()
+                }),
+                read: None,
+                update: Some(OperationMapping {
+                    sdk_operation: "set_queue_attributes".to_string(),
+                    deprecated: Some(Deprecation::default()),
+                    ..Default::default()
+                }),
+                delete: None,
+                import: None,
+            },
+            deprecated: Some(Deprecation {
+                message: Some("Use the topic resource instead".to_string()),
+            }),
+            stability: Stability::Beta,
+        }],
+    };
+
+    let provider_def = ProviderDefinition {
+        provider: Provider::Aws,
+        provider_name: "aws".to_string(),
+        sdk_version: "1.0.0".to_string(),
+        services: vec![sqs_service],
+    };
+
+    let temp_dir = TempDir::new().unwrap();
+    let generator = UnifiedProviderGenerator::new(provider_def).unwrap();
+    generator
+        .generate_to_directory(temp_dir.path())
+        .expect("Failed to generate unified provider");
+
+    // Plan warns about the deprecated parts in use
+    let lib_rs =
+        fs::read_to_string(temp_dir.path().join("src/lib.rs")).expect("Failed to read lib.rs");
+    let deprecations = lib_rs
+        .split(r#""sqs_queue" => Deprecations {"#)
+        .nth(1)
+        .expect("Should generate deprecations of sqs_queue");
+    let deprecations = &deprecations[..deprecations.find("},").unwrap()];
+    assert!(deprecations.contains(r#"resource: Some("Use the topic resource instead"),"#));
+    assert!(deprecations.contains(r#"("visibility", "Use visibility_timeout instead"),"#));
+    assert!(deprecations.contains(r#"("receive_wait", "Use \"wait_time\"\u{c}instead"),"#));
+    assert!(!deprecations.contains(r#"("queue_name","#));
+    assert!(deprecations.contains("create: None,"));
+    assert!(deprecations.contains(r#"update: Some(""),"#));
+    assert!(lib_rs.contains("result.diagnostics.extend(deprecation_diagnostics(resource_type, prior_state.as_ref(), &proposed_state));"));
+
+    // The manifest and docs flag them
+    let provider_jcf = fs::read_to_string(temp_dir.path().join("provider.jcf"))
+        .expect("Failed to read provider.jcf");
+    assert_eq!(provider_jcf.matches("deprecated: true,").count(), 3);
+    assert!(provider_jcf.contains(r#"stability: "beta","#));
+
+    let docs = fs::read_to_string(temp_dir.path().join("docs/services/sqs.md"))
+        .expect("Failed to read sqs.md");
+    assert!(docs.contains("[Queue](#queue) [CU] ⚠️ Deprecated (beta)"));
+    assert!(docs.contains("> ⚠️ **Deprecated**: Use the topic resource instead"));
+    assert!(docs.contains("> **Stability**: beta"));
+    assert!(docs.contains("✅ Update (deprecated)"));
+    assert!(
+        docs.contains("| ⚠️ **Deprecated**: Use visibility_timeout instead. Visibility timeout |")
+    );
+
+    println!("✅ Generated deprecation handling verified");
+}
+
 #[test]
 #[ignore] // Run with `cargo test -- --ignored` - takes longer due to compilation
 fn test_generated_unified_provider_compiles() {
@@ -312,6 +1519,7 @@ fn test_generated_unified_provider_compiles() {
                 immutable: true,
                 description: Some("The name of the bucket".to_string()),
                 response_accessor: None,
+                ..Default::default()
             }],
             outputs: vec![], // Simplified - no outputs for basic compilation test
             blocks: vec![],
@@ -320,18 +1528,23 @@ fn test_generated_unified_provider_compiles() {
                 create: Some(OperationMapping {
                     sdk_operation: "create_bucket".to_string(),
                     additional_operations: vec![],
+                    ..Default::default()
                 }),
                 read: Some(OperationMapping {
                     sdk_operation: "head_bucket".to_string(),
                     additional_operations: vec![],
+                    ..Default::default()
                 }),
                 update: None, // S3 buckets don't have a simple update operation
                 delete: Some(OperationMapping {
                     sdk_operation: "delete_bucket".to_string(),
                     additional_operations: vec![],
+                    ..Default::default()
                 }),
                 import: None,
             },
+            deprecated: None,
+            stability: Stability::Ga,
         }],
     };
 
@@ -351,6 +1564,7 @@ fn test_generated_unified_provider_compiles() {
                 immutable: true,
                 description: Some("The name of the table".to_string()),
                 response_accessor: None,
+                ..Default::default()
             }],
             outputs: vec![], // Simplified - no outputs for basic compilation test
             blocks: vec![],
@@ -359,18 +1573,23 @@ fn test_generated_unified_provider_compiles() {
                 create: Some(OperationMapping {
                     sdk_operation: "create_table".to_string(),
                     additional_operations: vec![],
+                    ..Default::default()
                 }),
                 read: Some(OperationMapping {
                     sdk_operation: "describe_table".to_string(),
                     additional_operations: vec![],
+                    ..Default::default()
                 }),
                 update: None, // Simplified - update_table is complex
                 delete: Some(OperationMapping {
                     sdk_operation: "delete_table".to_string(),
                     additional_operations: vec![],
+                    ..Default::default()
                 }),
                 import: None,
             },
+            deprecated: None,
+            stability: Stability::Ga,
         }],
     };
 
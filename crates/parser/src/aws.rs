@@ -14,10 +14,10 @@
 
 use hemmer_provider_generator_common::{
     FieldDefinition, GeneratorError, OperationMapping, Operations, Provider, ResourceDefinition,
-    Result, ServiceDefinition,
+    Result, ServiceDefinition, Stability,
 };
 
-use crate::{CrudOperation, OperationClassifier, RustdocLoader};
+use crate::{CrudOperation, CrudRules, OperationClassifier, RustdocLoader};
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 
@@ -26,6 +26,7 @@ pub struct AwsParser {
     service_name: String,
     sdk_version: String,
     rustdoc_json_path: Option<PathBuf>,
+    crud_rules: CrudRules,
 }
 
 impl AwsParser {
@@ -35,6 +36,7 @@ impl AwsParser {
             service_name: service_name.to_string(),
             sdk_version: sdk_version.to_string(),
             rustdoc_json_path: None,
+            crud_rules: CrudRules::default(),
         }
     }
 
@@ -48,9 +50,16 @@ impl AwsParser {
             service_name: service_name.to_string(),
             sdk_version: sdk_version.to_string(),
             rustdoc_json_path: Some(rustdoc_json_path),
+            crud_rules: CrudRules::default(),
         }
     }
 
+    /// Classify operations with the given rules instead of the built-in ones
+    pub fn with_crud_rules(mut self, crud_rules: CrudRules) -> Self {
+        self.crud_rules = crud_rules;
+        self
+    }
+
     /// Parse the AWS service into ServiceDefinition
     ///
     /// # Modes
@@ -129,6 +138,7 @@ impl AwsParser {
                     immutable: true, // Bucket name is immutable
                     description: Some("Bucket name (globally unique)".to_string()),
                     response_accessor: None,
+                    ..Default::default()
                 },
                 FieldDefinition {
                     name: "acl".to_string(),
@@ -138,6 +148,7 @@ impl AwsParser {
                     immutable: false,
                     description: Some("Canned ACL to apply to the bucket".to_string()),
                     response_accessor: None,
+                    ..Default::default()
                 },
                 FieldDefinition {
                     name: "tags".to_string(),
@@ -150,6 +161,7 @@ impl AwsParser {
                     immutable: false,
                     description: Some("Tags to apply to the bucket".to_string()),
                     response_accessor: None,
+                    ..Default::default()
                 },
             ],
             outputs: vec![
@@ -161,6 +173,7 @@ impl AwsParser {
                     immutable: false,
                     description: Some("Bucket location/region".to_string()),
                     response_accessor: Some("location".to_string()),
+                    ..Default::default()
                 },
                 FieldDefinition {
                     name: "arn".to_string(),
@@ -170,6 +183,7 @@ impl AwsParser {
                     immutable: true,
                     description: Some("Amazon Resource Name (ARN) of the bucket".to_string()),
                     response_accessor: Some("arn".to_string()),
+                    ..Default::default()
                 },
             ],
             // Nested blocks will be detected in future parser enhancements
@@ -179,21 +193,27 @@ impl AwsParser {
                 create: Some(OperationMapping {
                     sdk_operation: "create_bucket".to_string(),
                     additional_operations: vec![],
+                    ..Default::default()
                 }),
                 read: Some(OperationMapping {
                     sdk_operation: "head_bucket".to_string(),
                     additional_operations: vec!["get_bucket_location".to_string()],
+                    ..Default::default()
                 }),
                 update: Some(OperationMapping {
                     sdk_operation: "put_bucket_tagging".to_string(),
                     additional_operations: vec!["put_bucket_acl".to_string()],
+                    ..Default::default()
                 }),
                 delete: Some(OperationMapping {
                     sdk_operation: "delete_bucket".to_string(),
                     additional_operations: vec![],
+                    ..Default::default()
                 }),
                 import: None, // Will implement later
             },
+            deprecated: None,
+            stability: Stability::Ga,
         }
     }
 
@@ -207,7 +227,9 @@ impl AwsParser {
         let mut grouped: HashMap<String, Vec<(String, CrudOperation)>> = HashMap::new();
 
         for op in operations {
-            if let Some(crud) = OperationClassifier::classify(&op) {
+            if let Some(crud) =
+                OperationClassifier::classify_with(&self.crud_rules, &Provider::Aws, &op)
+            {
                 let resource = OperationClassifier::extract_resource(&op);
                 grouped.entry(resource).or_default().push((op, crud));
             }
@@ -241,6 +263,7 @@ impl AwsParser {
             let mapping = OperationMapping {
                 sdk_operation: op_name.clone(),
                 additional_operations: vec![],
+                ..Default::default()
             };
 
             match crud_type {
@@ -300,6 +323,8 @@ impl AwsParser {
             blocks: vec![],
             id_field: None, // Will implement ID detection later
             operations: ops,
+            deprecated: None,
+            stability: Stability::Ga,
         }
     }
 
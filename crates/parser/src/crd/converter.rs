@@ -0,0 +1,393 @@
+//! Converts CustomResourceDefinitions to ServiceDefinition IR
+
+use super::types::{AdditionalProperties, CrdVersion, CustomResourceDefinition, JsonSchemaProps};
+use crate::naming::to_snake_case;
+use hemmer_provider_generator_common::{
+    BlockDefinition, Deprecation, FieldConstraints, FieldDefinition, FieldType, NestingMode,
+    OperationMapping, Operations, Provider, ResourceDefinition, Result, ServiceDefinition,
+    Stability,
+};
+
+/// Convert CRDs to ServiceDefinition
+pub fn convert_crds_to_service_definition(
+    crds: &[CustomResourceDefinition],
+    service_name: &str,
+    api_version: &str,
+) -> Result<ServiceDefinition> {
+    let mut resources = Vec::new();
+
+    for crd in crds {
+        let served: Vec<&CrdVersion> = crd.spec.versions.iter().filter(|v| v.served).collect();
+
+        for version in &served {
+            let name = if served.len() > 1 {
+                format!("{}_{}", to_snake_case(&crd.spec.names.kind), version.name)
+            } else {
+                to_snake_case(&crd.spec.names.kind)
+            };
+            resources.push(build_resource(crd, version, name)?);
+        }
+    }
+
+    Ok(ServiceDefinition {
+        provider: Provider::Kubernetes,
+        name: service_name.to_string(),
+        sdk_version: api_version.to_string(),
+        resources,
+        data_sources: vec![],
+    })
+}
+
+/// Build the resource for one served version of a CRD
+fn build_resource(
+    crd: &CustomResourceDefinition,
+    version: &CrdVersion,
+    name: String,
+) -> Result<ResourceDefinition> {
+    let spec = &crd.spec;
+    let api_version = format!("{}/{}", spec.group, version.name);
+    let schema = version
+        .schema
+        .as_ref()
+        .map(|s| &s.open_api_v3_schema)
+        .cloned()
+        .unwrap_or_default();
+
+    // With the status subresource, status is written by the controller, not the user
+    let status_is_output = version
+        .subresources
+        .as_ref()
+        .is_some_and(|s| s.status.is_some());
+
+    let mut fields = vec![
+        fixed_field(
+            "api_version",
+            &api_version,
+            "APIVersion defines the versioned schema of this representation of an object",
+        ),
+        fixed_field(
+            "kind",
+            &spec.names.kind,
+            "Kind is a string value representing the REST resource this object represents",
+        ),
+        metadata_field("name", FieldType::String, true, "Name of the object"),
+    ];
+    if spec.is_namespaced() {
+        fields.push(metadata_field(
+            "namespace",
+            FieldType::String,
+            true,
+            "Namespace of the object",
+        ));
+    }
+    fields.push(metadata_field(
+        "labels",
+        string_map(),
+        false,
+        "Labels to organize and select objects",
+    ));
+    fields.push(metadata_field(
+        "annotations",
+        string_map(),
+        false,
+        "Annotations to store arbitrary non-identifying metadata",
+    ));
+
+    let mut blocks = Vec::new();
+    let mut outputs = Vec::new();
+    for (prop_name, prop_schema) in sorted_properties(&schema) {
+        match prop_name {
+            // Covered by the fixed and metadata fields above
+            "apiVersion" | "kind" | "metadata" => continue,
+            "status" if status_is_output => {
+                outputs = extract_outputs_from_schema(prop_schema);
+                continue;
+            },
+            _ => {},
+        }
+
+        let required = schema.required.iter().any(|r| r == prop_name);
+        if let Some(block) = try_extract_block(prop_name, prop_schema, required) {
+            blocks.push(block);
+        } else {
+            fields.push(build_field(prop_name, prop_schema, required));
+        }
+    }
+
+    let description = schema.description.clone().or_else(|| {
+        Some(format!(
+            "{} ({}) custom resource",
+            spec.names.kind, api_version
+        ))
+    });
+
+    let mut resource = ResourceDefinition {
+        name,
+        description,
+        fields,
+        outputs,
+        blocks,
+        operations: build_operations(spec.is_namespaced()),
+        id_field: Some("name".to_string()),
+        deprecated: version
+            .deprecated
+            .then(|| Deprecation::new(version.deprecation_warning.as_deref())),
+        // e.g., v1alpha1
+        stability: Stability::from_version(&version.name),
+    };
+    resource.infer_computed_fields();
+
+    Ok(resource)
+}
+
+/// CRUD operations of the Kubernetes custom objects API
+fn build_operations(namespaced: bool) -> Operations {
+    let scope = if namespaced { "namespaced" } else { "cluster" };
+    let operation = |verb: &str| format!("{}_{}_custom_object", verb, scope);
+
+    Operations {
+        create: Some(OperationMapping {
+            sdk_operation: operation("create"),
+            ..Default::default()
+        }),
+        read: Some(OperationMapping {
+            sdk_operation: operation("get"),
+            readonly: true,
+            idempotent: true,
+            ..Default::default()
+        }),
+        update: Some(OperationMapping {
+            sdk_operation: operation("replace"),
+            idempotent: true,
+            ..Default::default()
+        }),
+        delete: Some(OperationMapping {
+            sdk_operation: operation("delete"),
+            idempotent: true,
+            ..Default::default()
+        }),
+        import: None,
+    }
+}
+
+/// Try to turn a property into a nested block (objects and arrays of objects)
+fn try_extract_block(
+    prop_name: &str,
+    schema: &JsonSchemaProps,
+    required: bool,
+) -> Option<BlockDefinition> {
+    let (object_schema, nesting_mode, min_items, max_items) = match schema.schema_type.as_deref() {
+        Some("array") => {
+            let items = schema.items.as_deref()?;
+            if !is_structured_object(items) {
+                return None;
+            }
+            (
+                items,
+                NestingMode::List,
+                schema.min_items.unwrap_or(if required { 1 } else { 0 }),
+                schema.max_items.unwrap_or(0), // 0 = unlimited
+            )
+        },
+        Some("object") if is_structured_object(schema) => {
+            (schema, NestingMode::Single, if required { 1 } else { 0 }, 1)
+        },
+        _ => return None,
+    };
+
+    let mut attributes = Vec::new();
+    let mut blocks = Vec::new();
+    for (name, prop_schema) in sorted_properties(object_schema) {
+        let required = object_schema.required.iter().any(|r| r == name);
+        if let Some(block) = try_extract_block(name, prop_schema, required) {
+            blocks.push(block);
+        } else {
+            attributes.push(build_field(name, prop_schema, required));
+        }
+    }
+
+    Some(BlockDefinition {
+        name: to_snake_case(prop_name),
+        description: schema.description.clone(),
+        attributes,
+        blocks,
+        nesting_mode,
+        min_items,
+        max_items,
+        // Custom resources have no generated SDK types
+        sdk_type_name: None,
+        sdk_accessor_method: None,
+        one_of: false,
+    })
+}
+
+/// Objects with declared properties become blocks; free-form objects stay attributes
+fn is_structured_object(schema: &JsonSchemaProps) -> bool {
+    schema.schema_type.as_deref() == Some("object")
+        && !schema.properties.is_empty()
+        && schema.int_or_string != Some(true)
+}
+
+/// Extract status properties as outputs
+fn extract_outputs_from_schema(schema: &JsonSchemaProps) -> Vec<FieldDefinition> {
+    sorted_properties(schema)
+        .into_iter()
+        .map(|(name, prop_schema)| FieldDefinition {
+            response_accessor: Some(to_snake_case(name)),
+            ..build_field(name, prop_schema, false)
+        })
+        .collect()
+}
+
+/// Build a field from a schema property
+fn build_field(name: &str, schema: &JsonSchemaProps, required: bool) -> FieldDefinition {
+    FieldDefinition {
+        name: to_snake_case(name),
+        field_type: convert_schema_to_field_type(schema),
+        required,
+        sensitive: false,
+        immutable: false,
+        description: schema.description.clone(),
+        constraints: FieldConstraints {
+            minimum: schema.minimum,
+            maximum: schema.maximum,
+            min_length: schema.min_length.or(schema.min_items.map(u64::from)),
+            max_length: schema.max_length.or(schema.max_items.map(u64::from)),
+            pattern: schema.pattern.clone(),
+        },
+        default_value: schema.default.clone(),
+        ..Default::default()
+    }
+}
+
+/// Convert a CRD schema to FieldType
+fn convert_schema_to_field_type(schema: &JsonSchemaProps) -> FieldType {
+    // Ports, percentages, ... are passed through as strings
+    if schema.int_or_string == Some(true) {
+        return FieldType::String;
+    }
+
+    match schema.schema_type.as_deref() {
+        Some("string") => match schema.format.as_deref() {
+            Some("date-time") => FieldType::DateTime,
+            Some("byte") => FieldType::Bytes,
+            _ if !schema.enum_values.is_empty() => FieldType::Enum(
+                schema
+                    .enum_values
+                    .iter()
+                    .map(|v| match v {
+                        serde_json::Value::String(s) => s.clone(),
+                        other => other.to_string(),
+                    })
+                    .collect(),
+            ),
+            _ => FieldType::String,
+        },
+        Some("integer") => match schema.format.as_deref() {
+            Some("int32") => FieldType::Int32,
+            _ => FieldType::Integer,
+        },
+        Some("number") => FieldType::Float,
+        Some("boolean") => FieldType::Boolean,
+        Some("array") => FieldType::List(Box::new(
+            schema
+                .items
+                .as_deref()
+                .map(convert_schema_to_field_type)
+                .unwrap_or(FieldType::String),
+        )),
+        Some("object") => match &schema.additional_properties {
+            Some(AdditionalProperties::Schema(value_schema)) => FieldType::Map(
+                Box::new(FieldType::String),
+                Box::new(convert_schema_to_field_type(value_schema)),
+            ),
+            _ => FieldType::Object(
+                schema
+                    .properties
+                    .iter()
+                    .map(|(name, prop)| {
+                        (name.clone(), Box::new(convert_schema_to_field_type(prop)))
+                    })
+                    .collect(),
+            ),
+        },
+        // Untyped with x-kubernetes-preserve-unknown-fields: arbitrary content
+        None if schema.preserve_unknown_fields == Some(true) => FieldType::Document,
+        _ => FieldType::String,
+    }
+}
+
+/// Schema properties in a stable order
+fn sorted_properties(schema: &JsonSchemaProps) -> Vec<(&str, &JsonSchemaProps)> {
+    let mut properties: Vec<_> = schema
+        .properties
+        .iter()
+        .map(|(name, prop)| (name.as_str(), prop))
+        .collect();
+    properties.sort_by_key(|(name, _)| *name);
+    properties
+}
+
+/// Field pinned to a single value, such as `apiVersion` and `kind`
+fn fixed_field(name: &str, value: &str, description: &str) -> FieldDefinition {
+    FieldDefinition {
+        name: name.to_string(),
+        field_type: FieldType::Enum(vec![value.to_string()]),
+        required: false,
+        sensitive: false,
+        immutable: true,
+        description: Some(format!("{} (always \"{}\")", description, value)),
+        default_value: Some(serde_json::Value::String(value.to_string())),
+        ..Default::default()
+    }
+}
+
+/// Field stored in the object's `metadata`
+fn metadata_field(
+    name: &str,
+    field_type: FieldType,
+    identifying: bool,
+    description: &str,
+) -> FieldDefinition {
+    FieldDefinition {
+        name: name.to_string(),
+        field_type,
+        required: identifying,
+        sensitive: false,
+        // Objects can't be renamed or moved between namespaces
+        immutable: identifying,
+        description: Some(description.to_string()),
+        ..Default::default()
+    }
+}
+
+fn string_map() -> FieldType {
+    FieldType::Map(Box::new(FieldType::String), Box::new(FieldType::String))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_int_or_string_and_preserve_unknown_fields() {
+        let int_or_string = JsonSchemaProps {
+            int_or_string: Some(true),
+            ..Default::default()
+        };
+        assert_eq!(
+            convert_schema_to_field_type(&int_or_string),
+            FieldType::String
+        );
+
+        let free_form = JsonSchemaProps {
+            preserve_unknown_fields: Some(true),
+            ..Default::default()
+        };
+        assert_eq!(
+            convert_schema_to_field_type(&free_form),
+            FieldType::Document
+        );
+        assert!(try_extract_block("values", &free_form, false).is_none());
+    }
+}
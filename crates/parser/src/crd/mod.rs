@@ -0,0 +1,34 @@
+//! Kubernetes CustomResourceDefinition parser
+//!
+//! Parses CRD manifests into ServiceDefinition IR, so providers can be generated
+//! for the custom resources that operators install.
+//!
+//! ## Mapping
+//!
+//! - Every served version becomes a resource (suffixed with the version when a
+//!   CRD serves more than one)
+//! - `openAPIV3Schema` properties become fields, objects and arrays of objects
+//!   become nested blocks
+//! - With the `status` subresource enabled, `status` properties become outputs
+//! - `x-kubernetes-int-or-string` fields are strings, and objects with
+//!   `x-kubernetes-preserve-unknown-fields` accept arbitrary content
+//!
+//! ## CRD Sources
+//!
+//! - From cluster: `kubectl get crd certificates.cert-manager.io -o yaml`
+//! - From operator releases: e.g., `cert-manager.crds.yaml`
+//!
+//! ## Usage
+//! ```rust,ignore
+//! use hemmer_provider_generator_parser::crd::CrdParser;
+//!
+//! let parser = CrdParser::from_file("cert-manager.crds.yaml", "cert_manager", "v1.14.0")?;
+//! let service_def = parser.parse()?;
+//! ```
+
+mod converter;
+mod parser;
+mod types;
+
+pub use parser::{is_crd_document, is_crd_manifest, CrdParser};
+pub use types::*;
@@ -0,0 +1,201 @@
+//! CustomResourceDefinition manifest parser
+
+use super::types::CustomResourceDefinition;
+use hemmer_provider_generator_common::{GeneratorError, Result, ServiceDefinition};
+use serde::Deserialize;
+use std::fs;
+use std::path::Path;
+
+/// Kubernetes CustomResourceDefinition parser
+///
+/// Reads CRD manifests (single or multi-document YAML, or JSON) as installed
+/// by operators, and turns every served version into a resource.
+pub struct CrdParser {
+    /// Loaded CRDs
+    crds: Vec<CustomResourceDefinition>,
+
+    /// Service name (e.g., "cert_manager")
+    service_name: String,
+
+    /// API version
+    api_version: String,
+}
+
+impl CrdParser {
+    /// Load CRD manifests from file path
+    ///
+    /// # Example
+    /// ```rust,ignore
+    /// let parser = CrdParser::from_file(
+    ///     "cert-manager.crds.yaml",
+    ///     "cert_manager",
+    ///     "v1.14.0"
+    /// )?;
+    /// ```
+    pub fn from_file<P: AsRef<Path>>(
+        path: P,
+        service_name: &str,
+        api_version: &str,
+    ) -> Result<Self> {
+        let content = fs::read_to_string(path.as_ref()).map_err(|e| {
+            GeneratorError::Parse(format!(
+                "Failed to read CRD file {}: {}",
+                path.as_ref().display(),
+                e
+            ))
+        })?;
+
+        // JSON is valid YAML, so one code path handles both
+        Self::from_yaml(&content, service_name, api_version)
+    }
+
+    /// Parse CRD manifests from a (multi-document) YAML string
+    ///
+    /// Documents that aren't CRDs are skipped; `kind: List` documents are unpacked.
+    pub fn from_yaml(yaml: &str, service_name: &str, api_version: &str) -> Result<Self> {
+        let mut crds = Vec::new();
+
+        for document in serde_yaml::Deserializer::from_str(yaml) {
+            let value = serde_json::Value::deserialize(document)
+                .map_err(|e| GeneratorError::Parse(format!("Failed to parse CRD YAML: {}", e)))?;
+            collect_crds(value, &mut crds)?;
+        }
+
+        if crds.is_empty() {
+            return Err(GeneratorError::Parse(
+                "No CustomResourceDefinition found".to_string(),
+            ));
+        }
+
+        Ok(Self {
+            crds,
+            service_name: service_name.to_string(),
+            api_version: api_version.to_string(),
+        })
+    }
+
+    /// Parse CRDs into ServiceDefinition IR
+    pub fn parse(&self) -> Result<ServiceDefinition> {
+        super::converter::convert_crds_to_service_definition(
+            &self.crds,
+            &self.service_name,
+            &self.api_version,
+        )
+    }
+
+    /// Get reference to the loaded CRDs
+    pub fn crds(&self) -> &[CustomResourceDefinition] {
+        &self.crds
+    }
+}
+
+/// Check whether (multi-document) YAML or JSON holds a CustomResourceDefinition
+///
+/// Only each document's top-level `kind` counts, however it is quoted.
+/// Documents that aren't valid YAML are ignored.
+pub fn is_crd_manifest(yaml: &str) -> bool {
+    serde_yaml::Deserializer::from_str(yaml)
+        .filter_map(|document| serde_json::Value::deserialize(document).ok())
+        .any(|value| is_crd_document(&value))
+}
+
+/// Check whether a manifest document is (or contains) a CustomResourceDefinition
+pub fn is_crd_document(value: &serde_json::Value) -> bool {
+    match value.get("kind").and_then(|k| k.as_str()) {
+        Some("CustomResourceDefinition") => true,
+        Some("List") => value
+            .get("items")
+            .and_then(|items| items.as_array())
+            .is_some_and(|items| items.iter().any(is_crd_document)),
+        _ => false,
+    }
+}
+
+/// Collect the CRDs in a manifest document
+fn collect_crds(value: serde_json::Value, crds: &mut Vec<CustomResourceDefinition>) -> Result<()> {
+    match value.get("kind").and_then(|k| k.as_str()) {
+        Some("CustomResourceDefinition") => {
+            let crd = serde_json::from_value(value)
+                .map_err(|e| GeneratorError::Parse(format!("Invalid CRD: {}", e)))?;
+            crds.push(crd);
+        },
+        Some("List") => {
+            if let Some(serde_json::Value::Array(items)) = value.get("items").cloned() {
+                for item in items {
+                    collect_crds(item, crds)?;
+                }
+            }
+        },
+        // Empty documents, Namespaces, RBAC, ... that ship alongside CRDs
+        _ => {},
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_multi_document_yaml() {
+        let yaml = r#"
+apiVersion: v1
+kind: Namespace
+metadata:
+  name: widgets-system
+---
+apiVersion: apiextensions.k8s.io/v1
+kind: CustomResourceDefinition
+metadata:
+  name: widgets.example.com
+spec:
+  group: example.com
+  names:
+    kind: Widget
+    plural: widgets
+  scope: Namespaced
+  versions:
+    - name: v1
+      served: true
+      storage: true
+---
+"#;
+
+        let parser = CrdParser::from_yaml(yaml, "widgets", "v1").unwrap();
+        assert_eq!(parser.crds().len(), 1);
+        assert_eq!(parser.crds()[0].spec.names.kind, "Widget");
+        assert!(parser.crds()[0].spec.is_namespaced());
+    }
+
+    #[test]
+    fn test_is_crd_manifest() {
+        let crd = "apiVersion: apiextensions.k8s.io/v1\nkind: CustomResourceDefinition\n";
+        assert!(is_crd_manifest(crd));
+        assert!(is_crd_manifest("kind: \"CustomResourceDefinition\"\n"));
+        assert!(is_crd_manifest("kind: 'CustomResourceDefinition'\n"));
+        assert!(is_crd_manifest("{kind: CustomResourceDefinition}\n"));
+        assert!(is_crd_manifest(&format!(
+            "apiVersion: v1\nkind: Namespace\n---\n{}",
+            crd
+        )));
+        assert!(is_crd_manifest(
+            "apiVersion: v1\nkind: List\nitems:\n  - kind: CustomResourceDefinition\n"
+        ));
+
+        // Nested or commented-out kinds are not CRDs
+        assert!(!is_crd_manifest(
+            "apiVersion: v1\nkind: ConfigMap\ndata:\n  crd.yaml: |\n    kind: CustomResourceDefinition\n"
+        ));
+        assert!(!is_crd_manifest(
+            "# kind: CustomResourceDefinition\nkind: Service\n"
+        ));
+        assert!(!is_crd_manifest("openapi: 3.0.0\npaths: {}\n"));
+    }
+
+    #[test]
+    fn test_no_crds_is_an_error() {
+        let yaml = "apiVersion: v1\nkind: ConfigMap\nmetadata:\n  name: config\n";
+        assert!(CrdParser::from_yaml(yaml, "config", "v1").is_err());
+    }
+}
@@ -0,0 +1,227 @@
+//! Kubernetes CustomResourceDefinition type definitions
+//!
+//! These types represent the `apiextensions.k8s.io/v1` CustomResourceDefinition manifest.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// A CustomResourceDefinition manifest
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CustomResourceDefinition {
+    /// API version (e.g., "apiextensions.k8s.io/v1")
+    pub api_version: String,
+
+    /// Kind (always "CustomResourceDefinition")
+    pub kind: String,
+
+    /// Object metadata
+    #[serde(default)]
+    pub metadata: ObjectMeta,
+
+    /// CRD specification
+    pub spec: CrdSpec,
+}
+
+/// Subset of object metadata used by the parser
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ObjectMeta {
+    /// Object name (e.g., "certificates.cert-manager.io")
+    #[serde(default)]
+    pub name: Option<String>,
+}
+
+/// CRD specification
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CrdSpec {
+    /// API group (e.g., "cert-manager.io")
+    pub group: String,
+
+    /// Names of the custom resource
+    pub names: CrdNames,
+
+    /// Scope: "Namespaced" or "Cluster"
+    pub scope: String,
+
+    /// Versions of the custom resource
+    #[serde(default)]
+    pub versions: Vec<CrdVersion>,
+}
+
+impl CrdSpec {
+    /// Whether objects of this kind live in a namespace
+    pub fn is_namespaced(&self) -> bool {
+        self.scope == "Namespaced"
+    }
+}
+
+/// Names of a custom resource
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CrdNames {
+    /// Kind (e.g., "Certificate")
+    pub kind: String,
+
+    /// Plural name used in URLs (e.g., "certificates")
+    pub plural: String,
+
+    /// Singular name (e.g., "certificate")
+    #[serde(default)]
+    pub singular: Option<String>,
+
+    /// Kind of the list type (e.g., "CertificateList")
+    #[serde(default)]
+    pub list_kind: Option<String>,
+}
+
+/// A version of a custom resource
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CrdVersion {
+    /// Version name (e.g., "v1", "v1beta1")
+    pub name: String,
+
+    /// Whether the version is served by the API server
+    #[serde(default)]
+    pub served: bool,
+
+    /// Whether this is the storage version
+    #[serde(default)]
+    pub storage: bool,
+
+    /// Whether the version is deprecated
+    #[serde(default)]
+    pub deprecated: bool,
+
+    /// Warning returned to API clients using a deprecated version
+    #[serde(rename = "deprecationWarning")]
+    #[serde(default)]
+    pub deprecation_warning: Option<String>,
+
+    /// Validation schema
+    #[serde(default)]
+    pub schema: Option<CrdValidation>,
+
+    /// Enabled subresources
+    #[serde(default)]
+    pub subresources: Option<CrdSubresources>,
+}
+
+/// Validation schema wrapper
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CrdValidation {
+    /// OpenAPI v3 schema of the custom resource
+    #[serde(rename = "openAPIV3Schema")]
+    pub open_api_v3_schema: JsonSchemaProps,
+}
+
+/// Subresources of a custom resource version
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CrdSubresources {
+    /// `/status` subresource (an empty object when enabled)
+    #[serde(default)]
+    pub status: Option<serde_json::Value>,
+
+    /// `/scale` subresource
+    #[serde(default)]
+    pub scale: Option<serde_json::Value>,
+}
+
+/// Structural OpenAPI v3 schema used by CRDs
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct JsonSchemaProps {
+    /// Type: string, number, integer, boolean, array, object
+    #[serde(rename = "type")]
+    #[serde(default)]
+    pub schema_type: Option<String>,
+
+    /// Format (e.g., int32, date-time, byte)
+    #[serde(default)]
+    pub format: Option<String>,
+
+    /// Description
+    #[serde(default)]
+    pub description: Option<String>,
+
+    /// Properties (for object type)
+    #[serde(default)]
+    pub properties: HashMap<String, JsonSchemaProps>,
+
+    /// Required properties
+    #[serde(default)]
+    pub required: Vec<String>,
+
+    /// Items schema (for array type)
+    #[serde(default)]
+    pub items: Option<Box<JsonSchemaProps>>,
+
+    /// Additional properties (for map type)
+    #[serde(rename = "additionalProperties")]
+    #[serde(default)]
+    pub additional_properties: Option<AdditionalProperties>,
+
+    /// Enum values
+    #[serde(rename = "enum")]
+    #[serde(default)]
+    pub enum_values: Vec<serde_json::Value>,
+
+    /// Minimum number of items (for array type)
+    #[serde(rename = "minItems")]
+    #[serde(default)]
+    pub min_items: Option<u32>,
+
+    /// Maximum number of items (for array type)
+    #[serde(rename = "maxItems")]
+    #[serde(default)]
+    pub max_items: Option<u32>,
+
+    /// Smallest allowed number
+    #[serde(default)]
+    pub minimum: Option<f64>,
+
+    /// Largest allowed number
+    #[serde(default)]
+    pub maximum: Option<f64>,
+
+    /// Minimum string length
+    #[serde(rename = "minLength")]
+    #[serde(default)]
+    pub min_length: Option<u64>,
+
+    /// Maximum string length
+    #[serde(rename = "maxLength")]
+    #[serde(default)]
+    pub max_length: Option<u64>,
+
+    /// Regular expression string values have to match
+    #[serde(default)]
+    pub pattern: Option<String>,
+
+    /// Value the API server defaults the property to
+    #[serde(default)]
+    pub default: Option<serde_json::Value>,
+
+    /// Keep fields that aren't described by the schema
+    #[serde(rename = "x-kubernetes-preserve-unknown-fields")]
+    #[serde(default)]
+    pub preserve_unknown_fields: Option<bool>,
+
+    /// Accept either an integer or a string (e.g., ports, percentages)
+    #[serde(rename = "x-kubernetes-int-or-string")]
+    #[serde(default)]
+    pub int_or_string: Option<bool>,
+
+    /// Value is an embedded Kubernetes object (with apiVersion, kind and metadata)
+    #[serde(rename = "x-kubernetes-embedded-resource")]
+    #[serde(default)]
+    pub embedded_resource: Option<bool>,
+}
+
+/// `additionalProperties` is either a schema or a boolean
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum AdditionalProperties {
+    /// Whether any additional properties are allowed
+    Bool(bool),
+    /// Schema of the additional property values
+    Schema(Box<JsonSchemaProps>),
+}
@@ -0,0 +1,406 @@
+//! Declarative CRUD classification rules
+//!
+//! Every parser classifies operations into lifecycle roles with the same
+//! [`CrudRules`]: the built-in rules (`crud_rules.yaml`) plus, optionally, a
+//! rules file checked before them.
+//!
+//! ## Rules File
+//! ```yaml
+//! rules:
+//!   # Matchers: name (exact), prefix (whole words), regex, http_method
+//!   - prefix: put_bucket
+//!     role: update
+//!     providers: [aws]
+//!   - regex: "^(start|stop)_"
+//!     role: ignore
+//! overrides:
+//!   PutObject: create
+//!   GetBucketPolicyStatus: ignore
+//! ```
+//!
+//! Operation names are matched in snake_case. Roles are `create`, `read`,
+//! `update`, `delete`, `list` and `ignore`; ignored operations are left out of
+//! resources. Overrides apply to an operation by name and beat every rule.
+
+use crate::naming::to_snake_case;
+use hemmer_provider_generator_common::{GeneratorError, Provider, Result};
+use regex::Regex;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::OnceLock;
+
+/// Built-in rules, checked after those of a rules file
+const BUILTIN_RULES: &str = include_str!("crud_rules.yaml");
+
+/// Lifecycle role of an operation
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CrudRole {
+    Create,
+    Read,
+    Update,
+    Delete,
+    /// Lists a collection; parsers may use it as a read fallback or data source
+    List,
+    /// Not part of any resource lifecycle
+    Ignore,
+}
+
+/// Role given to an operation, with the position of the rule that matched
+///
+/// Rank 0 is an override; lower ranks come from earlier rules.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Classification {
+    pub role: CrudRole,
+    pub rank: usize,
+}
+
+/// Ordered CRUD classification rules with per-operation overrides
+#[derive(Debug, Clone)]
+pub struct CrudRules {
+    rules: Vec<Rule>,
+    /// Roles by snake_case operation name
+    overrides: HashMap<String, CrudRole>,
+}
+
+/// A rule: every matcher given has to match
+#[derive(Debug, Clone)]
+struct Rule {
+    name: Option<String>,
+    prefix: Option<String>,
+    regex: Option<Regex>,
+    http_method: Option<String>,
+    /// Providers the rule applies to (all when empty)
+    providers: Vec<String>,
+    role: CrudRole,
+}
+
+/// Rules file layout
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RulesFile {
+    #[serde(default)]
+    rules: Vec<RuleEntry>,
+    #[serde(default)]
+    overrides: HashMap<String, CrudRole>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RuleEntry {
+    name: Option<String>,
+    prefix: Option<String>,
+    regex: Option<String>,
+    http_method: Option<String>,
+    #[serde(default)]
+    providers: Vec<String>,
+    role: CrudRole,
+}
+
+impl Default for CrudRules {
+    /// The built-in rules
+    fn default() -> Self {
+        static BUILTIN: OnceLock<CrudRules> = OnceLock::new();
+        BUILTIN
+            .get_or_init(|| {
+                Self::parse(BUILTIN_RULES).expect("built-in CRUD rules should be valid")
+            })
+            .clone()
+    }
+}
+
+impl CrudRules {
+    /// Load a rules file, checked before the built-in rules
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let content = std::fs::read_to_string(path.as_ref()).map_err(|e| {
+            GeneratorError::Parse(format!(
+                "Failed to read CRUD rules file {}: {}",
+                path.as_ref().display(),
+                e
+            ))
+        })?;
+        Self::from_yaml(&content)
+    }
+
+    /// Parse rules from YAML, checked before the built-in rules
+    pub fn from_yaml(yaml: &str) -> Result<Self> {
+        let mut rules = Self::parse(yaml)?;
+        rules.rules.extend(Self::default().rules);
+        Ok(rules)
+    }
+
+    fn parse(yaml: &str) -> Result<Self> {
+        let file: RulesFile = serde_yaml::from_str(yaml)
+            .map_err(|e| GeneratorError::Parse(format!("Failed to parse CRUD rules: {}", e)))?;
+
+        let rules = file
+            .rules
+            .into_iter()
+            .map(|entry| {
+                if entry.name.is_none()
+                    && entry.prefix.is_none()
+                    && entry.regex.is_none()
+                    && entry.http_method.is_none()
+                {
+                    return Err(GeneratorError::Parse(format!(
+                        "CRUD rule for role {:?} needs a name, prefix, regex or http_method",
+                        entry.role
+                    )));
+                }
+                let regex = entry
+                    .regex
+                    .map(|pattern| {
+                        Regex::new(&pattern).map_err(|e| {
+                            GeneratorError::Parse(format!(
+                                "Invalid CRUD rule regex '{}': {}",
+                                pattern, e
+                            ))
+                        })
+                    })
+                    .transpose()?;
+
+                Ok(Rule {
+                    name: entry.name.as_deref().map(to_snake_case),
+                    prefix: entry.prefix.as_deref().map(to_snake_case),
+                    regex,
+                    http_method: entry.http_method,
+                    providers: entry.providers,
+                    role: entry.role,
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let overrides = file
+            .overrides
+            .into_iter()
+            .map(|(name, role)| (to_snake_case(&name), role))
+            .collect();
+
+        Ok(Self { rules, overrides })
+    }
+
+    /// Classify an operation of `provider`
+    ///
+    /// `http_method` is matched by `http_method` rules, for specs that have one.
+    /// Returns `None` for ignored and unmatched operations.
+    pub fn classify(
+        &self,
+        provider: &Provider,
+        operation: &str,
+        http_method: Option<&str>,
+    ) -> Option<Classification> {
+        let name = to_snake_case(operation);
+
+        let (role, rank) = match self.overrides.get(&name) {
+            Some(role) => (*role, 0),
+            None => self
+                .rules
+                .iter()
+                .position(|rule| rule.matches(provider, &name, http_method))
+                .map(|index| (self.rules[index].role, index + 1))?,
+        };
+
+        (role != CrudRole::Ignore).then_some(Classification { role, rank })
+    }
+}
+
+impl Rule {
+    fn matches(&self, provider: &Provider, name: &str, http_method: Option<&str>) -> bool {
+        let applies =
+            self.providers.is_empty() || self.providers.iter().any(|p| p == provider.name());
+
+        applies
+            && self.name.as_ref().is_none_or(|n| n == name)
+            && self.prefix.as_ref().is_none_or(|prefix| {
+                name.strip_prefix(prefix.as_str())
+                    .is_some_and(|rest| rest.is_empty() || rest.starts_with('_'))
+            })
+            && self.regex.as_ref().is_none_or(|regex| regex.is_match(name))
+            && self
+                .http_method
+                .as_ref()
+                .is_none_or(|method| http_method.is_some_and(|m| m.eq_ignore_ascii_case(method)))
+    }
+}
+
+/// Operations of one resource, one per CRUD role
+///
+/// An operation replaces the one held for its role unless that one was
+/// matched by an earlier rule.
+#[derive(Debug, Clone)]
+pub struct CrudOperations<T> {
+    pub create: Option<T>,
+    pub read: Option<T>,
+    pub update: Option<T>,
+    pub delete: Option<T>,
+    pub list: Option<T>,
+    /// Rank of the classification holding each role
+    ranks: HashMap<CrudRole, usize>,
+}
+
+impl<T> Default for CrudOperations<T> {
+    fn default() -> Self {
+        Self {
+            create: None,
+            read: None,
+            update: None,
+            delete: None,
+            list: None,
+            ranks: HashMap::new(),
+        }
+    }
+}
+
+impl<T> CrudOperations<T> {
+    /// Add a classified operation
+    pub fn insert(&mut self, classification: Classification, operation: T) {
+        let slot = match classification.role {
+            CrudRole::Create => &mut self.create,
+            CrudRole::Read => &mut self.read,
+            CrudRole::Update => &mut self.update,
+            CrudRole::Delete => &mut self.delete,
+            CrudRole::List => &mut self.list,
+            CrudRole::Ignore => return,
+        };
+        let held = self.ranks.get(&classification.role);
+        if slot.is_none() || held.is_none_or(|rank| classification.rank <= *rank) {
+            *slot = Some(operation);
+            self.ranks.insert(classification.role, classification.rank);
+        }
+    }
+
+    /// Whether no create, read, update or delete operation was found
+    pub fn is_empty(&self) -> bool {
+        self.create.is_none()
+            && self.read.is_none()
+            && self.update.is_none()
+            && self.delete.is_none()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn role(rules: &CrudRules, provider: &Provider, operation: &str) -> Option<CrudRole> {
+        rules.classify(provider, operation, None).map(|c| c.role)
+    }
+
+    #[test]
+    fn test_builtin_rules() {
+        let rules = CrudRules::default();
+
+        assert_eq!(
+            role(&rules, &Provider::Aws, "CreateBucket"),
+            Some(CrudRole::Create)
+        );
+        assert_eq!(
+            role(&rules, &Provider::Aws, "describe_instances"),
+            Some(CrudRole::Read)
+        );
+        assert_eq!(
+            role(&rules, &Provider::Gcp, "ListBuckets"),
+            Some(CrudRole::List)
+        );
+        assert_eq!(
+            role(&rules, &Provider::Aws, "RemoveTags"),
+            Some(CrudRole::Delete)
+        );
+        assert_eq!(role(&rules, &Provider::Aws, "TagResource"), None);
+
+        // Prefixes match whole words
+        assert_eq!(role(&rules, &Provider::Aws, "Getaway"), None);
+
+        // Put means create on AWS but replace on GCP
+        assert_eq!(
+            role(&rules, &Provider::Aws, "PutObject"),
+            Some(CrudRole::Create)
+        );
+        assert_eq!(
+            role(&rules, &Provider::Gcp, "PutObject"),
+            Some(CrudRole::Update)
+        );
+        assert_eq!(role(&rules, &Provider::Azure, "PutObject"), None);
+
+        // Unnamed Discovery methods fall back to their HTTP method
+        assert_eq!(
+            rules
+                .classify(&Provider::Gcp, "setIamPolicy", Some("POST"))
+                .map(|c| c.role),
+            Some(CrudRole::Create)
+        );
+        assert_eq!(role(&rules, &Provider::Gcp, "setIamPolicy"), None);
+
+        // Azure creates with a PUT, Google replaces with one
+        let put = |provider: &Provider| {
+            rules
+                .classify(provider, "Widgets_CreateOrUpdate", Some("PUT"))
+                .map(|c| c.role)
+        };
+        assert_eq!(put(&Provider::Azure), Some(CrudRole::Create));
+        assert_eq!(put(&Provider::Gcp), Some(CrudRole::Update));
+    }
+
+    #[test]
+    fn test_rules_file_comes_first() {
+        let rules = CrudRules::from_yaml(
+            r#"
+rules:
+  - prefix: put_bucket
+    role: update
+    providers: [aws]
+  - regex: "^(start|stop)_"
+    role: ignore
+overrides:
+  GetBucketPolicyStatus: ignore
+  ListTags: read
+"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            role(&rules, &Provider::Aws, "PutBucketAcl"),
+            Some(CrudRole::Update)
+        );
+        assert_eq!(
+            role(&rules, &Provider::Aws, "PutObject"),
+            Some(CrudRole::Create)
+        );
+        assert_eq!(role(&rules, &Provider::Aws, "StartInstances"), None);
+        assert_eq!(role(&rules, &Provider::Aws, "GetBucketPolicyStatus"), None);
+        assert_eq!(
+            rules.classify(&Provider::Aws, "list_tags", None),
+            Some(Classification {
+                role: CrudRole::Read,
+                rank: 0
+            })
+        );
+
+        // Built-in rules still apply
+        assert_eq!(
+            role(&rules, &Provider::Aws, "DeleteBucket"),
+            Some(CrudRole::Delete)
+        );
+    }
+
+    #[test]
+    fn test_invalid_rules() {
+        assert!(CrudRules::from_yaml("rules:\n  - role: create\n").is_err());
+        assert!(CrudRules::from_yaml("rules:\n  - regex: '('\n    role: create\n").is_err());
+        assert!(CrudRules::from_yaml("rules:\n  - prefix: put\n    role: upsert\n").is_err());
+    }
+
+    #[test]
+    fn test_earlier_rules_win() {
+        let rules = CrudRules::default();
+        let mut operations = CrudOperations::default();
+        for name in ["getIamPolicy", "get", "getHealth"] {
+            let classification = rules.classify(&Provider::Gcp, name, Some("GET")).unwrap();
+            operations.insert(classification, name);
+        }
+
+        assert_eq!(operations.read, Some("get"));
+        assert!(!operations.is_empty());
+    }
+}
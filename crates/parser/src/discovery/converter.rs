@@ -1,9 +1,12 @@
 //! Converts Discovery document to ServiceDefinition IR
 
-use super::types::{DiscoveryDoc, Method, Schema};
+use super::types::{DiscoveryDoc, Method, Parameter, Schema};
+use crate::crud_rules::{CrudOperations, CrudRules};
+use crate::naming::to_snake_case;
 use hemmer_provider_generator_common::{
-    BlockDefinition, FieldDefinition, FieldType, NestingMode, OperationMapping, Operations,
-    Provider, ResourceDefinition, Result, ServiceDefinition,
+    BlockDefinition, DataSourceDefinition, Deprecation, FieldConstraints, FieldDefinition,
+    FieldType, LongRunningConfig, NestingMode, OperationMapping, Operations, PaginationConfig,
+    Provider, ResourceDefinition, Result, ServiceDefinition, Stability,
 };
 use std::collections::HashMap;
 
@@ -12,66 +15,70 @@ pub fn convert_discovery_to_service_definition(
     doc: &DiscoveryDoc,
     service_name: &str,
     api_version: &str,
+    crud_rules: &CrudRules,
 ) -> Result<ServiceDefinition> {
-    // Extract resources from methods
-    let resources = extract_resources_from_doc(doc)?;
+    // Extract resources and data sources from methods
+    let (resources, data_sources) = extract_resources_from_doc(doc, crud_rules)?;
 
     Ok(ServiceDefinition {
         provider: Provider::Gcp,
         name: service_name.to_string(),
         sdk_version: api_version.to_string(),
         resources,
-        data_sources: vec![], // Will implement data source detection later
+        data_sources,
     })
 }
 
-/// Extract resources from Discovery document
-fn extract_resources_from_doc(doc: &DiscoveryDoc) -> Result<Vec<ResourceDefinition>> {
-    let mut resource_map: HashMap<String, ResourceMethods> = HashMap::new();
+/// Extract resources, and data sources from their `list` methods, from Discovery document
+fn extract_resources_from_doc(
+    doc: &DiscoveryDoc,
+    crud_rules: &CrudRules,
+) -> Result<(Vec<ResourceDefinition>, Vec<DataSourceDefinition>)> {
+    let mut resource_map: HashMap<String, CrudOperations<Method>> = HashMap::new();
 
     // Collect all methods from resources
-    collect_methods_from_resources(&doc.resources, &mut resource_map);
+    collect_methods_from_resources(&doc.resources, crud_rules, &mut resource_map);
 
     // Also check root-level methods (rare but possible)
     for (method_name, method) in &doc.methods {
         if let Some(resource_name) = DiscoveryDoc::extract_resource_from_method_id(&method.id) {
-            let entry = resource_map
-                .entry(resource_name.clone())
-                .or_insert_with(|| ResourceMethods::new(resource_name));
-
-            classify_method(method_name, method, entry);
+            let entry = resource_map.entry(resource_name).or_default();
+            classify_method(crud_rules, method_name, method, entry);
         }
     }
 
-    // Convert to ResourceDefinitions
+    // Convert to ResourceDefinitions and DataSourceDefinitions
     let mut resources = Vec::new();
-    for (_name, methods) in resource_map {
+    let mut data_sources = Vec::new();
+    for (name, operations) in resource_map {
+        let methods = ResourceMethods::new(name, operations);
+        if let Some(data_source) = build_data_source_from_methods(doc, &methods)? {
+            data_sources.push(data_source);
+        }
         if let Some(resource_def) = build_resource_from_methods(doc, methods)? {
             resources.push(resource_def);
         }
     }
 
-    Ok(resources)
+    Ok((resources, data_sources))
 }
 
 /// Recursively collect methods from resources
 fn collect_methods_from_resources(
     resources: &HashMap<String, super::types::Resource>,
-    resource_map: &mut HashMap<String, ResourceMethods>,
+    crud_rules: &CrudRules,
+    resource_map: &mut HashMap<String, CrudOperations<Method>>,
 ) {
     for resource in resources.values() {
         for (method_name, method) in &resource.methods {
             if let Some(resource_name) = DiscoveryDoc::extract_resource_from_method_id(&method.id) {
-                let entry = resource_map
-                    .entry(resource_name.clone())
-                    .or_insert_with(|| ResourceMethods::new(resource_name));
-
-                classify_method(method_name, method, entry);
+                let entry = resource_map.entry(resource_name).or_default();
+                classify_method(crud_rules, method_name, method, entry);
             }
         }
 
         // Recursively process nested resources
-        collect_methods_from_resources(&resource.resources, resource_map);
+        collect_methods_from_resources(&resource.resources, crud_rules, resource_map);
     }
 }
 
@@ -83,42 +90,37 @@ struct ResourceMethods {
     read: Option<Method>,
     update: Option<Method>,
     delete: Option<Method>,
+    list: Option<Method>,
 }
 
 impl ResourceMethods {
-    fn new(name: String) -> Self {
+    fn new(name: String, operations: CrudOperations<Method>) -> Self {
         Self {
             name,
-            create: None,
-            read: None,
-            update: None,
-            delete: None,
+            create: operations.create,
+            // Lists stand in for reads of resources without a get method
+            read: operations.read.or_else(|| operations.list.clone()),
+            update: operations.update,
+            delete: operations.delete,
+            list: operations.list,
         }
     }
 }
 
 /// Classify method into CRUD operation
-fn classify_method(method_name: &str, method: &Method, methods: &mut ResourceMethods) {
-    // Discovery methods are typically named: insert, get, update, patch, delete
-    match method_name {
-        "insert" | "create" => methods.create = Some(method.clone()),
-        "get" | "read" => methods.read = Some(method.clone()),
-        "update" | "patch" => methods.update = Some(method.clone()),
-        "delete" => methods.delete = Some(method.clone()),
-        _ => {
-            // Try to infer from HTTP method
-            match method.http_method.as_str() {
-                "POST" => methods.create = methods.create.clone().or_else(|| Some(method.clone())),
-                "GET" => methods.read = methods.read.clone().or_else(|| Some(method.clone())),
-                "PUT" | "PATCH" => {
-                    methods.update = methods.update.clone().or_else(|| Some(method.clone()))
-                },
-                "DELETE" => {
-                    methods.delete = methods.delete.clone().or_else(|| Some(method.clone()))
-                },
-                _ => {},
-            }
-        },
+///
+/// Discovery methods are typically named insert, get, update, patch, delete and
+/// list; the built-in rules fall back to the HTTP method for other names.
+fn classify_method(
+    crud_rules: &CrudRules,
+    method_name: &str,
+    method: &Method,
+    operations: &mut CrudOperations<Method>,
+) {
+    if let Some(classification) =
+        crud_rules.classify(&Provider::Gcp, method_name, Some(&method.http_method))
+    {
+        operations.insert(classification, method.clone());
     }
 }
 
@@ -159,6 +161,9 @@ fn build_resource_from_methods(
         Vec::new()
     };
 
+    // The get method's trailing path parameter identifies the resource
+    let id_field = methods.read.as_ref().and_then(trailing_path_parameter);
+
     // Get description
     let description = methods
         .create
@@ -166,35 +171,170 @@ fn build_resource_from_methods(
         .and_then(|m| m.description.clone())
         .or_else(|| methods.read.as_ref().and_then(|m| m.description.clone()));
 
-    Ok(Some(ResourceDefinition {
+    let operations = Operations {
+        create: methods
+            .create
+            .as_ref()
+            .map(|m| build_operation_mapping(doc, m)),
+        read: methods
+            .read
+            .as_ref()
+            .map(|m| build_operation_mapping(doc, m)),
+        update: methods
+            .update
+            .as_ref()
+            .map(|m| build_operation_mapping(doc, m)),
+        delete: methods
+            .delete
+            .as_ref()
+            .map(|m| build_operation_mapping(doc, m)),
+        import: None, // Will implement later
+    };
+
+    let mut resource = ResourceDefinition {
         name: to_snake_case(&methods.name),
         description,
         fields,
         outputs,
         blocks,
-        id_field: None, // Will implement ID detection later
-        operations: Operations {
-            create: methods.create.map(|m| OperationMapping {
-                sdk_operation: to_snake_case(m.id.split('.').next_back().unwrap_or(&m.id)),
-                additional_operations: vec![],
-            }),
-            read: methods.read.map(|m| OperationMapping {
-                sdk_operation: to_snake_case(m.id.split('.').next_back().unwrap_or(&m.id)),
-                additional_operations: vec![],
-            }),
-            update: methods.update.map(|m| OperationMapping {
-                sdk_operation: to_snake_case(m.id.split('.').next_back().unwrap_or(&m.id)),
-                additional_operations: vec![],
-            }),
-            delete: methods.delete.map(|m| OperationMapping {
-                sdk_operation: to_snake_case(m.id.split('.').next_back().unwrap_or(&m.id)),
-                additional_operations: vec![],
-            }),
-            import: None, // Will implement later
+        id_field,
+        // New resources can't be created once the create method is deprecated
+        deprecated: operations
+            .create
+            .as_ref()
+            .and_then(|op| op.deprecated.clone()),
+        operations,
+        // e.g., v1beta1
+        stability: Stability::from_version(&doc.version),
+    };
+    resource.infer_computed_fields();
+
+    Ok(Some(resource))
+}
+
+/// Build a DataSourceDefinition from a resource's `list` method
+///
+/// Path and query parameters (such as `filter`) become arguments, except the
+/// page token and page size. The data source returns the first entry of the
+/// response's `items` (or its first array of objects), whose properties are
+/// the attributes.
+fn build_data_source_from_methods(
+    doc: &DiscoveryDoc,
+    methods: &ResourceMethods,
+) -> Result<Option<DataSourceDefinition>> {
+    let Some(list) = methods.list.as_ref() else {
+        return Ok(None);
+    };
+    let Some(response) = list
+        .response
+        .as_ref()
+        .and_then(|r| doc.resolve_schema_ref(&r.ref_schema))
+    else {
+        return Ok(None);
+    };
+
+    // Property names sorted for deterministic output, `items` first
+    let mut names: Vec<&String> = response.properties.keys().collect();
+    names.sort_by_key(|name| (name.as_str() != "items", name.as_str()));
+    let Some((items_name, item)) = names
+        .into_iter()
+        .find_map(|name| Some((name, list_item_schema(doc, &response.properties[name])?)))
+    else {
+        return Ok(None);
+    };
+
+    let items_accessor = to_snake_case(items_name);
+    let page_size = ["maxResults", "pageSize"]
+        .into_iter()
+        .find(|p| list.parameters.contains_key(*p))
+        .map(to_snake_case);
+    let pagination = list
+        .parameters
+        .contains_key("pageToken")
+        .then(|| PaginationConfig {
+            input_token: Some("page_token".to_string()),
+            output_token: Some("next_page_token".to_string()),
+            items: Some(items_accessor.clone()),
+            page_size: page_size.clone(),
+        });
+
+    // Page tokens and sizes are driven by the provider, not by users
+    let arguments = extract_fields_from_method(doc, list)?
+        .into_iter()
+        .filter(|f| f.name != "page_token" && Some(&f.name) != page_size.as_ref())
+        .collect();
+
+    Ok(Some(DataSourceDefinition {
+        name: to_snake_case(&methods.name),
+        description: list.description.clone(),
+        arguments,
+        attributes: extract_fields_from_schema(doc, item, &list.id)?,
+        read_operation: OperationMapping {
+            pagination,
+            readonly: true,
+            idempotent: true,
+            ..build_operation_mapping(doc, list)
         },
+        items_accessor: Some(items_accessor),
     }))
 }
 
+/// Object schema of the entries of an array property
+fn list_item_schema<'a>(doc: &'a DiscoveryDoc, property: &'a Schema) -> Option<&'a Schema> {
+    let items = property.items.as_ref()?;
+    match items.ref_schema.as_deref() {
+        Some(ref_name) => doc.resolve_schema_ref(ref_name),
+        None => Some(items.as_ref()),
+    }
+    .filter(|item| !item.properties.is_empty())
+}
+
+/// Build an OperationMapping for a Discovery method
+fn build_operation_mapping(doc: &DiscoveryDoc, method: &Method) -> OperationMapping {
+    OperationMapping {
+        sdk_operation: to_snake_case(method.id.split('.').next_back().unwrap_or(&method.id)),
+        additional_operations: vec![],
+        long_running: operation_polling(doc, method),
+        deprecated: method.deprecated.then(Deprecation::default),
+        ..Default::default()
+    }
+}
+
+/// Polling details of a method returning an `Operation` resource
+///
+/// Compute-style APIs poll operations through the resource matching the
+/// method's scope (`zoneOperations`, `regionOperations` or `globalOperations`);
+/// others such as SQL Admin have a single `operations` resource.
+fn operation_polling(doc: &DiscoveryDoc, method: &Method) -> Option<LongRunningConfig> {
+    if method.response.as_ref()?.ref_schema != "Operation" {
+        return None;
+    }
+
+    let scoped = if method.parameters.contains_key("zone") {
+        "zoneOperations"
+    } else if method.parameters.contains_key("region") {
+        "regionOperations"
+    } else {
+        "globalOperations"
+    };
+    let (name, resource) = [scoped, "operations"]
+        .into_iter()
+        .find_map(|name| doc.resources.get_key_value(name))?;
+    let get = resource.methods.get("get")?;
+
+    Some(LongRunningConfig {
+        operations_resource: Some(to_snake_case(name)),
+        // Everything but the operation name itself, e.g. project and zone
+        operation_scope: get
+            .parameter_order
+            .iter()
+            .filter(|p| p.as_str() != "operation")
+            .map(|p| to_snake_case(p))
+            .collect(),
+        ..Default::default()
+    })
+}
+
 /// Extract fields from method request
 fn extract_fields_from_method(doc: &DiscoveryDoc, method: &Method) -> Result<Vec<FieldDefinition>> {
     let mut fields = Vec::new();
@@ -202,36 +342,83 @@ fn extract_fields_from_method(doc: &DiscoveryDoc, method: &Method) -> Result<Vec
     // Extract from request schema
     if let Some(ref request) = method.request {
         if let Some(schema) = doc.resolve_schema_ref(&request.ref_schema) {
-            fields.extend(extract_fields_from_schema(doc, schema)?);
+            fields.extend(extract_fields_from_schema(doc, schema, &method.id)?);
         }
     }
 
-    // Extract from parameters
-    for (param_name, param) in &method.parameters {
-        if param.location.as_deref() == Some("path") {
-            // Path parameters are typically identifiers
-            let field_type = match param.param_type.as_deref() {
-                Some("string") => FieldType::String,
-                Some("integer") => FieldType::Integer,
-                Some("boolean") => FieldType::Boolean,
-                _ => FieldType::String,
-            };
-
-            fields.push(FieldDefinition {
-                name: to_snake_case(param_name),
-                field_type,
-                required: param.required,
-                sensitive: false,
-                immutable: true, // Path params are usually immutable identifiers
-                description: param.description.clone(),
-                response_accessor: None, // Input fields don't have response accessors
-            });
+    // Extract from path and query parameters (e.g., project, zone, bucket)
+    for (param_name, param) in ordered_parameters(method) {
+        let location = param.location.as_deref();
+        if !matches!(location, Some("path") | Some("query")) {
+            continue;
+        }
+
+        let field_type = convert_parameter_to_field_type(param);
+        let field = FieldDefinition {
+            name: to_snake_case(param_name),
+            default_value: typed_default(param.default.as_deref(), &field_type),
+            field_type,
+            required: param.required,
+            sensitive: false,
+            // Path params and required query params (such as the parent
+            // project) place the resource, so they can't change
+            immutable: location == Some("path") || param.required,
+            description: param.description.clone(),
+            response_accessor: None, // Input fields don't have response accessors
+            location: location.map(String::from),
+            constraints: constraints(&param.minimum, &param.maximum, &param.pattern),
+            deprecated: param.deprecated.then(Deprecation::default),
+            ..Default::default()
+        };
+
+        // A parameter takes the place of a body property with the same name
+        match fields.iter_mut().find(|f| f.name == field.name) {
+            Some(existing) => *existing = field,
+            None => fields.push(field),
         }
     }
 
     Ok(fields)
 }
 
+/// Method parameters: those in `parameterOrder` first, the rest by name
+fn ordered_parameters(method: &Method) -> Vec<(&String, &Parameter)> {
+    let mut parameters: Vec<_> = method.parameters.iter().collect();
+    parameters.sort_by_key(|(name, _)| {
+        let position = method.parameter_order.iter().position(|p| p == *name);
+        (position.unwrap_or(usize::MAX), name.as_str())
+    });
+    parameters
+}
+
+/// Name of the last parameter in a method's path
+///
+/// e.g., "instance" for "projects/{project}/zones/{zone}/instances/{instance}"
+/// and "name" for "v1/{+name}"
+fn trailing_path_parameter(method: &Method) -> Option<String> {
+    let start = method.path.rfind('{')?;
+    let end = start + method.path[start..].find('}')?;
+    let name = method.path[start + 1..end].trim_start_matches('+');
+    (!name.is_empty()).then(|| to_snake_case(name))
+}
+
+/// Convert a method parameter to FieldType
+fn convert_parameter_to_field_type(param: &Parameter) -> FieldType {
+    let field_type = match param.param_type.as_deref() {
+        Some("integer") => integer_type(param.format.as_deref()),
+        Some("number") => FieldType::Float,
+        Some("boolean") => FieldType::Boolean,
+        _ if !param.enum_values.is_empty() => FieldType::Enum(param.enum_values.clone()),
+        _ => FieldType::String,
+    };
+
+    if param.repeated {
+        FieldType::List(Box::new(field_type))
+    } else {
+        field_type
+    }
+}
+
 /// Extract outputs from method response
 fn extract_outputs_from_method(
     doc: &DiscoveryDoc,
@@ -241,7 +428,7 @@ fn extract_outputs_from_method(
 
     if let Some(ref response) = method.response {
         if let Some(schema) = doc.resolve_schema_ref(&response.ref_schema) {
-            outputs = extract_fields_from_schema(doc, schema)?;
+            outputs = extract_fields_from_schema(doc, schema, &method.id)?;
         }
     }
 
@@ -249,16 +436,25 @@ fn extract_outputs_from_method(
 }
 
 /// Extract fields from schema (used for response/output fields)
-fn extract_fields_from_schema(doc: &DiscoveryDoc, schema: &Schema) -> Result<Vec<FieldDefinition>> {
+///
+/// Properties are required when the schema says so or their
+/// `annotations.required` lists `method_id`.
+fn extract_fields_from_schema(
+    doc: &DiscoveryDoc,
+    schema: &Schema,
+    method_id: &str,
+) -> Result<Vec<FieldDefinition>> {
     let mut fields = Vec::new();
 
     for (field_name, field_schema) in &schema.properties {
         let field_type = convert_schema_to_field_type(doc, field_schema)?;
-        let required = schema.required.contains(field_name);
+        let required =
+            schema.required.contains(field_name) || field_schema.is_required_for(method_id);
         let accessor_name = to_snake_case(field_name);
 
         fields.push(FieldDefinition {
             name: accessor_name.clone(),
+            default_value: typed_default(field_schema.default.as_deref(), &field_type),
             field_type,
             required,
             sensitive: false,
@@ -266,6 +462,9 @@ fn extract_fields_from_schema(doc: &DiscoveryDoc, schema: &Schema) -> Result<Vec
             description: field_schema.description.clone(),
             // Response fields have accessors for extracting values from SDK responses
             response_accessor: Some(accessor_name),
+            constraints: schema_constraints(field_schema),
+            deprecated: field_schema.deprecated.then(Deprecation::default),
+            ..Default::default()
         });
     }
 
@@ -278,7 +477,7 @@ fn extract_blocks_from_method(doc: &DiscoveryDoc, method: &Method) -> Result<Vec
 
     if let Some(ref request) = method.request {
         if let Some(schema) = doc.resolve_schema_ref(&request.ref_schema) {
-            blocks = detect_nested_blocks_from_schema(doc, schema)?;
+            blocks = detect_nested_blocks_from_schema(doc, schema, &method.id)?;
         }
     }
 
@@ -289,12 +488,15 @@ fn extract_blocks_from_method(doc: &DiscoveryDoc, method: &Method) -> Result<Vec
 fn detect_nested_blocks_from_schema(
     doc: &DiscoveryDoc,
     schema: &Schema,
+    method_id: &str,
 ) -> Result<Vec<BlockDefinition>> {
     let mut blocks = Vec::new();
 
     // Check each property for potential blocks
     for (prop_name, prop_schema) in &schema.properties {
-        if let Some(block) = try_extract_block_from_property(doc, prop_name, prop_schema)? {
+        if let Some(block) =
+            try_extract_block_from_property(doc, prop_name, prop_schema, method_id)?
+        {
             blocks.push(block);
         }
     }
@@ -307,6 +509,7 @@ fn try_extract_block_from_property(
     doc: &DiscoveryDoc,
     prop_name: &str,
     schema: &Schema,
+    method_id: &str,
 ) -> Result<Option<BlockDefinition>> {
     // Resolve reference if needed
     let resolved_schema = if let Some(ref ref_name) = schema.ref_schema {
@@ -337,8 +540,8 @@ fn try_extract_block_from_property(
                 // Check if items are objects (not primitive types)
                 if items.schema_type.as_deref() == Some("object") || items.ref_schema.is_some() {
                     // This is an array of objects - perfect for a block!
-                    let attributes = extract_fields_from_schema_for_block(doc, items)?;
-                    let nested_blocks = detect_nested_blocks_from_schema(doc, items)?;
+                    let attributes = extract_fields_from_schema_for_block(doc, items, method_id)?;
+                    let nested_blocks = detect_nested_blocks_from_schema(doc, items, method_id)?;
 
                     // Extract SDK type name from schema
                     let sdk_type_name = Some(extract_type_name_from_schema(items, prop_name));
@@ -357,6 +560,7 @@ fn try_extract_block_from_property(
                         max_items: 0, // 0 = unlimited
                         sdk_type_name,
                         sdk_accessor_method,
+                        one_of: false,
                     }));
                 }
             }
@@ -372,8 +576,10 @@ fn try_extract_block_from_property(
 
             // Only treat as block if it's complex enough (3+ properties or has nested structures)
             if is_complex_schema(doc, resolved_schema) {
-                let attributes = extract_fields_from_schema_for_block(doc, resolved_schema)?;
-                let nested_blocks = detect_nested_blocks_from_schema(doc, resolved_schema)?;
+                let attributes =
+                    extract_fields_from_schema_for_block(doc, resolved_schema, method_id)?;
+                let nested_blocks =
+                    detect_nested_blocks_from_schema(doc, resolved_schema, method_id)?;
 
                 // Extract SDK type name from schema
                 let sdk_type_name = Some(extract_type_name_from_schema(resolved_schema, prop_name));
@@ -391,6 +597,7 @@ fn try_extract_block_from_property(
                     max_items: 1,
                     sdk_type_name,
                     sdk_accessor_method,
+                    one_of: false,
                 }));
             }
         },
@@ -431,6 +638,7 @@ fn is_complex_schema(doc: &DiscoveryDoc, schema: &Schema) -> bool {
 fn extract_fields_from_schema_for_block(
     doc: &DiscoveryDoc,
     schema: &Schema,
+    method_id: &str,
 ) -> Result<Vec<FieldDefinition>> {
     let mut fields = Vec::new();
 
@@ -456,12 +664,17 @@ fn extract_fields_from_schema_for_block(
 
         fields.push(FieldDefinition {
             name: accessor_name,
+            default_value: typed_default(resolved.default.as_deref(), &field_type),
             field_type,
-            required: false, // Discovery format doesn't have clear required markers in the same way
+            required: schema.required.contains(field_name)
+                || field_schema.is_required_for(method_id),
             sensitive: false,
             immutable: false,
             description: resolved.description.clone(),
             response_accessor: None,
+            constraints: schema_constraints(resolved),
+            deprecated: (field_schema.deprecated || resolved.deprecated).then(Deprecation::default),
+            ..Default::default()
         });
     }
 
@@ -512,6 +725,43 @@ fn is_potential_block_property(doc: &DiscoveryDoc, schema: &Schema) -> bool {
     }
 }
 
+/// Validation constraints of a schema
+fn schema_constraints(schema: &Schema) -> FieldConstraints {
+    constraints(&schema.minimum, &schema.maximum, &schema.pattern)
+}
+
+/// Validation constraints from Discovery's `minimum`, `maximum` and `pattern`
+///
+/// Bounds are numbers written as strings (e.g. "1", "4294967295").
+fn constraints(
+    minimum: &Option<String>,
+    maximum: &Option<String>,
+    pattern: &Option<String>,
+) -> FieldConstraints {
+    FieldConstraints {
+        minimum: minimum.as_deref().and_then(|m| m.parse().ok()),
+        maximum: maximum.as_deref().and_then(|m| m.parse().ok()),
+        pattern: pattern.clone(),
+        ..Default::default()
+    }
+}
+
+/// Convert a Discovery default, always written as a string, to the field's type
+fn typed_default(default: Option<&str>, field_type: &FieldType) -> Option<serde_json::Value> {
+    let default = default?;
+    Some(match field_type {
+        FieldType::Integer | FieldType::Int8 | FieldType::Int16 | FieldType::Int32 => {
+            serde_json::Value::from(default.parse::<i64>().ok()?)
+        },
+        FieldType::UInt32 | FieldType::UInt64 => {
+            serde_json::Value::from(default.parse::<u64>().ok()?)
+        },
+        FieldType::Float => serde_json::Value::from(default.parse::<f64>().ok()?),
+        FieldType::Boolean => serde_json::Value::from(default.parse::<bool>().ok()?),
+        _ => serde_json::Value::String(default.to_string()),
+    })
+}
+
 /// Convert Discovery schema to FieldType
 fn convert_schema_to_field_type(doc: &DiscoveryDoc, schema: &Schema) -> Result<FieldType> {
     // Handle reference
@@ -525,9 +775,11 @@ fn convert_schema_to_field_type(doc: &DiscoveryDoc, schema: &Schema) -> Result<F
     match schema.schema_type.as_deref() {
         Some("string") => match schema.format.as_deref() {
             Some("date-time") | Some("date") => Ok(FieldType::DateTime),
+            Some("byte") => Ok(FieldType::Bytes),
+            _ if !schema.enum_values.is_empty() => Ok(FieldType::Enum(schema.enum_values.clone())),
             _ => Ok(FieldType::String),
         },
-        Some("integer") => Ok(FieldType::Integer),
+        Some("integer") => Ok(integer_type(schema.format.as_deref())),
         Some("number") => Ok(FieldType::Float),
         Some("boolean") => Ok(FieldType::Boolean),
         Some("array") => {
@@ -550,47 +802,18 @@ fn convert_schema_to_field_type(doc: &DiscoveryDoc, schema: &Schema) -> Result<F
                 Ok(FieldType::String) // Complex object, default to string
             }
         },
+        Some("any") => Ok(FieldType::Document),
         _ => Ok(FieldType::String), // Default fallback
     }
 }
 
-/// Convert PascalCase or camelCase to snake_case
-fn to_snake_case(s: &str) -> String {
-    let mut result = String::new();
-    let chars: Vec<char> = s.chars().collect();
-
-    for (i, &ch) in chars.iter().enumerate() {
-        if ch.is_uppercase() {
-            // Add underscore before uppercase if:
-            // 1. Not at the start
-            // 2. Previous char is lowercase or digit
-            // 3. OR next char is lowercase (handles HTTPServer -> http_server)
-            let should_add_underscore = i > 0
-                && (chars[i - 1].is_lowercase()
-                    || chars[i - 1].is_ascii_digit()
-                    || (i + 1 < chars.len() && chars[i + 1].is_lowercase()));
-
-            if should_add_underscore && !result.ends_with('_') {
-                result.push('_');
-            }
-            result.push(ch.to_ascii_lowercase());
-        } else if ch == '-' || ch == ' ' {
-            // Replace hyphens and spaces with underscores
-            if !result.is_empty() && !result.ends_with('_') {
-                result.push('_');
-            }
-        } else {
-            result.push(ch);
-        }
-    }
-
-    // Clean up multiple consecutive underscores
-    while result.contains("__") {
-        result = result.replace("__", "_");
+/// FieldType of an integer of the given format
+fn integer_type(format: Option<&str>) -> FieldType {
+    match format {
+        Some("int32") => FieldType::Int32,
+        Some("uint32") => FieldType::UInt32,
+        _ => FieldType::Integer,
     }
-
-    // Strip leading and trailing underscores
-    result.trim_matches('_').to_string()
 }
 
 /// Extract SDK type name from GCP Discovery $ref or property name
@@ -615,21 +838,3 @@ fn extract_type_name_from_schema(schema: &Schema, prop_name: &str) -> String {
         })
         .collect::<String>()
 }
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_to_snake_case() {
-        assert_eq!(to_snake_case("BucketName"), "bucket_name");
-        assert_eq!(
-            to_snake_case("storage.buckets.insert"),
-            "storage.buckets.insert"
-        );
-        assert_eq!(to_snake_case("CloudStorage"), "cloud_storage");
-        assert_eq!(to_snake_case("HTTPServer"), "http_server");
-        assert_eq!(to_snake_case("XMLParser"), "xml_parser");
-        assert_eq!(to_snake_case("__test__"), "test");
-    }
-}
@@ -17,6 +17,18 @@
 //! - Compute Engine: `https://compute.googleapis.com/$discovery/rest?version=v1`
 //! - BigQuery: `https://bigquery.googleapis.com/$discovery/rest?version=v2`
 //!
+//! ## Mapping
+//!
+//! - Request body properties and path/query parameters of the create (or
+//!   update) method become inputs; parameters keep their `location`
+//! - Properties are required when a schema's `annotations.required` lists the method
+//! - The `get` method's trailing path parameter is the resource's ID field
+//! - Methods returning an `Operation` are long-running, polled through the
+//!   `zoneOperations`, `regionOperations`, `globalOperations` or `operations`
+//!   resource matching their scope
+//! - `list` methods become data sources returning the first of the response's
+//!   `items`, with the method's parameters (`filter`, ...) as arguments
+//!
 //! ## Usage
 //! ```rust,ignore
 //! use hemmer_provider_generator_parser::discovery::DiscoveryParser;
@@ -1,6 +1,7 @@
 //! Discovery document parser
 
 use super::types::DiscoveryDoc;
+use crate::CrudRules;
 use hemmer_provider_generator_common::{GeneratorError, Result, ServiceDefinition};
 use std::fs;
 use std::path::Path;
@@ -18,6 +19,9 @@ pub struct DiscoveryParser {
 
     /// API version (e.g., "v1", "v2")
     api_version: String,
+
+    /// Rules classifying methods into CRUD roles
+    crud_rules: CrudRules,
 }
 
 impl DiscoveryParser {
@@ -56,9 +60,16 @@ impl DiscoveryParser {
             doc,
             service_name: service_name.to_string(),
             api_version: api_version.to_string(),
+            crud_rules: CrudRules::default(),
         })
     }
 
+    /// Classify methods with the given rules instead of the built-in ones
+    pub fn with_crud_rules(mut self, crud_rules: CrudRules) -> Self {
+        self.crud_rules = crud_rules;
+        self
+    }
+
     /// Parse Discovery document into ServiceDefinition IR
     pub fn parse(&self) -> Result<ServiceDefinition> {
         // Use the converter module to transform Discovery -> ServiceDefinition
@@ -66,6 +77,7 @@ impl DiscoveryParser {
             &self.doc,
             &self.service_name,
             &self.api_version,
+            &self.crud_rules,
         )
     }
 
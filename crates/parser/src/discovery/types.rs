@@ -90,6 +90,10 @@ pub struct Parameter {
     #[serde(default)]
     pub param_type: Option<String>,
 
+    /// Format of the type (int32, uint32, byte, ...)
+    #[serde(default)]
+    pub format: Option<String>,
+
     /// Description
     #[serde(default)]
     pub description: Option<String>,
@@ -110,6 +114,26 @@ pub struct Parameter {
     #[serde(rename = "enum")]
     #[serde(default)]
     pub enum_values: Vec<String>,
+
+    /// Whether the parameter may be given multiple times
+    #[serde(default)]
+    pub repeated: bool,
+
+    /// Smallest allowed value (a number written as a string)
+    #[serde(default)]
+    pub minimum: Option<String>,
+
+    /// Largest allowed value (a number written as a string)
+    #[serde(default)]
+    pub maximum: Option<String>,
+
+    /// Regular expression the value has to match
+    #[serde(default)]
+    pub pattern: Option<String>,
+
+    /// Whether the value should no longer be used
+    #[serde(default)]
+    pub deprecated: bool,
 }
 
 /// Schema (data type) definition
@@ -158,6 +182,48 @@ pub struct Schema {
     /// Required properties
     #[serde(default)]
     pub required: Vec<String>,
+
+    /// Annotations (e.g., the methods this property is required for)
+    #[serde(default)]
+    pub annotations: Option<SchemaAnnotations>,
+
+    /// Default value (written as a string whatever the type)
+    #[serde(default)]
+    pub default: Option<String>,
+
+    /// Smallest allowed value (a number written as a string)
+    #[serde(default)]
+    pub minimum: Option<String>,
+
+    /// Largest allowed value (a number written as a string)
+    #[serde(default)]
+    pub maximum: Option<String>,
+
+    /// Regular expression the value has to match
+    #[serde(default)]
+    pub pattern: Option<String>,
+
+    /// Whether the value should no longer be used
+    #[serde(default)]
+    pub deprecated: bool,
+}
+
+/// Schema property annotations
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SchemaAnnotations {
+    /// IDs of the methods the property is required for
+    /// e.g., ["compute.instances.insert"]
+    #[serde(default)]
+    pub required: Vec<String>,
+}
+
+impl Schema {
+    /// Whether the property is required by the given method
+    pub fn is_required_for(&self, method_id: &str) -> bool {
+        self.annotations
+            .as_ref()
+            .is_some_and(|a| a.required.iter().any(|id| id == method_id))
+    }
 }
 
 /// Resource (collection of methods)
@@ -193,6 +259,11 @@ pub struct Method {
     #[serde(default)]
     pub parameters: HashMap<String, Parameter>,
 
+    /// Required parameters in the order they appear in the path
+    #[serde(rename = "parameterOrder")]
+    #[serde(default)]
+    pub parameter_order: Vec<String>,
+
     /// Request body schema
     #[serde(default)]
     pub request: Option<MethodRequest>,
@@ -204,6 +275,10 @@ pub struct Method {
     /// Scopes required
     #[serde(default)]
     pub scopes: Vec<String>,
+
+    /// Whether the method should no longer be used
+    #[serde(default)]
+    pub deprecated: bool,
 }
 
 /// Method request
@@ -7,7 +7,7 @@
 //!
 //! ### Spec-Based Parsing (Recommended)
 //! - **AWS**: Parse Smithy JSON AST from github.com/aws/api-models-aws
-//! - **Kubernetes**: Parse OpenAPI 3.0 specs from API server
+//! - **Kubernetes**: Parse OpenAPI 3.0 specs from API server, or CRD manifests from operators
 //! - **GCP**: Parse Discovery Documents from googleapis.com or gRPC FileDescriptorSet
 //! - **Azure**: Parse OpenAPI specs from github.com/Azure/azure-rest-api-specs
 //! - **gRPC**: Parse Protocol Buffer FileDescriptorSet from .proto files or gRPC reflection
@@ -24,23 +24,28 @@
 //! - DeleteX, RemoveX → Delete
 
 mod aws;
+mod crud_rules;
+mod naming;
 mod operation_mapper;
 mod rustdoc_loader;
 mod type_mapper;
 
 // Spec format parsers
+pub mod crd;
 pub mod discovery;
 pub mod openapi;
 pub mod protobuf;
 pub mod smithy;
 
 pub use aws::AwsParser;
+pub use crd::CrdParser;
+pub use crud_rules::{Classification, CrudOperations, CrudRole, CrudRules};
 pub use discovery::DiscoveryParser;
 pub use openapi::OpenApiParser;
 pub use operation_mapper::{CrudOperation, OperationClassifier};
 pub use protobuf::ProtobufParser;
 pub use rustdoc_loader::RustdocLoader;
-pub use smithy::SmithyParser;
+pub use smithy::{SmithyModelPath, SmithyParser};
 pub use type_mapper::TypeMapper;
 
 use hemmer_provider_generator_common::{Result, ServiceDefinition};
@@ -0,0 +1,74 @@
+//! Naming conventions shared by the spec parsers
+
+/// Convert PascalCase or camelCase to snake_case
+///
+/// Acronyms stay together (`HTTPServer` → `http_server`), hyphens and spaces
+/// become underscores, and dots are kept so dotted paths convert segment-wise.
+pub(crate) fn to_snake_case(s: &str) -> String {
+    let mut result = String::new();
+    let chars: Vec<char> = s.chars().collect();
+
+    for (i, &ch) in chars.iter().enumerate() {
+        if ch.is_uppercase() {
+            // Add underscore before uppercase if:
+            // 1. Not at the start
+            // 2. Previous char is lowercase or digit
+            // 3. OR next char is lowercase (handles HTTPServer -> http_server)
+            let should_add_underscore = i > 0
+                && (chars[i - 1].is_lowercase()
+                    || chars[i - 1].is_ascii_digit()
+                    || (i + 1 < chars.len() && chars[i + 1].is_lowercase()));
+
+            if should_add_underscore && !result.ends_with('_') {
+                result.push('_');
+            }
+            result.push(ch.to_ascii_lowercase());
+        } else if ch == '-' || ch == ' ' {
+            // Replace hyphens and spaces with underscores
+            if !result.is_empty() && !result.ends_with('_') {
+                result.push('_');
+            }
+        } else {
+            result.push(ch);
+        }
+    }
+
+    // Clean up multiple consecutive underscores
+    while result.contains("__") {
+        result = result.replace("__", "_");
+    }
+
+    // Strip leading and trailing underscores
+    result.trim_matches('_').to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_snake_case() {
+        assert_eq!(to_snake_case("PodName"), "pod_name");
+        assert_eq!(to_snake_case("CreateBucket"), "create_bucket");
+        assert_eq!(
+            to_snake_case("createNamespacedPod"),
+            "create_namespaced_pod"
+        );
+        assert_eq!(to_snake_case("dnsNames"), "dns_names");
+        assert_eq!(
+            to_snake_case("privateKeySecretRef"),
+            "private_key_secret_ref"
+        );
+        assert_eq!(to_snake_case("S3Bucket"), "s3_bucket");
+        assert_eq!(to_snake_case("HTTPServer"), "http_server");
+        assert_eq!(to_snake_case("XMLParser"), "xml_parser");
+        assert_eq!(to_snake_case("IOError"), "io_error");
+        assert_eq!(to_snake_case("v1_api"), "v1_api"); // Already snake_case
+        assert_eq!(to_snake_case("__test__"), "test"); // Strip extra underscores
+        assert_eq!(to_snake_case("some-resource"), "some_resource"); // Hyphens
+        assert_eq!(
+            to_snake_case("storage.buckets.insert"),
+            "storage.buckets.insert"
+        );
+    }
+}
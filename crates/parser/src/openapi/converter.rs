@@ -1,10 +1,14 @@
 //! Converts OpenAPI spec to ServiceDefinition IR
 
+use super::kubernetes;
 use super::parser::ProviderHint;
-use super::types::{OpenApiSpec, Operation, Schema, SchemaOrRef};
+use super::types::{Discriminator, OpenApiSpec, Operation, Schema, SchemaOrRef};
+use crate::crud_rules::{CrudOperations, CrudRole, CrudRules};
+use crate::naming::to_snake_case;
 use hemmer_provider_generator_common::{
-    BlockDefinition, FieldDefinition, FieldType, NestingMode, OperationMapping, Operations,
-    Provider, ResourceDefinition, Result, ServiceDefinition,
+    BlockDefinition, DataSourceDefinition, Deprecation, FieldConstraints, FieldDefinition,
+    FieldType, LongRunningConfig, NestingMode, OperationMapping, Operations, PaginationConfig,
+    Provider, ResourceDefinition, Result, ServiceDefinition, Stability,
 };
 use std::collections::HashMap;
 
@@ -14,6 +18,7 @@ pub fn convert_openapi_to_service_definition(
     service_name: &str,
     api_version: &str,
     provider_hint: Option<ProviderHint>,
+    crud_rules: &CrudRules,
 ) -> Result<ServiceDefinition> {
     // Determine provider from hint or default
     let provider = match provider_hint {
@@ -22,58 +27,90 @@ pub fn convert_openapi_to_service_definition(
         _ => Provider::Kubernetes, // Default to Kubernetes for generic OpenAPI
     };
 
-    // Extract resources from paths
-    let resources = extract_resources_from_paths(spec)?;
+    // Kubernetes specs say which kind each operation belongs to; otherwise
+    // fall back to grouping by path
+    let (resources, data_sources) =
+        if provider == Provider::Kubernetes && kubernetes::has_group_version_kinds(spec) {
+            kubernetes::extract_kubernetes_resources(spec)?
+        } else {
+            (
+                extract_resources_from_paths(spec, &provider, crud_rules)?,
+                extract_data_sources_from_paths(spec)?,
+            )
+        };
 
     Ok(ServiceDefinition {
         provider,
         name: service_name.to_string(),
         sdk_version: api_version.to_string(),
         resources,
-        data_sources: vec![], // Will implement data source detection later
+        data_sources,
     })
 }
 
 /// Extract resources from OpenAPI paths
-fn extract_resources_from_paths(spec: &OpenApiSpec) -> Result<Vec<ResourceDefinition>> {
-    let mut resource_map: HashMap<String, ResourceOperations> = HashMap::new();
+///
+/// Operations are classified by `operationId` and HTTP method. A read found on
+/// a collection path (one not ending in a parameter) lists the resources
+/// instead, and only stands in for a read when the resource has no other.
+fn extract_resources_from_paths(
+    spec: &OpenApiSpec,
+    provider: &Provider,
+    crud_rules: &CrudRules,
+) -> Result<Vec<ResourceDefinition>> {
+    let mut resource_map: HashMap<String, CrudOperations<(&str, Operation)>> = HashMap::new();
+
+    // Sorted so that operations with the same classification resolve the same way every run
+    let mut paths: Vec<_> = spec.paths.iter().collect();
+    paths.sort_by(|a, b| a.0.cmp(b.0));
 
     // Group operations by resource
-    for (path, path_item) in &spec.paths {
+    for (path, path_item) in paths {
         if let Some(resource_name) = OpenApiSpec::extract_resource_from_path(path) {
-            let entry = resource_map
-                .entry(resource_name.clone())
-                .or_insert_with(|| ResourceOperations::new(resource_name));
-
-            // Map HTTP methods to CRUD operations
-            if let Some(ref op) = path_item.post {
-                entry.create = Some(op.clone());
-            }
-            if let Some(ref op) = path_item.get {
-                // GET can be read (single resource) or list (collection)
-                if path.contains('{') && path.matches('{').count() >= 2 {
-                    // Path like /pods/{name} - this is a read
-                    entry.read = Some(op.clone());
-                } else {
-                    // Path like /pods - this might be list, but we'll use it as read
-                    entry.read = entry.read.clone().or_else(|| Some(op.clone()));
+            let entry = resource_map.entry(resource_name).or_default();
+            let is_collection = path
+                .rsplit('/')
+                .find(|segment| !segment.is_empty())
+                .is_some_and(|segment| !segment.starts_with('{'));
+
+            let methods = [
+                ("POST", &path_item.post),
+                ("GET", &path_item.get),
+                ("PUT", &path_item.put),
+                ("PATCH", &path_item.patch),
+                ("DELETE", &path_item.delete),
+            ];
+            for (http_method, op) in methods {
+                let Some(op) = op else { continue };
+                let operation_id = op.operation_id.as_deref().unwrap_or_default();
+                if let Some(mut classification) =
+                    crud_rules.classify(provider, operation_id, Some(http_method))
+                {
+                    if classification.role == CrudRole::Read && is_collection {
+                        classification.role = CrudRole::List;
+                    }
+                    entry.insert(classification, (http_method, op.clone()));
                 }
             }
-            if let Some(ref op) = path_item.put {
-                entry.update = Some(op.clone());
-            }
-            if let Some(ref op) = path_item.patch {
-                entry.update = entry.update.clone().or_else(|| Some(op.clone()));
-            }
-            if let Some(ref op) = path_item.delete {
-                entry.delete = Some(op.clone());
-            }
         }
     }
 
     // Convert to ResourceDefinitions
     let mut resources = Vec::new();
-    for (_name, ops) in resource_map {
+    for (name, ops) in resource_map {
+        // A create by PUT replaces the whole resource, so it also updates
+        // resources without an update of their own
+        let update = ops
+            .update
+            .or_else(|| ops.create.clone().filter(|(m, _)| *m == "PUT"));
+        let operation = |op: Option<(&str, Operation)>| op.map(|(_, op)| op);
+        let ops = ResourceOperations {
+            name,
+            create: operation(ops.create),
+            read: operation(ops.read.or(ops.list)),
+            update: operation(update),
+            delete: operation(ops.delete),
+        };
         if let Some(resource_def) = build_resource_from_operations(spec, ops)? {
             resources.push(resource_def);
         }
@@ -82,6 +119,98 @@ fn extract_resources_from_paths(spec: &OpenApiSpec) -> Result<Vec<ResourceDefini
     Ok(resources)
 }
 
+/// Extract data sources from collection GETs (paths not ending in a parameter)
+///
+/// Path and query parameters become arguments. The response's item array (the
+/// `x-ms-pageable` items, or else the first array of objects) holds the
+/// matches; the data source returns the first one, with its properties as
+/// attributes. e.g. `GET /vpcs` -> `vpc`
+fn extract_data_sources_from_paths(spec: &OpenApiSpec) -> Result<Vec<DataSourceDefinition>> {
+    let mut data_sources: Vec<DataSourceDefinition> = Vec::new();
+
+    // Paths sorted for deterministic output
+    let mut paths: Vec<&String> = spec.paths.keys().collect();
+    paths.sort();
+
+    for path in paths {
+        let is_collection = path
+            .rsplit('/')
+            .find(|segment| !segment.is_empty())
+            .is_some_and(|segment| !segment.starts_with('{'));
+        let Some(list) = spec.paths[path].get.as_ref().filter(|_| is_collection) else {
+            continue;
+        };
+        let Some(resource_name) = OpenApiSpec::extract_resource_from_path(path) else {
+            continue;
+        };
+        let name = to_snake_case(&resource_name);
+        if data_sources.iter().any(|ds| ds.name == name) {
+            continue;
+        }
+        let Some(mut read_operation) = build_operation_mapping(list) else {
+            continue;
+        };
+
+        let Some((items_accessor, item_schema)) =
+            find_items_property(spec, list, read_operation.pagination.as_ref())
+        else {
+            continue;
+        };
+        let attributes = extract_fields_from_schema(spec, &item_schema, true)?;
+        if attributes.is_empty() {
+            continue;
+        }
+
+        read_operation.readonly = true;
+        read_operation.idempotent = true;
+
+        data_sources.push(DataSourceDefinition {
+            name,
+            description: list.description.clone().or_else(|| list.summary.clone()),
+            arguments: extract_fields_from_operation(spec, list)?,
+            attributes,
+            read_operation,
+            items_accessor: Some(items_accessor),
+        });
+    }
+
+    Ok(data_sources)
+}
+
+/// Find the array property of a list response holding the items, and the item schema
+fn find_items_property(
+    spec: &OpenApiSpec,
+    operation: &Operation,
+    pagination: Option<&PaginationConfig>,
+) -> Option<(String, SchemaOrRef)> {
+    let schema_or_ref = operation
+        .responses
+        .get("200")?
+        .content
+        .values()
+        .next()?
+        .schema
+        .as_ref()?;
+    let schema = resolve_schema(spec, schema_or_ref)?;
+
+    let item_schema = |property: &SchemaOrRef| {
+        let items = resolve_schema(spec, property)?.items?;
+        let item = resolve_schema(spec, &items)?;
+        (!item.properties.is_empty()).then_some(*items)
+    };
+
+    // Property names sorted for deterministic output
+    let mut names: Vec<&String> = schema.properties.keys().collect();
+    names.sort();
+
+    let paginated_items = pagination.and_then(|p| p.items.as_deref());
+    names
+        .iter()
+        .filter(|name| paginated_items == Some(to_snake_case(name).as_str()))
+        .chain(names.iter())
+        .find_map(|name| Some((to_snake_case(name), item_schema(&schema.properties[*name])?)))
+}
+
 /// Temporary structure to collect operations for a resource
 #[derive(Debug, Clone)]
 struct ResourceOperations {
@@ -92,18 +221,6 @@ struct ResourceOperations {
     delete: Option<Operation>,
 }
 
-impl ResourceOperations {
-    fn new(name: String) -> Self {
-        Self {
-            name,
-            create: None,
-            read: None,
-            update: None,
-            delete: None,
-        }
-    }
-}
-
 /// Build ResourceDefinition from operations
 fn build_resource_from_operations(
     spec: &OpenApiSpec,
@@ -144,41 +261,83 @@ fn build_resource_from_operations(
         .and_then(|op| op.description.clone())
         .or_else(|| ops.read.as_ref().and_then(|op| op.description.clone()));
 
-    Ok(Some(ResourceDefinition {
+    let operations = Operations {
+        create: ops.create.as_ref().and_then(build_operation_mapping),
+        read: ops.read.as_ref().and_then(build_operation_mapping),
+        update: ops.update.as_ref().and_then(build_operation_mapping),
+        delete: ops.delete.as_ref().and_then(build_operation_mapping),
+        import: None, // Will implement later
+    };
+
+    let mut resource = ResourceDefinition {
         name: to_snake_case(&ops.name),
         description,
         fields,
         outputs,
         blocks,
         id_field: None, // Will implement ID detection later
-        operations: Operations {
-            create: ops.create.and_then(|op| {
-                op.operation_id.map(|id| OperationMapping {
-                    sdk_operation: to_snake_case(&id),
-                    additional_operations: vec![],
-                })
-            }),
-            read: ops.read.and_then(|op| {
-                op.operation_id.map(|id| OperationMapping {
-                    sdk_operation: to_snake_case(&id),
-                    additional_operations: vec![],
-                })
-            }),
-            update: ops.update.and_then(|op| {
-                op.operation_id.map(|id| OperationMapping {
-                    sdk_operation: to_snake_case(&id),
-                    additional_operations: vec![],
-                })
-            }),
-            delete: ops.delete.and_then(|op| {
-                op.operation_id.map(|id| OperationMapping {
-                    sdk_operation: to_snake_case(&id),
-                    additional_operations: vec![],
-                })
-            }),
-            import: None, // Will implement later
-        },
-    }))
+        // New resources can't be created once the create operation is deprecated
+        deprecated: operations
+            .create
+            .as_ref()
+            .and_then(|op| op.deprecated.clone()),
+        operations,
+        stability: Stability::from_version(&spec.info.version),
+    };
+    resource.infer_computed_fields();
+
+    Ok(Some(resource))
+}
+
+/// Build an OperationMapping, applying Azure operation extensions
+pub(super) fn build_operation_mapping(operation: &Operation) -> Option<OperationMapping> {
+    let id = operation.operation_id.as_ref()?;
+
+    // x-ms-long-running-operation: the service answers 201/202 and has to be polled
+    let long_running = operation
+        .extensions
+        .get("x-ms-long-running-operation")
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false)
+        .then(|| LongRunningConfig {
+            final_state_via: operation
+                .extensions
+                .get("x-ms-long-running-operation-options")
+                .and_then(|options| options.get("final-state-via"))
+                .and_then(|v| v.as_str())
+                .map(|v| v.to_string()),
+            ..Default::default()
+        });
+
+    // x-ms-pageable: results are followed through a next link; a null
+    // nextLinkName means everything arrives in a single page
+    let pagination = operation
+        .extensions
+        .get("x-ms-pageable")
+        .map(|pageable| PaginationConfig {
+            input_token: None,
+            output_token: pageable
+                .get("nextLinkName")
+                .and_then(|v| v.as_str())
+                .map(to_snake_case),
+            items: Some(
+                pageable
+                    .get("itemName")
+                    .and_then(|v| v.as_str())
+                    .map(to_snake_case)
+                    .unwrap_or_else(|| "value".to_string()),
+            ),
+            page_size: None,
+        });
+
+    Some(OperationMapping {
+        sdk_operation: to_snake_case(id),
+        additional_operations: vec![],
+        pagination,
+        long_running,
+        deprecated: deprecation(&operation.extensions),
+        ..Default::default()
+    })
 }
 
 /// Extract fields from operation request body
@@ -209,6 +368,10 @@ fn extract_fields_from_operation(
                 immutable: param.location == "path", // Path params are usually immutable identifiers
                 description: param.description.clone(),
                 response_accessor: None, // Input fields don't have response accessors
+                constraints: schema_constraints(schema),
+                default_value: schema.default.clone(),
+                deprecated: param.deprecated.then(Deprecation::default),
+                ..Default::default()
             });
         }
     }
@@ -217,7 +380,7 @@ fn extract_fields_from_operation(
 }
 
 /// Extract outputs from operation responses
-fn extract_outputs_from_operation(
+pub(super) fn extract_outputs_from_operation(
     spec: &OpenApiSpec,
     operation: &Operation,
 ) -> Result<Vec<FieldDefinition>> {
@@ -241,38 +404,35 @@ fn extract_outputs_from_operation(
 /// Extract fields from schema
 ///
 /// `is_response` - if true, fields are from a response and should have response accessors
-fn extract_fields_from_schema(
+pub(super) fn extract_fields_from_schema(
     spec: &OpenApiSpec,
     schema_or_ref: &SchemaOrRef,
     is_response: bool,
 ) -> Result<Vec<FieldDefinition>> {
     let mut fields = Vec::new();
 
-    let schema = match schema_or_ref {
-        SchemaOrRef::Schema(s) => s.as_ref(),
-        SchemaOrRef::Reference { ref_path } => {
-            if let Some(s) = spec.resolve_schema_ref(ref_path) {
-                s
-            } else {
-                return Ok(fields);
-            }
-        },
+    let schema = match resolve_schema(spec, schema_or_ref) {
+        Some(s) => s,
+        None => return Ok(fields),
     };
 
     // Extract properties
     for (field_name, field_schema_or_ref) in &schema.properties {
-        let field_schema = match field_schema_or_ref {
-            SchemaOrRef::Schema(s) => s.as_ref(),
-            SchemaOrRef::Reference { ref_path } => {
-                if let Some(s) = spec.resolve_schema_ref(ref_path) {
-                    s
-                } else {
-                    continue;
-                }
-            },
+        let field_schema = match resolve_schema(spec, field_schema_or_ref) {
+            Some(s) => s,
+            None => continue,
         };
 
-        let field_type = convert_schema_to_field_type(spec, field_schema)?;
+        // Read-only properties are computed by the service, not inputs, and
+        // write-only properties are never returned
+        if !is_response && is_read_only(&field_schema) {
+            continue;
+        }
+        if is_response && is_write_only(&field_schema) {
+            continue;
+        }
+
+        let field_type = convert_schema_to_field_type(spec, &field_schema)?;
         let required = schema.required.contains(field_name);
         let accessor_name = to_snake_case(field_name);
 
@@ -280,8 +440,8 @@ fn extract_fields_from_schema(
             name: accessor_name.clone(),
             field_type,
             required,
-            sensitive: false,
-            immutable: false,
+            sensitive: is_secret(&field_schema),
+            immutable: is_create_only(&field_schema),
             description: field_schema.description.clone(),
             // Only response fields have accessors
             response_accessor: if is_response {
@@ -289,6 +449,12 @@ fn extract_fields_from_schema(
             } else {
                 None
             },
+            sdk_type_name: enum_type_name(&field_schema),
+            constraints: schema_constraints(&field_schema),
+            default_value: field_schema.default.clone(),
+            write_only: is_write_only(&field_schema),
+            deprecated: deprecation(&field_schema.extensions),
+            ..Default::default()
         });
     }
 
@@ -296,7 +462,7 @@ fn extract_fields_from_schema(
 }
 
 /// Extract nested blocks from operation request body
-fn extract_blocks_from_operation(
+pub(super) fn extract_blocks_from_operation(
     spec: &OpenApiSpec,
     operation: &Operation,
 ) -> Result<Vec<BlockDefinition>> {
@@ -321,15 +487,9 @@ fn detect_nested_blocks_from_schema(
 ) -> Result<Vec<BlockDefinition>> {
     let mut blocks = Vec::new();
 
-    let schema = match schema_or_ref {
-        SchemaOrRef::Schema(s) => s.as_ref(),
-        SchemaOrRef::Reference { ref_path } => {
-            if let Some(s) = spec.resolve_schema_ref(ref_path) {
-                s
-            } else {
-                return Ok(blocks);
-            }
-        },
+    let schema = match resolve_schema(spec, schema_or_ref) {
+        Some(s) => s,
+        None => return Ok(blocks),
     };
 
     // Check each property for potential blocks
@@ -348,30 +508,52 @@ fn try_extract_block_from_property(
     prop_name: &str,
     schema_or_ref: &SchemaOrRef,
 ) -> Result<Option<BlockDefinition>> {
-    let schema = match schema_or_ref {
-        SchemaOrRef::Schema(s) => s.as_ref(),
-        SchemaOrRef::Reference { ref_path } => {
-            if let Some(s) = spec.resolve_schema_ref(ref_path) {
-                s
-            } else {
-                return Ok(None);
-            }
-        },
+    let schema = match resolve_schema(spec, schema_or_ref) {
+        Some(s) => s,
+        None => return Ok(None),
     };
 
+    // Object variants → one-of block with a nested block per variant
+    let variants = polymorphic_variants(spec, schema_or_ref);
+    if !variants.is_empty() {
+        let mut blocks = Vec::new();
+        for variant in variants {
+            let variant_ref = SchemaOrRef::Schema(Box::new(variant.schema.clone()));
+            blocks.push(BlockDefinition {
+                name: to_snake_case(&variant.name),
+                description: variant.schema.description.clone(),
+                attributes: extract_fields_from_schema_for_block(spec, &variant.schema)?,
+                blocks: detect_nested_blocks_from_schema(spec, &variant_ref)?,
+                nesting_mode: NestingMode::Single,
+                min_items: 0,
+                max_items: 1,
+                sdk_type_name: variant.ref_path.as_deref().map(extract_type_name_from_ref),
+                sdk_accessor_method: Some(to_snake_case(&variant.name)),
+                one_of: false,
+            });
+        }
+
+        return Ok(Some(BlockDefinition {
+            name: to_snake_case(prop_name),
+            description: schema.description.clone(),
+            attributes: vec![],
+            blocks,
+            nesting_mode: NestingMode::Single,
+            min_items: 0,
+            max_items: 1,
+            sdk_type_name: schema.ref_path.as_deref().map(extract_type_name_from_ref),
+            sdk_accessor_method: Some(to_snake_case(prop_name)),
+            one_of: true,
+        }));
+    }
+
     match schema.schema_type.as_deref() {
         // Array of objects → List block
         Some("array") => {
             if let Some(ref items) = schema.items {
-                let items_schema = match items.as_ref() {
-                    SchemaOrRef::Schema(s) => s.as_ref(),
-                    SchemaOrRef::Reference { ref_path } => {
-                        if let Some(s) = spec.resolve_schema_ref(ref_path) {
-                            s
-                        } else {
-                            return Ok(None);
-                        }
-                    },
+                let items_schema = match resolve_schema(spec, items.as_ref()) {
+                    Some(s) => s,
+                    None => return Ok(None),
                 };
 
                 // Check if items are objects (not primitive types)
@@ -379,7 +561,7 @@ fn try_extract_block_from_property(
                     || items_schema.ref_path.is_some()
                 {
                     // This is an array of objects - perfect for a block!
-                    let attributes = extract_fields_from_schema_for_block(spec, items_schema)?;
+                    let attributes = extract_fields_from_schema_for_block(spec, &items_schema)?;
                     let nested_blocks = detect_nested_blocks_from_schema(spec, items)?;
 
                     // Extract SDK type name from $ref if available
@@ -402,6 +584,7 @@ fn try_extract_block_from_property(
                         max_items: 0, // 0 = unlimited
                         sdk_type_name,
                         sdk_accessor_method,
+                        one_of: false,
                     }));
                 }
             }
@@ -414,8 +597,8 @@ fn try_extract_block_from_property(
             }
 
             // Only treat as block if it's complex enough (3+ properties or has nested structures)
-            if is_complex_schema(spec, schema) {
-                let attributes = extract_fields_from_schema_for_block(spec, schema)?;
+            if is_complex_schema(spec, &schema) {
+                let attributes = extract_fields_from_schema_for_block(spec, &schema)?;
                 let nested_blocks = detect_nested_blocks_from_schema(spec, schema_or_ref)?;
 
                 // Extract SDK type name from $ref if available, or use property name
@@ -452,6 +635,7 @@ fn try_extract_block_from_property(
                     max_items: 1,
                     sdk_type_name,
                     sdk_accessor_method,
+                    one_of: false,
                 }));
             }
         },
@@ -469,15 +653,9 @@ fn is_complex_schema(spec: &OpenApiSpec, schema: &Schema) -> bool {
 
     // Check if any property is itself an object or array
     for prop_schema_or_ref in schema.properties.values() {
-        let prop_schema = match prop_schema_or_ref {
-            SchemaOrRef::Schema(s) => s.as_ref(),
-            SchemaOrRef::Reference { ref_path } => {
-                if let Some(s) = spec.resolve_schema_ref(ref_path) {
-                    s
-                } else {
-                    continue;
-                }
-            },
+        let prop_schema = match resolve_schema(spec, prop_schema_or_ref) {
+            Some(s) => s,
+            None => continue,
         };
 
         match prop_schema.schema_type.as_deref() {
@@ -497,23 +675,20 @@ fn extract_fields_from_schema_for_block(
     let mut fields = Vec::new();
 
     for (field_name, field_schema_or_ref) in &schema.properties {
-        let field_schema = match field_schema_or_ref {
-            SchemaOrRef::Schema(s) => s.as_ref(),
-            SchemaOrRef::Reference { ref_path } => {
-                if let Some(s) = spec.resolve_schema_ref(ref_path) {
-                    s
-                } else {
-                    continue;
-                }
-            },
+        let field_schema = match resolve_schema(spec, field_schema_or_ref) {
+            Some(s) => s,
+            None => continue,
         };
 
-        // Skip complex nested structures (those will be blocks)
-        if is_potential_block_property(spec, field_schema) {
+        // Skip complex nested structures (those will be blocks) and computed properties
+        if is_potential_block_property(spec, &field_schema)
+            || !polymorphic_variants(spec, field_schema_or_ref).is_empty()
+            || is_read_only(&field_schema)
+        {
             continue;
         }
 
-        let field_type = convert_schema_to_field_type(spec, field_schema)?;
+        let field_type = convert_schema_to_field_type(spec, &field_schema)?;
         let required = schema.required.contains(field_name);
         let accessor_name = to_snake_case(field_name);
 
@@ -521,31 +696,42 @@ fn extract_fields_from_schema_for_block(
             name: accessor_name,
             field_type,
             required,
-            sensitive: false,
-            immutable: false,
+            sensitive: is_secret(&field_schema),
+            immutable: is_create_only(&field_schema),
             description: field_schema.description.clone(),
             response_accessor: None,
+            sdk_type_name: enum_type_name(&field_schema),
+            constraints: schema_constraints(&field_schema),
+            default_value: field_schema.default.clone(),
+            write_only: is_write_only(&field_schema),
+            deprecated: deprecation(&field_schema.extensions),
+            ..Default::default()
         });
     }
 
     Ok(fields)
 }
 
+/// Validation constraints of a schema (`minimum`, `maxLength`, `pattern`, ...)
+fn schema_constraints(schema: &Schema) -> FieldConstraints {
+    FieldConstraints {
+        minimum: schema.minimum,
+        maximum: schema.maximum,
+        min_length: schema.min_length.or(schema.min_items),
+        max_length: schema.max_length.or(schema.max_items),
+        pattern: schema.pattern.clone(),
+    }
+}
+
 /// Check if a property should be treated as a block rather than a field
 fn is_potential_block_property(spec: &OpenApiSpec, schema: &Schema) -> bool {
     match schema.schema_type.as_deref() {
         // Array of objects
         Some("array") => {
             if let Some(ref items) = schema.items {
-                let items_schema = match items.as_ref() {
-                    SchemaOrRef::Schema(s) => s.as_ref(),
-                    SchemaOrRef::Reference { ref_path } => {
-                        if let Some(s) = spec.resolve_schema_ref(ref_path) {
-                            s
-                        } else {
-                            return false;
-                        }
-                    },
+                let items_schema = match resolve_schema(spec, items.as_ref()) {
+                    Some(s) => s,
+                    None => return false,
                 };
                 items_schema.schema_type.as_deref() == Some("object")
                     || items_schema.ref_path.is_some()
@@ -567,33 +753,53 @@ fn is_potential_block_property(spec: &OpenApiSpec, schema: &Schema) -> bool {
 
 /// Convert OpenAPI schema to FieldType
 fn convert_schema_to_field_type(spec: &OpenApiSpec, schema: &Schema) -> Result<FieldType> {
-    // Handle reference
-    if let Some(ref ref_path) = schema.ref_path {
-        if let Some(resolved) = spec.resolve_schema_ref(ref_path) {
-            return convert_schema_to_field_type(spec, resolved);
-        }
-    }
+    // Follow references and merge compositions first
+    let schema = compose_schema(spec, schema);
 
     // Handle type
     match schema.schema_type.as_deref() {
         Some("string") => match schema.format.as_deref() {
             Some("date-time") => Ok(FieldType::DateTime),
+            Some("byte") | Some("binary") => Ok(FieldType::Bytes),
+            _ if !schema.enum_values.is_empty() => Ok(FieldType::Enum(
+                schema
+                    .enum_values
+                    .iter()
+                    .filter_map(|v| v.as_str().map(|s| s.to_string()))
+                    .collect(),
+            )),
+            // x-ms-enum can list its values without a plain `enum`
+            _ if schema.extensions.contains_key("x-ms-enum") => {
+                let values: Vec<String> = schema.extensions["x-ms-enum"]
+                    .get("values")
+                    .and_then(|v| v.as_array())
+                    .map(|values| {
+                        values
+                            .iter()
+                            .filter_map(|v| v.get("value").and_then(|v| v.as_str()))
+                            .map(|v| v.to_string())
+                            .collect()
+                    })
+                    .unwrap_or_default();
+                if values.is_empty() {
+                    Ok(FieldType::String)
+                } else {
+                    Ok(FieldType::Enum(values))
+                }
+            },
             _ => Ok(FieldType::String),
         },
-        Some("integer") => Ok(FieldType::Integer),
+        Some("integer") => Ok(match schema.format.as_deref() {
+            Some("int32") => FieldType::Int32,
+            _ => FieldType::Integer,
+        }),
         Some("number") => Ok(FieldType::Float),
         Some("boolean") => Ok(FieldType::Boolean),
         Some("array") => {
             if let Some(ref items) = schema.items {
-                let item_type = match items.as_ref() {
-                    SchemaOrRef::Schema(s) => convert_schema_to_field_type(spec, s.as_ref())?,
-                    SchemaOrRef::Reference { ref_path } => {
-                        if let Some(s) = spec.resolve_schema_ref(ref_path) {
-                            convert_schema_to_field_type(spec, s)?
-                        } else {
-                            FieldType::String
-                        }
-                    },
+                let item_type = match resolve_schema(spec, items.as_ref()) {
+                    Some(s) => convert_schema_to_field_type(spec, &s)?,
+                    None => FieldType::String,
                 };
                 Ok(FieldType::List(Box::new(item_type)))
             } else {
@@ -603,20 +809,17 @@ fn convert_schema_to_field_type(spec: &OpenApiSpec, schema: &Schema) -> Result<F
         Some("object") => {
             if let Some(ref additional_props) = schema.additional_properties {
                 // This is a map
-                let value_type = match additional_props.as_ref() {
-                    SchemaOrRef::Schema(s) => convert_schema_to_field_type(spec, s.as_ref())?,
-                    SchemaOrRef::Reference { ref_path } => {
-                        if let Some(s) = spec.resolve_schema_ref(ref_path) {
-                            convert_schema_to_field_type(spec, s)?
-                        } else {
-                            FieldType::String
-                        }
-                    },
+                let value_type = match resolve_schema(spec, additional_props.as_ref()) {
+                    Some(s) => convert_schema_to_field_type(spec, &s)?,
+                    None => FieldType::String,
                 };
                 Ok(FieldType::Map(
                     Box::new(FieldType::String),
                     Box::new(value_type),
                 ))
+            } else if schema.properties.is_empty() {
+                // Free-form object
+                Ok(FieldType::Document)
             } else {
                 Ok(FieldType::String) // Complex object, default to string
             }
@@ -625,43 +828,418 @@ fn convert_schema_to_field_type(spec: &OpenApiSpec, schema: &Schema) -> Result<F
     }
 }
 
-/// Convert PascalCase or camelCase to snake_case
-fn to_snake_case(s: &str) -> String {
-    let mut result = String::new();
-    let chars: Vec<char> = s.chars().collect();
-
-    for (i, &ch) in chars.iter().enumerate() {
-        if ch.is_uppercase() {
-            // Add underscore before uppercase if:
-            // 1. Not at the start
-            // 2. Previous char is lowercase or digit
-            // 3. OR next char is lowercase (handles HTTPServer -> http_server)
-            let should_add_underscore = i > 0
-                && (chars[i - 1].is_lowercase()
-                    || chars[i - 1].is_ascii_digit()
-                    || (i + 1 < chars.len() && chars[i + 1].is_lowercase()));
+/// Resolve a schema or reference into its effective schema
+/// (references followed, `allOf`/`oneOf`/`anyOf` merged)
+pub(super) fn resolve_schema(spec: &OpenApiSpec, schema_or_ref: &SchemaOrRef) -> Option<Schema> {
+    resolve_schema_inner(spec, schema_or_ref, &mut Vec::new())
+}
 
-            if should_add_underscore && !result.ends_with('_') {
-                result.push('_');
+fn resolve_schema_inner(
+    spec: &OpenApiSpec,
+    schema_or_ref: &SchemaOrRef,
+    visiting: &mut Vec<String>,
+) -> Option<Schema> {
+    match schema_or_ref {
+        SchemaOrRef::Schema(s) => Some(compose_schema_inner(spec, s, visiting)),
+        SchemaOrRef::Reference { ref_path } => {
+            if visiting.contains(ref_path) {
+                return None;
             }
-            result.push(ch.to_ascii_lowercase());
-        } else if ch == '-' || ch == ' ' {
-            // Replace hyphens and spaces with underscores
-            if !result.is_empty() && !result.ends_with('_') {
-                result.push('_');
+            let target = spec.resolve_schema_ref(ref_path)?;
+            visiting.push(ref_path.clone());
+            let resolved = compose_schema_inner(spec, target, visiting);
+            visiting.pop();
+            Some(resolved)
+        },
+    }
+}
+
+/// Build the effective schema by following `$ref` and merging compositions
+///
+/// - `allOf` parts are merged in, including their required properties
+/// - `oneOf`/`anyOf` variants contribute their properties as optional fields
+///   (properties holding object variants become one-of blocks instead, see
+///   [`polymorphic_variants`])
+/// - A discriminator becomes a required enum property selecting the variant;
+///   it is not inherited by schemas extending this one through `allOf`
+fn compose_schema(spec: &OpenApiSpec, schema: &Schema) -> Schema {
+    compose_schema_inner(spec, schema, &mut Vec::new())
+}
+
+fn compose_schema_inner(spec: &OpenApiSpec, schema: &Schema, visiting: &mut Vec<String>) -> Schema {
+    let mut composed = schema.clone();
+
+    // Follow a $ref, keeping it so SDK type names can still be derived from it.
+    // Self-referencing schemas stop at the first repeat.
+    if let Some(ref ref_path) = schema.ref_path {
+        if !visiting.contains(ref_path) {
+            if let Some(target) = spec.resolve_schema_ref(ref_path) {
+                visiting.push(ref_path.clone());
+                let resolved = compose_schema_inner(spec, target, visiting);
+                visiting.pop();
+                merge_schema(&mut composed, &resolved, true);
             }
+        }
+    }
+
+    for part in std::mem::take(&mut composed.all_of) {
+        if let Some(part) = resolve_schema_inner(spec, &part, visiting) {
+            merge_schema(&mut composed, &part, true);
+        }
+    }
+
+    let variants: Vec<SchemaOrRef> = std::mem::take(&mut composed.one_of)
+        .into_iter()
+        .chain(std::mem::take(&mut composed.any_of))
+        .collect();
+    let variant_schemas: Vec<Schema> = variants
+        .iter()
+        .filter_map(|variant| resolve_schema_inner(spec, variant, visiting))
+        .collect();
+
+    if composed.schema_type.is_none() && !variant_schemas.is_empty() {
+        let first_type = variant_schemas[0].schema_type.clone();
+        if variant_schemas.iter().all(|v| v.schema_type == first_type) {
+            composed.schema_type = first_type;
+        } else if variant_schemas.iter().any(|v| !v.properties.is_empty()) {
+            composed.schema_type = Some("object".to_string());
         } else {
-            result.push(ch);
+            // Mixed primitives (e.g. int-or-string) round-trip as strings
+            composed.schema_type = Some("string".to_string());
         }
     }
+    for variant in &variant_schemas {
+        merge_schema(&mut composed, variant, false);
+    }
 
-    // Clean up multiple consecutive underscores
-    while result.contains("__") {
-        result = result.replace("__", "_");
+    if let Some(discriminator) = composed.discriminator.take() {
+        // Without variants, the subtypes are the schemas extending this one
+        // (Swagger/Azure polymorphism)
+        let variants = if variants.is_empty() {
+            subtypes(spec, visiting.last().map(String::as_str))
+        } else {
+            variants
+        };
+        let mut values: Vec<String> = discriminator.mapping.keys().cloned().collect();
+        values.extend(
+            variants
+                .iter()
+                .filter_map(|variant| variant_name(spec, Some(&discriminator), variant)),
+        );
+        values.sort();
+        values.dedup();
+
+        let description = composed
+            .properties
+            .get(&discriminator.property_name)
+            .and_then(|p| match p {
+                SchemaOrRef::Schema(s) => s.description.clone(),
+                SchemaOrRef::Reference { .. } => None,
+            });
+        composed.properties.insert(
+            discriminator.property_name.clone(),
+            SchemaOrRef::Schema(Box::new(Schema {
+                schema_type: Some("string".to_string()),
+                description,
+                enum_values: values.into_iter().map(serde_json::Value::String).collect(),
+                ..Default::default()
+            })),
+        );
+        if !composed.required.contains(&discriminator.property_name) {
+            composed.required.push(discriminator.property_name);
+        }
     }
 
-    // Strip leading and trailing underscores
-    result.trim_matches('_').to_string()
+    // x-ms-client-flatten lifts a property's own properties (usually the ARM
+    // `properties` envelope) into this schema
+    let flattened: Vec<String> = composed
+        .properties
+        .iter()
+        .filter(|(_, property)| match property {
+            SchemaOrRef::Schema(s) => s
+                .extensions
+                .get("x-ms-client-flatten")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false),
+            SchemaOrRef::Reference { .. } => false,
+        })
+        .map(|(name, _)| name.clone())
+        .collect();
+    for name in flattened {
+        let Some(property) = composed.properties.remove(&name) else {
+            continue;
+        };
+        let Some(inner) = resolve_schema_inner(spec, &property, visiting) else {
+            continue;
+        };
+        let was_required = composed.required.contains(&name);
+        composed.required.retain(|r| r != &name);
+        for (inner_name, inner_property) in inner.properties {
+            composed
+                .properties
+                .entry(inner_name)
+                .or_insert(inner_property);
+        }
+        if was_required {
+            for inner_name in inner.required {
+                if !composed.required.contains(&inner_name) {
+                    composed.required.push(inner_name);
+                }
+            }
+        }
+    }
+
+    if composed.schema_type.is_none() && !composed.properties.is_empty() {
+        composed.schema_type = Some("object".to_string());
+    }
+
+    composed
+}
+
+/// Merge `other` into `target`, keeping anything `target` already defines
+fn merge_schema(target: &mut Schema, other: &Schema, include_required: bool) {
+    for (name, property) in &other.properties {
+        target
+            .properties
+            .entry(name.clone())
+            .or_insert_with(|| property.clone());
+    }
+    if include_required {
+        for name in &other.required {
+            if !target.required.contains(name) {
+                target.required.push(name.clone());
+            }
+        }
+    }
+    if target.schema_type.is_none() {
+        target.schema_type = other.schema_type.clone();
+    }
+    if target.format.is_none() {
+        target.format = other.format.clone();
+    }
+    if target.description.is_none() {
+        target.description = other.description.clone();
+    }
+    if target.items.is_none() {
+        target.items = other.items.clone();
+    }
+    if target.additional_properties.is_none() {
+        target.additional_properties = other.additional_properties.clone();
+    }
+    if target.enum_values.is_empty() {
+        target.enum_values = other.enum_values.clone();
+    }
+    for (name, value) in &other.extensions {
+        target
+            .extensions
+            .entry(name.clone())
+            .or_insert_with(|| value.clone());
+    }
+}
+
+/// Schemas whose `allOf` extends the schema at `base_ref`, sorted by name
+fn subtypes(spec: &OpenApiSpec, base_ref: Option<&str>) -> Vec<SchemaOrRef> {
+    let (Some(base_ref), Some(components)) = (base_ref, &spec.components) else {
+        return vec![];
+    };
+
+    let mut names: Vec<&String> = components
+        .schemas
+        .iter()
+        .filter(|(_, schema)| {
+            schema
+                .all_of
+                .iter()
+                .any(|part| reference_of(part) == Some(base_ref))
+        })
+        .map(|(name, _)| name)
+        .collect();
+    names.sort();
+
+    names
+        .into_iter()
+        .map(|name| SchemaOrRef::Reference {
+            ref_path: format!("#/components/schemas/{}", name),
+        })
+        .collect()
+}
+
+/// The `$ref` of a schema that is only a reference
+fn reference_of(schema_or_ref: &SchemaOrRef) -> Option<&str> {
+    match schema_or_ref {
+        SchemaOrRef::Reference { ref_path } => Some(ref_path),
+        SchemaOrRef::Schema(s) => s.ref_path.as_deref(),
+    }
+}
+
+/// Discriminator value selecting a variant: its key in the discriminator
+/// mapping, its `x-ms-discriminator-value`, or else its schema name
+fn variant_name(
+    spec: &OpenApiSpec,
+    discriminator: Option<&Discriminator>,
+    variant: &SchemaOrRef,
+) -> Option<String> {
+    let ref_path = reference_of(variant);
+
+    if let (Some(discriminator), Some(ref_path)) = (discriminator, ref_path) {
+        let type_name = extract_type_name_from_ref(ref_path);
+        let mapped = discriminator
+            .mapping
+            .iter()
+            .find(|(_, target)| extract_type_name_from_ref(target) == type_name);
+        if let Some((value, _)) = mapped {
+            return Some(value.clone());
+        }
+    }
+
+    let schema = match (variant, ref_path) {
+        (_, Some(ref_path)) => spec.resolve_schema_ref(ref_path),
+        (SchemaOrRef::Schema(s), None) => Some(s.as_ref()),
+        (SchemaOrRef::Reference { .. }, None) => None,
+    };
+    schema
+        .and_then(|s| s.extensions.get("x-ms-discriminator-value"))
+        .and_then(|v| v.as_str())
+        .map(str::to_string)
+        .or_else(|| ref_path.map(extract_type_name_from_ref))
+}
+
+/// A variant of a polymorphic schema
+struct Variant {
+    /// Discriminator value or schema name of the variant
+    name: String,
+    ref_path: Option<String>,
+    /// Composed variant schema, without the discriminator property
+    schema: Schema,
+}
+
+/// Object variants of a `oneOf`/`anyOf` schema, or the subtypes of a
+/// discriminated base schema
+///
+/// Returns nothing unless every variant is an object, so unions of primitives
+/// (e.g. int-or-string) stay plain fields.
+fn polymorphic_variants(spec: &OpenApiSpec, schema_or_ref: &SchemaOrRef) -> Vec<Variant> {
+    // Follow references to the schema declaring the composition
+    let mut base_ref = None;
+    let mut schema = match schema_or_ref {
+        SchemaOrRef::Schema(s) => s.as_ref(),
+        SchemaOrRef::Reference { ref_path } => {
+            base_ref = Some(ref_path.as_str());
+            match spec.resolve_schema_ref(ref_path) {
+                Some(target) => target,
+                None => return vec![],
+            }
+        },
+    };
+    let mut visited = Vec::new();
+    while let Some(ref_path) = schema.ref_path.as_deref() {
+        if visited.contains(&ref_path) {
+            return vec![];
+        }
+        visited.push(ref_path);
+        let Some(target) = spec.resolve_schema_ref(ref_path) else {
+            return vec![];
+        };
+        base_ref = Some(ref_path);
+        schema = target;
+    }
+
+    let discriminator = schema.discriminator.as_ref();
+    let mut variant_refs: Vec<SchemaOrRef> = schema
+        .one_of
+        .iter()
+        .chain(&schema.any_of)
+        .cloned()
+        .collect();
+    if variant_refs.is_empty() && discriminator.is_some() {
+        variant_refs = subtypes(spec, base_ref);
+    }
+
+    let mut variants = Vec::new();
+    for (index, variant) in variant_refs.iter().enumerate() {
+        let Some(mut variant_schema) = resolve_schema(spec, variant) else {
+            continue;
+        };
+        if variant_schema.schema_type.as_deref() != Some("object")
+            || variant_schema.properties.is_empty()
+        {
+            return vec![];
+        }
+        if let Some(discriminator) = discriminator {
+            variant_schema
+                .properties
+                .remove(&discriminator.property_name);
+            variant_schema
+                .required
+                .retain(|r| r != &discriminator.property_name);
+        }
+
+        variants.push(Variant {
+            name: variant_name(spec, discriminator, variant)
+                .unwrap_or_else(|| format!("variant_{}", index + 1)),
+            ref_path: reference_of(variant).map(str::to_string),
+            schema: variant_schema,
+        });
+    }
+
+    variants
+}
+
+/// Whether a property is only ever set by the service
+/// (`readOnly`, or `x-ms-mutability` without create/update)
+fn is_read_only(schema: &Schema) -> bool {
+    if schema.extensions.get("readOnly").and_then(|v| v.as_bool()) == Some(true) {
+        return true;
+    }
+    mutability(schema).is_some_and(|m| !m.contains(&"create") && !m.contains(&"update"))
+}
+
+/// Whether a property is never returned by the service
+/// (`writeOnly`, or `x-ms-mutability` without read)
+fn is_write_only(schema: &Schema) -> bool {
+    if schema.extensions.get("writeOnly").and_then(|v| v.as_bool()) == Some(true) {
+        return true;
+    }
+    mutability(schema).is_some_and(|m| !m.contains(&"read"))
+}
+
+/// `deprecated: true` of a schema or operation
+fn deprecation(extensions: &HashMap<String, serde_json::Value>) -> Option<Deprecation> {
+    (extensions.get("deprecated").and_then(|v| v.as_bool()) == Some(true))
+        .then(Deprecation::default)
+}
+
+/// Whether a property can be set on create but never updated (`x-ms-mutability`)
+fn is_create_only(schema: &Schema) -> bool {
+    mutability(schema).is_some_and(|m| m.contains(&"create") && !m.contains(&"update"))
+}
+
+/// Values of `x-ms-mutability` ("create", "read", "update")
+fn mutability(schema: &Schema) -> Option<Vec<&str>> {
+    schema
+        .extensions
+        .get("x-ms-mutability")
+        .and_then(|v| v.as_array())
+        .map(|values| values.iter().filter_map(|v| v.as_str()).collect())
+}
+
+/// Whether a property holds a secret (`x-ms-secret`)
+fn is_secret(schema: &Schema) -> bool {
+    schema
+        .extensions
+        .get("x-ms-secret")
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false)
+}
+
+/// Name of the enum type declared by `x-ms-enum`
+fn enum_type_name(schema: &Schema) -> Option<String> {
+    schema
+        .extensions
+        .get("x-ms-enum")
+        .and_then(|e| e.get("name"))
+        .and_then(|v| v.as_str())
+        .map(|v| v.to_string())
 }
 
 /// Extract SDK type name from OpenAPI $ref path
@@ -683,23 +1261,3 @@ fn extract_type_name_from_ref(ref_or_name: &str) -> String {
         ref_or_name.to_string()
     }
 }
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_to_snake_case() {
-        assert_eq!(to_snake_case("PodName"), "pod_name");
-        assert_eq!(
-            to_snake_case("createNamespacedPod"),
-            "create_namespaced_pod"
-        );
-        assert_eq!(to_snake_case("HTTPServer"), "http_server");
-        assert_eq!(to_snake_case("XMLParser"), "xml_parser");
-        assert_eq!(to_snake_case("IOError"), "io_error");
-        assert_eq!(to_snake_case("v1_api"), "v1_api"); // Already snake_case
-        assert_eq!(to_snake_case("__test__"), "test"); // Strip extra underscores
-        assert_eq!(to_snake_case("some-resource"), "some_resource"); // Hyphens
-    }
-}
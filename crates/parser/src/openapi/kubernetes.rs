@@ -0,0 +1,364 @@
+//! Kubernetes-aware resource extraction
+//!
+//! Kubernetes OpenAPI specs tag every operation with `x-kubernetes-group-version-kind`
+//! and `x-kubernetes-action`. Grouping by group/version/kind instead of by path keeps
+//! e.g. `core/v1` and `events.k8s.io/v1` Events apart, and the action tells a `get`
+//! of one object apart from a `list` of many:
+//!
+//! - `post`/`get`/`put`/`patch`/`delete` become the resource's CRUD operations
+//! - `list` becomes a `<kind>_list` data source
+//! - `watch`, `deletecollection`, `connect` and subresources (`/status`, `/scale`, ...) are skipped
+//!
+//! Objects are identified by a required `name` field, which namespaced kinds pair
+//! with a required `namespace` field; both fill in the `{name}` and `{namespace}`
+//! path parameters. `apiVersion` and `kind` are emitted as fixed fields.
+
+use super::converter::{
+    build_operation_mapping, extract_blocks_from_operation, extract_fields_from_schema,
+    extract_outputs_from_operation, resolve_schema,
+};
+use super::types::{OpenApiSpec, Operation, SchemaOrRef};
+use crate::naming::to_snake_case;
+use hemmer_provider_generator_common::{
+    DataSourceDefinition, FieldDefinition, FieldType, Operations, PaginationConfig,
+    ResourceDefinition, Result, Stability,
+};
+use std::collections::{BTreeMap, HashMap};
+
+/// Kubernetes group/version/kind of an operation
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+struct GroupVersionKind {
+    group: String,
+    version: String,
+    kind: String,
+}
+
+impl GroupVersionKind {
+    /// Value of the `apiVersion` field (e.g., "v1", "apps/v1")
+    fn api_version(&self) -> String {
+        if self.group.is_empty() {
+            self.version.clone()
+        } else {
+            format!("{}/{}", self.group, self.version)
+        }
+    }
+}
+
+/// Operations collected for one kind
+#[derive(Debug, Default)]
+struct KindOperations<'a> {
+    create: Option<&'a Operation>,
+    read: Option<&'a Operation>,
+    replace: Option<&'a Operation>,
+    patch: Option<&'a Operation>,
+    delete: Option<&'a Operation>,
+    /// List within a namespace
+    list: Option<&'a Operation>,
+    /// List across all namespaces (or of a cluster-scoped kind)
+    list_all: Option<&'a Operation>,
+    namespaced: bool,
+}
+
+/// Check whether the spec carries Kubernetes group/version/kind extensions
+pub fn has_group_version_kinds(spec: &OpenApiSpec) -> bool {
+    spec.paths
+        .values()
+        .flat_map(|item| [&item.get, &item.post, &item.put, &item.patch, &item.delete])
+        .flatten()
+        .any(|op| group_version_kind(op).is_some())
+}
+
+/// Extract resources and list data sources, grouped by group/version/kind
+pub fn extract_kubernetes_resources(
+    spec: &OpenApiSpec,
+) -> Result<(Vec<ResourceDefinition>, Vec<DataSourceDefinition>)> {
+    let mut kinds: BTreeMap<GroupVersionKind, KindOperations> = BTreeMap::new();
+
+    for (path, item) in &spec.paths {
+        if is_subresource_path(path) {
+            continue;
+        }
+        let namespaced = path.contains("{namespace}");
+
+        for op in [&item.get, &item.post, &item.put, &item.patch, &item.delete]
+            .into_iter()
+            .flatten()
+        {
+            let (Some(gvk), Some(action)) = (
+                group_version_kind(op),
+                op.extensions
+                    .get("x-kubernetes-action")
+                    .and_then(|v| v.as_str()),
+            ) else {
+                continue;
+            };
+
+            let entry = kinds.entry(gvk).or_default();
+            match action {
+                "post" => entry.create = Some(op),
+                "get" => entry.read = Some(op),
+                "put" => entry.replace = Some(op),
+                "patch" => entry.patch = Some(op),
+                "delete" => entry.delete = Some(op),
+                "list" if namespaced => entry.list = Some(op),
+                "list" => entry.list_all = Some(op),
+                // watch, watchlist, deletecollection, connect
+                _ => continue,
+            }
+            entry.namespaced |= namespaced;
+        }
+    }
+
+    let names = resource_names(kinds.keys());
+
+    let mut resources = Vec::new();
+    let mut data_sources = Vec::new();
+    for (gvk, ops) in &kinds {
+        let name = &names[gvk];
+        if let Some(resource) = build_resource(spec, name, gvk, ops)? {
+            resources.push(resource);
+        }
+        if let Some(data_source) = build_list_data_source(spec, name, gvk, ops)? {
+            data_sources.push(data_source);
+        }
+    }
+
+    Ok((resources, data_sources))
+}
+
+/// Build the resource for one kind (kinds that can't be created are skipped)
+fn build_resource(
+    spec: &OpenApiSpec,
+    name: &str,
+    gvk: &GroupVersionKind,
+    ops: &KindOperations,
+) -> Result<Option<ResourceDefinition>> {
+    let Some(create) = ops.create else {
+        return Ok(None);
+    };
+
+    let body = request_schema(create);
+    let description = body
+        .and_then(|schema| resolve_schema(spec, schema))
+        .and_then(|schema| schema.description);
+
+    let mut fields = vec![
+        fixed_field(
+            "api_version",
+            &gvk.api_version(),
+            "APIVersion defines the versioned schema of this representation of an object",
+        ),
+        fixed_field(
+            "kind",
+            &gvk.kind,
+            "Kind is a string value representing the REST resource this object represents",
+        ),
+        name_field(),
+    ];
+    if ops.namespaced {
+        fields.push(namespace_field(true));
+    }
+    if let Some(body) = body {
+        fields.extend(
+            extract_fields_from_schema(spec, body, false)?
+                .into_iter()
+                .filter(|f| !is_server_managed(&f.name)),
+        );
+    }
+
+    let blocks = extract_blocks_from_operation(spec, create)?
+        .into_iter()
+        .filter(|b| !is_server_managed(&b.name))
+        .collect();
+
+    let outputs = extract_outputs_from_operation(spec, ops.read.unwrap_or(create))?;
+
+    let operations = Operations {
+        create: build_operation_mapping(create),
+        read: ops.read.and_then(build_operation_mapping),
+        // Prefer a full replace over a patch
+        update: ops.replace.or(ops.patch).and_then(build_operation_mapping),
+        delete: ops.delete.and_then(build_operation_mapping),
+        import: None,
+    };
+
+    let mut resource = ResourceDefinition {
+        name: name.to_string(),
+        description,
+        fields,
+        outputs,
+        blocks,
+        id_field: Some("name".to_string()),
+        deprecated: operations
+            .create
+            .as_ref()
+            .and_then(|op| op.deprecated.clone()),
+        operations,
+        // e.g., v1beta1
+        stability: Stability::from_version(&gvk.version),
+    };
+    resource.infer_computed_fields();
+
+    Ok(Some(resource))
+}
+
+/// Build a `<kind>_list` data source from the kind's list operations
+fn build_list_data_source(
+    spec: &OpenApiSpec,
+    name: &str,
+    gvk: &GroupVersionKind,
+    ops: &KindOperations,
+) -> Result<Option<DataSourceDefinition>> {
+    let Some(list) = ops.list.or(ops.list_all) else {
+        return Ok(None);
+    };
+    let Some(mut read_operation) = build_operation_mapping(list) else {
+        return Ok(None);
+    };
+
+    // A namespaced list falls back to the all-namespaces list when no namespace is given
+    let mut arguments = Vec::new();
+    if ops.namespaced {
+        let all_namespaces = ops
+            .list
+            .and(ops.list_all)
+            .and_then(|op| op.operation_id.as_deref())
+            .map(to_snake_case);
+        arguments.push(namespace_field(all_namespaces.is_none()));
+        read_operation.additional_operations.extend(all_namespaces);
+    }
+    for param in &list.parameters {
+        if param.location == "query"
+            && matches!(param.name.as_str(), "labelSelector" | "fieldSelector")
+        {
+            arguments.push(FieldDefinition {
+                name: to_snake_case(&param.name),
+                field_type: FieldType::String,
+                description: param.description.clone(),
+                ..Default::default()
+            });
+        }
+    }
+
+    read_operation.readonly = true;
+    read_operation.idempotent = true;
+    read_operation.pagination = Some(PaginationConfig {
+        input_token: Some("continue".to_string()),
+        output_token: Some("metadata.continue".to_string()),
+        items: Some("items".to_string()),
+        page_size: Some("limit".to_string()),
+    });
+
+    Ok(Some(DataSourceDefinition {
+        name: format!("{}_list", name),
+        description: Some(format!("List {} objects ({})", gvk.kind, gvk.api_version())),
+        arguments,
+        attributes: extract_outputs_from_operation(spec, list)?,
+        read_operation,
+        items_accessor: None,
+    }))
+}
+
+/// Read `x-kubernetes-group-version-kind` from an operation
+fn group_version_kind(op: &Operation) -> Option<GroupVersionKind> {
+    let gvk = op.extensions.get("x-kubernetes-group-version-kind")?;
+    let field = |name: &str| gvk.get(name).and_then(|v| v.as_str()).map(String::from);
+
+    Some(GroupVersionKind {
+        group: field("group").unwrap_or_default(),
+        version: field("version")?,
+        kind: field("kind")?,
+    })
+}
+
+/// Paths below an object, e.g. `/pods/{name}/status` or `/deployments/{name}/scale`
+fn is_subresource_path(path: &str) -> bool {
+    path.split_once("{name}")
+        .is_some_and(|(_, rest)| !rest.trim_matches('/').is_empty())
+}
+
+/// Resource names: the snake_case kind, with the version (and group) added when
+/// the same kind is served more than once
+fn resource_names<'a>(
+    gvks: impl Iterator<Item = &'a GroupVersionKind> + Clone,
+) -> HashMap<GroupVersionKind, String> {
+    let mut kind_counts: HashMap<&str, usize> = HashMap::new();
+    let mut kind_version_counts: HashMap<(&str, &str), usize> = HashMap::new();
+    for gvk in gvks.clone() {
+        *kind_counts.entry(&gvk.kind).or_default() += 1;
+        *kind_version_counts
+            .entry((&gvk.kind, &gvk.version))
+            .or_default() += 1;
+    }
+
+    gvks.map(|gvk| {
+        let kind = to_snake_case(&gvk.kind);
+        let name = if kind_counts[gvk.kind.as_str()] == 1 {
+            kind
+        } else if kind_version_counts[&(gvk.kind.as_str(), gvk.version.as_str())] == 1 {
+            format!("{}_{}", kind, gvk.version)
+        } else {
+            let group = gvk.group.split('.').next().unwrap_or_default();
+            let group = if group.is_empty() { "core" } else { group };
+            format!("{}_{}_{}", to_snake_case(group), kind, gvk.version)
+        };
+        (gvk.clone(), name)
+    })
+    .collect()
+}
+
+/// Fields the API server owns; they're outputs, not inputs
+fn is_server_managed(name: &str) -> bool {
+    matches!(name, "api_version" | "kind" | "status")
+}
+
+/// Field pinned to a single value, such as `apiVersion` and `kind`
+fn fixed_field(name: &str, value: &str, description: &str) -> FieldDefinition {
+    FieldDefinition {
+        name: name.to_string(),
+        field_type: FieldType::Enum(vec![value.to_string()]),
+        required: false,
+        sensitive: false,
+        immutable: true,
+        description: Some(format!("{} (always \"{}\")", description, value)),
+        default_value: Some(serde_json::Value::String(value.to_string())),
+        ..Default::default()
+    }
+}
+
+/// The `name` identifying an object (objects can't be renamed)
+fn name_field() -> FieldDefinition {
+    FieldDefinition {
+        name: "name".to_string(),
+        field_type: FieldType::String,
+        required: true,
+        sensitive: false,
+        immutable: true,
+        description: Some("Name of the object".to_string()),
+        ..Default::default()
+    }
+}
+
+/// The `namespace` a namespaced object lives in
+fn namespace_field(required: bool) -> FieldDefinition {
+    FieldDefinition {
+        name: "namespace".to_string(),
+        field_type: FieldType::String,
+        required,
+        sensitive: false,
+        immutable: true,
+        description: Some("Namespace of the object".to_string()),
+        ..Default::default()
+    }
+}
+
+/// Request body schema of an operation
+fn request_schema(op: &Operation) -> Option<&SchemaOrRef> {
+    op.request_body
+        .as_ref()?
+        .content
+        .values()
+        .next()?
+        .schema
+        .as_ref()
+}
@@ -1,11 +1,13 @@
 //! OpenAPI 3.0 specification parser
 //!
-//! Parses OpenAPI 3.0 specs (Swagger) into ServiceDefinition IR.
+//! Parses OpenAPI 3.0 and Swagger 2.0 specs (JSON or YAML) into ServiceDefinition IR.
+//! Swagger 2.0 documents are normalized to OpenAPI 3.0 before conversion, and
+//! `$ref`s to other files are resolved into a single merged spec.
 //!
 //! ## Supported Platforms
 //! - **Kubernetes**: Parse OpenAPI specs from K8s API server
 //! - **Azure**: Parse OpenAPI specs from azure-rest-api-specs repo
-//! - **Generic**: Any OpenAPI 3.0 compliant specification
+//! - **Generic**: Any OpenAPI 3.0 or Swagger 2.0 compliant specification
 //!
 //! ## OpenAPI Sources
 //!
@@ -26,8 +28,11 @@
 //! ```
 
 mod converter;
+mod kubernetes;
 mod parser;
+mod refs;
+mod swagger;
 mod types;
 
-pub use parser::{OpenApiParser, ProviderHint};
+pub use parser::{is_azure_spec, is_openapi_spec, OpenApiParser, ProviderHint};
 pub use types::*;
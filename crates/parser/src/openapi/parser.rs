@@ -1,14 +1,15 @@
 //! OpenAPI spec file parser
 
 use super::types::OpenApiSpec;
+use super::{refs, swagger};
+use crate::crud_rules::CrudRules;
 use hemmer_provider_generator_common::{GeneratorError, Result, ServiceDefinition};
-use std::fs;
 use std::path::Path;
 
 /// OpenAPI specification parser
 ///
-/// Reads and parses OpenAPI 3.0 specifications from Kubernetes, Azure, or any
-/// OpenAPI 3.0 compliant API.
+/// Reads and parses OpenAPI 3.0 and Swagger 2.0 specifications (JSON or YAML)
+/// from Kubernetes, Azure, or any OpenAPI compliant API.
 pub struct OpenApiParser {
     /// Loaded OpenAPI spec
     spec: OpenApiSpec,
@@ -21,6 +22,52 @@ pub struct OpenApiParser {
 
     /// Provider type hint (optional)
     provider_hint: Option<ProviderHint>,
+
+    /// Rules for classifying operations as CRUD operations
+    crud_rules: CrudRules,
+}
+
+/// Check whether a JSON or YAML document is an OpenAPI 3.x or Swagger 2.0 spec
+///
+/// Looks for the top-level `openapi` or `swagger` version key.
+pub fn is_openapi_spec(content: &str) -> bool {
+    serde_yaml::from_str::<serde_json::Value>(content)
+        .is_ok_and(|doc| doc.get("openapi").is_some() || doc.get("swagger").is_some())
+}
+
+/// Check whether an OpenAPI or Swagger document describes an Azure API
+///
+/// Azure specs use AutoRest `x-ms-*` extensions or Azure Resource Manager
+/// paths (`.../providers/Microsoft.Storage/...`).
+pub fn is_azure_spec(content: &str) -> bool {
+    let Ok(doc) = serde_yaml::from_str::<serde_json::Value>(content) else {
+        return false;
+    };
+    if doc.get("openapi").is_none() && doc.get("swagger").is_none() {
+        return false;
+    }
+
+    let arm_paths = doc
+        .get("paths")
+        .and_then(|paths| paths.as_object())
+        .is_some_and(|paths| {
+            paths
+                .keys()
+                .any(|path| path.to_ascii_lowercase().contains("/providers/microsoft."))
+        });
+
+    arm_paths || has_ms_extension(&doc)
+}
+
+/// Whether any object in the document has an `x-ms-*` key
+fn has_ms_extension(value: &serde_json::Value) -> bool {
+    match value {
+        serde_json::Value::Object(map) => map
+            .iter()
+            .any(|(key, value)| key.starts_with("x-ms-") || has_ms_extension(value)),
+        serde_json::Value::Array(items) => items.iter().any(has_ms_extension),
+        _ => false,
+    }
 }
 
 /// Provider type hint for OpenAPI specs
@@ -52,27 +99,46 @@ impl OpenApiParser {
         service_name: &str,
         api_version: &str,
     ) -> Result<Self> {
-        let content = fs::read_to_string(path.as_ref()).map_err(|e| {
-            GeneratorError::Parse(format!(
-                "Failed to read OpenAPI file {}: {}",
-                path.as_ref().display(),
-                e
-            ))
-        })?;
-
-        Self::from_json(&content, service_name, api_version)
+        // External $refs (e.g. shared common-types files) are merged into one spec
+        let doc = refs::load_document(path.as_ref())?;
+        let doc = refs::resolve_external_refs(doc, path.as_ref())?;
+
+        Self::from_value(doc, service_name, api_version)
     }
 
-    /// Parse OpenAPI spec from JSON string
+    /// Parse OpenAPI 3.0 or Swagger 2.0 spec from JSON string
     pub fn from_json(json: &str, service_name: &str, api_version: &str) -> Result<Self> {
-        let spec: OpenApiSpec = serde_json::from_str(json)
+        let doc: serde_json::Value = serde_json::from_str(json)
             .map_err(|e| GeneratorError::Parse(format!("Failed to parse OpenAPI JSON: {}", e)))?;
 
+        Self::from_value(doc, service_name, api_version)
+    }
+
+    /// Parse OpenAPI 3.0 or Swagger 2.0 spec from YAML string
+    pub fn from_yaml(yaml: &str, service_name: &str, api_version: &str) -> Result<Self> {
+        let doc: serde_json::Value = serde_yaml::from_str(yaml)
+            .map_err(|e| GeneratorError::Parse(format!("Failed to parse OpenAPI YAML: {}", e)))?;
+
+        Self::from_value(doc, service_name, api_version)
+    }
+
+    /// Build the parser from a raw document, normalizing Swagger 2.0 to OpenAPI 3.0
+    fn from_value(doc: serde_json::Value, service_name: &str, api_version: &str) -> Result<Self> {
+        let doc = if swagger::is_swagger2(&doc) {
+            swagger::normalize_swagger2(doc)?
+        } else {
+            doc
+        };
+
+        let spec: OpenApiSpec = serde_json::from_value(doc)
+            .map_err(|e| GeneratorError::Parse(format!("Failed to parse OpenAPI spec: {}", e)))?;
+
         Ok(Self {
             spec,
             service_name: service_name.to_string(),
             api_version: api_version.to_string(),
             provider_hint: None,
+            crud_rules: CrudRules::default(),
         })
     }
 
@@ -82,6 +148,15 @@ impl OpenApiParser {
         self
     }
 
+    /// Use custom CRUD classification rules
+    ///
+    /// Operations of specs without Kubernetes group/version/kind extensions are
+    /// classified by `operationId` and HTTP method.
+    pub fn with_crud_rules(mut self, crud_rules: CrudRules) -> Self {
+        self.crud_rules = crud_rules;
+        self
+    }
+
     /// Parse OpenAPI spec into ServiceDefinition IR
     pub fn parse(&self) -> Result<ServiceDefinition> {
         // Use the converter module to transform OpenAPI -> ServiceDefinition
@@ -90,6 +165,7 @@ impl OpenApiParser {
             &self.service_name,
             &self.api_version,
             self.provider_hint,
+            &self.crud_rules,
         )
     }
 
@@ -121,4 +197,80 @@ mod tests {
         assert_eq!(parser.spec.openapi, "3.0.0");
         assert_eq!(parser.spec.info.title, "Test API");
     }
+
+    #[test]
+    fn test_parse_swagger2_yaml() {
+        let swagger_yaml = r##"
+swagger: "2.0"
+info:
+  title: Test API
+  version: "1.0.0"
+paths:
+  /widgets:
+    post:
+      operationId: Widgets_Create
+      parameters:
+        - name: body
+          in: body
+          schema:
+            $ref: "#/definitions/Widget"
+      responses:
+        201:
+          description: Created
+definitions:
+  Widget:
+    type: object
+    properties:
+      name:
+        type: string
+"##;
+
+        let parser = OpenApiParser::from_yaml(swagger_yaml, "test", "1.0.0").unwrap();
+        assert_eq!(parser.spec.openapi, "3.0.0");
+        assert!(parser
+            .spec
+            .components
+            .as_ref()
+            .is_some_and(|c| c.schemas.contains_key("Widget")));
+
+        let create = parser.spec.paths["/widgets"].post.as_ref().unwrap();
+        assert!(create.request_body.is_some());
+        assert!(create.responses.contains_key("201"));
+    }
+
+    #[test]
+    fn test_is_openapi_spec() {
+        assert!(is_openapi_spec(
+            "openapi: 3.0.0\ninfo: {title: A, version: v1}\n"
+        ));
+        assert!(is_openapi_spec("swagger: \"2.0\"\npaths: {}\n"));
+        assert!(is_openapi_spec(r#"{"openapi": "3.1.0", "paths": {}}"#));
+
+        // Other YAML (CI config, Helm values, Kubernetes manifests) is not a spec
+        assert!(!is_openapi_spec("name: CI\non: [push]\n"));
+        assert!(!is_openapi_spec("apiVersion: v1\nkind: ConfigMap\n"));
+        assert!(!is_openapi_spec("info:\n  openapi: nested\n"));
+        assert!(!is_openapi_spec("- openapi\n"));
+        assert!(!is_openapi_spec("not: [valid"));
+    }
+
+    #[test]
+    fn test_is_azure_spec() {
+        // Azure Resource Manager paths
+        assert!(is_azure_spec(
+            r#"{"swagger": "2.0", "paths": {"/subscriptions/{subscriptionId}/resourceGroups/{resourceGroupName}/providers/Microsoft.Storage/storageAccounts/{accountName}": {}}}"#
+        ));
+
+        // AutoRest extensions anywhere in the document
+        assert!(is_azure_spec(
+            "swagger: \"2.0\"\npaths:\n  /keys:\n    get:\n      x-ms-pageable:\n        nextLinkName: nextLink\n"
+        ));
+
+        // Other OpenAPI specs and non-specs are not Azure
+        assert!(!is_azure_spec(
+            "openapi: 3.0.0\npaths:\n  /api/v1/namespaces/{namespace}/pods: {}\n"
+        ));
+        assert!(!is_azure_spec("x-ms-pageable: {}\n"));
+        assert!(!is_azure_spec("not: [valid"));
+    }
 }
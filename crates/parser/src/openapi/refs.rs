@@ -0,0 +1,421 @@
+//! External `$ref` resolution for multi-file OpenAPI specs
+//!
+//! Specs like Azure's reference shared files (`../common-types/types.json#/definitions/Resource`).
+//! Before conversion, every external reference is resolved against the local
+//! filesystem and folded into the root document, so the converter only ever
+//! sees local `#/...` references:
+//!
+//! - Referenced schemas are copied into the root document's schema section and
+//!   the `$ref` is rewritten to point at the copy
+//! - Anything else (parameters, responses, ...) is inlined in place
+//!
+//! Loaded files are cached, and each external schema is imported once, which
+//! also stops reference cycles between files.
+
+use hemmer_provider_generator_common::{GeneratorError, Result};
+use serde_json::{Map, Value};
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Load a JSON or YAML document from disk (YAML is picked by extension)
+pub fn load_document(path: &Path) -> Result<Value> {
+    let content = fs::read_to_string(path).map_err(|e| {
+        GeneratorError::Parse(format!(
+            "Failed to read OpenAPI file {}: {}",
+            path.display(),
+            e
+        ))
+    })?;
+
+    match path.extension().and_then(|e| e.to_str()) {
+        Some("yaml") | Some("yml") => serde_yaml::from_str(&content).map_err(|e| {
+            GeneratorError::Parse(format!(
+                "Failed to parse OpenAPI YAML {}: {}",
+                path.display(),
+                e
+            ))
+        }),
+        _ => serde_json::from_str(&content).map_err(|e| {
+            GeneratorError::Parse(format!(
+                "Failed to parse OpenAPI JSON {}: {}",
+                path.display(),
+                e
+            ))
+        }),
+    }
+}
+
+/// Resolve all external `$ref`s in `doc`, which was loaded from `path`
+pub fn resolve_external_refs(mut doc: Value, path: &Path) -> Result<Value> {
+    let root_path = canonicalize(path)?;
+
+    // Swagger 2.0 keeps schemas under `definitions`, OpenAPI 3 under `components/schemas`
+    let schema_prefix = if doc.get("swagger").is_some() {
+        "#/definitions/"
+    } else {
+        "#/components/schemas/"
+    };
+    let taken = local_schemas(&doc, schema_prefix)
+        .map(|schemas| schemas.keys().cloned().collect())
+        .unwrap_or_default();
+
+    let mut resolver = RefResolver {
+        root_path: root_path.clone(),
+        schema_prefix,
+        documents: HashMap::new(),
+        imported: HashMap::new(),
+        schemas: Map::new(),
+        taken,
+        inlining: Vec::new(),
+    };
+    resolver.rewrite(&mut doc, &root_path)?;
+
+    if !resolver.schemas.is_empty() {
+        let schemas = local_schemas_mut(&mut doc, schema_prefix)?;
+        schemas.extend(resolver.schemas);
+    }
+
+    Ok(doc)
+}
+
+/// A reference target: a file plus a JSON pointer into it
+type RefTarget = (PathBuf, String);
+
+struct RefResolver {
+    /// Canonical path of the root document
+    root_path: PathBuf,
+    /// Local `$ref` prefix for schemas in the root document
+    schema_prefix: &'static str,
+    /// Cache of loaded external documents
+    documents: HashMap<PathBuf, Value>,
+    /// External schemas already imported (or being imported), with their local names
+    imported: HashMap<RefTarget, String>,
+    /// Imported schemas to add to the root document
+    schemas: Map<String, Value>,
+    /// Schema names already used in the root document
+    taken: HashSet<String>,
+    /// Non-schema references currently being inlined
+    inlining: Vec<RefTarget>,
+}
+
+impl RefResolver {
+    /// Rewrite references in `value`, which lives in the document at `current_file`
+    fn rewrite(&mut self, value: &mut Value, current_file: &Path) -> Result<()> {
+        match value {
+            Value::Object(map) => {
+                if let Some(Value::String(ref_path)) = map.get("$ref") {
+                    let ref_path = ref_path.clone();
+                    match self.target_of(&ref_path, current_file)? {
+                        Some(target) => {
+                            if is_schema_pointer(&target.1) {
+                                let name = self.import_schema(target)?;
+                                map.insert(
+                                    "$ref".to_string(),
+                                    Value::String(format!("{}{}", self.schema_prefix, name)),
+                                );
+                            } else if !self.inlining.contains(&target) {
+                                let mut inlined = self.lookup(&target)?;
+                                self.inlining.push(target.clone());
+                                self.rewrite(&mut inlined, &target.0)?;
+                                self.inlining.pop();
+                                *value = inlined;
+                            }
+                            return Ok(());
+                        },
+                        // An external file pointing back into the root document
+                        None if !ref_path.starts_with('#') => {
+                            let pointer = ref_path.split_once('#').map_or("", |(_, p)| p);
+                            map.insert("$ref".to_string(), Value::String(format!("#{}", pointer)));
+                        },
+                        None => {},
+                    }
+                }
+
+                for child in map.values_mut() {
+                    self.rewrite(child, current_file)?;
+                }
+            },
+            Value::Array(items) => {
+                for item in items {
+                    self.rewrite(item, current_file)?;
+                }
+            },
+            _ => {},
+        }
+
+        Ok(())
+    }
+
+    /// Work out what a `$ref` points at, or `None` if it points into the root document
+    fn target_of(&self, ref_path: &str, current_file: &Path) -> Result<Option<RefTarget>> {
+        let (file, pointer) = ref_path.split_once('#').unwrap_or((ref_path, ""));
+
+        let file = if file.is_empty() {
+            current_file.to_path_buf()
+        } else {
+            let base_dir = current_file.parent().unwrap_or_else(|| Path::new("."));
+            canonicalize(&base_dir.join(file))?
+        };
+
+        if file == self.root_path {
+            return Ok(None);
+        }
+
+        Ok(Some((file, pointer.to_string())))
+    }
+
+    /// Import an external schema into the root document, returning its local name
+    fn import_schema(&mut self, target: RefTarget) -> Result<String> {
+        if let Some(name) = self.imported.get(&target) {
+            return Ok(name.clone());
+        }
+
+        // Register the name before resolving the schema so cycles find it
+        let name = self.unique_name(&target);
+        self.imported.insert(target.clone(), name.clone());
+
+        let mut schema = self.lookup(&target)?;
+        self.rewrite(&mut schema, &target.0)?;
+        self.schemas.insert(name.clone(), schema);
+
+        Ok(name)
+    }
+
+    /// Pick a schema name that doesn't clash with the root document or earlier imports
+    fn unique_name(&mut self, (file, pointer): &RefTarget) -> String {
+        let stem = file
+            .file_stem()
+            .and_then(|s| s.to_str())
+            .unwrap_or("external")
+            .replace(['-', '.'], "_");
+        let base = pointer
+            .rsplit('/')
+            .next()
+            .filter(|s| !s.is_empty())
+            .map(|s| s.replace("~1", "/").replace("~0", "~"))
+            .unwrap_or_else(|| stem.clone());
+
+        let mut name = base.clone();
+        let mut counter = 1;
+        while self.taken.contains(&name) {
+            name = if counter == 1 {
+                format!("{}_{}", stem, base)
+            } else {
+                format!("{}_{}{}", stem, base, counter)
+            };
+            counter += 1;
+        }
+
+        self.taken.insert(name.clone());
+        name
+    }
+
+    /// Fetch the value a target points at, loading (and caching) its file
+    fn lookup(&mut self, (file, pointer): &RefTarget) -> Result<Value> {
+        if !self.documents.contains_key(file) {
+            let document = load_document(file)?;
+            self.documents.insert(file.clone(), document);
+        }
+
+        self.documents[file]
+            .pointer(pointer)
+            .cloned()
+            .ok_or_else(|| {
+                GeneratorError::Parse(format!("Unresolved $ref: {}#{}", file.display(), pointer))
+            })
+    }
+}
+
+/// Whether a JSON pointer addresses a schema definition
+fn is_schema_pointer(pointer: &str) -> bool {
+    pointer.is_empty()
+        || pointer
+            .strip_prefix("/definitions/")
+            .or_else(|| pointer.strip_prefix("/components/schemas/"))
+            .is_some_and(|name| !name.contains('/'))
+}
+
+/// Schema section of the root document
+fn local_schemas<'a>(doc: &'a Value, schema_prefix: &str) -> Option<&'a Map<String, Value>> {
+    doc.pointer(schema_prefix.trim_start_matches('#').trim_end_matches('/'))
+        .and_then(|v| v.as_object())
+}
+
+/// Schema section of the root document, created if missing
+fn local_schemas_mut<'a>(
+    doc: &'a mut Value,
+    schema_prefix: &str,
+) -> Result<&'a mut Map<String, Value>> {
+    let mut current = doc;
+    for segment in schema_prefix
+        .trim_start_matches("#/")
+        .trim_end_matches('/')
+        .split('/')
+    {
+        current = current
+            .as_object_mut()
+            .ok_or_else(|| GeneratorError::Parse("OpenAPI document is not an object".to_string()))?
+            .entry(segment)
+            .or_insert_with(|| Value::Object(Map::new()));
+    }
+
+    current
+        .as_object_mut()
+        .ok_or_else(|| GeneratorError::Parse(format!("{} is not an object", schema_prefix)))
+}
+
+fn canonicalize(path: &Path) -> Result<PathBuf> {
+    path.canonicalize().map_err(|e| {
+        GeneratorError::Parse(format!(
+            "Failed to resolve OpenAPI file {}: {}",
+            path.display(),
+            e
+        ))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn write(dir: &Path, name: &str, value: &Value) -> PathBuf {
+        let path = dir.join(name);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, serde_json::to_string_pretty(value).unwrap()).unwrap();
+        path
+    }
+
+    #[test]
+    fn test_resolve_external_schema_refs_with_cycle() {
+        let dir = tempfile::TempDir::new().unwrap();
+
+        write(
+            dir.path(),
+            "common/types.json",
+            &json!({
+                "swagger": "2.0",
+                "definitions": {
+                    "Resource": {
+                        "properties": {
+                            "id": { "type": "string" },
+                            "parent": { "$ref": "#/definitions/Resource" }
+                        }
+                    }
+                },
+                "parameters": {
+                    "ApiVersionParameter": { "name": "api-version", "in": "query", "type": "string" }
+                }
+            }),
+        );
+        let root = write(
+            dir.path(),
+            "service/vaults.json",
+            &json!({
+                "swagger": "2.0",
+                "info": { "title": "Vaults", "version": "1.0" },
+                "paths": {
+                    "/vaults": {
+                        "get": {
+                            "parameters": [
+                                { "$ref": "../common/types.json#/parameters/ApiVersionParameter" }
+                            ],
+                            "responses": {}
+                        }
+                    }
+                },
+                "definitions": {
+                    "Resource": { "type": "string" },
+                    "Vault": {
+                        "allOf": [{ "$ref": "../common/types.json#/definitions/Resource" }]
+                    }
+                }
+            }),
+        );
+
+        let doc = load_document(&root).unwrap();
+        let resolved = resolve_external_refs(doc, &root).unwrap();
+
+        // The imported schema is renamed around the local "Resource" and its self-reference follows
+        assert_eq!(
+            resolved["definitions"]["Vault"]["allOf"][0]["$ref"],
+            "#/definitions/types_Resource"
+        );
+        assert_eq!(
+            resolved["definitions"]["types_Resource"]["properties"]["parent"]["$ref"],
+            "#/definitions/types_Resource"
+        );
+        assert_eq!(resolved["definitions"]["Resource"]["type"], "string");
+
+        // Non-schema references are inlined
+        assert_eq!(
+            resolved["paths"]["/vaults"]["get"]["parameters"][0]["name"],
+            "api-version"
+        );
+    }
+
+    #[test]
+    fn test_missing_external_file_is_an_error() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let root = write(
+            dir.path(),
+            "spec.json",
+            &json!({
+                "openapi": "3.0.0",
+                "components": {
+                    "schemas": {
+                        "Widget": { "$ref": "./missing.json#/components/schemas/Widget" }
+                    }
+                }
+            }),
+        );
+
+        let doc = load_document(&root).unwrap();
+        let result = resolve_external_refs(doc, &root);
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_refs_back_into_the_root_document_become_local() {
+        let dir = tempfile::TempDir::new().unwrap();
+        write(
+            dir.path(),
+            "common/types.json",
+            &json!({
+                "swagger": "2.0",
+                "definitions": {
+                    "TrackedResource": {
+                        "properties": {
+                            "sku": { "$ref": "../service/vaults.json#/definitions/Sku" }
+                        }
+                    }
+                }
+            }),
+        );
+        let root = write(
+            dir.path(),
+            "service/vaults.json",
+            &json!({
+                "swagger": "2.0",
+                "info": { "title": "Vaults", "version": "1.0" },
+                "paths": {},
+                "definitions": {
+                    "Sku": { "type": "string" },
+                    "Vault": {
+                        "allOf": [{ "$ref": "../common/types.json#/definitions/TrackedResource" }]
+                    }
+                }
+            }),
+        );
+
+        let doc = load_document(&root).unwrap();
+        let resolved = resolve_external_refs(doc, &root).unwrap();
+
+        assert_eq!(
+            resolved["definitions"]["TrackedResource"]["properties"]["sku"]["$ref"],
+            "#/definitions/Sku"
+        );
+    }
+}
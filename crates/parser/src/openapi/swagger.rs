@@ -0,0 +1,496 @@
+//! Swagger 2.0 → OpenAPI 3.0 normalization
+//!
+//! Rewrites a Swagger 2.0 document into the OpenAPI 3.0 layout expected by
+//! [`OpenApiSpec`](super::types::OpenApiSpec), so the converter only has to
+//! understand one shape:
+//!
+//! - `definitions`/`parameters`/`responses` move under `components`
+//! - `in: body` and `in: formData` parameters become a `requestBody`
+//! - Response `schema` moves into `content`
+//! - Non-body parameter types move into a `schema`
+//! - `host`/`basePath`/`schemes` become `servers`
+//! - String discriminators become `{ "propertyName": ... }` objects
+
+use hemmer_provider_generator_common::{GeneratorError, Result};
+use serde_json::{json, Map, Value};
+
+/// HTTP methods that can appear in a path item
+const METHODS: &[&str] = &["get", "put", "post", "delete", "options", "head", "patch"];
+
+/// Parameter keywords that describe the parameter's type in Swagger 2.0
+const TYPE_KEYWORDS: &[&str] = &[
+    "type",
+    "format",
+    "items",
+    "enum",
+    "default",
+    "minimum",
+    "maximum",
+    "minLength",
+    "maxLength",
+    "pattern",
+];
+
+/// Check whether a raw document is a Swagger 2.0 spec
+pub fn is_swagger2(doc: &Value) -> bool {
+    doc.get("swagger")
+        .and_then(|v| v.as_str())
+        .is_some_and(|v| v.starts_with("2."))
+        && doc.get("openapi").is_none()
+}
+
+/// Normalize a Swagger 2.0 document into OpenAPI 3.0 form
+pub fn normalize_swagger2(mut doc: Value) -> Result<Value> {
+    let root = doc
+        .as_object_mut()
+        .ok_or_else(|| GeneratorError::Parse("Swagger document is not an object".to_string()))?;
+
+    // Rewrite refs and discriminators everywhere before moving things around
+    for value in root.values_mut() {
+        rewrite_refs(value);
+    }
+
+    let global_consumes = media_types(root.get("consumes"));
+    let global_produces = media_types(root.get("produces"));
+    let global_parameters = root
+        .remove("parameters")
+        .and_then(|v| v.as_object().cloned())
+        .unwrap_or_default();
+    let global_responses = root
+        .remove("responses")
+        .and_then(|v| v.as_object().cloned())
+        .unwrap_or_default();
+
+    // Paths
+    if let Some(Value::Object(paths)) = root.get_mut("paths") {
+        for path_item in paths.values_mut() {
+            if let Value::Object(path_item) = path_item {
+                normalize_path_item(
+                    path_item,
+                    &global_parameters,
+                    &global_responses,
+                    &global_consumes,
+                    &global_produces,
+                );
+            }
+        }
+    }
+
+    // Components
+    let mut components = Map::new();
+    if let Some(definitions) = root.remove("definitions") {
+        components.insert("schemas".to_string(), definitions);
+    }
+    let parameters: Map<String, Value> = global_parameters
+        .iter()
+        .filter(|(_, p)| !matches!(location(p), "body" | "formData"))
+        .map(|(name, p)| (name.clone(), normalize_parameter(p.clone())))
+        .collect();
+    if !parameters.is_empty() {
+        components.insert("parameters".to_string(), Value::Object(parameters));
+    }
+    let responses: Map<String, Value> = global_responses
+        .iter()
+        .map(|(name, r)| {
+            (
+                name.clone(),
+                normalize_response(r.clone(), &global_produces),
+            )
+        })
+        .collect();
+    if !responses.is_empty() {
+        components.insert("responses".to_string(), Value::Object(responses));
+    }
+    if !components.is_empty() {
+        root.insert("components".to_string(), Value::Object(components));
+    }
+
+    // Servers
+    if let Some(host) = root
+        .remove("host")
+        .and_then(|v| v.as_str().map(String::from))
+    {
+        let base_path = root
+            .remove("basePath")
+            .and_then(|v| v.as_str().map(String::from))
+            .unwrap_or_default();
+        let schemes = root
+            .remove("schemes")
+            .and_then(|v| v.as_array().cloned())
+            .unwrap_or_default();
+        let scheme = schemes
+            .iter()
+            .filter_map(|s| s.as_str())
+            .find(|s| *s == "https")
+            .or_else(|| schemes.first().and_then(|s| s.as_str()))
+            .unwrap_or("https");
+        root.insert(
+            "servers".to_string(),
+            json!([{ "url": format!("{}://{}{}", scheme, host, base_path) }]),
+        );
+    }
+
+    for key in ["swagger", "consumes", "produces", "basePath", "schemes"] {
+        root.remove(key);
+    }
+    root.insert("openapi".to_string(), json!("3.0.0"));
+
+    Ok(doc)
+}
+
+/// Normalize the operations of one path item
+fn normalize_path_item(
+    path_item: &mut Map<String, Value>,
+    global_parameters: &Map<String, Value>,
+    global_responses: &Map<String, Value>,
+    global_consumes: &[String],
+    global_produces: &[String],
+) {
+    let shared_parameters = path_item
+        .remove("parameters")
+        .and_then(|v| v.as_array().cloned())
+        .unwrap_or_default();
+    let shared_parameters: Vec<Value> = shared_parameters
+        .into_iter()
+        .map(|p| inline_parameter(p, global_parameters))
+        .collect();
+
+    for method in METHODS {
+        let Some(Value::Object(operation)) = path_item.get_mut(*method) else {
+            continue;
+        };
+
+        let consumes = media_types(operation.get("consumes"));
+        let consumes = if consumes.is_empty() {
+            global_consumes
+        } else {
+            &consumes
+        };
+        let produces = media_types(operation.get("produces"));
+        let produces = if produces.is_empty() {
+            global_produces
+        } else {
+            &produces
+        };
+
+        // Operation parameters override path-level ones with the same name and location
+        let mut parameters: Vec<Value> = operation
+            .remove("parameters")
+            .and_then(|v| v.as_array().cloned())
+            .unwrap_or_default()
+            .into_iter()
+            .map(|p| inline_parameter(p, global_parameters))
+            .collect();
+        for shared in &shared_parameters {
+            let overridden = parameters
+                .iter()
+                .any(|p| p.get("name") == shared.get("name") && location(p) == location(shared));
+            if !overridden {
+                parameters.push(shared.clone());
+            }
+        }
+
+        let mut body: Option<Value> = None;
+        let mut form_properties = Map::new();
+        let mut form_required = Vec::new();
+        let mut regular = Vec::new();
+        for parameter in parameters {
+            match location(&parameter) {
+                "body" => body = Some(parameter),
+                "formData" => {
+                    let name = parameter
+                        .get("name")
+                        .and_then(|v| v.as_str())
+                        .unwrap_or_default()
+                        .to_string();
+                    if parameter.get("required").and_then(|v| v.as_bool()) == Some(true) {
+                        form_required.push(json!(name));
+                    }
+                    form_properties.insert(name, parameter_schema(&parameter));
+                },
+                _ => regular.push(normalize_parameter(parameter)),
+            }
+        }
+
+        if !regular.is_empty() {
+            operation.insert("parameters".to_string(), Value::Array(regular));
+        }
+
+        if let Some(body) = body {
+            let media_type = consumes
+                .first()
+                .cloned()
+                .unwrap_or_else(|| "application/json".to_string());
+            let mut request_body = Map::new();
+            if let Some(description) = body.get("description") {
+                request_body.insert("description".to_string(), description.clone());
+            }
+            if let Some(required) = body.get("required") {
+                request_body.insert("required".to_string(), required.clone());
+            }
+            let schema = body.get("schema").cloned().unwrap_or_else(|| json!({}));
+            request_body.insert(
+                "content".to_string(),
+                json!({ media_type: { "schema": schema } }),
+            );
+            operation.insert("requestBody".to_string(), Value::Object(request_body));
+        } else if !form_properties.is_empty() {
+            let media_type = consumes
+                .iter()
+                .find(|m| m.contains("form"))
+                .cloned()
+                .unwrap_or_else(|| "application/x-www-form-urlencoded".to_string());
+            let mut schema = json!({ "type": "object", "properties": form_properties });
+            if !form_required.is_empty() {
+                schema["required"] = Value::Array(form_required);
+            }
+            operation.insert(
+                "requestBody".to_string(),
+                json!({ "content": { media_type: { "schema": schema } } }),
+            );
+        }
+
+        if let Some(Value::Object(responses)) = operation.get_mut("responses") {
+            for response in responses.values_mut() {
+                let inlined = inline_response(response.take(), global_responses);
+                *response = normalize_response(inlined, produces);
+            }
+        }
+
+        operation.remove("consumes");
+        operation.remove("produces");
+    }
+}
+
+/// Replace a `#/parameters/...` reference with the parameter it points to
+///
+/// Refs have already been rewritten to their OpenAPI 3 location at this point.
+fn inline_parameter(parameter: Value, global_parameters: &Map<String, Value>) -> Value {
+    parameter
+        .get("$ref")
+        .and_then(|r| r.as_str())
+        .and_then(|r| r.strip_prefix("#/components/parameters/"))
+        .and_then(|name| global_parameters.get(name))
+        .cloned()
+        .unwrap_or(parameter)
+}
+
+/// Replace a `#/responses/...` reference with the response it points to
+fn inline_response(response: Value, global_responses: &Map<String, Value>) -> Value {
+    response
+        .get("$ref")
+        .and_then(|r| r.as_str())
+        .and_then(|r| r.strip_prefix("#/components/responses/"))
+        .and_then(|name| global_responses.get(name))
+        .cloned()
+        .unwrap_or(response)
+}
+
+/// Move a non-body parameter's type keywords into a `schema`
+fn normalize_parameter(mut parameter: Value) -> Value {
+    if parameter.get("schema").is_none() {
+        let schema = parameter_schema(&parameter);
+        if let Value::Object(ref mut map) = parameter {
+            for keyword in TYPE_KEYWORDS {
+                map.remove(*keyword);
+            }
+            map.remove("collectionFormat");
+            map.insert("schema".to_string(), schema);
+        }
+    }
+    parameter
+}
+
+/// Build a schema from a Swagger 2.0 parameter's type keywords
+fn parameter_schema(parameter: &Value) -> Value {
+    let mut schema = Map::new();
+    for keyword in TYPE_KEYWORDS {
+        if let Some(value) = parameter.get(*keyword) {
+            schema.insert(keyword.to_string(), value.clone());
+        }
+    }
+    if let Some(description) = parameter.get("description") {
+        schema.insert("description".to_string(), description.clone());
+    }
+    // Swagger's `file` type has no OpenAPI 3 equivalent
+    if schema.get("type").and_then(|t| t.as_str()) == Some("file") {
+        schema.insert("type".to_string(), json!("string"));
+        schema.insert("format".to_string(), json!("binary"));
+    }
+    Value::Object(schema)
+}
+
+/// Move a response's `schema` into `content`
+fn normalize_response(mut response: Value, produces: &[String]) -> Value {
+    if let Value::Object(ref mut map) = response {
+        if let Some(schema) = map.remove("schema") {
+            let media_type = produces
+                .first()
+                .cloned()
+                .unwrap_or_else(|| "application/json".to_string());
+            map.insert(
+                "content".to_string(),
+                json!({ media_type: { "schema": schema } }),
+            );
+        }
+        map.remove("examples");
+        map.entry("description").or_insert_with(|| json!(""));
+    }
+    response
+}
+
+/// Point Swagger 2.0 `$ref`s at their OpenAPI 3 locations and convert
+/// string discriminators, recursively
+fn rewrite_refs(value: &mut Value) {
+    match value {
+        Value::Object(map) => {
+            if let Some(Value::String(ref_path)) = map.get_mut("$ref") {
+                for (from, to) in [
+                    ("#/definitions/", "#/components/schemas/"),
+                    ("#/parameters/", "#/components/parameters/"),
+                    ("#/responses/", "#/components/responses/"),
+                ] {
+                    if let Some(rest) = ref_path.strip_prefix(from) {
+                        *ref_path = format!("{}{}", to, rest);
+                        break;
+                    }
+                }
+            }
+            if let Some(Value::String(property_name)) = map.get("discriminator") {
+                let property_name = property_name.clone();
+                map.insert(
+                    "discriminator".to_string(),
+                    json!({ "propertyName": property_name }),
+                );
+            }
+            for child in map.values_mut() {
+                rewrite_refs(child);
+            }
+        },
+        Value::Array(items) => {
+            for item in items {
+                rewrite_refs(item);
+            }
+        },
+        _ => {},
+    }
+}
+
+/// Location (`in`) of a parameter
+fn location(parameter: &Value) -> &str {
+    parameter
+        .get("in")
+        .and_then(|v| v.as_str())
+        .unwrap_or_default()
+}
+
+/// Read a `consumes`/`produces` list
+fn media_types(value: Option<&Value>) -> Vec<String> {
+    value
+        .and_then(|v| v.as_array())
+        .map(|items| {
+            items
+                .iter()
+                .filter_map(|m| m.as_str().map(String::from))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_swagger2_body_and_refs() {
+        let doc = json!({
+            "swagger": "2.0",
+            "info": { "title": "Test", "version": "1.0" },
+            "host": "management.azure.com",
+            "schemes": ["https"],
+            "parameters": {
+                "ApiVersion": { "name": "api-version", "in": "query", "required": true, "type": "string" }
+            },
+            "paths": {
+                "/vaults/{vaultName}": {
+                    "parameters": [
+                        { "name": "vaultName", "in": "path", "required": true, "type": "string" }
+                    ],
+                    "put": {
+                        "operationId": "Vaults_CreateOrUpdate",
+                        "parameters": [
+                            { "$ref": "#/parameters/ApiVersion" },
+                            { "name": "body", "in": "body", "required": true, "schema": { "$ref": "#/definitions/Vault" } }
+                        ],
+                        "responses": {
+                            "200": { "description": "OK", "schema": { "$ref": "#/definitions/Vault" } }
+                        }
+                    }
+                }
+            },
+            "definitions": {
+                "Vault": { "type": "object", "discriminator": "kind", "properties": { "kind": { "type": "string" } } }
+            }
+        });
+
+        assert!(is_swagger2(&doc));
+        let normalized = normalize_swagger2(doc).unwrap();
+
+        assert_eq!(normalized["openapi"], "3.0.0");
+        assert!(normalized.get("swagger").is_none());
+        assert_eq!(
+            normalized["servers"][0]["url"],
+            "https://management.azure.com"
+        );
+        assert_eq!(
+            normalized["components"]["schemas"]["Vault"]["discriminator"]["propertyName"],
+            "kind"
+        );
+
+        let put = &normalized["paths"]["/vaults/{vaultName}"]["put"];
+        assert_eq!(
+            put["requestBody"]["content"]["application/json"]["schema"]["$ref"],
+            "#/components/schemas/Vault"
+        );
+        assert_eq!(put["requestBody"]["required"], true);
+        assert_eq!(
+            put["responses"]["200"]["content"]["application/json"]["schema"]["$ref"],
+            "#/components/schemas/Vault"
+        );
+
+        // Referenced and path-level parameters are inlined, with types moved into schemas
+        let parameters = put["parameters"].as_array().unwrap();
+        assert_eq!(parameters.len(), 2);
+        assert_eq!(parameters[0]["name"], "api-version");
+        assert_eq!(parameters[0]["schema"]["type"], "string");
+        assert!(parameters[0].get("type").is_none());
+        assert_eq!(parameters[1]["name"], "vaultName");
+    }
+
+    #[test]
+    fn test_normalize_swagger2_form_data() {
+        let doc = json!({
+            "swagger": "2.0",
+            "info": { "title": "Test", "version": "1.0" },
+            "paths": {
+                "/uploads": {
+                    "post": {
+                        "consumes": ["multipart/form-data"],
+                        "parameters": [
+                            { "name": "file", "in": "formData", "required": true, "type": "file" },
+                            { "name": "label", "in": "formData", "type": "string" }
+                        ],
+                        "responses": { "201": { "description": "Created" } }
+                    }
+                }
+            }
+        });
+
+        let normalized = normalize_swagger2(doc).unwrap();
+        let schema = &normalized["paths"]["/uploads"]["post"]["requestBody"]["content"]
+            ["multipart/form-data"]["schema"];
+        assert_eq!(schema["properties"]["file"]["format"], "binary");
+        assert_eq!(schema["properties"]["label"]["type"], "string");
+        assert_eq!(schema["required"], json!(["file"]));
+    }
+}
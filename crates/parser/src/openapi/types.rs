@@ -111,6 +111,10 @@ pub struct Operation {
     /// Tags (for grouping)
     #[serde(default)]
     pub tags: Vec<String>,
+
+    /// Extensions (x-ms-long-running-operation, x-ms-pageable, etc.)
+    #[serde(flatten)]
+    pub extensions: HashMap<String, serde_json::Value>,
 }
 
 /// Parameter definition
@@ -131,6 +135,10 @@ pub struct Parameter {
     #[serde(default)]
     pub required: bool,
 
+    /// Deprecated flag
+    #[serde(default)]
+    pub deprecated: bool,
+
     /// Schema
     #[serde(default)]
     pub schema: Option<Schema>,
@@ -185,7 +193,7 @@ pub enum SchemaOrRef {
 }
 
 /// Schema definition
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct Schema {
     /// Type: string, number, integer, boolean, array, object
     #[serde(rename = "type")]
@@ -227,11 +235,78 @@ pub struct Schema {
     #[serde(default)]
     pub ref_path: Option<String>,
 
-    /// Extensions (x-kubernetes-*, etc.)
+    /// Schemas that must all apply (inheritance/mixins)
+    #[serde(rename = "allOf")]
+    #[serde(default)]
+    pub all_of: Vec<SchemaOrRef>,
+
+    /// Schemas of which exactly one must apply
+    #[serde(rename = "oneOf")]
+    #[serde(default)]
+    pub one_of: Vec<SchemaOrRef>,
+
+    /// Schemas of which at least one must apply
+    #[serde(rename = "anyOf")]
+    #[serde(default)]
+    pub any_of: Vec<SchemaOrRef>,
+
+    /// Discriminator selecting between `oneOf`/`anyOf` variants
+    #[serde(default)]
+    pub discriminator: Option<Discriminator>,
+
+    /// Smallest allowed number
+    #[serde(default)]
+    pub minimum: Option<f64>,
+
+    /// Largest allowed number
+    #[serde(default)]
+    pub maximum: Option<f64>,
+
+    /// Minimum string length
+    #[serde(rename = "minLength")]
+    #[serde(default)]
+    pub min_length: Option<u64>,
+
+    /// Maximum string length
+    #[serde(rename = "maxLength")]
+    #[serde(default)]
+    pub max_length: Option<u64>,
+
+    /// Minimum number of array items
+    #[serde(rename = "minItems")]
+    #[serde(default)]
+    pub min_items: Option<u64>,
+
+    /// Maximum number of array items
+    #[serde(rename = "maxItems")]
+    #[serde(default)]
+    pub max_items: Option<u64>,
+
+    /// Regular expression string values have to match
+    #[serde(default)]
+    pub pattern: Option<String>,
+
+    /// Value the server assumes when the property is not set
+    #[serde(default)]
+    pub default: Option<serde_json::Value>,
+
+    /// Extensions (x-kubernetes-*, x-ms-*, etc.)
     #[serde(flatten)]
     pub extensions: HashMap<String, serde_json::Value>,
 }
 
+/// Discriminator for polymorphic schemas
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Discriminator {
+    /// Name of the property that selects the variant
+    #[serde(rename = "propertyName")]
+    pub property_name: String,
+
+    /// Property values mapped to variant schema references
+    #[serde(default)]
+    pub mapping: HashMap<String, String>,
+}
+
 /// Reusable components
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Components {
@@ -1,6 +1,10 @@
 //! Operation classification and CRUD mapping
 //!
-//! Maps AWS SDK operation names to CRUD operations.
+//! Maps AWS SDK operation names to CRUD operations with [`CrudRules`], the
+//! built-in ones unless others are given.
+
+use crate::crud_rules::{CrudRole, CrudRules};
+use hemmer_provider_generator_common::Provider;
 
 /// CRUD operation types
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -32,35 +36,36 @@ impl OperationClassifier {
     /// );
     /// ```
     pub fn classify(operation_name: &str) -> Option<CrudOperation> {
-        let lower = operation_name.to_lowercase();
-
-        // Create operations
-        if lower.starts_with("create") || lower.starts_with("put") {
-            // PutObject is create, but PutBucketAcl might be update
-            // For now, treat all put_* as potentially create
-            return Some(CrudOperation::Create);
-        }
-
-        // Read operations
-        if lower.starts_with("get")
-            || lower.starts_with("describe")
-            || lower.starts_with("head")
-            || lower.starts_with("list")
-        {
-            return Some(CrudOperation::Read);
-        }
-
-        // Update operations
-        if lower.starts_with("update") || lower.starts_with("modify") {
-            return Some(CrudOperation::Update);
-        }
+        Self::classify_with(&CrudRules::default(), &Provider::Aws, operation_name)
+    }
 
-        // Delete operations
-        if lower.starts_with("delete") || lower.starts_with("remove") {
-            return Some(CrudOperation::Delete);
+    /// Classify an operation name of `provider` with the given rules
+    ///
+    /// # Examples
+    /// ```
+    /// use hemmer_provider_generator_common::Provider;
+    /// use hemmer_provider_generator_parser::{CrudOperation, CrudRules, OperationClassifier};
+    ///
+    /// let rules = CrudRules::from_yaml("overrides:\n  PutObject: update\n").unwrap();
+    /// assert_eq!(
+    ///     OperationClassifier::classify_with(&rules, &Provider::Aws, "put_object"),
+    ///     Some(CrudOperation::Update)
+    /// );
+    /// ```
+    pub fn classify_with(
+        crud_rules: &CrudRules,
+        provider: &Provider,
+        operation_name: &str,
+    ) -> Option<CrudOperation> {
+        // The legacy AWS SDK parser has no list role; lists are reads
+        let classification = crud_rules.classify(provider, operation_name, None)?;
+        match classification.role {
+            CrudRole::Create => Some(CrudOperation::Create),
+            CrudRole::Read | CrudRole::List => Some(CrudOperation::Read),
+            CrudRole::Update => Some(CrudOperation::Update),
+            CrudRole::Delete => Some(CrudOperation::Delete),
+            CrudRole::Ignore => None,
         }
-
-        None
     }
 
     /// Extract resource name from operation name
@@ -0,0 +1,245 @@
+//! Google API resource annotations (AIP-123, AIP-151, AIP-203)
+//!
+//! Reads the `google.api.resource`, `google.api.resource_reference`,
+//! `google.api.field_behavior` and `google.longrunning.operation_info`
+//! extension options. The extensions are looked up in the descriptor pool, so
+//! they are only available when `google/api/resource.proto`,
+//! `google/api/field_behavior.proto` and `google/longrunning/operations.proto`
+//! were compiled in (as they are for any API that imports them).
+
+use prost_reflect::{
+    DescriptorPool, DynamicMessage, ExtensionDescriptor, FieldDescriptor, MessageDescriptor,
+    MethodDescriptor, Value,
+};
+use std::collections::HashMap;
+
+/// `google.api.FieldBehavior` values that affect the generated schema
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FieldBehavior {
+    Required,
+    OutputOnly,
+    InputOnly,
+    Immutable,
+    Identifier,
+}
+
+/// A message annotated with `google.api.resource`
+#[derive(Debug, Clone)]
+pub struct ResourceAnnotation {
+    /// Resource type, e.g. "storage.googleapis.com/Bucket"
+    pub resource_type: String,
+    /// Resource name patterns, e.g. "projects/{project}/buckets/{bucket}"
+    pub patterns: Vec<String>,
+    /// Field holding the resource name (defaults to `name`)
+    pub name_field: String,
+}
+
+/// `google.longrunning.operation_info` of a method returning an `Operation`
+#[derive(Debug, Clone, Default)]
+pub struct OperationInfo {
+    /// Message the operation resolves to (`google.protobuf.Empty` for deletes)
+    pub response: Option<MessageDescriptor>,
+    /// Fully-qualified name of the progress metadata message
+    pub metadata_type: Option<String>,
+}
+
+/// Full name of the message returned by long-running methods
+const LONG_RUNNING_OPERATION: &str = "google.longrunning.Operation";
+
+/// AIP annotation extensions found in a descriptor pool
+pub struct AipAnnotations {
+    pool: DescriptorPool,
+    resource: Option<ExtensionDescriptor>,
+    resource_reference: Option<ExtensionDescriptor>,
+    field_behavior: Option<ExtensionDescriptor>,
+    operation_info: Option<ExtensionDescriptor>,
+    /// Annotated messages by resource type
+    resources_by_type: HashMap<String, MessageDescriptor>,
+}
+
+impl AipAnnotations {
+    pub fn from_pool(pool: &DescriptorPool) -> Self {
+        let mut annotations = Self {
+            pool: pool.clone(),
+            resource: pool.get_extension_by_name("google.api.resource"),
+            resource_reference: pool.get_extension_by_name("google.api.resource_reference"),
+            field_behavior: pool.get_extension_by_name("google.api.field_behavior"),
+            operation_info: pool.get_extension_by_name("google.longrunning.operation_info"),
+            resources_by_type: HashMap::new(),
+        };
+
+        for message in pool.all_messages() {
+            if let Some(resource) = annotations.resource(&message) {
+                annotations
+                    .resources_by_type
+                    .insert(resource.resource_type, message);
+            }
+        }
+
+        annotations
+    }
+
+    /// `google.api.resource` annotation of a message
+    pub fn resource(&self, message: &MessageDescriptor) -> Option<ResourceAnnotation> {
+        let extension = self.resource.as_ref()?;
+        let options = message.options();
+        if !options.has_extension(extension) {
+            return None;
+        }
+
+        let value = options.get_extension(extension);
+        let resource = value.as_message()?;
+        let resource_type = string_field(resource, "type")?;
+
+        let patterns = match resource.get_field_by_name("pattern").as_deref() {
+            Some(Value::List(values)) => values
+                .iter()
+                .filter_map(|v| v.as_str().map(String::from))
+                .collect(),
+            _ => Vec::new(),
+        };
+        let name_field = string_field(resource, "name_field")
+            .filter(|n| !n.is_empty())
+            .unwrap_or_else(|| "name".to_string());
+
+        Some(ResourceAnnotation {
+            resource_type,
+            patterns,
+            name_field,
+        })
+    }
+
+    /// Annotated message a method operates on
+    ///
+    /// Standard methods (AIP-131 to AIP-135) return the resource, take it as a
+    /// request field, or take its name as a `google.api.resource_reference`.
+    pub fn method_resource(&self, method: &MethodDescriptor) -> Option<MessageDescriptor> {
+        let output = self.method_output(method);
+        if self.resource(&output).is_some() {
+            return Some(output);
+        }
+
+        let input = method.input();
+        for field in input.fields() {
+            if field.is_list() || field.is_map() {
+                continue;
+            }
+            if let Some(message) = field.kind().as_message() {
+                if self.resource(message).is_some() {
+                    return Some(message.clone());
+                }
+            }
+        }
+
+        // `string name = 1 [(google.api.resource_reference).type = "..."]`;
+        // `child_type` references (on `parent`) point at the parent instead
+        let referenced = input
+            .fields()
+            .filter_map(|field| self.resource_reference(&field))
+            .find_map(|resource_type| self.resources_by_type.get(&resource_type).cloned());
+        referenced
+    }
+
+    /// `google.longrunning.operation_info` of a long-running method
+    ///
+    /// Returns `None` for methods that don't return a `google.longrunning.Operation`.
+    /// Type names may be relative to the method's package (AIP-151).
+    pub fn operation_info(&self, method: &MethodDescriptor) -> Option<OperationInfo> {
+        if method.output().full_name() != LONG_RUNNING_OPERATION {
+            return None;
+        }
+
+        let Some(extension) = &self.operation_info else {
+            return Some(OperationInfo::default());
+        };
+        let options = method.options();
+        if !options.has_extension(extension) {
+            return Some(OperationInfo::default());
+        }
+
+        let value = options.get_extension(extension);
+        let Some(info) = value.as_message() else {
+            return Some(OperationInfo::default());
+        };
+        let package = method.parent_service().package_name().to_string();
+        let resolve = |name: String| {
+            self.pool.get_message_by_name(&name).or_else(|| {
+                self.pool
+                    .get_message_by_name(&format!("{}.{}", package, name))
+            })
+        };
+
+        let response = string_field(info, "response_type")
+            .filter(|t| !t.is_empty())
+            .and_then(&resolve);
+        let metadata_type = string_field(info, "metadata_type")
+            .filter(|t| !t.is_empty())
+            .map(|name| match resolve(name.clone()) {
+                Some(message) => message.full_name().to_string(),
+                None => name,
+            });
+
+        Some(OperationInfo {
+            response,
+            metadata_type,
+        })
+    }
+
+    /// Message a method produces: the declared response for long-running methods
+    pub fn method_output(&self, method: &MethodDescriptor) -> MessageDescriptor {
+        self.operation_info(method)
+            .and_then(|info| info.response)
+            .unwrap_or_else(|| method.output())
+    }
+
+    /// Resource type referenced by a field's `google.api.resource_reference.type`
+    pub fn resource_reference(&self, field: &FieldDescriptor) -> Option<String> {
+        let extension = self.resource_reference.as_ref()?;
+        let options = field.options();
+        if !options.has_extension(extension) {
+            return None;
+        }
+
+        let value = options.get_extension(extension);
+        string_field(value.as_message()?, "type").filter(|t| !t.is_empty())
+    }
+
+    /// `google.api.field_behavior` annotations of a field
+    pub fn field_behaviors(&self, field: &FieldDescriptor) -> Vec<FieldBehavior> {
+        let Some(extension) = &self.field_behavior else {
+            return Vec::new();
+        };
+        let options = field.options();
+        if !options.has_extension(extension) {
+            return Vec::new();
+        }
+
+        let Some(enum_type) = extension.kind().as_enum().cloned() else {
+            return Vec::new();
+        };
+        let value = options.get_extension(extension);
+        let Some(values) = value.as_list() else {
+            return Vec::new();
+        };
+
+        values
+            .iter()
+            .filter_map(|v| v.as_enum_number())
+            .filter_map(|number| enum_type.get_value(number))
+            .filter_map(|value| match value.name() {
+                "REQUIRED" => Some(FieldBehavior::Required),
+                "OUTPUT_ONLY" => Some(FieldBehavior::OutputOnly),
+                "INPUT_ONLY" => Some(FieldBehavior::InputOnly),
+                "IMMUTABLE" => Some(FieldBehavior::Immutable),
+                "IDENTIFIER" => Some(FieldBehavior::Identifier),
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+fn string_field(message: &DynamicMessage, name: &str) -> Option<String> {
+    message
+        .get_field_by_name(name)
+        .and_then(|v| v.as_str().map(String::from))
+}
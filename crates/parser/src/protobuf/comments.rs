@@ -0,0 +1,79 @@
+//! Source comments from `SourceCodeInfo`
+//!
+//! Descriptor sets keep comments only when built with `--include_source_info`;
+//! `.proto` sources compiled in-process always keep them. Comments are looked up
+//! by file and location path (see `SourceCodeInfo.Location.path`).
+
+use prost_reflect::{DescriptorPool, FileDescriptor};
+use std::collections::HashMap;
+
+/// Leading and trailing comments of the elements of a descriptor pool
+#[derive(Default)]
+pub struct SourceComments {
+    /// Description by file name and location path
+    by_location: HashMap<(String, Vec<i32>), String>,
+}
+
+impl SourceComments {
+    pub fn from_pool(pool: &DescriptorPool) -> Self {
+        let mut comments = Self::default();
+
+        for file in pool.files() {
+            let Some(info) = &file.file_descriptor_proto().source_code_info else {
+                continue;
+            };
+            for location in &info.location {
+                let text = [&location.leading_comments, &location.trailing_comments]
+                    .into_iter()
+                    .flatten()
+                    .map(String::as_str)
+                    .collect::<Vec<_>>()
+                    .join("\n");
+                if let Some(description) = to_description(&text) {
+                    comments.by_location.insert(
+                        (file.name().to_string(), location.path.clone()),
+                        description,
+                    );
+                }
+            }
+        }
+
+        comments
+    }
+
+    /// Description of the element at `path` in `file`
+    pub fn get(&self, file: &FileDescriptor, path: &[i32]) -> Option<String> {
+        self.by_location
+            .get(&(file.name().to_string(), path.to_vec()))
+            .cloned()
+    }
+}
+
+/// Join comment lines into a single-line description
+///
+/// Descriptions end up in quoted strings and markdown table cells, so line
+/// breaks and indentation are collapsed into single spaces.
+fn to_description(text: &str) -> Option<String> {
+    let description = text
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    (!description.is_empty()).then_some(description)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_description() {
+        assert_eq!(
+            to_description(" A bucket.\n  Holds objects.\n\n"),
+            Some("A bucket. Holds objects.".to_string())
+        );
+        assert_eq!(to_description(" \n"), None);
+    }
+}
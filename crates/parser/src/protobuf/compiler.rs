@@ -0,0 +1,737 @@
+//! In-process `.proto` compiler
+//!
+//! Compiles `.proto` sources into a [`DescriptorPool`] without `protoc`, using the
+//! pure-Rust parser of `protobuf-parse`: imports are searched in the import roots,
+//! `google/protobuf/*.proto` falls back to the bundled well-known types, and custom
+//! options (`option (google.api.http) = { get: "/v1/{name}" };`) are interpreted.
+//!
+//! That parser drops comments, so each source is scanned for the comments of its
+//! declarations, which are matched to the descriptors by scoped name and added
+//! back as `SourceCodeInfo` before the pool is built.
+
+use hemmer_provider_generator_common::{GeneratorError, Result};
+use prost_reflect::DescriptorPool;
+use protobuf::descriptor::{
+    source_code_info, DescriptorProto, EnumDescriptorProto, FileDescriptorProto, FileDescriptorSet,
+    SourceCodeInfo,
+};
+use protobuf::Message;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Compile `.proto` files and their imports into a descriptor pool
+///
+/// Returns the pool and the names (relative to their import root) of `files`.
+/// A file outside every import root is resolved against its own directory.
+pub fn compile(
+    files: &[PathBuf],
+    import_roots: &[PathBuf],
+) -> Result<(DescriptorPool, Vec<String>)> {
+    let mut roots = import_roots
+        .iter()
+        .map(|root| canonicalize(root))
+        .collect::<Result<Vec<_>>>()?;
+
+    let mut inputs = Vec::new();
+    let mut names = Vec::new();
+    for file in files {
+        let path = canonicalize(file)?;
+        let relative = roots
+            .iter()
+            .find_map(|root| path.strip_prefix(root).ok().map(Path::to_path_buf));
+        let relative = match relative {
+            Some(relative) => relative,
+            None => {
+                // Not under any import root: its directory becomes one
+                let parent = path.parent().unwrap_or_else(|| Path::new("/"));
+                roots.push(parent.to_path_buf());
+                PathBuf::from(path.file_name().unwrap_or_default())
+            },
+        };
+
+        names.push(
+            relative
+                .components()
+                .map(|c| c.as_os_str().to_string_lossy())
+                .collect::<Vec<_>>()
+                .join("/"),
+        );
+        inputs.push(path);
+    }
+
+    let parsed = protobuf_parse::Parser::new()
+        .pure()
+        .includes(&roots)
+        .inputs(&inputs)
+        .parse_and_typecheck()
+        .map_err(|e| GeneratorError::Parse(format!("Failed to compile .proto files: {:#}", e)))?;
+
+    let mut set = FileDescriptorSet::new();
+    for mut file in parsed.file_descriptors {
+        // Bundled well-known types have no source in the import roots
+        let source = roots
+            .iter()
+            .map(|root| root.join(file.name()))
+            .find(|path| path.is_file())
+            .and_then(|path| fs::read_to_string(path).ok());
+        if let Some(source) = source {
+            add_source_comments(&mut file, &source);
+        }
+        set.file.push(file);
+    }
+
+    // Decoding the encoded set keeps the custom options, which are unknown fields
+    // of the option messages
+    let bytes = set
+        .write_to_bytes()
+        .map_err(|e| GeneratorError::Parse(format!("Failed to encode descriptors: {}", e)))?;
+    let pool = DescriptorPool::decode(bytes.as_slice())
+        .map_err(|e| GeneratorError::Parse(format!("Failed to compile .proto files: {}", e)))?;
+
+    Ok((pool, names))
+}
+
+fn canonicalize(path: &Path) -> Result<PathBuf> {
+    path.canonicalize()
+        .map_err(|e| GeneratorError::Parse(format!("Failed to resolve {}: {}", path.display(), e)))
+}
+
+// --- Source comments ---
+
+/// Comments of a declaration
+#[derive(Debug, Default, PartialEq)]
+struct Comments {
+    leading: Option<String>,
+    trailing: Option<String>,
+}
+
+/// Add the comments of the declarations in `source` to `file` as `SourceCodeInfo`
+fn add_source_comments(file: &mut FileDescriptorProto, source: &str) {
+    let comments = scan_comments(source);
+    let mut locations = Vec::new();
+
+    for (i, message) in file.message_type.iter().enumerate() {
+        message_locations(
+            message,
+            vec![4, i as i32],
+            message.name(),
+            &comments,
+            &mut locations,
+        );
+    }
+    for (i, enum_type) in file.enum_type.iter().enumerate() {
+        enum_locations(
+            enum_type,
+            vec![5, i as i32],
+            enum_type.name(),
+            &comments,
+            &mut locations,
+        );
+    }
+    for (i, service) in file.service.iter().enumerate() {
+        let path = vec![6, i as i32];
+        add_location(&mut locations, &comments, path.clone(), service.name());
+        for (j, method) in service.method.iter().enumerate() {
+            add_location(
+                &mut locations,
+                &comments,
+                child_path(&path, 2, j),
+                &format!("{}.{}", service.name(), method.name()),
+            );
+        }
+    }
+
+    if !locations.is_empty() {
+        file.source_code_info = Some(SourceCodeInfo {
+            location: locations,
+            ..Default::default()
+        })
+        .into();
+    }
+}
+
+fn message_locations(
+    message: &DescriptorProto,
+    path: Vec<i32>,
+    scope: &str,
+    comments: &HashMap<String, Comments>,
+    locations: &mut Vec<source_code_info::Location>,
+) {
+    add_location(locations, comments, path.clone(), scope);
+    for (i, field) in message.field.iter().enumerate() {
+        let name = format!("{}.{}", scope, field.name());
+        add_location(locations, comments, child_path(&path, 2, i), &name);
+    }
+    for (i, nested) in message.nested_type.iter().enumerate() {
+        let name = format!("{}.{}", scope, nested.name());
+        message_locations(nested, child_path(&path, 3, i), &name, comments, locations);
+    }
+    for (i, enum_type) in message.enum_type.iter().enumerate() {
+        let name = format!("{}.{}", scope, enum_type.name());
+        enum_locations(
+            enum_type,
+            child_path(&path, 4, i),
+            &name,
+            comments,
+            locations,
+        );
+    }
+    for (i, oneof) in message.oneof_decl.iter().enumerate() {
+        let name = format!("{}.{}", scope, oneof.name());
+        add_location(locations, comments, child_path(&path, 8, i), &name);
+    }
+}
+
+fn enum_locations(
+    enum_type: &EnumDescriptorProto,
+    path: Vec<i32>,
+    scope: &str,
+    comments: &HashMap<String, Comments>,
+    locations: &mut Vec<source_code_info::Location>,
+) {
+    add_location(locations, comments, path.clone(), scope);
+    for (i, value) in enum_type.value.iter().enumerate() {
+        let name = format!("{}.{}", scope, value.name());
+        add_location(locations, comments, child_path(&path, 2, i), &name);
+    }
+}
+
+fn add_location(
+    locations: &mut Vec<source_code_info::Location>,
+    comments: &HashMap<String, Comments>,
+    path: Vec<i32>,
+    name: &str,
+) {
+    if let Some(comments) = comments.get(name) {
+        locations.push(source_code_info::Location {
+            path,
+            leading_comments: comments.leading.clone(),
+            trailing_comments: comments.trailing.clone(),
+            ..Default::default()
+        });
+    }
+}
+
+fn child_path(path: &[i32], field_number: i32, index: usize) -> Vec<i32> {
+    let mut path = path.to_vec();
+    path.push(field_number);
+    path.push(index as i32);
+    path
+}
+
+/// Block the scanner is in
+struct Scope {
+    kind: ScopeKind,
+    /// Scoped name of the definition (of the enclosing message for oneofs)
+    name: String,
+    /// Statement interrupted by an option value, resumed when the block closes
+    statement: Vec<usize>,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum ScopeKind {
+    /// Message or oneof: statements are fields
+    Fields,
+    Enum,
+    Service,
+    /// Option values, `extend` and method bodies
+    Other,
+}
+
+/// Comments of the declarations in a `.proto` source, by scoped name
+/// (`Message`, `Message.field`, `Enum.VALUE`, `Service.Method`)
+///
+/// The source is known to compile, so the scan only follows blocks and
+/// statements instead of parsing the grammar.
+fn scan_comments(source: &str) -> HashMap<String, Comments> {
+    let tokens = tokenize(source);
+    let mut comments = HashMap::new();
+    let mut record = |name: String, first: usize, last: usize| {
+        let entry = Comments {
+            leading: tokens[first].leading_comment.clone(),
+            trailing: tokens[last].trailing_comment.clone(),
+        };
+        if entry != Comments::default() {
+            comments.insert(name, entry);
+        }
+    };
+
+    let mut scopes: Vec<Scope> = Vec::new();
+    let mut statement: Vec<usize> = Vec::new();
+
+    for (i, token) in tokens.iter().enumerate() {
+        let (kind, prefix) = scopes
+            .last()
+            .map_or((ScopeKind::Other, ""), |s| (s.kind, s.name.as_str()));
+        let scoped = |name: &str| {
+            if prefix.is_empty() {
+                name.to_string()
+            } else {
+                format!("{}.{}", prefix, name)
+            }
+        };
+        let word = |index: usize| {
+            statement
+                .get(index)
+                .and_then(|&t| tokens[t].ident())
+                .unwrap_or_default()
+        };
+
+        match token.text.as_str() {
+            "{" => {
+                let keyword = word(0);
+                let scope = match keyword {
+                    "message" | "enum" | "service" | "oneof" if statement.len() == 2 => {
+                        let name = scoped(word(1));
+                        record(name.clone(), statement[0], i);
+                        let (kind, name) = match keyword {
+                            "message" => (ScopeKind::Fields, name),
+                            "enum" => (ScopeKind::Enum, name),
+                            "service" => (ScopeKind::Service, name),
+                            _ => (ScopeKind::Fields, prefix.to_string()),
+                        };
+                        statement.clear();
+                        Scope {
+                            kind,
+                            name,
+                            statement: vec![],
+                        }
+                    },
+                    "rpc" | "extend" => {
+                        if keyword == "rpc" && kind == ScopeKind::Service {
+                            record(scoped(word(1)), statement[0], i);
+                        }
+                        statement.clear();
+                        Scope {
+                            kind: ScopeKind::Other,
+                            name: String::new(),
+                            statement: vec![],
+                        }
+                    },
+                    _ => Scope {
+                        kind: ScopeKind::Other,
+                        name: String::new(),
+                        statement: std::mem::take(&mut statement),
+                    },
+                };
+                scopes.push(scope);
+            },
+            "}" => {
+                statement = scopes.pop().map(|s| s.statement).unwrap_or_default();
+            },
+            ";" => {
+                let first = statement.first().copied();
+                let keyword = word(0);
+                let skipped = matches!(keyword, "option" | "reserved" | "extensions");
+                match (kind, first) {
+                    (ScopeKind::Fields | ScopeKind::Enum, Some(first)) if !skipped => {
+                        // The name is the identifier before the first `=`
+                        let name = statement
+                            .iter()
+                            .position(|&t| tokens[t].text == "=")
+                            .and_then(|p| p.checked_sub(1))
+                            .and_then(|p| tokens[statement[p]].ident());
+                        if let Some(name) = name {
+                            record(scoped(name), first, i);
+                        }
+                    },
+                    (ScopeKind::Service, Some(first)) if keyword == "rpc" => {
+                        record(scoped(word(1)), first, i);
+                    },
+                    _ => {},
+                }
+                statement.clear();
+            },
+            _ => statement.push(i),
+        }
+    }
+
+    comments
+}
+
+#[derive(Debug)]
+struct Token {
+    text: String,
+    line: usize,
+    /// Comment block directly above the token
+    leading_comment: Option<String>,
+    /// Comment after the token on the same line
+    trailing_comment: Option<String>,
+}
+
+impl Token {
+    fn ident(&self) -> Option<&str> {
+        self.text
+            .starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+            .then_some(self.text.as_str())
+    }
+}
+
+/// Split a source into identifiers, numbers, strings and symbols, attaching comments
+fn tokenize(source: &str) -> Vec<Token> {
+    let bytes = source.as_bytes();
+    let mut tokens = Vec::new();
+    let mut pos = 0;
+    let mut line = 1;
+    // Comment block not attached to a token yet, and the line it ends on
+    let mut pending_comment: Option<(String, usize)> = None;
+
+    while pos < bytes.len() {
+        let c = bytes[pos];
+        let start = pos;
+
+        match c {
+            b'\n' => {
+                pos += 1;
+                line += 1;
+                continue;
+            },
+            _ if c.is_ascii_whitespace() => {
+                pos += 1;
+                continue;
+            },
+            b'/' if bytes.get(pos + 1) == Some(&b'/') => {
+                while pos < bytes.len() && bytes[pos] != b'\n' {
+                    pos += 1;
+                }
+                let text = format!("{}\n", &source[start + 2..pos]);
+                attach_comment(&mut tokens, &mut pending_comment, text, line, line);
+                continue;
+            },
+            b'/' if bytes.get(pos + 1) == Some(&b'*') => {
+                let start_line = line;
+                pos += 2;
+                while pos < bytes.len() && !bytes[pos..].starts_with(b"*/") {
+                    line += usize::from(bytes[pos] == b'\n');
+                    pos += 1;
+                }
+                let text = clean_block_comment(&source[start + 2..pos.min(bytes.len())]);
+                attach_comment(&mut tokens, &mut pending_comment, text, start_line, line);
+                pos += 2;
+                continue;
+            },
+            b'"' | b'\'' => {
+                pos += 1;
+                while pos < bytes.len() && bytes[pos] != c {
+                    pos += if bytes[pos] == b'\\' { 2 } else { 1 };
+                }
+                pos += 1;
+            },
+            _ if c.is_ascii_alphanumeric() || c == b'_' => {
+                while pos < bytes.len()
+                    && (bytes[pos].is_ascii_alphanumeric() || bytes[pos] == b'_')
+                {
+                    pos += 1;
+                }
+            },
+            _ => {
+                pos += source[pos..].chars().next().map_or(1, char::len_utf8);
+            },
+        }
+
+        // Comments separated from the token by a blank line are detached
+        let leading_comment = pending_comment
+            .take()
+            .filter(|(_, end_line)| end_line + 1 >= line)
+            .map(|(text, _)| text);
+        tokens.push(Token {
+            text: source[start..pos.min(bytes.len())].to_string(),
+            line,
+            leading_comment,
+            trailing_comment: None,
+        });
+    }
+
+    tokens
+}
+
+/// Attach a comment to the previous token when it is on the same line, or add
+/// it to the pending comment block (a blank line starts a new block)
+fn attach_comment(
+    tokens: &mut [Token],
+    pending: &mut Option<(String, usize)>,
+    text: String,
+    start_line: usize,
+    end_line: usize,
+) {
+    if pending.is_none() {
+        if let Some(last) = tokens.last_mut() {
+            if last.line == start_line && last.trailing_comment.is_none() {
+                last.trailing_comment = Some(text);
+                return;
+            }
+        }
+    }
+
+    match pending {
+        Some((block, block_end)) if *block_end + 1 >= start_line => {
+            block.push_str(&text);
+            *block_end = end_line;
+        },
+        _ => *pending = Some((text, end_line)),
+    }
+}
+
+/// Text of a `/* */` comment, without the ` * ` prefix of continuation lines
+fn clean_block_comment(text: &str) -> String {
+    text.lines()
+        .map(|line| {
+            let trimmed = line.trim_start();
+            trimmed.strip_prefix('*').unwrap_or(line)
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    /// Write `files` to a temporary import root and compile the first one
+    fn compile_sources(files: &[(&str, &str)]) -> Result<DescriptorPool> {
+        let dir = TempDir::new().unwrap();
+        for (name, source) in files {
+            let path = dir.path().join(name);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, source).unwrap();
+        }
+
+        compile(&[dir.path().join(files[0].0)], &[dir.path().to_path_buf()]).map(|(pool, _)| pool)
+    }
+
+    #[test]
+    fn test_source_code_info_comments() {
+        let pool = compile_sources(&[(
+            "widgets.proto",
+            r#"
+            syntax = "proto3";
+            import "google/protobuf/descriptor.proto";
+
+            extend google.protobuf.FieldOptions {
+              string label = 50000;
+            }
+
+            // Detached
+
+            // A widget
+            message Widget {
+              string name = 1 [(label) = "x;y"]; // The name
+              oneof source {
+                // Where it came from
+                string url = 2;
+              }
+              enum Kind {
+                KIND_UNSPECIFIED = 0; // Not set
+              }
+            }
+
+            service Widgets {
+              /* Get a widget */
+              rpc GetWidget(Widget) returns (Widget) {
+                option deprecated = true;
+              }
+            }
+            "#,
+        )])
+        .unwrap();
+
+        let file = pool.get_file_by_name("widgets.proto").unwrap();
+        let locations = &file
+            .file_descriptor_proto()
+            .source_code_info
+            .as_ref()
+            .unwrap()
+            .location;
+        let comments = locations
+            .iter()
+            .map(|l| {
+                (
+                    l.path.clone(),
+                    l.leading_comments.as_deref(),
+                    l.trailing_comments.as_deref(),
+                )
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            comments,
+            vec![
+                (vec![4, 0], Some(" A widget\n"), None),
+                (vec![4, 0, 2, 0], None, Some(" The name\n")),
+                (vec![4, 0, 2, 1], Some(" Where it came from\n"), None),
+                (vec![4, 0, 4, 0, 2, 0], None, Some(" Not set\n")),
+                (vec![6, 0, 2, 0], Some(" Get a widget "), None),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_custom_options_are_kept() {
+        let pool = compile_sources(&[(
+            "annotated.proto",
+            r#"
+            syntax = "proto3";
+            package example;
+            import "google/protobuf/descriptor.proto";
+
+            message Rule {
+              string get = 1;
+              repeated Rule additional = 2;
+            }
+            extend google.protobuf.MethodOptions {
+              Rule rule = 50000;
+            }
+            extend google.protobuf.FieldOptions {
+              Rule field_rule = 50001;
+            }
+
+            message Empty {
+              string name = 1 [(field_rule).get = "/v1/name"];
+            }
+            service Things {
+              rpc GetThing(Empty) returns (Empty) {
+                option (rule) = {
+                  get: "/v1/{name=things/*}"
+                  additional { get: "/v1/other" }
+                };
+              }
+            }
+            "#,
+        )])
+        .unwrap();
+
+        let rule_get = |options: prost_reflect::DynamicMessage, extension: &str| {
+            let extension = pool.get_extension_by_name(extension).unwrap();
+            options
+                .get_extension(&extension)
+                .as_message()
+                .unwrap()
+                .get_field_by_name("get")
+                .unwrap()
+                .as_str()
+                .map(str::to_string)
+        };
+
+        let method = pool
+            .get_service_by_name("example.Things")
+            .unwrap()
+            .methods()
+            .next()
+            .unwrap();
+        assert_eq!(
+            rule_get(method.options(), "example.rule").as_deref(),
+            Some("/v1/{name=things/*}")
+        );
+
+        // Options can be set on a sub-field of an extension
+        let field = pool
+            .get_message_by_name("example.Empty")
+            .unwrap()
+            .get_field_by_name("name")
+            .unwrap();
+        assert_eq!(
+            rule_get(field.options(), "example.field_rule").as_deref(),
+            Some("/v1/name")
+        );
+    }
+
+    #[test]
+    fn test_compile_imports_across_files() {
+        let dir = TempDir::new().unwrap();
+        let files = [
+            (
+                "shop/v1/shop.proto",
+                r#"
+                syntax = "proto3";
+                package example.shop.v1;
+                import "shared.proto";
+                import "google/protobuf/timestamp.proto";
+
+                message Order {
+                  example.common.v1.Money total = 1;
+                  common.v1.Color color = 2;
+                  .google.protobuf.Timestamp created = 3;
+                }
+                "#,
+            ),
+            (
+                "shared.proto",
+                r#"
+                syntax = "proto3";
+                import public "common/v1/types.proto";
+                "#,
+            ),
+            (
+                "common/v1/types.proto",
+                r#"
+                syntax = "proto3";
+                package example.common.v1;
+
+                message Money {
+                  string currency = 1;
+                }
+                enum Color {
+                  COLOR_UNSPECIFIED = 0;
+                }
+                "#,
+            ),
+        ];
+        for (name, source) in files {
+            let path = dir.path().join(name);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, source).unwrap();
+        }
+
+        let (pool, names) = compile(
+            &[dir.path().join("shop/v1/shop.proto")],
+            &[dir.path().to_path_buf()],
+        )
+        .unwrap();
+        assert_eq!(names, vec!["shop/v1/shop.proto"]);
+
+        // Names are resolved from the package outwards, fully qualified ones as written
+        let order = pool.get_message_by_name("example.shop.v1.Order").unwrap();
+        let kind = |name: &str| order.get_field_by_name(name).unwrap().kind();
+        assert_eq!(
+            kind("total").as_message().unwrap().full_name(),
+            "example.common.v1.Money"
+        );
+        assert_eq!(
+            kind("color").as_enum().unwrap().full_name(),
+            "example.common.v1.Color"
+        );
+        assert_eq!(
+            kind("created").as_message().unwrap().full_name(),
+            "google.protobuf.Timestamp"
+        );
+
+        // A file outside the import roots is resolved against its own directory
+        let (_, names) = compile(&[dir.path().join("common/v1/types.proto")], &[]).unwrap();
+        assert_eq!(names, vec!["types.proto"]);
+    }
+
+    #[test]
+    fn test_compile_errors() {
+        let error = compile_sources(&[(
+            "a.proto",
+            "syntax = \"proto3\";\nimport \"missing.proto\";\n",
+        )])
+        .unwrap_err()
+        .to_string();
+        assert!(
+            error.contains("Failed to compile .proto files") && error.contains("missing.proto"),
+            "{}",
+            error
+        );
+
+        let error = compile_sources(&[(
+            "a.proto",
+            "syntax = \"proto3\";\nmessage A {\n  Missing thing = 1;\n}\n",
+        )])
+        .unwrap_err()
+        .to_string();
+        assert!(error.contains("Missing"), "{}", error);
+    }
+}
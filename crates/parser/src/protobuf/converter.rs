@@ -1,71 +1,133 @@
 //! Converts protobuf descriptors to ServiceDefinition IR
 
+use super::annotations::{AipAnnotations, FieldBehavior};
+use super::comments::SourceComments;
+use crate::crud_rules::{CrudOperations, CrudRole, CrudRules};
+use crate::naming::to_snake_case;
 use hemmer_provider_generator_common::{
-    FieldDefinition, FieldType, OperationMapping, Operations, Provider, ResourceDefinition, Result,
-    ServiceDefinition,
+    BlockDefinition, DataSourceDefinition, Deprecation, FieldDefinition, FieldType,
+    LongRunningConfig, NestingMode, OperationMapping, Operations, PaginationConfig, Provider,
+    ResourceDefinition, Result, ServiceDefinition, Stability,
+};
+use prost_reflect::{
+    Cardinality, DescriptorPool, FieldDescriptor, Kind, MessageDescriptor, MethodDescriptor,
+    OneofDescriptor, ServiceDescriptor,
 };
-use prost_reflect::{DescriptorPool, Kind, MethodDescriptor, ServiceDescriptor};
 use std::collections::HashMap;
 
 /// Convert protobuf DescriptorPool to ServiceDefinition
+///
+/// Only services declared in `source_files` are converted (all when empty), so
+/// services of imported files don't leak into the provider.
 pub fn convert_protobuf_to_service_definition(
     pool: &DescriptorPool,
+    source_files: &[String],
     service_name: &str,
     api_version: &str,
+    provider: &Provider,
+    crud_rules: &CrudRules,
 ) -> Result<ServiceDefinition> {
+    let ctx = Context {
+        annotations: AipAnnotations::from_pool(pool),
+        comments: SourceComments::from_pool(pool),
+        provider,
+        crud_rules,
+    };
     let mut resources = Vec::new();
-
-    // Iterate through all services in the pool
-    for service in pool.services() {
-        // Extract resources from each service
-        let service_resources = extract_resources_from_service(pool, &service)?;
+    let mut data_sources = Vec::new();
+
+    // Iterate through the services of the source files
+    for service in pool.services().filter(|service| {
+        source_files.is_empty()
+            || source_files
+                .iter()
+                .any(|f| f == service.parent_file().name())
+    }) {
+        // Extract resources and data sources from each service
+        let (service_resources, service_data_sources) =
+            extract_resources_from_service(&ctx, &service)?;
         resources.extend(service_resources);
+        data_sources.extend(service_data_sources);
     }
 
     Ok(ServiceDefinition {
-        provider: Provider::Gcp, // Most gRPC APIs are GCP, but could be configurable
+        provider: provider.clone(),
         name: service_name.to_string(),
         sdk_version: api_version.to_string(),
         resources,
-        data_sources: vec![], // Will implement data source detection later
+        data_sources,
     })
 }
 
-/// Extract resources from a gRPC service
+/// Pool-wide lookups used during conversion
+struct Context<'a> {
+    annotations: AipAnnotations,
+    comments: SourceComments,
+    provider: &'a Provider,
+    crud_rules: &'a CrudRules,
+}
+
+/// Methods operating on one resource
+#[derive(Default)]
+struct ResourceMethods {
+    /// Message annotated with `google.api.resource`, if any
+    message: Option<MessageDescriptor>,
+    methods: Vec<MethodDescriptor>,
+}
+
+/// Extract resources, and data sources from their `List*` methods, from a gRPC service
 fn extract_resources_from_service(
-    pool: &DescriptorPool,
+    ctx: &Context,
     service: &ServiceDescriptor,
-) -> Result<Vec<ResourceDefinition>> {
+) -> Result<(Vec<ResourceDefinition>, Vec<DataSourceDefinition>)> {
     // Group methods by resource
-    let resource_map = group_methods_by_resource(service);
+    let mut resource_map = group_methods_by_resource(ctx, service);
+
+    // With `google.api.resource` annotations, only annotated messages are resources
+    // (this drops methods like GetIamPolicy that match by name only)
+    if resource_map.values().any(|group| group.message.is_some()) {
+        resource_map.retain(|_, group| group.message.is_some());
+    }
 
     let mut resources = Vec::new();
+    let mut data_sources = Vec::new();
 
-    for (resource_name, methods) in resource_map {
-        if let Some(resource_def) = build_resource_from_methods(pool, &resource_name, methods)? {
+    for (resource_name, group) in resource_map {
+        if let Some(data_source) = build_data_source_from_methods(ctx, &resource_name, &group) {
+            data_sources.push(data_source);
+        }
+        if let Some(resource_def) = build_resource_from_methods(ctx, &resource_name, group)? {
             resources.push(resource_def);
         }
     }
 
-    Ok(resources)
+    Ok((resources, data_sources))
 }
 
 /// Group gRPC methods by resource name
 ///
+/// Methods are matched to `google.api.resource` messages first, then by name.
+///
 /// Examples:
 /// - CreateBucket, GetBucket, DeleteBucket -> "Bucket"
 /// - CreateInstance, UpdateInstance -> "Instance"
 fn group_methods_by_resource(
+    ctx: &Context,
     service: &ServiceDescriptor,
-) -> HashMap<String, Vec<MethodDescriptor>> {
-    let mut resource_map: HashMap<String, Vec<MethodDescriptor>> = HashMap::new();
+) -> HashMap<String, ResourceMethods> {
+    let mut resource_map: HashMap<String, ResourceMethods> = HashMap::new();
 
     for method in service.methods() {
-        if let Some(resource_name) = extract_resource_from_method_name(method.name()) {
+        if let Some(message) = ctx.annotations.method_resource(&method) {
+            let group = resource_map.entry(message.name().to_string()).or_default();
+            group.message = Some(message);
+            group.methods.push(method);
+        } else if let Some(resource_name) = extract_resource_from_method_name(method.name()) {
             resource_map
                 .entry(resource_name)
                 .or_default()
-                .push(method.clone());
+                .methods
+                .push(method);
         }
     }
 
@@ -101,187 +163,607 @@ fn extract_resource_from_method_name(method_name: &str) -> Option<String> {
 
 /// Build ResourceDefinition from gRPC methods
 fn build_resource_from_methods(
-    pool: &DescriptorPool,
+    ctx: &Context,
     resource_name: &str,
-    methods: Vec<MethodDescriptor>,
+    group: ResourceMethods,
 ) -> Result<Option<ResourceDefinition>> {
     // Classify methods into CRUD operations
-    let mut create_method = None;
-    let mut read_method = None;
-    let mut update_method = None;
-    let mut delete_method = None;
-
-    for method in &methods {
-        let name = method.name();
-        if name.starts_with("Create") || name.starts_with("Insert") {
-            create_method = Some(method);
-        } else if name.starts_with("Get") || name.starts_with("Describe") {
-            read_method = Some(method);
-        } else if name.starts_with("Update") || name.starts_with("Patch") || name.starts_with("Put")
-        {
-            update_method = Some(method);
-        } else if name.starts_with("Delete") {
-            delete_method = Some(method);
+    let mut crud = CrudOperations::default();
+    for method in &group.methods {
+        if let Some(classification) = ctx.crud_rules.classify(ctx.provider, method.name(), None) {
+            crud.insert(classification, method);
         }
     }
 
     // Need at least one CRUD operation
-    if create_method.is_none()
-        && read_method.is_none()
-        && update_method.is_none()
-        && delete_method.is_none()
-    {
+    if crud.is_empty() {
         return Ok(None);
     }
-
-    // Extract fields from create/update request (input fields, no response accessors)
-    let fields = if let Some(method) = create_method {
-        extract_fields_from_message(pool, method.input(), false)?
-    } else if let Some(method) = update_method {
-        extract_fields_from_message(pool, method.input(), false)?
+    let CrudOperations {
+        create: create_method,
+        read: read_method,
+        update: update_method,
+        delete: delete_method,
+        ..
+    } = crud;
+
+    let (fields, blocks, outputs, id_field) = if let Some(message) = &group.message {
+        extract_annotated_resource_schema(ctx, message, create_method)
     } else {
-        Vec::new()
+        // Extract fields from create/update request (input fields, no response accessors)
+        let (fields, blocks) = if let Some(method) = create_method {
+            extract_input_schema(ctx, &method.input(), &mut Vec::new())
+        } else if let Some(method) = update_method {
+            extract_input_schema(ctx, &method.input(), &mut Vec::new())
+        } else {
+            (Vec::new(), Vec::new())
+        };
+
+        // Extract outputs from the read (or create) response (output fields, have
+        // response accessors); long-running methods resolve to their declared response
+        let outputs = if let Some(method) = read_method.or(create_method) {
+            ctx.annotations
+                .method_output(method)
+                .fields()
+                .map(|field| {
+                    let behaviors = ctx.annotations.field_behaviors(&field);
+                    build_field(ctx, &field, &behaviors, true)
+                })
+                .collect()
+        } else {
+            Vec::new()
+        };
+
+        (fields, blocks, outputs, None)
+    };
+
+    // The resource message's comment, or the comment of its first method
+    let description = group
+        .message
+        .clone()
+        .or_else(|| read_method.map(|m| m.output()))
+        .and_then(|message| ctx.comments.get(&message.parent_file(), message.path()))
+        .or_else(|| {
+            [create_method, read_method, update_method, delete_method]
+                .into_iter()
+                .flatten()
+                .find_map(|m| ctx.comments.get(&m.parent_file(), m.path()))
+        });
+
+    let operations = Operations {
+        create: create_method.map(|m| build_operation_mapping(ctx, m)),
+        read: read_method.map(|m| build_operation_mapping(ctx, m)),
+        update: update_method.map(|m| build_operation_mapping(ctx, m)),
+        delete: delete_method.map(|m| build_operation_mapping(ctx, m)),
+        import: None, // Will implement later
     };
 
-    // Extract outputs from read response (output fields, have response accessors)
-    let outputs = if let Some(method) = read_method {
-        extract_fields_from_message(pool, method.output(), true)?
+    // A deprecated resource message, or a deprecated create method since new
+    // resources can't be created through it
+    let message_deprecated = group.message.as_ref().is_some_and(|message| {
+        message
+            .descriptor_proto()
+            .options
+            .as_ref()
+            .is_some_and(|o| o.deprecated())
+    });
+    let deprecated = if message_deprecated {
+        Some(Deprecation::default())
     } else {
-        Vec::new()
+        operations
+            .create
+            .as_ref()
+            .and_then(|op| op.deprecated.clone())
     };
 
-    Ok(Some(ResourceDefinition {
+    let mut resource = ResourceDefinition {
         name: to_snake_case(resource_name),
-        description: None, // Could extract from proto comments in future
+        description,
         fields,
         outputs,
-        // Nested blocks will be detected in future parser enhancements
-        blocks: vec![],
-        id_field: None, // Will implement ID detection later
-        operations: Operations {
-            create: create_method.map(|m| OperationMapping {
-                sdk_operation: to_snake_case(m.name()),
-                additional_operations: vec![],
-            }),
-            read: read_method.map(|m| OperationMapping {
-                sdk_operation: to_snake_case(m.name()),
-                additional_operations: vec![],
-            }),
-            update: update_method.map(|m| OperationMapping {
-                sdk_operation: to_snake_case(m.name()),
-                additional_operations: vec![],
-            }),
-            delete: delete_method.map(|m| OperationMapping {
-                sdk_operation: to_snake_case(m.name()),
-                additional_operations: vec![],
-            }),
-            import: None, // Will implement later
+        blocks,
+        id_field,
+        operations,
+        deprecated,
+        // e.g., google.cloud.functions.v2beta
+        stability: group
+            .methods
+            .first()
+            .map(|m| Stability::from_version(m.parent_file().package_name()))
+            .unwrap_or_default(),
+    };
+    resource.infer_computed_fields();
+
+    Ok(Some(resource))
+}
+
+/// Build a DataSourceDefinition from a resource's `List*` method (AIP-132)
+///
+/// Request fields other than `page_token` and `page_size` (e.g. `parent`,
+/// `filter`) become arguments. The data source returns the first entry of the
+/// response's repeated resource field, whose fields are the attributes.
+fn build_data_source_from_methods(
+    ctx: &Context,
+    resource_name: &str,
+    group: &ResourceMethods,
+) -> Option<DataSourceDefinition> {
+    let list = group.methods.iter().find(|m| {
+        ctx.crud_rules
+            .classify(ctx.provider, m.name(), None)
+            .is_some_and(|c| c.role == CrudRole::List)
+    })?;
+    let output = ctx.annotations.method_output(list);
+
+    // The repeated field of the resource message, or else the first repeated message
+    let repeated_messages: Vec<_> = output
+        .fields()
+        .filter(|field| field.is_list())
+        .filter_map(|field| Some((field.clone(), field.kind().as_message()?.clone())))
+        .collect();
+    let (items_field, item) = repeated_messages
+        .iter()
+        .find(|(_, message)| group.message.as_ref() == Some(message))
+        .or_else(|| repeated_messages.first())
+        .cloned()?;
+
+    let items_accessor = to_snake_case(items_field.name());
+    let input = list.input();
+    let pagination = input
+        .get_field_by_name("page_token")
+        .map(|_| PaginationConfig {
+            input_token: Some("page_token".to_string()),
+            output_token: Some("next_page_token".to_string()),
+            items: Some(items_accessor.clone()),
+            page_size: input
+                .get_field_by_name("page_size")
+                .map(|_| "page_size".to_string()),
+        });
+
+    // Page tokens and sizes are driven by the provider, not by users
+    let arguments = input
+        .fields()
+        .filter(|field| !matches!(field.name(), "page_token" | "page_size"))
+        .map(|field| {
+            let behaviors = ctx.annotations.field_behaviors(&field);
+            build_field(ctx, &field, &behaviors, false)
+        })
+        .collect();
+    let attributes = item
+        .fields()
+        .filter_map(|field| {
+            let behaviors = ctx.annotations.field_behaviors(&field);
+            (!behaviors.contains(&FieldBehavior::InputOnly))
+                .then(|| build_field(ctx, &field, &behaviors, true))
+        })
+        .collect();
+
+    Some(DataSourceDefinition {
+        name: to_snake_case(resource_name),
+        description: ctx.comments.get(&list.parent_file(), list.path()),
+        arguments,
+        attributes,
+        read_operation: OperationMapping {
+            pagination,
+            readonly: true,
+            idempotent: true,
+            ..build_operation_mapping(ctx, list)
         },
-    }))
+        items_accessor: Some(items_accessor),
+    })
 }
 
-/// Extract fields from protobuf message descriptor
+/// Build an OperationMapping for a gRPC method
 ///
-/// `is_response` - if true, fields are from a response and should have response accessors
-fn extract_fields_from_message(
-    pool: &DescriptorPool,
-    message: prost_reflect::MessageDescriptor,
-    is_response: bool,
-) -> Result<Vec<FieldDefinition>> {
-    let mut fields = Vec::new();
+/// Methods returning a `google.longrunning.Operation` are marked long-running,
+/// with the response and metadata types of their `operation_info`.
+fn build_operation_mapping(ctx: &Context, method: &MethodDescriptor) -> OperationMapping {
+    let long_running = ctx
+        .annotations
+        .operation_info(method)
+        .map(|info| LongRunningConfig {
+            response_type: info.response.map(|m| m.full_name().to_string()),
+            metadata_type: info.metadata_type,
+            ..Default::default()
+        });
+
+    OperationMapping {
+        sdk_operation: to_snake_case(method.name()),
+        additional_operations: vec![],
+        long_running,
+        deprecated: method
+            .method_descriptor_proto()
+            .options
+            .as_ref()
+            .is_some_and(|o| o.deprecated())
+            .then(Deprecation::default),
+        ..Default::default()
+    }
+}
+
+/// Extract inputs, blocks, outputs and the ID field of a `google.api.resource` message
+///
+/// Inputs are the resource's fields minus `OUTPUT_ONLY` ones, plus the other
+/// fields of the create request (`parent`, `{resource}_id`, ...), which can't
+/// change after creation. Outputs are all fields except `INPUT_ONLY` ones.
+fn extract_annotated_resource_schema(
+    ctx: &Context,
+    message: &MessageDescriptor,
+    create_method: Option<&MethodDescriptor>,
+) -> (
+    Vec<FieldDefinition>,
+    Vec<BlockDefinition>,
+    Vec<FieldDefinition>,
+    Option<String>,
+) {
+    let resource = ctx.annotations.resource(message);
+    let id_field = resource.as_ref().map(|r| to_snake_case(&r.name_field));
+
+    let mut visiting = vec![message.full_name().to_string()];
+    let (mut fields, mut blocks) = extract_input_schema(ctx, message, &mut visiting);
+
+    if let (Some(id), Some(pattern)) = (
+        &id_field,
+        resource.as_ref().and_then(|r| r.patterns.first()),
+    ) {
+        if let Some(field) = fields.iter_mut().find(|f| &f.name == id) {
+            // A comment on the name field takes precedence
+            field
+                .description
+                .get_or_insert_with(|| format!("Resource name ({})", pattern));
+        }
+    }
+
+    let outputs = message
+        .fields()
+        .filter_map(|field| {
+            let behaviors = ctx.annotations.field_behaviors(&field);
+            (!behaviors.contains(&FieldBehavior::InputOnly))
+                .then(|| build_field(ctx, &field, &behaviors, true))
+        })
+        .collect();
+
+    if let Some(method) = create_method {
+        for field in method.input().fields() {
+            let is_resource = field
+                .kind()
+                .as_message()
+                .is_some_and(|m| m.full_name() == message.full_name());
+            let name = to_snake_case(field.name());
+            if is_resource
+                || fields.iter().any(|f| f.name == name)
+                || blocks.iter().any(|b| b.name == name)
+            {
+                continue;
+            }
+
+            let first_new = fields.len();
+            push_input_field(ctx, &field, &mut visiting, &mut fields, &mut blocks);
+            for field in &mut fields[first_new..] {
+                field.immutable = true;
+            }
+        }
+    }
+
+    (fields, blocks, outputs, id_field)
+}
+
+/// Extract the input attributes and nested blocks of a message
+///
+/// Message fields become blocks, real `oneof` groups become one-of blocks, and
+/// `OUTPUT_ONLY` fields are skipped. `visiting` holds the messages being
+/// expanded, so recursive messages end in a free-form attribute.
+fn extract_input_schema(
+    ctx: &Context,
+    message: &MessageDescriptor,
+    visiting: &mut Vec<String>,
+) -> (Vec<FieldDefinition>, Vec<BlockDefinition>) {
+    let mut attributes = Vec::new();
+    let mut blocks = Vec::new();
 
     for field in message.fields() {
-        let field_type = convert_protobuf_kind_to_field_type(pool, &field.kind())?;
-        let accessor_name = to_snake_case(field.name());
-
-        fields.push(FieldDefinition {
-            name: accessor_name.clone(),
-            field_type,
-            required: !field.is_list() && !field.is_map(),
-            sensitive: false,
-            immutable: false,
-            description: None, // Could extract from proto comments
-            // Only response fields have accessors
-            response_accessor: if is_response {
-                Some(accessor_name)
-            } else {
-                None
+        match field.containing_oneof() {
+            Some(oneof) if !oneof.is_synthetic() => {
+                // The whole group is added with its first field
+                if oneof.fields().next().is_some_and(|first| first == field) {
+                    blocks.push(build_oneof_block(ctx, &oneof, visiting));
+                }
             },
-        });
+            _ => push_input_field(ctx, &field, visiting, &mut attributes, &mut blocks),
+        }
     }
 
-    Ok(fields)
+    (attributes, blocks)
+}
+
+/// Add a field as a nested block (message types) or an attribute
+fn push_input_field(
+    ctx: &Context,
+    field: &FieldDescriptor,
+    visiting: &mut Vec<String>,
+    attributes: &mut Vec<FieldDefinition>,
+    blocks: &mut Vec<BlockDefinition>,
+) {
+    let behaviors = ctx.annotations.field_behaviors(field);
+    if behaviors.contains(&FieldBehavior::OutputOnly) {
+        return;
+    }
+
+    match block_message(field, visiting) {
+        Some(message) => blocks.push(build_block(ctx, field, &message, &behaviors, visiting)),
+        None => attributes.push(build_field(ctx, field, &behaviors, false)),
+    }
+}
+
+/// Message type of a field that should become a block
+///
+/// Maps, well-known types and messages already being expanded stay attributes.
+fn block_message(field: &FieldDescriptor, visiting: &[String]) -> Option<MessageDescriptor> {
+    if field.is_map() {
+        return None;
+    }
+    let message = field.kind().as_message()?.clone();
+    if well_known_type(&message).is_some() || visiting.iter().any(|m| m == message.full_name()) {
+        return None;
+    }
+    Some(message)
+}
+
+/// Build a nested block from a message field
+fn build_block(
+    ctx: &Context,
+    field: &FieldDescriptor,
+    message: &MessageDescriptor,
+    behaviors: &[FieldBehavior],
+    visiting: &mut Vec<String>,
+) -> BlockDefinition {
+    visiting.push(message.full_name().to_string());
+    let (attributes, blocks) = extract_input_schema(ctx, message, visiting);
+    visiting.pop();
+
+    let required = behaviors.contains(&FieldBehavior::Required)
+        || field.cardinality() == Cardinality::Required;
+
+    BlockDefinition {
+        name: to_snake_case(field.name()),
+        description: ctx.comments.get(&field.parent_file(), field.path()),
+        attributes,
+        blocks,
+        nesting_mode: if field.is_list() {
+            NestingMode::List
+        } else {
+            NestingMode::Single
+        },
+        min_items: if required { 1 } else { 0 },
+        max_items: if field.is_list() { 0 } else { 1 }, // 0 = unlimited
+        sdk_type_name: Some(rust_type_path(message)),
+        // prost messages are plain structs: fields are assigned directly
+        sdk_accessor_method: Some(to_snake_case(field.name())),
+        one_of: false,
+    }
+}
+
+/// Build a one-of block from a `oneof` group (at most one member may be set)
+fn build_oneof_block(
+    ctx: &Context,
+    oneof: &OneofDescriptor,
+    visiting: &mut Vec<String>,
+) -> BlockDefinition {
+    let mut attributes = Vec::new();
+    let mut blocks = Vec::new();
+    for field in oneof.fields() {
+        push_input_field(ctx, &field, visiting, &mut attributes, &mut blocks);
+    }
+    for attribute in &mut attributes {
+        attribute.required = false;
+    }
+    for block in &mut blocks {
+        block.min_items = 0;
+    }
+
+    BlockDefinition {
+        name: to_snake_case(oneof.name()),
+        description: ctx.comments.get(&oneof.parent_file(), oneof.path()),
+        attributes,
+        blocks,
+        nesting_mode: NestingMode::Single,
+        min_items: 0,
+        max_items: 1,
+        // prost generates an enum in the parent message's module
+        sdk_type_name: Some(format!(
+            "{}::{}",
+            rust_module_path(oneof.parent_message()),
+            to_pascal_case(oneof.name())
+        )),
+        // The enum is assigned to the oneof's field like any other message field
+        sdk_accessor_method: Some(to_snake_case(oneof.name())),
+        one_of: true,
+    }
+}
+
+/// Build a field, with flags from its `google.api.field_behavior` annotations
+///
+/// - `REQUIRED` (or a proto2 `required` label) → required
+/// - `IMMUTABLE`, `IDENTIFIER` → immutable
+/// - `INPUT_ONLY` → sensitive, since the API never returns the value
+fn build_field(
+    ctx: &Context,
+    field: &FieldDescriptor,
+    behaviors: &[FieldBehavior],
+    is_response: bool,
+) -> FieldDefinition {
+    let field_type = convert_protobuf_field_type(field, &mut Vec::new());
+    let accessor_name = to_snake_case(field.name());
+
+    FieldDefinition {
+        name: accessor_name.clone(),
+        default_value: explicit_default(field, &field_type),
+        field_type,
+        required: !is_response
+            && (behaviors.contains(&FieldBehavior::Required)
+                || field.cardinality() == Cardinality::Required),
+        sensitive: behaviors.contains(&FieldBehavior::InputOnly),
+        write_only: !is_response && behaviors.contains(&FieldBehavior::InputOnly),
+        immutable: behaviors.contains(&FieldBehavior::Immutable)
+            || behaviors.contains(&FieldBehavior::Identifier),
+        description: ctx.comments.get(&field.parent_file(), field.path()),
+        // Only response fields have accessors
+        response_accessor: if is_response {
+            Some(accessor_name)
+        } else {
+            None
+        },
+        deprecated: field
+            .field_descriptor_proto()
+            .options
+            .as_ref()
+            .is_some_and(|o| o.deprecated())
+            .then(Deprecation::default),
+        ..Default::default()
+    }
+}
+
+/// Explicit `[default = ...]` of a proto2 field, converted to the field's type
+///
+/// proto3 fields have no defaults besides the zero value of their type.
+fn explicit_default(field: &FieldDescriptor, field_type: &FieldType) -> Option<serde_json::Value> {
+    let default = field.field_descriptor_proto().default_value.as_deref()?;
+    Some(match field_type {
+        FieldType::Integer | FieldType::Int8 | FieldType::Int16 | FieldType::Int32 => {
+            serde_json::Value::from(default.parse::<i64>().ok()?)
+        },
+        FieldType::UInt32 | FieldType::UInt64 => {
+            serde_json::Value::from(default.parse::<u64>().ok()?)
+        },
+        FieldType::Float => serde_json::Value::from(default.parse::<f64>().ok()?),
+        FieldType::Boolean => serde_json::Value::from(default.parse::<bool>().ok()?),
+        _ => serde_json::Value::String(default.to_string()),
+    })
+}
+
+/// Convert a protobuf field to FieldType (maps and repeated fields included)
+fn convert_protobuf_field_type(field: &FieldDescriptor, visiting: &mut Vec<String>) -> FieldType {
+    if field.is_map() {
+        if let Some(entry) = field.kind().as_message() {
+            return FieldType::Map(
+                Box::new(convert_protobuf_kind_to_field_type(
+                    &entry.map_entry_key_field().kind(),
+                    visiting,
+                )),
+                Box::new(convert_protobuf_kind_to_field_type(
+                    &entry.map_entry_value_field().kind(),
+                    visiting,
+                )),
+            );
+        }
+    }
+
+    let field_type = convert_protobuf_kind_to_field_type(&field.kind(), visiting);
+    if field.is_list() {
+        FieldType::List(Box::new(field_type))
+    } else {
+        field_type
+    }
 }
 
 /// Convert protobuf Kind to FieldType
-fn convert_protobuf_kind_to_field_type(_pool: &DescriptorPool, kind: &Kind) -> Result<FieldType> {
-    Ok(match kind {
+///
+/// Messages become objects of their fields; recursive references end in a
+/// free-form object.
+fn convert_protobuf_kind_to_field_type(kind: &Kind, visiting: &mut Vec<String>) -> FieldType {
+    match kind {
         Kind::Double | Kind::Float => FieldType::Float,
-        Kind::Int32
-        | Kind::Int64
-        | Kind::Uint32
-        | Kind::Uint64
-        | Kind::Sint32
-        | Kind::Sint64
-        | Kind::Fixed32
-        | Kind::Fixed64
-        | Kind::Sfixed32
-        | Kind::Sfixed64 => FieldType::Integer,
+        Kind::Int32 | Kind::Sint32 | Kind::Sfixed32 => FieldType::Int32,
+        Kind::Int64 | Kind::Sint64 | Kind::Sfixed64 => FieldType::Integer,
+        Kind::Uint32 | Kind::Fixed32 => FieldType::UInt32,
+        Kind::Uint64 | Kind::Fixed64 => FieldType::UInt64,
         Kind::Bool => FieldType::Boolean,
-        Kind::String | Kind::Bytes => FieldType::String,
-        Kind::Message(msg_desc) => {
-            // Check if it's a well-known type
-            let full_name = msg_desc.full_name();
-            match full_name {
-                "google.protobuf.Timestamp" => FieldType::DateTime,
-                _ => {
-                    // Complex message - could recursively extract fields
-                    // For now, treat as string (JSON representation)
-                    FieldType::String
-                },
+        Kind::String => FieldType::String,
+        Kind::Bytes => FieldType::Bytes,
+        Kind::Message(message) => {
+            if let Some(field_type) = well_known_type(message) {
+                return field_type;
+            }
+            if visiting.iter().any(|m| m == message.full_name()) {
+                return FieldType::Object(HashMap::new());
             }
+
+            visiting.push(message.full_name().to_string());
+            let properties = message
+                .fields()
+                .map(|field| {
+                    let field_type = convert_protobuf_field_type(&field, visiting);
+                    (to_snake_case(field.name()), Box::new(field_type))
+                })
+                .collect();
+            visiting.pop();
+
+            FieldType::Object(properties)
         },
-        Kind::Enum(_) => FieldType::String, // Enums as strings
+        // Enums are set by value name in the JSON mapping
+        Kind::Enum(descriptor) => FieldType::Enum(
+            descriptor
+                .values()
+                .map(|value| value.name().to_string())
+                .collect(),
+        ),
+    }
+}
+
+/// IR type of a `google.protobuf` well-known type, following its JSON mapping
+fn well_known_type(message: &MessageDescriptor) -> Option<FieldType> {
+    Some(match message.full_name() {
+        "google.protobuf.Timestamp" => FieldType::DateTime,
+        // "3.5s"
+        "google.protobuf.Duration" => FieldType::String,
+        "google.protobuf.FieldMask" => FieldType::List(Box::new(FieldType::String)),
+        // Arbitrary JSON
+        "google.protobuf.Struct" | "google.protobuf.Value" | "google.protobuf.Any" => {
+            FieldType::Document
+        },
+        "google.protobuf.Empty" => FieldType::Object(HashMap::new()),
+        "google.protobuf.ListValue" => FieldType::List(Box::new(FieldType::Document)),
+        // Wrappers are nullable scalars
+        "google.protobuf.DoubleValue" | "google.protobuf.FloatValue" => FieldType::Float,
+        "google.protobuf.Int64Value" => FieldType::Integer,
+        "google.protobuf.UInt64Value" => FieldType::UInt64,
+        "google.protobuf.Int32Value" => FieldType::Int32,
+        "google.protobuf.UInt32Value" => FieldType::UInt32,
+        "google.protobuf.BoolValue" => FieldType::Boolean,
+        "google.protobuf.StringValue" => FieldType::String,
+        "google.protobuf.BytesValue" => FieldType::Bytes,
+        _ => return None,
     })
 }
 
-/// Convert PascalCase to snake_case
-fn to_snake_case(s: &str) -> String {
-    let mut result = String::new();
-    let chars: Vec<char> = s.chars().collect();
-
-    for (i, &ch) in chars.iter().enumerate() {
-        if ch.is_uppercase() {
-            // Add underscore before uppercase if:
-            // 1. Not at the start
-            // 2. Previous char is lowercase or digit
-            // 3. OR next char is lowercase (handles HTTPServer -> http_server)
-            let should_add_underscore = i > 0
-                && (chars[i - 1].is_lowercase()
-                    || chars[i - 1].is_ascii_digit()
-                    || (i + 1 < chars.len() && chars[i + 1].is_lowercase()));
-
-            if should_add_underscore && !result.ends_with('_') {
-                result.push('_');
-            }
-            result.push(ch.to_ascii_lowercase());
-        } else if ch == '-' || ch == ' ' {
-            // Replace hyphens and spaces with underscores
-            if !result.is_empty() && !result.ends_with('_') {
-                result.push('_');
-            }
-        } else {
-            result.push(ch);
-        }
+/// Rust path of a message's prost type, e.g. `bucket::Lifecycle` for `Bucket.Lifecycle`
+fn rust_type_path(message: &MessageDescriptor) -> String {
+    match message.parent_message() {
+        Some(parent) => format!("{}::{}", rust_module_path(&parent), message.name()),
+        None => message.name().to_string(),
     }
+}
 
-    // Clean up multiple consecutive underscores
-    while result.contains("__") {
-        result = result.replace("__", "_");
+/// Module prost generates for a message's nested types, e.g. `bucket::lifecycle`
+fn rust_module_path(message: &MessageDescriptor) -> String {
+    let module = to_snake_case(message.name());
+    match message.parent_message() {
+        Some(parent) => format!("{}::{}", rust_module_path(&parent), module),
+        None => module,
     }
+}
 
-    // Strip leading and trailing underscores
-    result.trim_matches('_').to_string()
+/// Convert snake_case to PascalCase
+fn to_pascal_case(s: &str) -> String {
+    s.split('_')
+        .filter(|part| !part.is_empty())
+        .map(|part| {
+            let mut chars = part.chars();
+            match chars.next() {
+                Some(first) => first.to_uppercase().chain(chars).collect(),
+                None => String::new(),
+            }
+        })
+        .collect()
 }
 
 #[cfg(test)]
@@ -312,11 +794,4 @@ mod tests {
         );
         assert_eq!(extract_resource_from_method_name("InvalidMethod"), None);
     }
-
-    #[test]
-    fn test_to_snake_case() {
-        assert_eq!(to_snake_case("CreateBucket"), "create_bucket");
-        assert_eq!(to_snake_case("GetInstance"), "get_instance");
-        assert_eq!(to_snake_case("BucketName"), "bucket_name");
-    }
 }
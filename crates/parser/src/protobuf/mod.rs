@@ -1,11 +1,41 @@
 //! Protobuf/gRPC service parser
 //!
-//! Parses Protocol Buffer FileDescriptorSet to extract gRPC service definitions.
+//! Parses Protocol Buffer FileDescriptorSet or `.proto` sources to extract gRPC
+//! service definitions.
 //!
 //! ## Sources
+//! - **.proto files**: Compiled in-process, no protoc needed (imports are
+//!   resolved against import roots, well-known types are built in)
 //! - **Generated FileDescriptorSet**: Compiled from .proto files using protoc
 //! - **gRPC Reflection**: Retrieved from live gRPC services
 //!
+//! ## Type Mapping
+//! - Message fields become nested blocks (repeated → list blocks), with the
+//!   prost type path as `sdk_type_name`; real `oneof` groups become one-of blocks
+//! - `map<K, V>` → `Map`, `repeated T` → `List`
+//! - Well-known types follow their JSON mapping: `Timestamp` → `DateTime`,
+//!   `Duration` → `String`, `FieldMask` → list of paths, `Struct`/`Value`/`Any`
+//!   → free-form object, wrappers → their scalar type
+//!
+//! ## Google API Annotations
+//! When the pool contains them, AIP annotations drive the mapping:
+//! - `google.api.resource` messages become resources, `name_field` the ID field
+//! - Methods are matched to resources by their request/response messages and
+//!   `google.api.resource_reference` fields, falling back to method names
+//! - `google.api.field_behavior`: `REQUIRED` → required, `IMMUTABLE` → immutable,
+//!   `OUTPUT_ONLY` → output only, `INPUT_ONLY` → sensitive input
+//! - Methods returning `google.longrunning.Operation` are long-running; their
+//!   `operation_info` response type stands in for the method's output
+//!
+//! ## Data Sources
+//! `List*` methods become data sources returning the first entry of the
+//! response's repeated field; request fields other than `page_token` and
+//! `page_size` are the lookup's arguments.
+//!
+//! ## Descriptions
+//! Leading and trailing comments become resource, field and block descriptions.
+//! Descriptor sets only carry them when built with `--include_source_info`.
+//!
 //! ## Use Cases
 //! - Google Cloud APIs (many expose gRPC interfaces)
 //! - gRPC-first microservices
@@ -21,8 +51,18 @@
 //!     "v1"
 //! )?;
 //! let service_def = parser.parse()?;
+//!
+//! let parser = ProtobufParser::from_proto_files(
+//!     &[PathBuf::from("protos/my_service.proto")],
+//!     &[PathBuf::from("protos")],
+//!     "my_service",
+//!     "v1"
+//! )?;
 //! ```
 
+mod annotations;
+mod comments;
+mod compiler;
 mod converter;
 mod parser;
 
@@ -1,29 +1,42 @@
-//! Protobuf FileDescriptorSet parser
+//! Protobuf FileDescriptorSet and `.proto` source parser
 
-use hemmer_provider_generator_common::{GeneratorError, Result, ServiceDefinition};
+use crate::CrudRules;
+use hemmer_provider_generator_common::{GeneratorError, Provider, Result, ServiceDefinition};
 use prost::Message;
 use prost_reflect::DescriptorPool;
 use prost_types::FileDescriptorSet;
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 /// Protobuf/gRPC service parser
 ///
-/// Parses a FileDescriptorSet (compiled .proto files) to extract
-/// service definitions, RPC methods, and message types.
+/// Parses a FileDescriptorSet (compiled .proto files) or `.proto` sources to
+/// extract service definitions, RPC methods, and message types.
 pub struct ProtobufParser {
     /// Descriptor pool for reflection
     pool: DescriptorPool,
 
+    /// Files whose services are converted (all files when empty)
+    source_files: Vec<String>,
+
     /// Service name (e.g., "storage", "compute")
     service_name: String,
 
     /// API version (e.g., "v1", "v2")
     api_version: String,
+
+    /// Rules classifying methods into CRUD roles
+    crud_rules: CrudRules,
+
+    /// Provider the service belongs to (GCP unless set)
+    provider: Provider,
 }
 
 impl ProtobufParser {
-    /// Load FileDescriptorSet from binary file
+    /// Load FileDescriptorSet from binary file, or compile a `.proto` file
+    ///
+    /// Imports of a `.proto` file are resolved against its own directory; use
+    /// [`Self::from_file_with_import_roots`] for other include paths.
     ///
     /// # Example
     /// ```rust,ignore
@@ -38,6 +51,36 @@ impl ProtobufParser {
         service_name: &str,
         api_version: &str,
     ) -> Result<Self> {
+        Self::from_file_with_import_roots(path, &[], service_name, api_version)
+    }
+
+    /// Load FileDescriptorSet from binary file, or compile a `.proto` file
+    /// with imports resolved against `import_roots` (like protoc's `-I`)
+    ///
+    /// # Example
+    /// ```rust,ignore
+    /// let parser = ProtobufParser::from_file_with_import_roots(
+    ///     "googleapis/google/storage/v2/storage.proto",
+    ///     &[PathBuf::from("googleapis")],
+    ///     "storage",
+    ///     "v2"
+    /// )?;
+    /// ```
+    pub fn from_file_with_import_roots<P: AsRef<Path>>(
+        path: P,
+        import_roots: &[PathBuf],
+        service_name: &str,
+        api_version: &str,
+    ) -> Result<Self> {
+        if path.as_ref().extension().and_then(|e| e.to_str()) == Some("proto") {
+            return Self::from_proto_files(
+                &[path.as_ref().to_path_buf()],
+                import_roots,
+                service_name,
+                api_version,
+            );
+        }
+
         let bytes = fs::read(path.as_ref()).map_err(|e| {
             GeneratorError::Parse(format!(
                 "Failed to read FileDescriptorSet file {}: {}",
@@ -77,18 +120,70 @@ impl ProtobufParser {
 
         Ok(Self {
             pool,
+            source_files: vec![],
+            service_name: service_name.to_string(),
+            api_version: api_version.to_string(),
+            crud_rules: CrudRules::default(),
+            provider: Provider::Gcp,
+        })
+    }
+
+    /// Compile `.proto` files in-process, without protoc
+    ///
+    /// Imports are searched in `import_roots`, then in the directory of each
+    /// file that is outside all of them. The well-known types
+    /// (`google/protobuf/*.proto`) are built in. Only services declared in
+    /// `files` are converted, not those of their imports.
+    ///
+    /// # Example
+    /// ```rust,ignore
+    /// let parser = ProtobufParser::from_proto_files(
+    ///     &[PathBuf::from("protos/storage/v1/storage.proto")],
+    ///     &[PathBuf::from("protos")],
+    ///     "storage",
+    ///     "v1"
+    /// )?;
+    /// ```
+    pub fn from_proto_files(
+        files: &[PathBuf],
+        import_roots: &[PathBuf],
+        service_name: &str,
+        api_version: &str,
+    ) -> Result<Self> {
+        let (pool, source_files) = super::compiler::compile(files, import_roots)?;
+
+        Ok(Self {
+            pool,
+            source_files,
             service_name: service_name.to_string(),
             api_version: api_version.to_string(),
+            crud_rules: CrudRules::default(),
+            provider: Provider::Gcp,
         })
     }
 
+    /// Classify methods with the given rules instead of the built-in ones
+    pub fn with_crud_rules(mut self, crud_rules: CrudRules) -> Self {
+        self.crud_rules = crud_rules;
+        self
+    }
+
+    /// Set the provider of the service, which also selects the CRUD rules that apply
+    pub fn with_provider(mut self, provider: Provider) -> Self {
+        self.provider = provider;
+        self
+    }
+
     /// Parse FileDescriptorSet into ServiceDefinition IR
     pub fn parse(&self) -> Result<ServiceDefinition> {
         // Use the converter module to transform protobuf -> ServiceDefinition
         super::converter::convert_protobuf_to_service_definition(
             &self.pool,
+            &self.source_files,
             &self.service_name,
             &self.api_version,
+            &self.provider,
+            &self.crud_rules,
         )
     }
 
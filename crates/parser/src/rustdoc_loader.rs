@@ -143,6 +143,7 @@ impl RustdocLoader {
                 description: field_item.docs.clone(),
                 // For rustdoc-parsed fields, the accessor is the field name itself
                 response_accessor: Some(field_name),
+                ..Default::default()
             })
         } else {
             None
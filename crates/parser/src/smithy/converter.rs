@@ -1,17 +1,23 @@
 //! Converts Smithy model to ServiceDefinition IR
 
-use super::types::{Shape, SmithyModel};
+use super::types::{Shape, ShapeReference, SmithyModel};
+use crate::crud_rules::{CrudOperations, CrudRules};
+use crate::naming::to_snake_case;
 use hemmer_provider_generator_common::{
-    BlockDefinition, FieldDefinition, FieldType, GeneratorError, NestingMode, OperationMapping,
-    Operations, Provider, ResourceDefinition, Result, ServiceDefinition,
+    BlockDefinition, DataSourceDefinition, Deprecation, FieldConstraints, FieldDefinition,
+    FieldType, GeneratorError, NestingMode, OperationMapping, Operations, PaginationConfig,
+    Provider, ResourceDefinition, Result, ServiceDefinition, Stability, WaiterAcceptor,
+    WaiterDefinition, WaiterState,
 };
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 /// Convert Smithy model to ServiceDefinition
 pub fn convert_smithy_to_service_definition(
     model: &SmithyModel,
     service_name: &str,
     sdk_version: &str,
+    provider: &Provider,
+    crud_rules: &CrudRules,
 ) -> Result<ServiceDefinition> {
     // Find service shape
     let (_service_id, service_shape) = model.find_service().ok_or_else(|| {
@@ -19,36 +25,67 @@ pub fn convert_smithy_to_service_definition(
     })?;
 
     // Extract resources from the model
-    let resources = extract_resources(model, service_shape)?;
+    let resources = extract_resources(model, service_shape, provider, crud_rules)?;
+    let data_sources = extract_data_sources(model)?;
 
     Ok(ServiceDefinition {
-        provider: Provider::Aws,
+        provider: provider.clone(),
         name: service_name.to_string(),
         sdk_version: sdk_version.to_string(),
         resources,
-        data_sources: vec![], // Will implement data source detection later
+        data_sources,
     })
 }
 
 /// Extract resources from Smithy service
+///
+/// Resources bound to the service via `resource` shapes are built from their
+/// lifecycle operations. Operations that are not bound to any resource fall back
+/// to name-based grouping (e.g. `CreateBucket`, `DeleteBucket` -> `Bucket`).
 fn extract_resources(
     model: &SmithyModel,
     service_shape: &Shape,
+    provider: &Provider,
+    crud_rules: &CrudRules,
 ) -> Result<Vec<ResourceDefinition>> {
     let mut resources = Vec::new();
 
-    // Get operations from service
-    let operations = match service_shape {
-        Shape::Service { operations, .. } => operations,
+    let (operations, resource_refs) = match service_shape {
+        Shape::Service {
+            operations,
+            resources,
+            ..
+        } => (operations, resources),
         _ => return Ok(resources),
     };
 
-    // Group operations by resource
-    let grouped = group_operations_by_resource(model, operations)?;
+    // Build resources from lifecycle bindings, tracking every bound operation
+    let mut bound_operations = HashSet::new();
+    let mut visited = HashSet::new();
+    for resource_ref in resource_refs {
+        collect_bound_resources(
+            model,
+            &resource_ref.target,
+            &[],
+            &mut visited,
+            &mut bound_operations,
+            &mut resources,
+        )?;
+    }
+
+    // Group the remaining operations by resource
+    let unbound: Vec<ShapeReference> = operations
+        .iter()
+        .filter(|op_ref| !bound_operations.contains(&extract_operation_name(&op_ref.target)))
+        .cloned()
+        .collect();
+    let grouped = group_operations_by_resource(model, &unbound)?;
 
     // Convert each group to a ResourceDefinition
     for (resource_name, ops) in grouped {
-        if let Some(resource) = build_resource_from_operations(model, &resource_name, ops)? {
+        if let Some(resource) =
+            build_resource_from_operations(model, provider, crud_rules, &resource_name, ops)?
+        {
             resources.push(resource);
         }
     }
@@ -56,11 +93,120 @@ fn extract_resources(
     Ok(resources)
 }
 
+/// Build a ResourceDefinition from a Smithy `resource` shape and recurse into its children
+///
+/// `parent_identifiers` holds the identifier names inherited from parent resources,
+/// so the resource's own identifier can be picked as its `id_field`. A resource
+/// bound under several parents is built once, tracked in `visited`.
+fn collect_bound_resources(
+    model: &SmithyModel,
+    resource_id: &str,
+    parent_identifiers: &[String],
+    visited: &mut HashSet<String>,
+    bound_operations: &mut HashSet<String>,
+    resources: &mut Vec<ResourceDefinition>,
+) -> Result<()> {
+    if !visited.insert(resource_id.to_string()) {
+        return Ok(());
+    }
+
+    let Some(Shape::Resource {
+        identifiers,
+        create,
+        read,
+        update,
+        delete,
+        list,
+        put,
+        operations,
+        collection_operations,
+        resources: children,
+        traits,
+    }) = model.get_shape(resource_id)
+    else {
+        return Ok(());
+    };
+
+    let op_name =
+        |op: &Option<ShapeReference>| op.as_ref().map(|r| extract_operation_name(&r.target));
+
+    // Every operation bound to the resource is excluded from prefix grouping
+    for op_ref in [create, read, update, delete, list, put]
+        .into_iter()
+        .flatten()
+    {
+        bound_operations.insert(extract_operation_name(&op_ref.target));
+    }
+    for op_ref in operations.iter().chain(collection_operations) {
+        bound_operations.insert(extract_operation_name(&op_ref.target));
+    }
+
+    // `put` is a create-or-replace operation, so it stands in for whichever
+    // lifecycle operation is missing
+    let lifecycle = LifecycleOperations {
+        create: op_name(create).or_else(|| op_name(put)),
+        read: op_name(read),
+        update: op_name(update).or_else(|| op_name(put)),
+        delete: op_name(delete),
+    };
+
+    // Identifier names sorted for deterministic output
+    let mut identifier_names: Vec<String> = identifiers.keys().cloned().collect();
+    identifier_names.sort();
+
+    // Prefer the identifier introduced by this resource over inherited ones
+    let id_field = identifier_names
+        .iter()
+        .find(|name| !parent_identifiers.contains(name))
+        .or_else(|| identifier_names.first())
+        .map(|name| to_snake_case(name));
+
+    let resource_name = extract_type_name_from_shape_id(resource_id);
+    let description = extract_documentation(traits);
+
+    if let Some(mut resource) = build_resource(model, &resource_name, lifecycle)? {
+        // Identifiers cannot change without replacing the resource
+        let identifier_fields: Vec<String> = identifier_names
+            .iter()
+            .map(|name| to_snake_case(name))
+            .collect();
+        for field in &mut resource.fields {
+            if identifier_fields.contains(&field.name) {
+                field.immutable = true;
+            }
+        }
+        if description.is_some() {
+            resource.description = description;
+        }
+        if let Some(deprecated) = extract_deprecation(traits) {
+            resource.deprecated = Some(deprecated);
+        }
+        if traits.contains_key(super::types::traits::UNSTABLE) {
+            resource.stability = Stability::Beta;
+        }
+        resource.id_field = id_field;
+        resources.push(resource);
+    }
+
+    for child_ref in children {
+        collect_bound_resources(
+            model,
+            &child_ref.target,
+            &identifier_names,
+            visited,
+            bound_operations,
+            resources,
+        )?;
+    }
+
+    Ok(())
+}
+
 /// Group operations by resource name
 /// e.g., "CreateBucket", "DeleteBucket" -> "Bucket"
 fn group_operations_by_resource(
     _model: &SmithyModel,
-    operations: &[super::types::ShapeReference],
+    operations: &[ShapeReference],
 ) -> Result<HashMap<String, Vec<String>>> {
     let mut grouped: HashMap<String, Vec<String>> = HashMap::new();
 
@@ -108,33 +254,179 @@ fn extract_resource_from_operation(operation: &str) -> Option<String> {
     None
 }
 
+/// Extract data sources from `List*` and `Describe*` operations
+///
+/// Input members other than pagination and idempotency tokens become filter
+/// arguments. When the output holds a list of structures (the `@paginated`
+/// items member, or else the first such member), the data source returns the
+/// first item and its members are the attributes; otherwise the output members
+/// are. Only plural subjects fall back to the first list member, so a single
+/// object's list members are not mistaken for items.
+/// e.g. `DescribeVpcs` -> `vpc`, `DescribeTable` -> `table`
+fn extract_data_sources(model: &SmithyModel) -> Result<Vec<DataSourceDefinition>> {
+    let mut data_sources: Vec<DataSourceDefinition> = Vec::new();
+
+    // Shape IDs sorted for deterministic output
+    let mut shape_ids: Vec<&String> = model.shapes.keys().collect();
+    shape_ids.sort();
+
+    for shape_id in shape_ids {
+        let Some(Shape::Operation { output, traits, .. }) = model.get_shape(shape_id) else {
+            continue;
+        };
+        let op_name = extract_operation_name(shape_id);
+        let Some(subject) = ["List", "Describe"]
+            .iter()
+            .find_map(|prefix| op_name.strip_prefix(prefix))
+            .filter(|subject| !subject.is_empty())
+        else {
+            continue;
+        };
+
+        let singular = singularize(subject);
+        let plural = singular != subject;
+        let name = to_snake_case(&singular);
+        if data_sources.iter().any(|ds| ds.name == name) {
+            continue;
+        }
+
+        let Some(Shape::Structure { members, .. }) = output
+            .as_ref()
+            .and_then(|output_ref| model.get_shape(&output_ref.target))
+        else {
+            continue;
+        };
+
+        let read_operation = build_operation_mapping(model, &op_name);
+        let (items_accessor, attributes) =
+            match find_items_member(model, members, read_operation.pagination.as_ref(), plural) {
+                Some((member_name, item_members)) => (
+                    Some(to_snake_case(member_name)),
+                    extract_outputs_from_members(model, item_members)?,
+                ),
+                None => (None, extract_outputs_from_members(model, members)?),
+            };
+        if attributes.is_empty() {
+            continue;
+        }
+
+        // Pagination tokens are driven by the provider, not by users
+        let pagination_members: Vec<&String> = read_operation
+            .pagination
+            .iter()
+            .flat_map(|p| [&p.input_token, &p.page_size])
+            .flatten()
+            .collect();
+        let arguments = extract_fields_from_operation(model, &op_name)?
+            .into_iter()
+            .filter(|field| !pagination_members.contains(&&field.name))
+            .collect();
+
+        data_sources.push(DataSourceDefinition {
+            name,
+            description: extract_documentation(traits),
+            arguments,
+            attributes,
+            read_operation,
+            items_accessor,
+        });
+    }
+
+    Ok(data_sources)
+}
+
+/// Find the output member listing the matched items and the members of its item structure
+fn find_items_member<'a>(
+    model: &'a SmithyModel,
+    members: &'a HashMap<String, super::types::Member>,
+    pagination: Option<&PaginationConfig>,
+    plural: bool,
+) -> Option<(&'a str, &'a HashMap<String, super::types::Member>)> {
+    let item_members = |member: &super::types::Member| match model.get_shape(&member.target) {
+        Some(Shape::List {
+            member: list_member,
+            ..
+        }) => match model.get_shape(&list_member.target) {
+            Some(Shape::Structure { members, .. }) => Some(members),
+            _ => None,
+        },
+        _ => None,
+    };
+
+    // Member names sorted for deterministic output
+    let mut names: Vec<&String> = members.keys().collect();
+    names.sort();
+
+    let paginated_items = pagination.and_then(|p| p.items.as_deref());
+    names
+        .iter()
+        .find(|name| paginated_items == Some(to_snake_case(name).as_str()))
+        .into_iter()
+        .chain(names.iter().filter(|_| plural))
+        .find_map(|name| Some((name.as_str(), item_members(&members[*name])?)))
+}
+
+/// Singular form of a plural operation subject
+/// e.g. "Vpcs" -> "Vpc", "Policies" -> "Policy", "Address" -> "Address"
+fn singularize(name: &str) -> String {
+    if let Some(stem) = name.strip_suffix("ies") {
+        format!("{}y", stem)
+    } else if name.ends_with('s') && !name.ends_with("ss") {
+        name[..name.len() - 1].to_string()
+    } else {
+        name.to_string()
+    }
+}
+
 /// Build ResourceDefinition from grouped operations
 fn build_resource_from_operations(
     model: &SmithyModel,
+    provider: &Provider,
+    crud_rules: &CrudRules,
     resource_name: &str,
     operations: Vec<String>,
 ) -> Result<Option<ResourceDefinition>> {
     // Classify operations into CRUD
-    let mut create_op = None;
-    let mut read_op = None;
-    let mut update_op = None;
-    let mut delete_op = None;
-
-    for op_name in &operations {
-        if op_name.starts_with("Create") || op_name.starts_with("Put") {
-            create_op = Some(op_name.clone());
-        } else if op_name.starts_with("Get")
-            || op_name.starts_with("Describe")
-            || op_name.starts_with("Head")
-        {
-            read_op = Some(op_name.clone());
-        } else if op_name.starts_with("Update") || op_name.starts_with("Modify") {
-            update_op = Some(op_name.clone());
-        } else if op_name.starts_with("Delete") {
-            delete_op = Some(op_name.clone());
+    let mut crud = CrudOperations::default();
+    for op_name in operations {
+        if let Some(classification) = crud_rules.classify(provider, &op_name, None) {
+            crud.insert(classification, op_name);
         }
     }
 
+    build_resource(
+        model,
+        resource_name,
+        LifecycleOperations {
+            create: crud.create,
+            read: crud.read,
+            update: crud.update,
+            delete: crud.delete,
+        },
+    )
+}
+
+/// Lifecycle operation names for a single resource
+struct LifecycleOperations {
+    create: Option<String>,
+    read: Option<String>,
+    update: Option<String>,
+    delete: Option<String>,
+}
+
+/// Build ResourceDefinition from classified lifecycle operations
+fn build_resource(
+    model: &SmithyModel,
+    resource_name: &str,
+    lifecycle: LifecycleOperations,
+) -> Result<Option<ResourceDefinition>> {
+    let LifecycleOperations {
+        create: create_op,
+        read: read_op,
+        update: update_op,
+        delete: delete_op,
+    } = lifecycle;
+
     // Need at least one operation to create a resource
     if create_op.is_none() && read_op.is_none() && update_op.is_none() && delete_op.is_none() {
         return Ok(None);
@@ -163,33 +455,197 @@ fn build_resource_from_operations(
         Vec::new()
     };
 
-    Ok(Some(ResourceDefinition {
+    // Waiters defined on the read operation let create/update/delete block until
+    // the resource settles
+    let waiters = read_op
+        .as_deref()
+        .map(|op| extract_waiters(model, op))
+        .unwrap_or_default();
+    let ready_waiter = waiters
+        .iter()
+        .find(|w| {
+            !is_deletion_waiter(&w.name)
+                && w.acceptors.iter().any(|a| a.state == WaiterState::Success)
+        })
+        .cloned();
+    let deletion_waiter = waiters
+        .iter()
+        .find(|w| is_deletion_waiter(&w.name))
+        .cloned();
+
+    // New resources can't be created once the create operation is deprecated
+    let create_traits = create_op
+        .as_deref()
+        .and_then(|op| match find_shape_by_name(model, op) {
+            Some(Shape::Operation { traits, .. }) => Some(traits),
+            _ => None,
+        });
+    let deprecated = create_traits.and_then(extract_deprecation);
+    let stability = match create_traits {
+        Some(traits) if traits.contains_key(super::types::traits::UNSTABLE) => Stability::Beta,
+        _ => Stability::Ga,
+    };
+
+    let mut resource = ResourceDefinition {
         name: to_snake_case(resource_name),
         description: Some(format!("{} resource", resource_name)),
         fields,
         outputs,
         blocks,
-        id_field: None, // Will implement ID detection later
+        id_field: None, // Set from resource identifiers when bound
         operations: Operations {
             create: create_op.map(|op| OperationMapping {
-                sdk_operation: to_snake_case(&op),
-                additional_operations: vec![],
-            }),
-            read: read_op.map(|op| OperationMapping {
-                sdk_operation: to_snake_case(&op),
-                additional_operations: vec![],
+                waiter: ready_waiter.clone(),
+                ..build_operation_mapping(model, &op)
             }),
+            read: read_op.map(|op| build_operation_mapping(model, &op)),
             update: update_op.map(|op| OperationMapping {
-                sdk_operation: to_snake_case(&op),
-                additional_operations: vec![],
+                waiter: ready_waiter,
+                ..build_operation_mapping(model, &op)
             }),
             delete: delete_op.map(|op| OperationMapping {
-                sdk_operation: to_snake_case(&op),
-                additional_operations: vec![],
+                waiter: deletion_waiter,
+                ..build_operation_mapping(model, &op)
             }),
             import: None, // Will implement later
         },
-    }))
+        deprecated,
+        stability,
+    };
+    resource.infer_computed_fields();
+
+    Ok(Some(resource))
+}
+
+/// Build the OperationMapping for an operation, carrying its trait semantics
+fn build_operation_mapping(model: &SmithyModel, op_name: &str) -> OperationMapping {
+    let traits = match find_shape_by_name(model, op_name) {
+        Some(Shape::Operation { traits, .. }) => Some(traits),
+        _ => None,
+    };
+    let has_trait = |name: &str| traits.is_some_and(|t| t.contains_key(name));
+
+    // Read-only operations are implicitly idempotent
+    let readonly = has_trait(super::types::traits::READONLY);
+    let idempotent = readonly || has_trait(super::types::traits::IDEMPOTENT);
+
+    OperationMapping {
+        sdk_operation: to_snake_case(op_name),
+        additional_operations: vec![],
+        pagination: traits.and_then(|t| extract_pagination(model, t)),
+        idempotency_token: find_idempotency_token(model, op_name),
+        readonly,
+        idempotent,
+        deprecated: traits.and_then(extract_deprecation),
+        ..Default::default()
+    }
+}
+
+/// Extract pagination tokens from the `@paginated` trait
+///
+/// Members not set on the operation are inherited from the service's `@paginated` trait.
+fn extract_pagination(
+    model: &SmithyModel,
+    traits: &HashMap<String, serde_json::Value>,
+) -> Option<PaginationConfig> {
+    let op_paginated = traits.get(super::types::traits::PAGINATED)?;
+    let service_paginated = match model.find_service() {
+        Some((_, Shape::Service { traits, .. })) => traits.get(super::types::traits::PAGINATED),
+        _ => None,
+    };
+
+    let member = |key: &str| {
+        op_paginated
+            .get(key)
+            .or_else(|| service_paginated.and_then(|t| t.get(key)))
+            .and_then(|v| v.as_str())
+            .map(to_snake_case_path)
+    };
+
+    Some(PaginationConfig {
+        input_token: member("inputToken"),
+        output_token: member("outputToken"),
+        items: member("items"),
+        page_size: member("pageSize"),
+    })
+}
+
+/// Find the input member marked with `@idempotencyToken`
+fn find_idempotency_token(model: &SmithyModel, op_name: &str) -> Option<String> {
+    let Some(Shape::Operation {
+        input: Some(input_ref),
+        ..
+    }) = find_shape_by_name(model, op_name)
+    else {
+        return None;
+    };
+    let Some(Shape::Structure { members, .. }) = model.get_shape(&input_ref.target) else {
+        return None;
+    };
+
+    members
+        .iter()
+        .find(|(_, member)| {
+            member
+                .traits
+                .contains_key(super::types::traits::IDEMPOTENCY_TOKEN)
+        })
+        .map(|(name, _)| to_snake_case(name))
+}
+
+/// Extract waiters from the `smithy.waiters#waitable` trait of an operation
+fn extract_waiters(model: &SmithyModel, op_name: &str) -> Vec<WaiterDefinition> {
+    let Some(Shape::Operation { traits, .. }) = find_shape_by_name(model, op_name) else {
+        return Vec::new();
+    };
+    let Some(serde_json::Value::Object(waitable)) = traits.get(super::types::traits::WAITABLE)
+    else {
+        return Vec::new();
+    };
+
+    let mut waiters: Vec<WaiterDefinition> = waitable
+        .iter()
+        .map(|(name, waiter)| {
+            let acceptors = waiter
+                .get("acceptors")
+                .and_then(|a| a.as_array())
+                .map(|acceptors| {
+                    acceptors
+                        .iter()
+                        .filter_map(|acceptor| {
+                            Some(WaiterAcceptor {
+                                state: serde_json::from_value(acceptor.get("state")?.clone())
+                                    .ok()?,
+                                matcher: acceptor.get("matcher")?.clone(),
+                            })
+                        })
+                        .collect()
+                })
+                .unwrap_or_default();
+
+            WaiterDefinition {
+                name: to_snake_case(name),
+                description: waiter
+                    .get("documentation")
+                    .and_then(|d| d.as_str())
+                    .map(|d| d.to_string()),
+                operation: to_snake_case(op_name),
+                acceptors,
+                min_delay: waiter.get("minDelay").and_then(|d| d.as_u64()),
+                max_delay: waiter.get("maxDelay").and_then(|d| d.as_u64()),
+            }
+        })
+        .collect();
+
+    // Sort for deterministic waiter selection
+    waiters.sort_by(|a, b| a.name.cmp(&b.name));
+    waiters
+}
+
+/// Check if a waiter waits for a resource to go away
+/// e.g., "bucket_not_exists", "table_deleted", "instance_terminated"
+fn is_deletion_waiter(name: &str) -> bool {
+    name.contains("not_exist") || name.ends_with("deleted") || name.ends_with("terminated")
 }
 
 /// Extract fields from operation input
@@ -210,19 +666,33 @@ fn extract_fields_from_operation(
         // Get input structure
         if let Some(Shape::Structure { members, traits: _ }) = model.get_shape(&input_ref.target) {
             for (field_name, member) in members {
+                // Idempotency tokens are filled in by the generated provider
+                if member
+                    .traits
+                    .contains_key(super::types::traits::IDEMPOTENCY_TOKEN)
+                {
+                    continue;
+                }
+
                 let field_type = convert_smithy_type_to_field_type(model, &member.target)?;
                 let required = member.traits.contains_key(super::types::traits::REQUIRED);
                 let sensitive = member.traits.contains_key(super::types::traits::SENSITIVE);
                 let description = extract_documentation(&member.traits);
+                let (constraints, default_value) = extract_constraints(model, member);
 
                 fields.push(FieldDefinition {
                     name: to_snake_case(field_name),
+                    sdk_type_name: enum_type_name(&field_type, &member.target),
                     field_type,
                     required,
                     sensitive,
                     immutable: false, // TODO: determine from traits
                     description,
                     response_accessor: None, // Input fields don't have response accessors
+                    constraints,
+                    default_value,
+                    deprecated: extract_deprecation(&member.traits),
+                    ..Default::default()
                 });
             }
         }
@@ -258,8 +728,6 @@ fn extract_outputs_from_operation(
     model: &SmithyModel,
     op_name: &str,
 ) -> Result<Vec<FieldDefinition>> {
-    let mut outputs = Vec::new();
-
     // Find operation shape
     let op_shape = find_shape_by_name(model, op_name);
 
@@ -270,38 +738,53 @@ fn extract_outputs_from_operation(
     {
         // Get output structure
         if let Some(Shape::Structure { members, .. }) = model.get_shape(&output_ref.target) {
-            for (field_name, member) in members {
-                let field_type = convert_smithy_type_to_field_type(model, &member.target)?;
-                let description = extract_documentation(&member.traits);
-
-                // The SDK accessor method name is the snake_case version of the member name
-                let accessor_name = to_snake_case(field_name);
-                outputs.push(FieldDefinition {
-                    name: accessor_name.clone(),
-                    field_type,
-                    required: false,
-                    sensitive: member.traits.contains_key(super::types::traits::SENSITIVE),
-                    immutable: true,
-                    description,
-                    // For AWS SDK, the response accessor is the same as the field name
-                    response_accessor: Some(accessor_name),
-                });
-            }
+            return extract_outputs_from_members(model, members);
         }
     }
 
+    Ok(Vec::new())
+}
+
+/// Convert structure members to output fields read through SDK accessors
+fn extract_outputs_from_members(
+    model: &SmithyModel,
+    members: &HashMap<String, super::types::Member>,
+) -> Result<Vec<FieldDefinition>> {
+    let mut outputs = Vec::new();
+
+    for (field_name, member) in members {
+        let field_type = convert_smithy_type_to_field_type(model, &member.target)?;
+        let description = extract_documentation(&member.traits);
+
+        // The SDK accessor method name is the snake_case version of the member name
+        let accessor_name = to_snake_case(field_name);
+        outputs.push(FieldDefinition {
+            name: accessor_name.clone(),
+            sdk_type_name: enum_type_name(&field_type, &member.target),
+            field_type,
+            required: false,
+            sensitive: member.traits.contains_key(super::types::traits::SENSITIVE),
+            immutable: true,
+            description,
+            // For AWS SDK, the response accessor is the same as the field name
+            response_accessor: Some(accessor_name),
+            deprecated: extract_deprecation(&member.traits),
+            ..Default::default()
+        });
+    }
+
     Ok(outputs)
 }
 
 /// Detect nested blocks from structure members
-/// A member is considered a block if it's a List or Structure that contains complex data
+/// A member is considered a block if it's a List, Structure or Union that contains complex data
 fn detect_nested_blocks_from_structure(
     model: &SmithyModel,
     structure_shape: &Shape,
 ) -> Result<Vec<BlockDefinition>> {
     let mut blocks = Vec::new();
 
-    if let Shape::Structure { members, .. } = structure_shape {
+    if let Shape::Structure { members, .. } | Shape::Union { members, .. } = structure_shape {
         for (member_name, member) in members {
             if let Some(block) = try_extract_block_from_member(model, member_name, member)? {
                 blocks.push(block);
@@ -352,39 +835,63 @@ fn try_extract_block_from_member(
                         max_items: 0, // 0 = unlimited
                         sdk_type_name: Some(sdk_type_name),
                         sdk_accessor_method: Some(sdk_accessor_method),
+                        one_of: false,
                     }));
                 }
             }
         },
         // Single structure → Single block
+        // Skip if this looks like a simple wrapper (only 1-2 primitive fields)
         Some(Shape::Structure {
             members: nested_members,
             ..
+        }) if is_complex_structure(model, nested_members) => {
+            let attributes = extract_fields_from_structure_members(model, nested_members)?;
+            let nested_blocks = detect_nested_blocks_from_structure(model, target_shape.unwrap())?;
+
+            // Extract SDK type name from the member target
+            let sdk_type_name = extract_type_name_from_shape_id(&member.target);
+
+            // Generate accessor method name: member_name → set_member_name
+            let sdk_accessor_method = format!("set_{}", to_snake_case(member_name));
+
+            return Ok(Some(BlockDefinition {
+                name: to_snake_case(member_name),
+                description: extract_documentation(&member.traits),
+                attributes,
+                blocks: nested_blocks,
+                nesting_mode: NestingMode::Single,
+                min_items: 1,
+                max_items: 1,
+                sdk_type_name: Some(sdk_type_name),
+                sdk_accessor_method: Some(sdk_accessor_method),
+                one_of: false,
+            }));
+        },
+        // Union → Single one-of block (at most one variant may be set)
+        Some(Shape::Union {
+            members: variants, ..
         }) => {
-            // Skip if this looks like a simple wrapper (only 1-2 primitive fields)
-            if is_complex_structure(model, nested_members) {
-                let attributes = extract_fields_from_structure_members(model, nested_members)?;
-                let nested_blocks =
-                    detect_nested_blocks_from_structure(model, target_shape.unwrap())?;
-
-                // Extract SDK type name from the member target
-                let sdk_type_name = extract_type_name_from_shape_id(&member.target);
-
-                // Generate accessor method name: member_name → set_member_name
-                let sdk_accessor_method = format!("set_{}", to_snake_case(member_name));
-
-                return Ok(Some(BlockDefinition {
-                    name: to_snake_case(member_name),
-                    description: extract_documentation(&member.traits),
-                    attributes,
-                    blocks: nested_blocks,
-                    nesting_mode: NestingMode::Single,
-                    min_items: 1,
-                    max_items: 1,
-                    sdk_type_name: Some(sdk_type_name),
-                    sdk_accessor_method: Some(sdk_accessor_method),
-                }));
+            let mut attributes = extract_fields_from_structure_members(model, variants)?;
+            for attribute in &mut attributes {
+                attribute.required = false;
             }
+            let nested_blocks = detect_nested_blocks_from_structure(model, target_shape.unwrap())?;
+
+            return Ok(Some(BlockDefinition {
+                name: to_snake_case(member_name),
+                description: extract_documentation(&member.traits),
+                attributes,
+                blocks: nested_blocks,
+                nesting_mode: NestingMode::Single,
+                min_items: 0,
+                max_items: 1,
+                sdk_type_name: Some(extract_type_name_from_shape_id(&member.target)),
+                // Unions are enums in the SDKs: generated code builds the variant of the
+                // set member and passes it to the member's fluent setter
+                sdk_accessor_method: Some(to_snake_case(member_name)),
+                one_of: true,
+            }));
         },
         _ => {},
     }
@@ -404,8 +911,9 @@ fn is_complex_structure(
 
     // Check if any member is itself a structure or list
     for member in members.values() {
-        if let Some(Shape::Structure { .. } | Shape::List { .. } | Shape::Map { .. }) =
-            model.get_shape(&member.target)
+        if let Some(
+            Shape::Structure { .. } | Shape::Union { .. } | Shape::List { .. } | Shape::Map { .. },
+        ) = model.get_shape(&member.target)
         {
             return true;
         }
@@ -433,21 +941,32 @@ fn extract_fields_from_structure_members(
         let required = member.traits.contains_key(super::types::traits::REQUIRED);
         let sensitive = member.traits.contains_key(super::types::traits::SENSITIVE);
         let description = extract_documentation(&member.traits);
+        let (constraints, default_value) = extract_constraints(model, member);
 
         fields.push(FieldDefinition {
             name: to_snake_case(field_name),
+            sdk_type_name: enum_type_name(&field_type, &member.target),
             field_type,
             required,
             sensitive,
             immutable: false,
             description,
             response_accessor: None,
+            constraints,
+            default_value,
+            deprecated: extract_deprecation(&member.traits),
+            ..Default::default()
         });
     }
 
     Ok(fields)
 }
 
+/// SDK type name of a member targeting an enum shape (the SDKs generate a type for it)
+fn enum_type_name(field_type: &FieldType, target: &str) -> Option<String> {
+    matches!(field_type, FieldType::Enum(_)).then(|| extract_type_name_from_shape_id(target))
+}
+
 /// Check if a member should be treated as a block rather than a field
 fn is_potential_block_member(model: &SmithyModel, member: &super::types::Member) -> bool {
     if let Some(shape) = model.get_shape(&member.target) {
@@ -465,6 +984,8 @@ fn is_potential_block_member(model: &SmithyModel, member: &super::types::Member)
             },
             // Complex structures are blocks
             Shape::Structure { members, .. } => is_complex_structure(model, members),
+            // Unions are always one-of blocks
+            Shape::Union { .. } => true,
             _ => false,
         }
     } else {
@@ -474,31 +995,27 @@ fn is_potential_block_member(model: &SmithyModel, member: &super::types::Member)
 
 /// Convert Smithy type to FieldType
 fn convert_smithy_type_to_field_type(model: &SmithyModel, shape_id: &str) -> Result<FieldType> {
-    // Handle primitives by checking the type name
-    if shape_id.contains("#String") || shape_id.ends_with("String") {
-        return Ok(FieldType::String);
-    }
-    if shape_id.contains("#Integer") || shape_id.contains("#Long") {
-        return Ok(FieldType::Integer);
-    }
-    if shape_id.contains("#Boolean") {
-        return Ok(FieldType::Boolean);
-    }
-    if shape_id.contains("#Double") || shape_id.contains("#Float") {
-        return Ok(FieldType::Float);
-    }
-    if shape_id.contains("#Timestamp") {
-        return Ok(FieldType::DateTime);
-    }
-
-    // Look up the shape
+    // Shapes defined in the model take precedence over name-based guesses
     if let Some(shape) = model.get_shape(shape_id) {
-        match shape {
-            Shape::String { .. } => Ok(FieldType::String),
-            Shape::Integer { .. } | Shape::Long { .. } => Ok(FieldType::Integer),
+        return match shape {
+            Shape::String { traits } => Ok(match extract_legacy_enum_values(traits) {
+                Some(values) => FieldType::Enum(values),
+                None => FieldType::String,
+            }),
+            Shape::Enum { members, .. } => Ok(FieldType::Enum(extract_enum_values(members))),
+            // Integer enums are plain integers in the SDKs
+            Shape::IntEnum { .. } => Ok(FieldType::Int32),
+            Shape::Byte { .. } => Ok(FieldType::Int8),
+            Shape::Short { .. } => Ok(FieldType::Int16),
+            Shape::Integer { .. } => Ok(FieldType::Int32),
+            Shape::Long { .. } => Ok(FieldType::Integer),
             Shape::Boolean { .. } => Ok(FieldType::Boolean),
-            Shape::Double { .. } => Ok(FieldType::Float),
+            Shape::Float { .. } | Shape::Double { .. } => Ok(FieldType::Float),
+            // Arbitrary precision numbers don't fit a native width, keep them lossless
+            Shape::BigInteger { .. } | Shape::BigDecimal { .. } => Ok(FieldType::String),
+            Shape::Blob { .. } => Ok(FieldType::Bytes),
             Shape::Timestamp { .. } => Ok(FieldType::DateTime),
+            Shape::Document { .. } => Ok(FieldType::Document),
             Shape::List { member, .. } => {
                 let inner_type = convert_smithy_type_to_field_type(model, &member.target)?;
                 Ok(FieldType::List(Box::new(inner_type)))
@@ -508,10 +1025,66 @@ fn convert_smithy_type_to_field_type(model: &SmithyModel, shape_id: &str) -> Res
                 let value_type = convert_smithy_type_to_field_type(model, &value.target)?;
                 Ok(FieldType::Map(Box::new(key_type), Box::new(value_type)))
             },
+            // Unions have exactly one variant set
+            Shape::Union { members, .. } => {
+                let mut variants = HashMap::new();
+                for (variant_name, variant) in members {
+                    let variant_type = convert_smithy_type_to_field_type(model, &variant.target)?;
+                    variants.insert(to_snake_case(variant_name), Box::new(variant_type));
+                }
+                Ok(FieldType::Union(variants))
+            },
             _ => Ok(FieldType::String), // Default fallback
-        }
+        };
+    }
+
+    // Prelude shapes (smithy.api#...) are not part of the model
+    match shape_id.rsplit('#').next().unwrap_or(shape_id) {
+        "Byte" | "PrimitiveByte" => Ok(FieldType::Int8),
+        "Short" | "PrimitiveShort" => Ok(FieldType::Int16),
+        "Integer" | "PrimitiveInteger" => Ok(FieldType::Int32),
+        "Long" | "PrimitiveLong" => Ok(FieldType::Integer),
+        "Boolean" | "PrimitiveBoolean" => Ok(FieldType::Boolean),
+        "Float" | "PrimitiveFloat" | "Double" | "PrimitiveDouble" => Ok(FieldType::Float),
+        "Timestamp" => Ok(FieldType::DateTime),
+        "Blob" => Ok(FieldType::Bytes),
+        "Document" => Ok(FieldType::Document),
+        // String, BigInteger, BigDecimal and anything unknown
+        _ => Ok(FieldType::String),
+    }
+}
+
+/// Extract the values of an enum shape, sorted for deterministic output
+/// Members without an `@enumValue` trait use their member name as the value
+fn extract_enum_values(members: &HashMap<String, super::types::Member>) -> Vec<String> {
+    let mut values: Vec<String> = members
+        .iter()
+        .map(|(member_name, member)| {
+            member
+                .traits
+                .get(super::types::traits::ENUM_VALUE)
+                .and_then(|v| v.as_str())
+                .unwrap_or(member_name)
+                .to_string()
+        })
+        .collect();
+    values.sort();
+    values
+}
+
+/// Extract enum values from the Smithy 1.0 `@enum` trait on a string shape
+fn extract_legacy_enum_values(traits: &HashMap<String, serde_json::Value>) -> Option<Vec<String>> {
+    let definitions = traits.get(super::types::traits::ENUM)?.as_array()?;
+    let values: Vec<String> = definitions
+        .iter()
+        .filter_map(|definition| definition.get("value").and_then(|v| v.as_str()))
+        .map(|v| v.to_string())
+        .collect();
+
+    if values.is_empty() {
+        None
     } else {
-        Ok(FieldType::String) // Default fallback
+        Some(values)
     }
 }
 
@@ -540,6 +1113,47 @@ fn extract_operation_name(shape_id: &str) -> String {
         .to_string()
 }
 
+/// Extract `@range`, `@length`, `@pattern` and `@default` of a member
+///
+/// Traits of the member take precedence over those of its target shape.
+fn extract_constraints(
+    model: &SmithyModel,
+    member: &super::types::Member,
+) -> (FieldConstraints, Option<serde_json::Value>) {
+    let target_traits = model.get_shape(&member.target).and_then(Shape::traits);
+    let get = |name: &str| {
+        member
+            .traits
+            .get(name)
+            .or_else(|| target_traits.and_then(|t| t.get(name)))
+    };
+    let bound = |name: &str, key: &str| get(name).and_then(|t| t.get(key)).and_then(as_number);
+
+    let constraints = FieldConstraints {
+        minimum: bound(super::types::traits::RANGE, "min"),
+        maximum: bound(super::types::traits::RANGE, "max"),
+        min_length: bound(super::types::traits::LENGTH, "min").map(|n| n as u64),
+        max_length: bound(super::types::traits::LENGTH, "max").map(|n| n as u64),
+        pattern: get(super::types::traits::PATTERN)
+            .and_then(|p| p.as_str())
+            .map(String::from),
+    };
+
+    // `null` defaults only mark that the member has no default
+    let default_value = get(super::types::traits::DEFAULT)
+        .filter(|value| !value.is_null())
+        .cloned();
+
+    (constraints, default_value)
+}
+
+/// Read a numeric trait value; big numbers may be written as strings
+fn as_number(value: &serde_json::Value) -> Option<f64> {
+    value
+        .as_f64()
+        .or_else(|| value.as_str().and_then(|s| s.parse().ok()))
+}
+
 /// Extract documentation from traits
 fn extract_documentation(traits: &HashMap<String, serde_json::Value>) -> Option<String> {
     traits
@@ -548,6 +1162,13 @@ fn extract_documentation(traits: &HashMap<String, serde_json::Value>) -> Option<
         .map(|s| s.to_string())
 }
 
+/// Extract the `@deprecated` trait
+fn extract_deprecation(traits: &HashMap<String, serde_json::Value>) -> Option<Deprecation> {
+    traits
+        .get(super::types::traits::DEPRECATED)
+        .map(|v| Deprecation::new(v.get("message").and_then(|m| m.as_str())))
+}
+
 /// Extract SDK type name from Smithy shape ID
 /// e.g., "com.amazonaws.s3#LifecycleRule" -> "LifecycleRule"
 fn extract_type_name_from_shape_id(shape_id: &str) -> String {
@@ -558,43 +1179,13 @@ fn extract_type_name_from_shape_id(shape_id: &str) -> String {
         .to_string()
 }
 
-/// Convert PascalCase to snake_case
-fn to_snake_case(s: &str) -> String {
-    let mut result = String::new();
-    let chars: Vec<char> = s.chars().collect();
-
-    for (i, &ch) in chars.iter().enumerate() {
-        if ch.is_uppercase() {
-            // Add underscore before uppercase if:
-            // 1. Not at the start
-            // 2. Previous char is lowercase or digit
-            // 3. OR next char is lowercase (handles HTTPServer -> http_server)
-            let should_add_underscore = i > 0
-                && (chars[i - 1].is_lowercase()
-                    || chars[i - 1].is_ascii_digit()
-                    || (i + 1 < chars.len() && chars[i + 1].is_lowercase()));
-
-            if should_add_underscore && !result.ends_with('_') {
-                result.push('_');
-            }
-            result.push(ch.to_ascii_lowercase());
-        } else if ch == '-' || ch == ' ' {
-            // Replace hyphens and spaces with underscores
-            if !result.is_empty() && !result.ends_with('_') {
-                result.push('_');
-            }
-        } else {
-            result.push(ch);
-        }
-    }
-
-    // Clean up multiple consecutive underscores
-    while result.contains("__") {
-        result = result.replace("__", "_");
-    }
-
-    // Strip leading and trailing underscores
-    result.trim_matches('_').to_string()
+/// Convert each segment of a dotted member path to snake_case
+/// e.g., "Result.NextToken" -> "result.next_token"
+fn to_snake_case_path(path: &str) -> String {
+    path.split('.')
+        .map(to_snake_case)
+        .collect::<Vec<_>>()
+        .join(".")
 }
 
 #[cfg(test)]
@@ -621,16 +1212,6 @@ mod tests {
         );
     }
 
-    #[test]
-    fn test_to_snake_case() {
-        assert_eq!(to_snake_case("CreateBucket"), "create_bucket");
-        assert_eq!(to_snake_case("PutObject"), "put_object");
-        assert_eq!(to_snake_case("S3Bucket"), "s3_bucket");
-        assert_eq!(to_snake_case("HTTPServer"), "http_server");
-        assert_eq!(to_snake_case("XMLParser"), "xml_parser");
-        assert_eq!(to_snake_case("__test__"), "test");
-    }
-
     #[test]
     fn test_extract_operation_name() {
         assert_eq!(
@@ -0,0 +1,1582 @@
+//! Smithy IDL 2.0 parser
+//!
+//! Parses `.smithy` IDL text into the same [`SmithyModel`] that is produced from the
+//! JSON AST, so the converter works unchanged. Supports the control section, metadata,
+//! `namespace`, `use`, every shape statement, traits, documentation comments, mixins,
+//! elided members (`$name`), inline operation input/output (`:=`) and `apply`.
+//!
+//! A model set of several files is merged before mixins, elided members and `apply`
+//! are resolved, so shapes may reference shapes defined in any file of the set.
+//! Relative names refer to shapes of the file's namespace from any file of the set
+//! before `use` imports and the prelude. References to shapes that exist nowhere
+//! in the set are reported as errors.
+
+use super::types::SmithyModel;
+use hemmer_provider_generator_common::{GeneratorError, Result};
+use serde_json::{Map, Value};
+use std::collections::{HashMap, HashSet};
+
+/// Shapes from the `smithy.api` prelude that can be referenced by their short name
+const PRELUDE_SHAPES: &[&str] = &[
+    "String",
+    "Blob",
+    "BigInteger",
+    "BigDecimal",
+    "Timestamp",
+    "Document",
+    "Boolean",
+    "PrimitiveBoolean",
+    "Byte",
+    "PrimitiveByte",
+    "Short",
+    "PrimitiveShort",
+    "Integer",
+    "PrimitiveInteger",
+    "Long",
+    "PrimitiveLong",
+    "Float",
+    "PrimitiveFloat",
+    "Double",
+    "PrimitiveDouble",
+    "Unit",
+];
+
+/// Shape types without a body
+const SIMPLE_SHAPES: &[&str] = &[
+    "blob",
+    "boolean",
+    "string",
+    "byte",
+    "short",
+    "integer",
+    "long",
+    "float",
+    "double",
+    "bigInteger",
+    "bigDecimal",
+    "timestamp",
+    "document",
+];
+
+/// Parse Smithy IDL source into a SmithyModel
+pub fn parse_idl(source: &str) -> Result<SmithyModel> {
+    let file = IdlParser::new(tokenize(source)?).parse()?;
+    build_model(&[file], &[])
+}
+
+/// Parse the files of a Smithy IDL model set into one SmithyModel
+///
+/// Files are `(name, source)` pairs; the name only appears in error messages.
+/// Shapes from `dependencies` can be referenced, but their service shapes are
+/// left out so the model describes the services of `files` only.
+#[cfg(test)]
+fn parse_idl_files(files: &[(&str, &str)], dependencies: &[(&str, &str)]) -> Result<SmithyModel> {
+    let parse = |(name, source): &(&str, &str)| parse_idl_file(name, source);
+    let files = files.iter().map(parse).collect::<Result<Vec<_>>>()?;
+    let dependencies = dependencies.iter().map(parse).collect::<Result<Vec<_>>>()?;
+    build_model(&files, &dependencies)
+}
+
+/// Parse one file of a model set; its references are resolved by [`build_model`]
+///
+/// The name only appears in error messages.
+pub(crate) fn parse_idl_file(name: &str, source: &str) -> Result<UnresolvedFile> {
+    tokenize(source)
+        .and_then(|tokens| IdlParser::new(tokens).parse())
+        .map_err(|e| match e {
+            GeneratorError::Parse(message) => {
+                GeneratorError::Parse(format!("{}: {}", name, message))
+            },
+            other => other,
+        })
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum TokenKind {
+    Ident(String),
+    Str(String),
+    Number(String),
+    DocComment(String),
+    Punct(&'static str),
+}
+
+#[derive(Debug, Clone)]
+struct Token {
+    kind: TokenKind,
+    line: usize,
+}
+
+fn idl_error(line: usize, message: impl std::fmt::Display) -> GeneratorError {
+    GeneratorError::Parse(format!(
+        "Smithy IDL parse error on line {}: {}",
+        line, message
+    ))
+}
+
+/// Split IDL source into tokens
+///
+/// Commas are insignificant in the IDL and are treated as whitespace. Regular
+/// comments are dropped, `///` documentation comments are kept.
+fn tokenize(source: &str) -> Result<Vec<Token>> {
+    let chars: Vec<char> = source.chars().collect();
+    let mut tokens = Vec::new();
+    let mut line = 1;
+    let mut i = 0;
+
+    while i < chars.len() {
+        let ch = chars[i];
+        match ch {
+            '\n' => {
+                line += 1;
+                i += 1;
+            },
+            c if c.is_whitespace() || c == ',' => i += 1,
+            '/' if chars.get(i + 1) == Some(&'/') => {
+                let start = i;
+                while i < chars.len() && chars[i] != '\n' {
+                    i += 1;
+                }
+                let comment: String = chars[start..i].iter().collect();
+                if let Some(doc) = comment.strip_prefix("///") {
+                    tokens.push(Token {
+                        kind: TokenKind::DocComment(
+                            doc.strip_prefix(' ').unwrap_or(doc).trim_end().to_string(),
+                        ),
+                        line,
+                    });
+                }
+            },
+            '"' => {
+                let start_line = line;
+                let (value, consumed, newlines) = if chars[i..].starts_with(&['"', '"', '"']) {
+                    read_text_block(&chars[i..]).map_err(|e| idl_error(start_line, e))?
+                } else {
+                    read_string(&chars[i..]).map_err(|e| idl_error(start_line, e))?
+                };
+                tokens.push(Token {
+                    kind: TokenKind::Str(value),
+                    line: start_line,
+                });
+                i += consumed;
+                line += newlines;
+            },
+            '{' | '}' | '[' | ']' | '(' | ')' | '@' | '=' => {
+                let punct = match ch {
+                    '{' => "{",
+                    '}' => "}",
+                    '[' => "[",
+                    ']' => "]",
+                    '(' => "(",
+                    ')' => ")",
+                    '@' => "@",
+                    _ => "=",
+                };
+                tokens.push(Token {
+                    kind: TokenKind::Punct(punct),
+                    line,
+                });
+                i += 1;
+            },
+            ':' => {
+                if chars.get(i + 1) == Some(&'=') {
+                    tokens.push(Token {
+                        kind: TokenKind::Punct(":="),
+                        line,
+                    });
+                    i += 2;
+                } else {
+                    tokens.push(Token {
+                        kind: TokenKind::Punct(":"),
+                        line,
+                    });
+                    i += 1;
+                }
+            },
+            c if c.is_ascii_digit() || c == '-' => {
+                let start = i;
+                i += 1;
+                while i < chars.len()
+                    && (chars[i].is_ascii_digit()
+                        || matches!(chars[i], '.' | 'e' | 'E' | '+' | '-'))
+                {
+                    i += 1;
+                }
+                tokens.push(Token {
+                    kind: TokenKind::Number(chars[start..i].iter().collect()),
+                    line,
+                });
+            },
+            c if c.is_alphabetic() || c == '_' || c == '$' => {
+                let start = i;
+                while i < chars.len()
+                    && (chars[i].is_alphanumeric() || matches!(chars[i], '_' | '.' | '#' | '$'))
+                {
+                    i += 1;
+                }
+                tokens.push(Token {
+                    kind: TokenKind::Ident(chars[start..i].iter().collect()),
+                    line,
+                });
+            },
+            other => return Err(idl_error(line, format!("unexpected character '{}'", other))),
+        }
+    }
+
+    Ok(tokens)
+}
+
+/// Read a quoted string starting at `chars[0]`
+///
+/// Returns the unescaped value, the number of chars consumed and the number of newlines.
+fn read_string(chars: &[char]) -> std::result::Result<(String, usize, usize), String> {
+    let mut value = String::new();
+    let mut newlines = 0;
+    let mut i = 1;
+
+    while i < chars.len() {
+        match chars[i] {
+            '"' => return Ok((value, i + 1, newlines)),
+            '\\' => {
+                let (unescaped, consumed) = read_escape(&chars[i..])?;
+                value.push_str(&unescaped);
+                i += consumed;
+            },
+            c => {
+                if c == '\n' {
+                    newlines += 1;
+                }
+                value.push(c);
+                i += 1;
+            },
+        }
+    }
+
+    Err("unterminated string".to_string())
+}
+
+/// Read a `"""` text block starting at `chars[0]`, removing incidental indentation
+fn read_text_block(chars: &[char]) -> std::result::Result<(String, usize, usize), String> {
+    let mut raw = String::new();
+    let mut newlines = 0;
+    let mut i = 3;
+
+    loop {
+        if i >= chars.len() {
+            return Err("unterminated text block".to_string());
+        }
+        if chars[i..].starts_with(&['"', '"', '"']) {
+            i += 3;
+            break;
+        }
+        if chars[i] == '\\' {
+            let (unescaped, consumed) = read_escape(&chars[i..])?;
+            raw.push_str(&unescaped);
+            i += consumed;
+            continue;
+        }
+        if chars[i] == '\n' {
+            newlines += 1;
+        }
+        raw.push(chars[i]);
+        i += 1;
+    }
+
+    // The opening delimiter must be followed by a newline, which is not part of the value
+    let body = raw
+        .strip_prefix("\r\n")
+        .or_else(|| raw.strip_prefix('\n'))
+        .ok_or("text block must start with a new line")?;
+
+    let lines: Vec<&str> = body.split('\n').collect();
+    let indent = lines
+        .iter()
+        .enumerate()
+        .filter(|(idx, l)| !l.trim().is_empty() || *idx == lines.len() - 1)
+        .map(|(_, l)| l.len() - l.trim_start().len())
+        .min()
+        .unwrap_or(0);
+
+    let value = lines
+        .iter()
+        .map(|l| l.get(indent..).unwrap_or("").trim_end())
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    Ok((value, i, newlines))
+}
+
+/// Read an escape sequence starting at the backslash
+fn read_escape(chars: &[char]) -> std::result::Result<(String, usize), String> {
+    match chars.get(1) {
+        Some('n') => Ok(("\n".to_string(), 2)),
+        Some('t') => Ok(("\t".to_string(), 2)),
+        Some('r') => Ok(("\r".to_string(), 2)),
+        Some('b') => Ok(("\u{8}".to_string(), 2)),
+        Some('f') => Ok(("\u{c}".to_string(), 2)),
+        Some('"') => Ok(("\"".to_string(), 2)),
+        Some('\\') => Ok(("\\".to_string(), 2)),
+        Some('/') => Ok(("/".to_string(), 2)),
+        Some('\'') => Ok(("'".to_string(), 2)),
+        // Escaped newline continues the line
+        Some('\n') => Ok((String::new(), 2)),
+        Some('u') => {
+            let hex: String = chars.iter().skip(2).take(4).collect();
+            let code = u32::from_str_radix(&hex, 16)
+                .ok()
+                .and_then(char::from_u32)
+                .ok_or_else(|| format!("invalid unicode escape '\\u{}'", hex))?;
+            Ok((code.to_string(), 6))
+        },
+        Some(c) => Err(format!("invalid escape sequence '\\{}'", c)),
+        None => Err("unterminated escape sequence".to_string()),
+    }
+}
+
+/// Recursive-descent parser over IDL tokens
+///
+/// Shapes are collected with relative shape IDs and resolved once the whole
+/// file is known, since shapes may be referenced before they are defined.
+struct IdlParser {
+    tokens: Vec<Token>,
+    pos: usize,
+    version: String,
+    namespace: Option<String>,
+    uses: HashMap<String, String>,
+    metadata: Map<String, Value>,
+    /// Shapes by local name, in JSON AST form with unresolved references
+    shapes: Vec<(String, Value)>,
+    /// Mixins applied to each shape (local name -> relative mixin IDs)
+    mixins: HashMap<String, Vec<String>>,
+    /// Resource each shape is bound to with `for` (used to resolve elided members)
+    resource_bindings: HashMap<String, String>,
+    /// `apply` statements (relative target -> traits)
+    applies: Vec<(String, Map<String, Value>)>,
+    /// Suffixes for inline operation input/output structures
+    input_suffix: String,
+    output_suffix: String,
+}
+
+impl IdlParser {
+    fn new(tokens: Vec<Token>) -> Self {
+        Self {
+            tokens,
+            pos: 0,
+            version: "2.0".to_string(),
+            namespace: None,
+            uses: HashMap::new(),
+            metadata: Map::new(),
+            shapes: Vec::new(),
+            mixins: HashMap::new(),
+            resource_bindings: HashMap::new(),
+            applies: Vec::new(),
+            input_suffix: "Input".to_string(),
+            output_suffix: "Output".to_string(),
+        }
+    }
+
+    fn parse(mut self) -> Result<UnresolvedFile> {
+        // Control section: `$version: "2.0"`
+        while let Some(TokenKind::Ident(name)) = self.peek_significant() {
+            let Some(control) = name.strip_prefix('$').map(str::to_string) else {
+                break;
+            };
+            self.next();
+            self.expect_punct(":")?;
+            let value = self.parse_node(false)?;
+            let value = value.as_str().map(str::to_string);
+            match (control.as_str(), value) {
+                ("version", Some(v)) => self.version = v,
+                ("operationInputSuffix", Some(v)) => self.input_suffix = v,
+                ("operationOutputSuffix", Some(v)) => self.output_suffix = v,
+                _ => {},
+            }
+        }
+
+        // Metadata section: `metadata key = value`
+        while self.peek_keyword("metadata") {
+            self.next();
+            let key = match self.next_kind() {
+                Some(TokenKind::Ident(k)) | Some(TokenKind::Str(k)) => k,
+                _ => return Err(self.error("expected metadata key")),
+            };
+            self.expect_punct("=")?;
+            let value = self.parse_node(false)?;
+            self.metadata.insert(key, value);
+        }
+
+        // Shape section
+        if self.peek_keyword("namespace") {
+            self.next();
+            self.namespace = Some(self.expect_ident()?);
+        }
+
+        while self.peek_keyword("use") {
+            self.next();
+            let id = self.expect_ident()?;
+            let local = id.rsplit('#').next().unwrap_or(&id).to_string();
+            self.uses.insert(local, id);
+        }
+
+        while self.peek().is_some() {
+            self.parse_statement()?;
+        }
+
+        self.finish()
+    }
+
+    fn peek(&self) -> Option<&TokenKind> {
+        self.tokens.get(self.pos).map(|t| &t.kind)
+    }
+
+    /// Peek past documentation comments that aren't attached to a shape
+    fn peek_significant(&mut self) -> Option<&TokenKind> {
+        while let Some(TokenKind::DocComment(_)) = self.peek() {
+            self.pos += 1;
+        }
+        self.peek()
+    }
+
+    fn peek_keyword(&mut self, keyword: &str) -> bool {
+        matches!(self.peek_significant(), Some(TokenKind::Ident(k)) if k == keyword)
+    }
+
+    fn is_punct(&self, punct: &str) -> bool {
+        matches!(self.peek(), Some(TokenKind::Punct(p)) if *p == punct)
+    }
+
+    fn next(&mut self) {
+        self.pos += 1;
+    }
+
+    fn next_kind(&mut self) -> Option<TokenKind> {
+        let kind = self.peek().cloned();
+        self.pos += 1;
+        kind
+    }
+
+    fn line(&self) -> usize {
+        self.tokens
+            .get(self.pos)
+            .or_else(|| self.tokens.last())
+            .map(|t| t.line)
+            .unwrap_or(1)
+    }
+
+    fn error(&self, message: impl std::fmt::Display) -> GeneratorError {
+        idl_error(self.line(), message)
+    }
+
+    fn expect_punct(&mut self, punct: &str) -> Result<()> {
+        if self.is_punct(punct) {
+            self.next();
+            Ok(())
+        } else {
+            Err(self.error(format!("expected '{}'", punct)))
+        }
+    }
+
+    fn expect_ident(&mut self) -> Result<String> {
+        match self.peek_significant().cloned() {
+            Some(TokenKind::Ident(name)) => {
+                self.next();
+                Ok(name)
+            },
+            _ => Err(self.error("expected identifier")),
+        }
+    }
+
+    /// Parse a shape statement or an `apply` statement
+    fn parse_statement(&mut self) -> Result<()> {
+        let traits = self.parse_traits()?;
+        let keyword = self.expect_ident()?;
+
+        if keyword == "apply" {
+            return self.parse_apply();
+        }
+
+        let name = self.expect_ident()?;
+        let mut shape = Map::new();
+        shape.insert("type".to_string(), Value::String(keyword.clone()));
+
+        if self.peek_keyword("for") {
+            self.next();
+            let resource = self.expect_ident()?;
+            self.resource_bindings.insert(name.clone(), resource);
+        }
+        if self.peek_keyword("with") {
+            self.next();
+            let mixins = self.parse_mixin_list()?;
+            self.mixins.insert(name.clone(), mixins);
+        }
+
+        match keyword.as_str() {
+            k if SIMPLE_SHAPES.contains(&k) => {},
+            "structure" | "union" => {
+                let members = self.parse_members()?;
+                shape.insert("members".to_string(), Value::Object(members));
+            },
+            "list" => {
+                let mut members = self.parse_members()?;
+                if let Some(member) = members.remove("member") {
+                    shape.insert("member".to_string(), member);
+                }
+            },
+            "map" => {
+                let mut members = self.parse_members()?;
+                for key in ["key", "value"] {
+                    if let Some(member) = members.remove(key) {
+                        shape.insert(key.to_string(), member);
+                    }
+                }
+            },
+            "enum" | "intEnum" => {
+                let members = self.parse_enum_members()?;
+                shape.insert("members".to_string(), Value::Object(members));
+            },
+            "service" | "resource" => {
+                if let Value::Object(body) = self.parse_node(true)? {
+                    shape.extend(body);
+                }
+            },
+            "operation" => {
+                let body = self.parse_operation_body(&name)?;
+                shape.extend(body);
+            },
+            other => return Err(self.error(format!("unknown shape type '{}'", other))),
+        }
+
+        if !traits.is_empty() {
+            shape.insert("traits".to_string(), Value::Object(traits));
+        }
+        self.shapes.push((name, Value::Object(shape)));
+
+        Ok(())
+    }
+
+    /// Parse `apply Target @trait` or `apply Target { @a @b }`
+    fn parse_apply(&mut self) -> Result<()> {
+        let target = self.expect_ident()?;
+        let traits = if self.is_punct("{") {
+            self.next();
+            let traits = self.parse_traits()?;
+            self.expect_punct("}")?;
+            traits
+        } else {
+            self.expect_punct("@")?;
+            let (name, value) = self.parse_trait_body()?;
+            let mut traits = Map::new();
+            traits.insert(name, value);
+            traits
+        };
+        self.applies.push((target, traits));
+        Ok(())
+    }
+
+    /// Parse `[A, B]` after `with`
+    fn parse_mixin_list(&mut self) -> Result<Vec<String>> {
+        self.expect_punct("[")?;
+        let mut mixins = Vec::new();
+        while !self.is_punct("]") {
+            mixins.push(self.expect_ident()?);
+        }
+        self.expect_punct("]")?;
+        Ok(mixins)
+    }
+
+    /// Parse documentation comments and traits preceding a shape or member
+    ///
+    /// Documentation comments become the `documentation` trait unless one is set explicitly.
+    fn parse_traits(&mut self) -> Result<Map<String, Value>> {
+        let mut traits = Map::new();
+        let mut docs = Vec::new();
+
+        loop {
+            match self.peek() {
+                Some(TokenKind::DocComment(doc)) => {
+                    docs.push(doc.clone());
+                    self.next();
+                },
+                Some(TokenKind::Punct("@")) => {
+                    self.next();
+                    let (name, value) = self.parse_trait_body()?;
+                    traits.insert(name, value);
+                },
+                _ => break,
+            }
+        }
+
+        if !docs.is_empty() && !traits.contains_key("documentation") {
+            traits.insert("documentation".to_string(), Value::String(docs.join("\n")));
+        }
+
+        Ok(traits)
+    }
+
+    /// Parse a trait after `@`: `name`, `name(value)` or `name(key: value, ...)`
+    fn parse_trait_body(&mut self) -> Result<(String, Value)> {
+        let name = self.expect_ident()?;
+
+        if !self.is_punct("(") {
+            return Ok((name, Value::Object(Map::new())));
+        }
+        self.next();
+
+        if self.is_punct(")") {
+            self.next();
+            return Ok((name, Value::Object(Map::new())));
+        }
+
+        // Structured trait values are written without surrounding braces
+        let is_structured = matches!(
+            self.tokens.get(self.pos + 1).map(|t| &t.kind),
+            Some(TokenKind::Punct(":"))
+        ) && matches!(
+            self.peek(),
+            Some(TokenKind::Ident(_)) | Some(TokenKind::Str(_))
+        );
+
+        let value = if is_structured {
+            Value::Object(self.parse_object_entries(")", false)?)
+        } else {
+            self.parse_node(false)?
+        };
+        self.expect_punct(")")?;
+
+        Ok((name, value))
+    }
+
+    /// Parse a node value
+    ///
+    /// Unquoted identifiers are shape IDs. Inside shape bodies (service, resource
+    /// and operation properties) they become `{"target": ...}` references like in
+    /// the JSON AST; inside trait values they are kept as plain strings.
+    fn parse_node(&mut self, shape_body: bool) -> Result<Value> {
+        match self.peek_significant().cloned() {
+            Some(TokenKind::Str(s)) => {
+                self.next();
+                Ok(Value::String(s))
+            },
+            Some(TokenKind::Number(n)) => {
+                self.next();
+                parse_number(&n).ok_or_else(|| self.error(format!("invalid number '{}'", n)))
+            },
+            Some(TokenKind::Ident(ident)) => {
+                self.next();
+                Ok(match ident.as_str() {
+                    "true" => Value::Bool(true),
+                    "false" => Value::Bool(false),
+                    "null" => Value::Null,
+                    _ if shape_body => {
+                        let mut reference = Map::new();
+                        reference.insert("target".to_string(), Value::String(ident));
+                        Value::Object(reference)
+                    },
+                    _ => Value::String(ident),
+                })
+            },
+            Some(TokenKind::Punct("{")) => {
+                self.next();
+                let entries = self.parse_object_entries("}", shape_body)?;
+                self.expect_punct("}")?;
+                Ok(Value::Object(entries))
+            },
+            Some(TokenKind::Punct("[")) => {
+                self.next();
+                let mut items = Vec::new();
+                while !matches!(self.peek_significant(), Some(TokenKind::Punct("]")) | None) {
+                    items.push(self.parse_node(shape_body)?);
+                }
+                self.expect_punct("]")?;
+                Ok(Value::Array(items))
+            },
+            _ => Err(self.error("expected node value")),
+        }
+    }
+
+    /// Parse `key: value` pairs up to (but not including) the closing delimiter
+    fn parse_object_entries(
+        &mut self,
+        close: &str,
+        shape_body: bool,
+    ) -> Result<Map<String, Value>> {
+        let mut entries = Map::new();
+        loop {
+            match self.peek_significant().cloned() {
+                Some(TokenKind::Punct(p)) if p == close => break,
+                Some(TokenKind::Ident(key)) | Some(TokenKind::Str(key)) => {
+                    self.next();
+                    self.expect_punct(":")?;
+                    let value = self.parse_node(shape_body)?;
+                    entries.insert(key, value);
+                },
+                _ => return Err(self.error("expected object key")),
+            }
+        }
+        Ok(entries)
+    }
+
+    /// Parse `{ [traits] name: Target [= default] ... }` members
+    fn parse_members(&mut self) -> Result<Map<String, Value>> {
+        self.expect_punct("{")?;
+        let mut members = Map::new();
+
+        while !self.is_punct("}") {
+            let mut traits = self.parse_traits()?;
+            if self.is_punct("}") {
+                break;
+            }
+            let token = self.expect_ident()?;
+            let mut member = Map::new();
+
+            // `$name` elides the target, which comes from a mixin or bound resource
+            let name = match token.strip_prefix('$') {
+                Some(elided) => elided.to_string(),
+                None => {
+                    self.expect_punct(":")?;
+                    let target = self.expect_ident()?;
+                    member.insert("target".to_string(), Value::String(target));
+                    token
+                },
+            };
+
+            if self.is_punct("=") {
+                self.next();
+                let default = self.parse_node(false)?;
+                traits.insert("default".to_string(), default);
+            }
+            if !traits.is_empty() {
+                member.insert("traits".to_string(), Value::Object(traits));
+            }
+            members.insert(name, Value::Object(member));
+        }
+
+        self.expect_punct("}")?;
+        Ok(members)
+    }
+
+    /// Parse `{ [traits] NAME [= value] ... }` enum and intEnum members
+    fn parse_enum_members(&mut self) -> Result<Map<String, Value>> {
+        self.expect_punct("{")?;
+        let mut members = Map::new();
+
+        while !self.is_punct("}") {
+            let mut traits = self.parse_traits()?;
+            if self.is_punct("}") {
+                break;
+            }
+            let name = self.expect_ident()?;
+            if self.is_punct("=") {
+                self.next();
+                let value = self.parse_node(false)?;
+                traits.insert("enumValue".to_string(), value);
+            }
+
+            let mut member = Map::new();
+            member.insert(
+                "target".to_string(),
+                Value::String("smithy.api#Unit".to_string()),
+            );
+            if !traits.is_empty() {
+                member.insert("traits".to_string(), Value::Object(traits));
+            }
+            members.insert(name, Value::Object(member));
+        }
+
+        self.expect_punct("}")?;
+        Ok(members)
+    }
+
+    /// Parse an operation body, including inline `input := { ... }` structures
+    fn parse_operation_body(&mut self, operation: &str) -> Result<Map<String, Value>> {
+        self.expect_punct("{")?;
+        let mut body = Map::new();
+
+        while !matches!(self.peek_significant(), Some(TokenKind::Punct("}")) | None) {
+            let key = self.expect_ident()?;
+
+            if self.is_punct(":=") {
+                self.next();
+                let (suffix, marker) = match key.as_str() {
+                    "input" => (self.input_suffix.clone(), "input"),
+                    "output" => (self.output_suffix.clone(), "output"),
+                    _ => return Err(self.error(format!("'{}' cannot be defined inline", key))),
+                };
+                let name = format!("{}{}", operation, suffix);
+
+                let mut traits = self.parse_traits()?;
+                traits.insert(marker.to_string(), Value::Object(Map::new()));
+                if self.peek_keyword("for") {
+                    self.next();
+                    let resource = self.expect_ident()?;
+                    self.resource_bindings.insert(name.clone(), resource);
+                }
+                if self.peek_keyword("with") {
+                    self.next();
+                    let mixins = self.parse_mixin_list()?;
+                    self.mixins.insert(name.clone(), mixins);
+                }
+                let members = self.parse_members()?;
+
+                let mut shape = Map::new();
+                shape.insert("type".to_string(), Value::String("structure".to_string()));
+                shape.insert("members".to_string(), Value::Object(members));
+                shape.insert("traits".to_string(), Value::Object(traits));
+                self.shapes.push((name.clone(), Value::Object(shape)));
+
+                let mut reference = Map::new();
+                reference.insert("target".to_string(), Value::String(name));
+                body.insert(key, Value::Object(reference));
+            } else {
+                self.expect_punct(":")?;
+                let value = self.parse_node(true)?;
+                body.insert(key, value);
+            }
+        }
+
+        self.expect_punct("}")?;
+        Ok(body)
+    }
+
+    /// The parsed file, with shape IDs still relative to its namespace and `use` imports
+    fn finish(self) -> Result<UnresolvedFile> {
+        let namespace = match (self.namespace, self.shapes.is_empty()) {
+            (Some(ns), _) => ns,
+            (None, true) => String::new(),
+            (None, false) => {
+                return Err(GeneratorError::Parse(
+                    "Smithy IDL defines shapes without a namespace".to_string(),
+                ))
+            },
+        };
+
+        Ok(UnresolvedFile {
+            version: self.version,
+            namespace,
+            uses: self.uses,
+            metadata: self.metadata,
+            shapes: self.shapes,
+            mixins: self.mixins,
+            resource_bindings: self.resource_bindings,
+            applies: self.applies,
+        })
+    }
+}
+
+/// One parsed IDL file whose shape IDs are not resolved yet
+///
+/// Which shape a relative name refers to depends on the other files of the
+/// model set, so references are resolved when the set is merged.
+#[derive(Debug, Clone)]
+pub(crate) struct UnresolvedFile {
+    version: String,
+    namespace: String,
+    uses: HashMap<String, String>,
+    metadata: Map<String, Value>,
+    /// Shapes by local name
+    shapes: Vec<(String, Value)>,
+    /// Mixins applied to each shape (local name -> relative mixin IDs)
+    mixins: HashMap<String, Vec<String>>,
+    /// Resource each shape is bound to with `for`
+    resource_bindings: HashMap<String, String>,
+    /// `apply` statements (relative target -> traits)
+    applies: Vec<(String, Map<String, Value>)>,
+}
+
+impl UnresolvedFile {
+    /// Resolve the relative shape IDs of this file
+    ///
+    /// `local` holds the names of the shapes of this file's namespace defined
+    /// anywhere in the model set.
+    fn resolve(&self, local: &HashSet<String>) -> ParsedFile {
+        let resolver = Resolver {
+            namespace: &self.namespace,
+            local,
+            uses: &self.uses,
+        };
+
+        let shapes = self
+            .shapes
+            .iter()
+            .map(|(name, shape)| {
+                let mut shape = shape.clone();
+                resolver.resolve_refs(&mut shape);
+                (resolver.shape(name), shape)
+            })
+            .collect();
+        let mixins = self
+            .mixins
+            .iter()
+            .map(|(name, mixins)| {
+                (
+                    resolver.shape(name),
+                    mixins.iter().map(|m| resolver.shape(m)).collect(),
+                )
+            })
+            .collect();
+        let resource_bindings = self
+            .resource_bindings
+            .iter()
+            .map(|(name, resource)| (resolver.shape(name), resolver.shape(resource)))
+            .collect();
+        let applies = self
+            .applies
+            .iter()
+            .map(|(target, traits)| {
+                let (shape, member) = match target.split_once('$') {
+                    Some((shape, member)) => (resolver.shape(shape), Some(member.to_string())),
+                    None => (resolver.shape(target), None),
+                };
+                let traits = traits
+                    .iter()
+                    .map(|(name, value)| (resolver.trait_id(name), value.clone()))
+                    .collect();
+                (shape, member, traits)
+            })
+            .collect();
+
+        ParsedFile {
+            metadata: self.metadata.clone(),
+            shapes,
+            mixins,
+            resource_bindings,
+            applies,
+        }
+    }
+}
+
+/// One parsed IDL file with absolute shape IDs, before it is merged into a model
+struct ParsedFile {
+    metadata: Map<String, Value>,
+    shapes: Vec<(String, Value)>,
+    /// Mixins applied to each shape
+    mixins: HashMap<String, Vec<String>>,
+    /// Resource each shape is bound to with `for`
+    resource_bindings: HashMap<String, String>,
+    /// `apply` statements (shape, optional member, traits)
+    applies: Vec<(String, Option<String>, Map<String, Value>)>,
+}
+
+/// Merge parsed files, apply mixins and `apply` statements, and build the model
+///
+/// Shapes from `dependencies` can be referenced, but their service shapes are
+/// left out so the model describes the services of `files` only.
+pub(crate) fn build_model(
+    files: &[UnresolvedFile],
+    dependencies: &[UnresolvedFile],
+) -> Result<SmithyModel> {
+    let version = files
+        .first()
+        .map(|file| file.version.clone())
+        .unwrap_or_else(|| "2.0".to_string());
+
+    // Shape names of each namespace across the whole set
+    let mut namespace_shapes: HashMap<&str, HashSet<String>> = HashMap::new();
+    for file in files.iter().chain(dependencies) {
+        namespace_shapes
+            .entry(&file.namespace)
+            .or_default()
+            .extend(file.shapes.iter().map(|(name, _)| name.clone()));
+    }
+    let resolve = |file: &UnresolvedFile| file.resolve(&namespace_shapes[file.namespace.as_str()]);
+
+    let mut shapes: HashMap<String, Value> = HashMap::new();
+    let mut dependency_services = Vec::new();
+    let mut metadata = Map::new();
+    let mut mixins = HashMap::new();
+    let mut resource_bindings = HashMap::new();
+    let mut applies = Vec::new();
+    let files = files.iter().map(|file| (resolve(file), false));
+    let dependencies = dependencies.iter().map(|file| (resolve(file), true));
+    for (file, is_dependency) in files.chain(dependencies) {
+        for (id, shape) in file.shapes {
+            if is_dependency && shape.get("type").and_then(Value::as_str) == Some("service") {
+                dependency_services.push(id.clone());
+            }
+            if shapes.insert(id.clone(), shape).is_some() {
+                return Err(GeneratorError::Parse(format!(
+                    "Shape {} is defined more than once in the model set",
+                    id
+                )));
+            }
+        }
+        metadata.extend(file.metadata);
+        mixins.extend(file.mixins);
+        resource_bindings.extend(file.resource_bindings);
+        applies.extend(file.applies);
+    }
+
+    validate_references(&shapes, &mixins)?;
+
+    // Mixins are flattened so the converter sees every inherited member
+    let mut flattened = HashSet::new();
+    let ids: Vec<String> = shapes.keys().cloned().collect();
+    for id in &ids {
+        flatten_mixins(id, &mut shapes, &mixins, &mut flattened);
+    }
+
+    // Elided members of shapes bound with `for` take their target from the resource
+    for (id, resource) in &resource_bindings {
+        let resource = shapes.get(resource).cloned();
+        if let (Some(resource), Some(Value::Object(shape))) = (resource, shapes.get_mut(id)) {
+            fill_elided_members(shape, &resource);
+        }
+    }
+
+    for (shape_id, member, traits) in applies {
+        let shape = shapes.get_mut(&shape_id).ok_or_else(|| {
+            GeneratorError::Parse(format!("Cannot apply traits to unknown shape {}", shape_id))
+        })?;
+        let owner = match &member {
+            Some(member) => shape.get_mut("members").and_then(|m| m.get_mut(member)),
+            None => Some(shape),
+        };
+        let Some(Value::Object(owner)) = owner else {
+            return Err(GeneratorError::Parse(format!(
+                "Cannot apply traits to unknown member {}${}",
+                shape_id,
+                member.unwrap_or_default()
+            )));
+        };
+        let existing = owner
+            .entry("traits")
+            .or_insert_with(|| Value::Object(Map::new()));
+        if let Value::Object(existing) = existing {
+            existing.extend(traits);
+        }
+    }
+
+    // Every elided member must have been resolved by now
+    for (id, shape) in &shapes {
+        if let Some(Value::Object(members)) = shape.get("members") {
+            for (member, value) in members {
+                if value.get("target").is_none() {
+                    return Err(GeneratorError::Parse(format!(
+                        "Cannot resolve target of elided member {}${}",
+                        id, member
+                    )));
+                }
+            }
+        }
+    }
+
+    for id in dependency_services {
+        shapes.remove(&id);
+    }
+
+    let mut model = Map::new();
+    model.insert("smithy".to_string(), Value::String(version));
+    model.insert(
+        "shapes".to_string(),
+        Value::Object(shapes.into_iter().collect()),
+    );
+    model.insert("metadata".to_string(), Value::Object(metadata));
+
+    serde_json::from_value(Value::Object(model))
+        .map_err(|e| GeneratorError::Parse(format!("Failed to build Smithy model: {}", e)))
+}
+
+/// Check that every shape reference and mixin points at a shape of the model set
+///
+/// Shapes that are neither defined in the set nor part of the `smithy.api`
+/// prelude usually live in a file that was not loaded.
+fn validate_references(
+    shapes: &HashMap<String, Value>,
+    mixins: &HashMap<String, Vec<String>>,
+) -> Result<()> {
+    let mut ids: Vec<&String> = shapes.keys().collect();
+    ids.sort();
+    for id in ids {
+        let mut targets = Vec::new();
+        collect_targets(&shapes[id], &mut targets);
+        targets.extend(mixins.get(id).into_iter().flatten().map(String::as_str));
+        let unresolved = targets
+            .into_iter()
+            .find(|target| !target.starts_with("smithy.api#") && !shapes.contains_key(*target));
+        if let Some(target) = unresolved {
+            return Err(GeneratorError::Parse(format!(
+                "Shape {} references unknown shape {}",
+                id, target
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Collect every `target` reference of a shape, skipping trait values
+fn collect_targets<'a>(value: &'a Value, targets: &mut Vec<&'a str>) {
+    match value {
+        Value::Object(map) => {
+            for (key, child) in map {
+                match (key.as_str(), child) {
+                    ("traits", _) => {},
+                    ("target", Value::String(target)) => targets.push(target),
+                    (_, child) => collect_targets(child, targets),
+                }
+            }
+        },
+        Value::Array(items) => items.iter().for_each(|item| collect_targets(item, targets)),
+        _ => {},
+    }
+}
+
+/// Parse a numeric literal into a JSON number
+fn parse_number(literal: &str) -> Option<Value> {
+    if literal.contains(['.', 'e', 'E']) {
+        literal
+            .parse::<f64>()
+            .ok()
+            .and_then(serde_json::Number::from_f64)
+            .map(Value::Number)
+    } else {
+        literal.parse::<i64>().ok().map(Value::from)
+    }
+}
+
+/// Resolves relative shape and trait IDs to absolute IDs
+struct Resolver<'a> {
+    namespace: &'a str,
+    /// Shapes of the namespace, from any file of the model set
+    local: &'a HashSet<String>,
+    uses: &'a HashMap<String, String>,
+}
+
+impl Resolver<'_> {
+    /// Resolve a shape ID: shapes of the namespace, then `use` imports, then the prelude
+    fn shape(&self, name: &str) -> String {
+        self.resolve(name, PRELUDE_SHAPES.contains(&name))
+    }
+
+    /// Resolve a trait ID; unknown traits are assumed to come from the prelude
+    fn trait_id(&self, name: &str) -> String {
+        self.resolve(name, true)
+    }
+
+    fn resolve(&self, name: &str, prelude: bool) -> String {
+        if name.contains('#') {
+            name.to_string()
+        } else if self.local.contains(name) {
+            format!("{}#{}", self.namespace, name)
+        } else if let Some(id) = self.uses.get(name) {
+            id.clone()
+        } else if prelude {
+            format!("smithy.api#{}", name)
+        } else {
+            format!("{}#{}", self.namespace, name)
+        }
+    }
+
+    /// Resolve every `target` reference and trait name in a shape
+    ///
+    /// Trait values are left untouched.
+    fn resolve_refs(&self, value: &mut Value) {
+        match value {
+            Value::Object(map) => {
+                for (key, child) in map.iter_mut() {
+                    match (key.as_str(), child) {
+                        ("traits", Value::Object(traits)) => {
+                            *traits = std::mem::take(traits)
+                                .into_iter()
+                                .map(|(name, value)| (self.trait_id(&name), value))
+                                .collect();
+                        },
+                        ("target", Value::String(target)) => *target = self.shape(target),
+                        (_, child) => self.resolve_refs(child),
+                    }
+                }
+            },
+            Value::Array(items) => items.iter_mut().for_each(|item| self.resolve_refs(item)),
+            _ => {},
+        }
+    }
+}
+
+/// Copy members and traits from a shape's mixins into the shape
+///
+/// Local members and traits take precedence over inherited ones. The `mixin`
+/// trait itself is not inherited.
+fn flatten_mixins(
+    id: &str,
+    shapes: &mut HashMap<String, Value>,
+    mixins: &HashMap<String, Vec<String>>,
+    flattened: &mut HashSet<String>,
+) {
+    if !flattened.insert(id.to_string()) {
+        return;
+    }
+    let Some(mixin_ids) = mixins.get(id) else {
+        return;
+    };
+
+    let mut inherited = Map::new();
+    let mut inherited_traits = Map::new();
+    for mixin_id in mixin_ids {
+        flatten_mixins(mixin_id, shapes, mixins, flattened);
+        let Some(mixin) = shapes.get(mixin_id) else {
+            continue;
+        };
+        if let Some(Value::Object(members)) = mixin.get("members") {
+            let entry = inherited
+                .entry("members")
+                .or_insert_with(|| Value::Object(Map::new()));
+            if let Value::Object(entry) = entry {
+                entry.extend(members.clone());
+            }
+        }
+        for key in ["member", "key", "value"] {
+            if let Some(member) = mixin.get(key) {
+                inherited.insert(key.to_string(), member.clone());
+            }
+        }
+        if let Some(Value::Object(traits)) = mixin.get("traits") {
+            for (name, value) in traits {
+                if name != "smithy.api#mixin" {
+                    inherited_traits.insert(name.clone(), value.clone());
+                }
+            }
+        }
+    }
+
+    let Some(Value::Object(shape)) = shapes.get_mut(id) else {
+        return;
+    };
+
+    if let Some(Value::Object(inherited_members)) = inherited.remove("members") {
+        let members = shape
+            .entry("members")
+            .or_insert_with(|| Value::Object(Map::new()));
+        if let Value::Object(members) = members {
+            for (name, inherited_member) in inherited_members {
+                let merged = match members.remove(&name) {
+                    Some(local) => merge_member(inherited_member, local),
+                    None => inherited_member,
+                };
+                members.insert(name, merged);
+            }
+        }
+    }
+    for (key, inherited_member) in inherited {
+        let merged = match shape.remove(&key) {
+            Some(local) => merge_member(inherited_member, local),
+            None => inherited_member,
+        };
+        shape.insert(key, merged);
+    }
+
+    if !inherited_traits.is_empty() {
+        let traits = shape
+            .entry("traits")
+            .or_insert_with(|| Value::Object(Map::new()));
+        if let Value::Object(traits) = traits {
+            for (name, value) in inherited_traits {
+                traits.entry(name).or_insert(value);
+            }
+        }
+    }
+}
+
+/// Merge a local member over an inherited one (target and traits)
+fn merge_member(inherited: Value, local: Value) -> Value {
+    let (Value::Object(mut merged), Value::Object(local)) = (inherited, local.clone()) else {
+        return local;
+    };
+    for (key, value) in local {
+        match (key.as_str(), merged.get_mut("traits"), value) {
+            ("traits", Some(Value::Object(traits)), Value::Object(local_traits)) => {
+                traits.extend(local_traits);
+            },
+            (_, _, value) => {
+                merged.insert(key, value);
+            },
+        }
+    }
+    Value::Object(merged)
+}
+
+/// Fill targets of elided members from a resource's identifiers and properties
+fn fill_elided_members(shape: &mut Map<String, Value>, resource: &Value) {
+    let Some(Value::Object(members)) = shape.get_mut("members") else {
+        return;
+    };
+    for (name, member) in members.iter_mut() {
+        let Value::Object(member) = member else {
+            continue;
+        };
+        if member.contains_key("target") {
+            continue;
+        }
+        let target = ["identifiers", "properties"]
+            .iter()
+            .find_map(|key| resource.get(key).and_then(|refs| refs.get(name)))
+            .and_then(|reference| reference.get("target"))
+            .cloned();
+        if let Some(target) = target {
+            member.insert("target".to_string(), target);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::smithy::Shape;
+
+    #[test]
+    fn test_tokenize_text_block() {
+        let tokens =
+            tokenize("@documentation(\"\"\"\n    Hello\n      World\n    \"\"\")").unwrap();
+        assert!(tokens
+            .iter()
+            .any(|t| t.kind == TokenKind::Str("Hello\n  World\n".to_string())));
+    }
+
+    #[test]
+    fn test_resolve_prelude_and_use() {
+        let model = parse_idl(
+            r#"
+            $version: "2"
+            namespace example.weather
+            use aws.api#arn
+
+            structure Forecast {
+                @required
+                chance: Float
+                city: City
+            }
+
+            string City
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(model.smithy, "2");
+        match model.get_shape("example.weather#Forecast") {
+            Some(Shape::Structure { members, .. }) => {
+                assert_eq!(members["chance"].target, "smithy.api#Float");
+                assert_eq!(members["city"].target, "example.weather#City");
+                assert!(members["chance"].traits.contains_key("smithy.api#required"));
+            },
+            other => panic!("Expected structure, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_mixins_and_elided_members() {
+        let model = parse_idl(
+            r#"
+            namespace example
+
+            @mixin
+            structure Tagged {
+                /// Resource tags
+                tags: TagList
+            }
+
+            list TagList {
+                member: String
+            }
+
+            resource Widget {
+                identifiers: { widgetId: String }
+            }
+
+            structure WidgetData for Widget with [Tagged] {
+                $widgetId
+                name: String
+            }
+            "#,
+        )
+        .unwrap();
+
+        match model.get_shape("example#WidgetData") {
+            Some(Shape::Structure { members, traits }) => {
+                assert_eq!(members["widgetId"].target, "smithy.api#String");
+                assert_eq!(members["tags"].target, "example#TagList");
+                assert_eq!(
+                    members["tags"].traits["smithy.api#documentation"],
+                    "Resource tags"
+                );
+                assert!(!traits.contains_key("smithy.api#mixin"));
+            },
+            other => panic!("Expected structure, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_merge_model_set_across_files() {
+        let model = parse_idl_files(
+            &[(
+                "weather.smithy",
+                r#"
+                namespace example.weather
+                use example.common#Tagged
+
+                structure Forecast with [Tagged] {
+                    city: CityName
+                    unit: example.common#Unit
+                }
+
+                apply CityName @length(min: 1)
+                "#,
+            )],
+            &[
+                (
+                    "common.smithy",
+                    r#"
+                    namespace example.common
+
+                    @mixin
+                    structure Tagged {
+                        tags: TagList
+                    }
+
+                    list TagList {
+                        member: String
+                    }
+
+                    enum Unit {
+                        CELSIUS
+                        FAHRENHEIT
+                    }
+
+                    service Common {}
+                    "#,
+                ),
+                (
+                    "names.smithy",
+                    "namespace example.weather
+
+string CityName
+",
+                ),
+            ],
+        )
+        .unwrap();
+
+        match model.get_shape("example.weather#Forecast") {
+            Some(Shape::Structure { members, .. }) => {
+                assert_eq!(members["city"].target, "example.weather#CityName");
+                assert_eq!(members["unit"].target, "example.common#Unit");
+                assert_eq!(members["tags"].target, "example.common#TagList");
+            },
+            other => panic!("Expected structure, got {:?}", other),
+        }
+        match model.get_shape("example.weather#CityName") {
+            Some(Shape::String { traits }) => {
+                assert!(traits.contains_key("smithy.api#length"));
+            },
+            other => panic!("Expected string, got {:?}", other),
+        }
+        // Services of dependencies are not part of the model
+        assert!(model.get_shape("example.common#Common").is_none());
+    }
+
+    #[test]
+    fn test_namespace_shapes_shadow_prelude_across_files() {
+        let model = parse_idl_files(
+            &[(
+                "weather.smithy",
+                "namespace example.weather
+
+structure Forecast {
+    unit: Unit
+    chance: Float
+}
+",
+            )],
+            &[(
+                "units.smithy",
+                "namespace example.weather
+
+enum Unit {
+    CELSIUS
+    FAHRENHEIT
+}
+",
+            )],
+        )
+        .unwrap();
+
+        match model.get_shape("example.weather#Forecast") {
+            Some(Shape::Structure { members, .. }) => {
+                assert_eq!(members["unit"].target, "example.weather#Unit");
+                assert_eq!(members["chance"].target, "smithy.api#Float");
+            },
+            other => panic!("Expected structure, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_unresolved_references_are_errors() {
+        let err = parse_idl(
+            "namespace example
+
+structure Foo {
+  bar: Missing
+}",
+        )
+        .unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("Shape example#Foo references unknown shape example#Missing"),
+            "{}",
+            err
+        );
+
+        let err = parse_idl(
+            "namespace example
+
+structure Foo with [Base] {}",
+        )
+        .unwrap_err();
+        assert!(
+            err.to_string().contains("unknown shape example#Base"),
+            "{}",
+            err
+        );
+
+        let err = parse_idl_files(
+            &[(
+                "a.smithy",
+                "namespace example
+string Name
+",
+            )],
+            &[(
+                "b.smithy",
+                "namespace example
+string Name
+",
+            )],
+        )
+        .unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("example#Name is defined more than once"),
+            "{}",
+            err
+        );
+
+        let err = parse_idl_files(
+            &[(
+                "a.smithy",
+                "namespace example
+string
+",
+            )],
+            &[],
+        )
+        .unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("a.smithy: Smithy IDL parse error on line 2"),
+            "{}",
+            err
+        );
+    }
+
+    #[test]
+    fn test_parse_error_reports_line() {
+        let err = parse_idl("namespace example\n\nstructure Foo {\n  bar String\n}").unwrap_err();
+        assert!(err.to_string().contains("line 4"), "{}", err);
+    }
+}
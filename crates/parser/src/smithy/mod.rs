@@ -1,6 +1,7 @@
 //! Smithy specification parser
 //!
-//! Parses AWS Smithy JSON AST format into ServiceDefinition IR.
+//! Parses AWS Smithy JSON AST format and Smithy IDL 2.0 (`.smithy`) files into
+//! ServiceDefinition IR.
 //!
 //! Smithy specs are available at: <https://github.com/aws/api-models-aws>
 //!
@@ -10,17 +11,27 @@
 //! - Shape definitions (structures, operations, primitives)
 //! - Traits (metadata like documentation, HTTP bindings)
 //!
+//! IDL files are parsed natively into the same `SmithyModel` as the JSON AST,
+//! so there is no need to run the Smithy CLI first. An IDL file and the files of
+//! its model path (see [`SmithyModelPath`]) form a model set: shapes and `use`
+//! imports may refer to shapes from any of them, and references to shapes
+//! missing from the set are errors.
+//!
 //! ## Usage
 //! ```rust,ignore
 //! use hemmer_provider_generator_parser::smithy::SmithyParser;
 //!
 //! let parser = SmithyParser::from_file("api-models-aws/s3/2006-03-01/s3-2006-03-01.json")?;
 //! let service_def = parser.parse()?;
+//!
+//! // Smithy IDL files are detected by their `.smithy` extension
+//! let parser = SmithyParser::from_file("model/weather.smithy", "weather", "1.0.0")?;
 //! ```
 
 mod converter;
+mod idl;
 mod parser;
 mod types;
 
-pub use parser::SmithyParser;
+pub use parser::{SmithyModelPath, SmithyParser};
 pub use types::*;
@@ -1,13 +1,16 @@
 //! Smithy spec file parser
 
+use super::idl::{self, UnresolvedFile};
 use super::types::SmithyModel;
-use hemmer_provider_generator_common::{GeneratorError, Result, ServiceDefinition};
+use crate::CrudRules;
+use hemmer_provider_generator_common::{GeneratorError, Provider, Result, ServiceDefinition};
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 /// Smithy specification parser
 ///
-/// Reads and parses Smithy JSON AST files from the AWS api-models-aws repository
+/// Reads and parses Smithy JSON AST files from the AWS api-models-aws repository,
+/// as well as Smithy IDL 2.0 `.smithy` files
 pub struct SmithyParser {
     /// Loaded Smithy model
     model: SmithyModel,
@@ -17,11 +20,78 @@ pub struct SmithyParser {
 
     /// SDK version
     sdk_version: String,
+
+    /// Rules classifying operations into CRUD roles
+    crud_rules: CrudRules,
+
+    /// Provider the service belongs to (AWS unless set)
+    provider: Provider,
+}
+
+/// Smithy IDL files that `.smithy` specs may reference shapes from
+///
+/// Built from the files and directories given as `--model-path`; each file is
+/// read and parsed once, so one model path can back any number of specs.
+#[derive(Debug, Clone, Default)]
+pub struct SmithyModelPath {
+    /// Parsed files by canonical path
+    files: Vec<(PathBuf, UnresolvedFile)>,
+}
+
+impl SmithyModelPath {
+    /// Load `.smithy` files, and the `.smithy` files directly inside directories
+    pub fn load(paths: &[PathBuf]) -> Result<Self> {
+        let mut sources = Vec::new();
+        for path in paths {
+            if path.is_dir() {
+                let mut entries: Vec<PathBuf> = fs::read_dir(path)
+                    .map_err(|e| read_error(path, e))?
+                    .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+                    .filter(|entry| entry.is_file() && is_idl_file(entry))
+                    .collect();
+                entries.sort();
+                sources.extend(entries);
+            } else {
+                sources.push(path.clone());
+            }
+        }
+
+        let mut files: Vec<(PathBuf, UnresolvedFile)> = Vec::new();
+        for source_path in sources {
+            let canonical = source_path
+                .canonicalize()
+                .map_err(|e| read_error(&source_path, e))?;
+            if files.iter().any(|(loaded, _)| *loaded == canonical) {
+                continue;
+            }
+            let source =
+                fs::read_to_string(&source_path).map_err(|e| read_error(&source_path, e))?;
+            let file = idl::parse_idl_file(&source_path.display().to_string(), &source)?;
+            files.push((canonical, file));
+        }
+
+        Ok(Self { files })
+    }
+
+    /// Files of the model path other than `path`
+    fn dependencies_of(&self, path: &Path) -> Vec<UnresolvedFile> {
+        let canonical = path.canonicalize().ok();
+        self.files
+            .iter()
+            .filter(|(loaded, _)| Some(loaded) != canonical.as_ref())
+            .map(|(_, file)| file.clone())
+            .collect()
+    }
 }
 
 impl SmithyParser {
     /// Load Smithy model from file path
     ///
+    /// Files with a `.smithy` extension are parsed as Smithy IDL, everything
+    /// else as the JSON AST. IDL files may only reference shapes defined in the
+    /// file itself; see [`SmithyParser::from_file_with_model_path`] for shapes
+    /// shared across files.
+    ///
     /// # Example
     /// ```rust,ignore
     /// let parser = SmithyParser::from_file(
@@ -35,15 +105,35 @@ impl SmithyParser {
         service_name: &str,
         sdk_version: &str,
     ) -> Result<Self> {
-        let content = fs::read_to_string(path.as_ref()).map_err(|e| {
-            GeneratorError::Parse(format!(
-                "Failed to read Smithy file {}: {}",
-                path.as_ref().display(),
-                e
-            ))
-        })?;
-
-        Self::from_json(&content, service_name, sdk_version)
+        Self::from_file_with_model_path(
+            path,
+            &SmithyModelPath::default(),
+            service_name,
+            sdk_version,
+        )
+    }
+
+    /// Load Smithy model from file path, resolving IDL shapes against a model path
+    ///
+    /// The IDL file and the files of `model_path` form one model set, so shapes
+    /// may be shared across files; only the services defined in `path` itself
+    /// are kept. JSON AST files are self-contained and ignore the model path.
+    pub fn from_file_with_model_path<P: AsRef<Path>>(
+        path: P,
+        model_path: &SmithyModelPath,
+        service_name: &str,
+        sdk_version: &str,
+    ) -> Result<Self> {
+        let path = path.as_ref();
+        let content = fs::read_to_string(path).map_err(|e| read_error(path, e))?;
+
+        if is_idl_file(path) {
+            let file = idl::parse_idl_file(&path.display().to_string(), &content)?;
+            let model = idl::build_model(&[file], &model_path.dependencies_of(path))?;
+            Ok(Self::from_model(model, service_name, sdk_version))
+        } else {
+            Self::from_json(&content, service_name, sdk_version)
+        }
     }
 
     /// Parse Smithy model from JSON string
@@ -51,11 +141,38 @@ impl SmithyParser {
         let model: SmithyModel = serde_json::from_str(json)
             .map_err(|e| GeneratorError::Parse(format!("Failed to parse Smithy JSON: {}", e)))?;
 
-        Ok(Self {
+        Ok(Self::from_model(model, service_name, sdk_version))
+    }
+
+    /// Parse Smithy model from Smithy IDL 2.0 source
+    ///
+    /// Produces the same `SmithyModel` as the equivalent JSON AST.
+    pub fn from_idl(idl: &str, service_name: &str, sdk_version: &str) -> Result<Self> {
+        let model = idl::parse_idl(idl)?;
+
+        Ok(Self::from_model(model, service_name, sdk_version))
+    }
+
+    fn from_model(model: SmithyModel, service_name: &str, sdk_version: &str) -> Self {
+        Self {
             model,
             service_name: service_name.to_string(),
             sdk_version: sdk_version.to_string(),
-        })
+            crud_rules: CrudRules::default(),
+            provider: Provider::Aws,
+        }
+    }
+
+    /// Classify operations with the given rules instead of the built-in ones
+    pub fn with_crud_rules(mut self, crud_rules: CrudRules) -> Self {
+        self.crud_rules = crud_rules;
+        self
+    }
+
+    /// Set the provider of the service, which also selects the CRUD rules that apply
+    pub fn with_provider(mut self, provider: Provider) -> Self {
+        self.provider = provider;
+        self
     }
 
     /// Parse Smithy model into ServiceDefinition IR
@@ -65,6 +182,8 @@ impl SmithyParser {
             &self.model,
             &self.service_name,
             &self.sdk_version,
+            &self.provider,
+            &self.crud_rules,
         )
     }
 
@@ -74,6 +193,20 @@ impl SmithyParser {
     }
 }
 
+/// Whether a file is Smithy IDL rather than the JSON AST
+fn is_idl_file(path: &Path) -> bool {
+    path.extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("smithy"))
+}
+
+fn read_error(path: &Path, e: std::io::Error) -> GeneratorError {
+    GeneratorError::Parse(format!(
+        "Failed to read Smithy file {}: {}",
+        path.display(),
+        e
+    ))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
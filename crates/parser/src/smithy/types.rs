@@ -73,6 +73,40 @@ pub enum Shape {
         traits: HashMap<String, serde_json::Value>,
     },
 
+    /// Union definition (exactly one member is set)
+    Union {
+        /// Union variants
+        #[serde(default)]
+        members: HashMap<String, Member>,
+
+        /// Traits (metadata)
+        #[serde(default)]
+        traits: HashMap<String, serde_json::Value>,
+    },
+
+    /// String enum definition
+    Enum {
+        /// Enum variants (values come from the `enumValue` trait)
+        #[serde(default)]
+        members: HashMap<String, Member>,
+
+        /// Traits (metadata)
+        #[serde(default)]
+        traits: HashMap<String, serde_json::Value>,
+    },
+
+    /// Integer enum definition
+    #[serde(rename = "intEnum")]
+    IntEnum {
+        /// Enum variants (values come from the `enumValue` trait)
+        #[serde(default)]
+        members: HashMap<String, Member>,
+
+        /// Traits (metadata)
+        #[serde(default)]
+        traits: HashMap<String, serde_json::Value>,
+    },
+
     /// String type
     String {
         /// Traits (metadata)
@@ -80,6 +114,27 @@ pub enum Shape {
         traits: HashMap<String, serde_json::Value>,
     },
 
+    /// Blob type (binary data)
+    Blob {
+        /// Traits (metadata)
+        #[serde(default)]
+        traits: HashMap<String, serde_json::Value>,
+    },
+
+    /// Byte type (8-bit integer)
+    Byte {
+        /// Traits (metadata)
+        #[serde(default)]
+        traits: HashMap<String, serde_json::Value>,
+    },
+
+    /// Short type (16-bit integer)
+    Short {
+        /// Traits (metadata)
+        #[serde(default)]
+        traits: HashMap<String, serde_json::Value>,
+    },
+
     /// Integer type
     Integer {
         /// Traits (metadata)
@@ -101,13 +156,43 @@ pub enum Shape {
         traits: HashMap<String, serde_json::Value>,
     },
 
-    /// Double/Float type
+    /// Float type (32-bit)
+    Float {
+        /// Traits (metadata)
+        #[serde(default)]
+        traits: HashMap<String, serde_json::Value>,
+    },
+
+    /// Double type (64-bit)
     Double {
         /// Traits (metadata)
         #[serde(default)]
         traits: HashMap<String, serde_json::Value>,
     },
 
+    /// Arbitrary precision integer
+    #[serde(rename = "bigInteger")]
+    BigInteger {
+        /// Traits (metadata)
+        #[serde(default)]
+        traits: HashMap<String, serde_json::Value>,
+    },
+
+    /// Arbitrary precision decimal
+    #[serde(rename = "bigDecimal")]
+    BigDecimal {
+        /// Traits (metadata)
+        #[serde(default)]
+        traits: HashMap<String, serde_json::Value>,
+    },
+
+    /// Document type (untyped JSON-like data)
+    Document {
+        /// Traits (metadata)
+        #[serde(default)]
+        traits: HashMap<String, serde_json::Value>,
+    },
+
     /// Timestamp type
     Timestamp {
         /// Traits (metadata)
@@ -168,6 +253,18 @@ pub enum Shape {
         #[serde(default)]
         put: Option<ShapeReference>,
 
+        /// Non-lifecycle instance operations bound to this resource
+        #[serde(default)]
+        operations: Vec<ShapeReference>,
+
+        /// Non-lifecycle collection operations bound to this resource
+        #[serde(default, rename = "collectionOperations")]
+        collection_operations: Vec<ShapeReference>,
+
+        /// Child resources bound to this resource
+        #[serde(default)]
+        resources: Vec<ShapeReference>,
+
         /// Traits (metadata)
         #[serde(default)]
         traits: HashMap<String, serde_json::Value>,
@@ -196,6 +293,37 @@ pub struct Member {
     pub traits: HashMap<String, serde_json::Value>,
 }
 
+impl Shape {
+    /// Traits applied to the shape
+    pub fn traits(&self) -> Option<&HashMap<String, serde_json::Value>> {
+        match self {
+            Shape::Service { traits, .. }
+            | Shape::Operation { traits, .. }
+            | Shape::Structure { traits, .. }
+            | Shape::Union { traits, .. }
+            | Shape::Enum { traits, .. }
+            | Shape::IntEnum { traits, .. }
+            | Shape::String { traits }
+            | Shape::Blob { traits }
+            | Shape::Byte { traits }
+            | Shape::Short { traits }
+            | Shape::Integer { traits }
+            | Shape::Long { traits }
+            | Shape::Boolean { traits }
+            | Shape::Float { traits }
+            | Shape::Double { traits }
+            | Shape::BigInteger { traits }
+            | Shape::BigDecimal { traits }
+            | Shape::Document { traits }
+            | Shape::Timestamp { traits }
+            | Shape::List { traits, .. }
+            | Shape::Map { traits, .. }
+            | Shape::Resource { traits, .. } => Some(traits),
+            Shape::Other => None,
+        }
+    }
+}
+
 impl SmithyModel {
     /// Find the service shape in the model
     pub fn find_service(&self) -> Option<(&String, &Shape)> {
@@ -231,4 +359,15 @@ pub mod traits {
     pub const HTTP_PAYLOAD: &str = "smithy.api#httpPayload";
     pub const IDEMPOTENT: &str = "smithy.api#idempotent";
     pub const PAGINATED: &str = "smithy.api#paginated";
+    pub const IDEMPOTENCY_TOKEN: &str = "smithy.api#idempotencyToken";
+    pub const WAITABLE: &str = "smithy.waiters#waitable";
+    pub const ENUM_VALUE: &str = "smithy.api#enumValue";
+    /// Smithy 1.0 enum trait on string shapes
+    pub const ENUM: &str = "smithy.api#enum";
+    pub const RANGE: &str = "smithy.api#range";
+    pub const LENGTH: &str = "smithy.api#length";
+    pub const PATTERN: &str = "smithy.api#pattern";
+    pub const DEFAULT: &str = "smithy.api#default";
+    pub const DEPRECATED: &str = "smithy.api#deprecated";
+    pub const UNSTABLE: &str = "smithy.api#unstable";
 }
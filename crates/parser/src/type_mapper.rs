@@ -28,9 +28,16 @@ impl TypeMapper {
 
         match type_str {
             "String" | "&str" | "str" => FieldType::String,
-            "i32" | "i64" | "u32" | "u64" => FieldType::Integer,
+            "i8" => FieldType::Int8,
+            "i16" => FieldType::Int16,
+            "i32" => FieldType::Int32,
+            "i64" => FieldType::Integer,
+            "u32" => FieldType::UInt32,
+            "u64" => FieldType::UInt64,
             "f32" | "f64" => FieldType::Float,
             "bool" => FieldType::Boolean,
+            "Vec<u8>" | "Blob" => FieldType::Bytes,
+            "Document" => FieldType::Document,
             s if s.starts_with("Vec<") => {
                 let inner = s
                     .strip_prefix("Vec<")
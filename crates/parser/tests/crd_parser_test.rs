@@ -0,0 +1,499 @@
+//! Integration tests for CustomResourceDefinition parsing
+
+use hemmer_provider_generator_common::{FieldType, NestingMode, Provider, ServiceDefinition};
+use hemmer_provider_generator_parser::crd::CrdParser;
+
+fn parse_crds(yaml: &str) -> ServiceDefinition {
+    CrdParser::from_yaml(yaml, "cert_manager", "v1.14.0")
+        .unwrap()
+        .parse()
+        .unwrap()
+}
+
+#[test]
+fn test_parse_crd_multi_document_yaml() {
+    let crds_yaml = r#"
+apiVersion: apiextensions.k8s.io/v1
+kind: CustomResourceDefinition
+metadata:
+  name: certificates.cert-manager.io
+spec:
+  group: cert-manager.io
+  names:
+    kind: Certificate
+    plural: certificates
+  scope: Namespaced
+  versions:
+    - name: v1
+      served: true
+      storage: true
+      schema:
+        openAPIV3Schema:
+          description: A Certificate resource should be created to ensure an up to date and signed X.509 certificate is stored in the Kubernetes Secret resource named in `spec.secretName`.
+          type: object
+          properties:
+            apiVersion:
+              type: string
+            kind:
+              type: string
+            metadata:
+              type: object
+---
+apiVersion: apiextensions.k8s.io/v1
+kind: CustomResourceDefinition
+metadata:
+  name: issuers.cert-manager.io
+spec:
+  group: cert-manager.io
+  names:
+    kind: Issuer
+    plural: issuers
+  scope: Namespaced
+  versions:
+    - name: v1
+      served: true
+      storage: true
+      schema:
+        openAPIV3Schema:
+          type: object
+"#;
+
+    let service_def = parse_crds(crds_yaml);
+
+    assert_eq!(service_def.provider, Provider::Kubernetes);
+    assert_eq!(service_def.name, "cert_manager");
+
+    // Every document becomes its own resource
+    let names: Vec<&str> = service_def
+        .resources
+        .iter()
+        .map(|r| r.name.as_str())
+        .collect();
+    assert_eq!(names, vec!["certificate", "issuer"]);
+
+    let certificate = &service_def.resources[0];
+    assert!(certificate
+        .description
+        .as_deref()
+        .unwrap()
+        .starts_with("A Certificate resource"));
+    let field = |name: &str| certificate.fields.iter().find(|f| f.name == name);
+    assert_eq!(
+        field("api_version").unwrap().field_type,
+        FieldType::Enum(vec!["cert-manager.io/v1".to_string()])
+    );
+    assert_eq!(
+        field("kind").unwrap().field_type,
+        FieldType::Enum(vec!["Certificate".to_string()])
+    );
+    assert!(field("metadata").is_none());
+
+    let issuer = &service_def.resources[1];
+    assert!(issuer
+        .fields
+        .iter()
+        .any(|f| f.name == "kind" && f.field_type == FieldType::Enum(vec!["Issuer".to_string()])));
+
+    println!("✅ Successfully parsed multi-document CRD YAML!");
+    println!("   Resources: {}", service_def.resources.len());
+}
+
+#[test]
+fn test_parse_crd_served_versions() {
+    let crds_yaml = r#"
+apiVersion: apiextensions.k8s.io/v1
+kind: CustomResourceDefinition
+metadata:
+  name: certificates.cert-manager.io
+spec:
+  group: cert-manager.io
+  names:
+    kind: Certificate
+    plural: certificates
+  scope: Namespaced
+  versions:
+    - name: v1
+      served: true
+      storage: true
+      schema:
+        openAPIV3Schema:
+          type: object
+    - name: v1alpha2
+      served: false
+      storage: false
+---
+apiVersion: apiextensions.k8s.io/v1
+kind: CustomResourceDefinition
+metadata:
+  name: clusterissuers.cert-manager.io
+spec:
+  group: cert-manager.io
+  names:
+    kind: ClusterIssuer
+    plural: clusterissuers
+  scope: Cluster
+  versions:
+    - name: v1
+      served: true
+      storage: true
+      schema:
+        openAPIV3Schema:
+          type: object
+    - name: v1beta1
+      served: true
+      storage: false
+      schema:
+        openAPIV3Schema:
+          type: object
+          x-kubernetes-preserve-unknown-fields: true
+"#;
+
+    let service_def = parse_crds(crds_yaml);
+
+    // Unserved versions are skipped; multiple served versions get a suffix
+    let names: Vec<&str> = service_def
+        .resources
+        .iter()
+        .map(|r| r.name.as_str())
+        .collect();
+    assert_eq!(
+        names,
+        vec!["certificate", "cluster_issuer_v1", "cluster_issuer_v1beta1"]
+    );
+
+    let api_version = |index: usize| {
+        service_def.resources[index]
+            .fields
+            .iter()
+            .find(|f| f.name == "api_version")
+            .unwrap()
+            .field_type
+            .clone()
+    };
+    assert_eq!(
+        api_version(1),
+        FieldType::Enum(vec!["cert-manager.io/v1".to_string()])
+    );
+    assert_eq!(
+        api_version(2),
+        FieldType::Enum(vec!["cert-manager.io/v1beta1".to_string()])
+    );
+
+    println!("✅ Successfully parsed CRD served versions!");
+    println!("   Resources: {:?}", names);
+}
+
+#[test]
+fn test_parse_crd_kubernetes_schema_extensions() {
+    let crds_yaml = r#"
+apiVersion: apiextensions.k8s.io/v1
+kind: CustomResourceDefinition
+metadata:
+  name: certificates.cert-manager.io
+spec:
+  group: cert-manager.io
+  names:
+    kind: Certificate
+    plural: certificates
+  scope: Namespaced
+  versions:
+    - name: v1
+      served: true
+      storage: true
+      schema:
+        openAPIV3Schema:
+          type: object
+          properties:
+            spec:
+              type: object
+              properties:
+                keystores:
+                  type: object
+                  x-kubernetes-preserve-unknown-fields: true
+                port:
+                  x-kubernetes-int-or-string: true
+"#;
+
+    let service_def = parse_crds(crds_yaml);
+    let spec = service_def.resources[0]
+        .blocks
+        .iter()
+        .find(|b| b.name == "spec")
+        .expect("Should have spec block");
+    let attribute = |name: &str| spec.attributes.iter().find(|a| a.name == name).unwrap();
+
+    // int-or-string is carried as a string
+    assert_eq!(attribute("port").field_type, FieldType::String);
+    // preserve-unknown-fields stays a free-form object instead of a block
+    assert!(matches!(
+        attribute("keystores").field_type,
+        FieldType::Object(ref props) if props.is_empty()
+    ));
+    assert!(spec.blocks.iter().all(|b| b.name != "keystores"));
+
+    println!("✅ Successfully parsed Kubernetes schema extensions!");
+}
+
+#[test]
+fn test_parse_crd_spec_blocks() {
+    let crds_yaml = r#"
+apiVersion: apiextensions.k8s.io/v1
+kind: CustomResourceDefinition
+metadata:
+  name: certificates.cert-manager.io
+spec:
+  group: cert-manager.io
+  names:
+    kind: Certificate
+    plural: certificates
+  scope: Namespaced
+  versions:
+    - name: v1
+      served: true
+      storage: true
+      schema:
+        openAPIV3Schema:
+          type: object
+          properties:
+            spec:
+              type: object
+              required:
+                - issuerRef
+                - secretName
+              properties:
+                secretName:
+                  type: string
+                dnsNames:
+                  type: array
+                  items:
+                    type: string
+                issuerRef:
+                  type: object
+                  required:
+                    - name
+                  properties:
+                    name:
+                      type: string
+                    kind:
+                      type: string
+                usages:
+                  type: array
+                  items:
+                    type: string
+                    enum:
+                      - digital signature
+                      - key encipherment
+                      - server auth
+                additionalOutputFormats:
+                  type: array
+                  items:
+                    type: object
+                    properties:
+                      type:
+                        type: string
+                secretTemplate:
+                  type: object
+                  properties:
+                    labels:
+                      type: object
+                      additionalProperties:
+                        type: string
+"#;
+
+    let service_def = parse_crds(crds_yaml);
+
+    // spec becomes a block with nested blocks
+    let spec = service_def.resources[0]
+        .blocks
+        .iter()
+        .find(|b| b.name == "spec")
+        .expect("Should have spec block");
+    assert_eq!(spec.nesting_mode, NestingMode::Single);
+    let attribute = |name: &str| spec.attributes.iter().find(|a| a.name == name).unwrap();
+    assert!(attribute("secret_name").required);
+    assert_eq!(
+        attribute("dns_names").field_type,
+        FieldType::List(Box::new(FieldType::String))
+    );
+    assert!(matches!(
+        attribute("usages").field_type,
+        FieldType::List(ref inner) if matches!(**inner, FieldType::Enum(_))
+    ));
+
+    let issuer_ref = spec.blocks.iter().find(|b| b.name == "issuer_ref").unwrap();
+    assert_eq!(issuer_ref.min_items, 1);
+    assert!(issuer_ref
+        .attributes
+        .iter()
+        .any(|a| a.name == "name" && a.required));
+    let formats = spec
+        .blocks
+        .iter()
+        .find(|b| b.name == "additional_output_formats")
+        .unwrap();
+    assert_eq!(formats.nesting_mode, NestingMode::List);
+    let secret_template = spec
+        .blocks
+        .iter()
+        .find(|b| b.name == "secret_template")
+        .unwrap();
+    assert!(matches!(
+        secret_template.attributes[0].field_type,
+        FieldType::Map(_, _)
+    ));
+
+    println!("✅ Successfully parsed CRD spec blocks!");
+    println!("   Spec blocks: {}", spec.blocks.len());
+}
+
+#[test]
+fn test_parse_crd_status_as_outputs() {
+    let crds_yaml = r#"
+apiVersion: apiextensions.k8s.io/v1
+kind: CustomResourceDefinition
+metadata:
+  name: certificates.cert-manager.io
+spec:
+  group: cert-manager.io
+  names:
+    kind: Certificate
+    plural: certificates
+  scope: Namespaced
+  versions:
+    - name: v1
+      served: true
+      storage: true
+      subresources:
+        status: {}
+      schema:
+        openAPIV3Schema:
+          type: object
+          properties:
+            status:
+              type: object
+              properties:
+                notAfter:
+                  type: string
+                  format: date-time
+                revision:
+                  type: integer
+                conditions:
+                  type: array
+                  items:
+                    type: object
+                    properties:
+                      type:
+                        type: string
+                      status:
+                        type: string
+---
+apiVersion: apiextensions.k8s.io/v1
+kind: CustomResourceDefinition
+metadata:
+  name: clusterissuers.cert-manager.io
+spec:
+  group: cert-manager.io
+  names:
+    kind: ClusterIssuer
+    plural: clusterissuers
+  scope: Cluster
+  versions:
+    - name: v1
+      served: true
+      storage: true
+      schema:
+        openAPIV3Schema:
+          type: object
+          properties:
+            status:
+              type: object
+              properties:
+                ready:
+                  type: boolean
+"#;
+
+    let service_def = parse_crds(crds_yaml);
+
+    // The status subresource maps to outputs
+    let certificate = &service_def.resources[0];
+    assert!(certificate.blocks.iter().all(|b| b.name != "status"));
+    let output = |name: &str| certificate.outputs.iter().find(|o| o.name == name).unwrap();
+    assert_eq!(output("not_after").field_type, FieldType::DateTime);
+    assert_eq!(output("revision").field_type, FieldType::Integer);
+    assert!(output("conditions").response_accessor.is_some());
+
+    // Without a status subresource, status stays an input
+    let cluster_issuer = &service_def.resources[1];
+    assert!(cluster_issuer.blocks.iter().any(|b| b.name == "status"));
+    assert!(cluster_issuer.outputs.is_empty());
+
+    println!("✅ Successfully mapped CRD status to outputs!");
+    println!("   Certificate outputs: {}", certificate.outputs.len());
+}
+
+#[test]
+fn test_parse_crd_cluster_and_namespaced_scope() {
+    let crds_yaml = r#"
+apiVersion: apiextensions.k8s.io/v1
+kind: CustomResourceDefinition
+metadata:
+  name: certificates.cert-manager.io
+spec:
+  group: cert-manager.io
+  names:
+    kind: Certificate
+    plural: certificates
+  scope: Namespaced
+  versions:
+    - name: v1
+      served: true
+      storage: true
+      schema:
+        openAPIV3Schema:
+          type: object
+---
+apiVersion: apiextensions.k8s.io/v1
+kind: CustomResourceDefinition
+metadata:
+  name: clusterissuers.cert-manager.io
+spec:
+  group: cert-manager.io
+  names:
+    kind: ClusterIssuer
+    plural: clusterissuers
+  scope: Cluster
+  versions:
+    - name: v1
+      served: true
+      storage: true
+      schema:
+        openAPIV3Schema:
+          type: object
+"#;
+
+    let service_def = parse_crds(crds_yaml);
+
+    // Namespaced resources require a namespace and use the namespaced API
+    let certificate = &service_def.resources[0];
+    let field = |name: &str| certificate.fields.iter().find(|f| f.name == name);
+    assert!(field("name").unwrap().required);
+    assert!(field("namespace").unwrap().required);
+    let create = certificate.operations.create.as_ref().unwrap();
+    assert_eq!(create.sdk_operation, "create_namespaced_custom_object");
+
+    // Cluster-scoped resources have no namespace
+    let cluster_issuer = &service_def.resources[1];
+    assert!(cluster_issuer.fields.iter().any(|f| f.name == "name"));
+    assert!(cluster_issuer.fields.iter().all(|f| f.name != "namespace"));
+    assert_eq!(
+        cluster_issuer
+            .operations
+            .read
+            .as_ref()
+            .unwrap()
+            .sdk_operation,
+        "get_cluster_custom_object"
+    );
+
+    println!("✅ Successfully parsed CRD scopes!");
+}
@@ -1,5 +1,6 @@
 //! Integration test for Discovery parser
 
+use hemmer_provider_generator_common::Stability;
 use hemmer_provider_generator_parser::discovery::DiscoveryParser;
 
 #[test]
@@ -200,3 +201,506 @@ fn test_parse_gcs_discovery() {
         bucket.operations.delete.is_some()
     );
 }
+
+#[test]
+fn test_parse_discovery_parameters_id_and_required_annotations() {
+    // Simplified Compute Engine Discovery document
+    let discovery_json = r##"{
+        "discoveryVersion": "v1",
+        "name": "compute",
+        "version": "v1",
+        "title": "Compute Engine API",
+        "rootUrl": "https://compute.googleapis.com/",
+        "servicePath": "compute/v1/",
+        "schemas": {
+            "Instance": {
+                "id": "Instance",
+                "type": "object",
+                "properties": {
+                    "name": {
+                        "type": "string",
+                        "annotations": {
+                            "required": ["compute.instances.insert"]
+                        }
+                    },
+                    "machineType": {
+                        "type": "string",
+                        "annotations": {
+                            "required": ["compute.instances.insert"]
+                        }
+                    },
+                    "description": {
+                        "type": "string"
+                    },
+                    "zone": {
+                        "type": "string",
+                        "description": "URL of the zone (output only)"
+                    }
+                }
+            }
+        },
+        "resources": {
+            "instances": {
+                "methods": {
+                    "insert": {
+                        "id": "compute.instances.insert",
+                        "path": "projects/{project}/zones/{zone}/instances",
+                        "httpMethod": "POST",
+                        "parameters": {
+                            "project": {
+                                "type": "string",
+                                "required": true,
+                                "location": "path"
+                            },
+                            "zone": {
+                                "type": "string",
+                                "description": "The name of the zone",
+                                "required": true,
+                                "location": "path"
+                            },
+                            "requestId": {
+                                "type": "string",
+                                "location": "query"
+                            },
+                            "sourceInstanceTemplate": {
+                                "type": "string",
+                                "location": "query"
+                            }
+                        },
+                        "parameterOrder": ["project", "zone"],
+                        "request": { "$ref": "Instance" }
+                    },
+                    "get": {
+                        "id": "compute.instances.get",
+                        "path": "projects/{project}/zones/{zone}/instances/{instance}",
+                        "httpMethod": "GET",
+                        "parameters": {
+                            "project": { "type": "string", "required": true, "location": "path" },
+                            "zone": { "type": "string", "required": true, "location": "path" },
+                            "instance": { "type": "string", "required": true, "location": "path" }
+                        },
+                        "parameterOrder": ["project", "zone", "instance"],
+                        "response": { "$ref": "Instance" }
+                    }
+                }
+            }
+        }
+    }"##;
+
+    let parser = DiscoveryParser::from_json(discovery_json, "compute", "v1").unwrap();
+    let service_def = parser.parse().unwrap();
+    let instance = &service_def.resources[0];
+
+    // The get method's trailing path parameter identifies the resource
+    assert_eq!(instance.id_field.as_deref(), Some("instance"));
+
+    let field = |name: &str| instance.fields.iter().find(|f| f.name == name).unwrap();
+
+    // Path parameters are required, immutable inputs
+    let project = field("project");
+    assert!(project.required && project.immutable);
+    assert_eq!(project.location.as_deref(), Some("path"));
+
+    // The zone path parameter replaces the output-only body property
+    assert_eq!(
+        instance.fields.iter().filter(|f| f.name == "zone").count(),
+        1
+    );
+    assert_eq!(field("zone").location.as_deref(), Some("path"));
+    assert_eq!(
+        field("zone").description.as_deref(),
+        Some("The name of the zone")
+    );
+
+    // Optional query parameters stay optional and mutable
+    let template = field("source_instance_template");
+    assert!(!template.required && !template.immutable);
+    assert_eq!(template.location.as_deref(), Some("query"));
+
+    // annotations.required marks properties required for the insert method
+    assert!(field("name").required);
+    assert!(field("machine_type").required);
+    assert!(!field("description").required);
+    assert_eq!(field("name").location, None);
+
+    println!("✅ Successfully parsed Discovery parameters and annotations!");
+}
+
+#[test]
+fn test_parse_discovery_operation_polling() {
+    let discovery_json = r##"{
+        "discoveryVersion": "v1",
+        "name": "compute",
+        "version": "v1",
+        "title": "Compute Engine API",
+        "rootUrl": "https://compute.googleapis.com/",
+        "servicePath": "compute/v1/",
+        "schemas": {
+            "Operation": {
+                "id": "Operation",
+                "type": "object",
+                "properties": {
+                    "name": { "type": "string" },
+                    "status": { "type": "string", "enum": ["DONE", "PENDING", "RUNNING"] }
+                }
+            },
+            "Disk": {
+                "id": "Disk",
+                "type": "object",
+                "properties": { "name": { "type": "string" } }
+            },
+            "Network": {
+                "id": "Network",
+                "type": "object",
+                "properties": { "name": { "type": "string" } }
+            }
+        },
+        "resources": {
+            "disks": {
+                "methods": {
+                    "insert": {
+                        "id": "compute.disks.insert",
+                        "path": "projects/{project}/zones/{zone}/disks",
+                        "httpMethod": "POST",
+                        "parameters": {
+                            "project": { "type": "string", "required": true, "location": "path" },
+                            "zone": { "type": "string", "required": true, "location": "path" }
+                        },
+                        "parameterOrder": ["project", "zone"],
+                        "request": { "$ref": "Disk" },
+                        "response": { "$ref": "Operation" }
+                    },
+                    "get": {
+                        "id": "compute.disks.get",
+                        "path": "projects/{project}/zones/{zone}/disks/{disk}",
+                        "httpMethod": "GET",
+                        "response": { "$ref": "Disk" }
+                    }
+                }
+            },
+            "networks": {
+                "methods": {
+                    "insert": {
+                        "id": "compute.networks.insert",
+                        "path": "projects/{project}/global/networks",
+                        "httpMethod": "POST",
+                        "parameters": {
+                            "project": { "type": "string", "required": true, "location": "path" }
+                        },
+                        "request": { "$ref": "Network" },
+                        "response": { "$ref": "Operation" }
+                    }
+                }
+            },
+            "zoneOperations": {
+                "methods": {
+                    "get": {
+                        "id": "compute.zoneOperations.get",
+                        "path": "projects/{project}/zones/{zone}/operations/{operation}",
+                        "httpMethod": "GET",
+                        "parameterOrder": ["project", "zone", "operation"],
+                        "response": { "$ref": "Operation" }
+                    }
+                }
+            },
+            "globalOperations": {
+                "methods": {
+                    "get": {
+                        "id": "compute.globalOperations.get",
+                        "path": "projects/{project}/global/operations/{operation}",
+                        "httpMethod": "GET",
+                        "parameterOrder": ["project", "operation"],
+                        "response": { "$ref": "Operation" }
+                    }
+                }
+            }
+        }
+    }"##;
+
+    let parser = DiscoveryParser::from_json(discovery_json, "compute", "v1").unwrap();
+    let service_def = parser.parse().unwrap();
+    let resource = |name: &str| {
+        service_def
+            .resources
+            .iter()
+            .find(|r| r.name == name)
+            .unwrap()
+    };
+
+    // Zonal methods are polled through zoneOperations
+    let disk = resource("disk");
+    let create = disk.operations.create.as_ref().unwrap();
+    let long_running = create
+        .long_running
+        .as_ref()
+        .expect("Insert returns an Operation");
+    assert_eq!(
+        long_running.operations_resource.as_deref(),
+        Some("zone_operations")
+    );
+    assert_eq!(long_running.operation_scope, vec!["project", "zone"]);
+    assert!(disk
+        .operations
+        .read
+        .as_ref()
+        .unwrap()
+        .long_running
+        .is_none());
+
+    // Global methods are polled through globalOperations
+    let network = resource("network");
+    let long_running = network
+        .operations
+        .create
+        .as_ref()
+        .unwrap()
+        .long_running
+        .as_ref();
+    assert_eq!(
+        long_running.unwrap().operations_resource.as_deref(),
+        Some("global_operations")
+    );
+    assert_eq!(long_running.unwrap().operation_scope, vec!["project"]);
+
+    println!("✅ Successfully detected Discovery operation polling!");
+}
+
+#[test]
+fn test_parse_discovery_data_sources() {
+    let discovery_json = r##"{
+        "discoveryVersion": "v1",
+        "name": "compute",
+        "version": "v1",
+        "title": "Compute Engine API",
+        "rootUrl": "https://compute.googleapis.com/",
+        "servicePath": "compute/v1/",
+        "schemas": {
+            "Image": {
+                "id": "Image",
+                "type": "object",
+                "properties": {
+                    "name": { "type": "string" },
+                    "family": { "type": "string" },
+                    "diskSizeGb": { "type": "string", "format": "int64" }
+                }
+            },
+            "ImageList": {
+                "id": "ImageList",
+                "type": "object",
+                "properties": {
+                    "kind": { "type": "string" },
+                    "items": { "type": "array", "items": { "$ref": "Image" } },
+                    "nextPageToken": { "type": "string" }
+                }
+            }
+        },
+        "resources": {
+            "images": {
+                "methods": {
+                    "get": {
+                        "id": "compute.images.get",
+                        "path": "projects/{project}/global/images/{image}",
+                        "httpMethod": "GET",
+                        "parameters": {
+                            "project": { "type": "string", "required": true, "location": "path" },
+                            "image": { "type": "string", "required": true, "location": "path" }
+                        },
+                        "parameterOrder": ["project", "image"],
+                        "response": { "$ref": "Image" }
+                    },
+                    "list": {
+                        "id": "compute.images.list",
+                        "path": "projects/{project}/global/images",
+                        "httpMethod": "GET",
+                        "description": "Retrieves the list of images available to the project.",
+                        "parameters": {
+                            "project": { "type": "string", "required": true, "location": "path" },
+                            "filter": { "type": "string", "location": "query" },
+                            "maxResults": { "type": "integer", "location": "query" },
+                            "pageToken": { "type": "string", "location": "query" }
+                        },
+                        "parameterOrder": ["project"],
+                        "response": { "$ref": "ImageList" }
+                    }
+                }
+            }
+        }
+    }"##;
+
+    let parser = DiscoveryParser::from_json(discovery_json, "compute", "v1").unwrap();
+    let service_def = parser.parse().unwrap();
+
+    // The get method still reads the resource
+    let resource = &service_def.resources[0];
+    assert_eq!(
+        resource.operations.read.as_ref().unwrap().sdk_operation,
+        "get"
+    );
+
+    // list methods look up the first of the response's items
+    assert_eq!(service_def.data_sources.len(), 1);
+    let image = &service_def.data_sources[0];
+    assert_eq!(image.name, "image");
+    assert_eq!(
+        image.description.as_deref(),
+        Some("Retrieves the list of images available to the project.")
+    );
+    assert_eq!(image.read_operation.sdk_operation, "list");
+    assert!(image.read_operation.readonly);
+    assert_eq!(image.items_accessor.as_deref(), Some("items"));
+
+    let pagination = image
+        .read_operation
+        .pagination
+        .as_ref()
+        .expect("pageToken makes the list paginated");
+    assert_eq!(pagination.input_token.as_deref(), Some("page_token"));
+    assert_eq!(pagination.output_token.as_deref(), Some("next_page_token"));
+    assert_eq!(pagination.page_size.as_deref(), Some("max_results"));
+
+    // The filter and scope are arguments; page tokens and sizes are not
+    let arguments: Vec<&str> = image.arguments.iter().map(|a| a.name.as_str()).collect();
+    assert_eq!(arguments, vec!["project", "filter"]);
+
+    let mut attributes: Vec<&str> = image.attributes.iter().map(|a| a.name.as_str()).collect();
+    attributes.sort();
+    assert_eq!(attributes, vec!["disk_size_gb", "family", "name"]);
+
+    println!("✅ Successfully extracted Discovery data sources!");
+}
+
+#[test]
+fn test_parse_discovery_constraints_and_defaults() {
+    let discovery_json = r##"{
+        "discoveryVersion": "v1",
+        "name": "storage",
+        "version": "v1",
+        "title": "Cloud Storage JSON API",
+        "rootUrl": "https://storage.googleapis.com/",
+        "servicePath": "storage/v1/",
+        "schemas": {
+            "Bucket": {
+                "id": "Bucket",
+                "type": "object",
+                "properties": {
+                    "name": { "type": "string", "pattern": "^[a-z0-9_.-]+$" },
+                    "storageClass": { "type": "string", "default": "STANDARD" },
+                    "retentionDays": { "type": "integer", "format": "int32", "minimum": "1", "maximum": "3650", "default": "30" }
+                }
+            }
+        },
+        "resources": {
+            "buckets": {
+                "methods": {
+                    "insert": {
+                        "id": "storage.buckets.insert",
+                        "path": "b",
+                        "httpMethod": "POST",
+                        "parameters": {
+                            "project": { "type": "string", "required": true, "location": "query" },
+                            "maxResults": {
+                                "type": "integer",
+                                "format": "uint32",
+                                "minimum": "0",
+                                "maximum": "1000",
+                                "default": "1000",
+                                "location": "query"
+                            }
+                        },
+                        "request": { "$ref": "Bucket" },
+                        "response": { "$ref": "Bucket" }
+                    }
+                }
+            }
+        }
+    }"##;
+
+    let parser = DiscoveryParser::from_json(discovery_json, "storage", "v1").unwrap();
+    let service_def = parser.parse().unwrap();
+    let bucket = &service_def.resources[0];
+    let field = |name: &str| bucket.fields.iter().find(|f| f.name == name).unwrap();
+
+    // Bounds and defaults are written as strings and converted to the field's type
+    let max_results = field("max_results");
+    assert_eq!(max_results.constraints.minimum, Some(0.0));
+    assert_eq!(max_results.constraints.maximum, Some(1000.0));
+    assert_eq!(max_results.default_value, Some(serde_json::json!(1000)));
+
+    let retention = field("retention_days");
+    assert_eq!(retention.constraints.maximum, Some(3650.0));
+    assert_eq!(retention.default_value, Some(serde_json::json!(30)));
+
+    assert_eq!(
+        field("name").constraints.pattern.as_deref(),
+        Some("^[a-z0-9_.-]+$")
+    );
+    assert_eq!(
+        field("storage_class").default_value,
+        Some(serde_json::json!("STANDARD"))
+    );
+    assert!(field("project").constraints.is_empty());
+
+    println!("✅ Successfully parsed Discovery constraints and defaults!");
+}
+
+#[test]
+fn test_parse_discovery_deprecation_and_stability() {
+    let discovery_json = r##"{
+        "discoveryVersion": "v1",
+        "name": "storage",
+        "version": "v1beta2",
+        "title": "Cloud Storage JSON API",
+        "rootUrl": "https://storage.googleapis.com/",
+        "servicePath": "storage/v1beta2/",
+        "schemas": {
+            "Bucket": {
+                "id": "Bucket",
+                "type": "object",
+                "properties": {
+                    "name": { "type": "string" },
+                    "legacyAcl": { "type": "string", "deprecated": true }
+                }
+            }
+        },
+        "resources": {
+            "buckets": {
+                "methods": {
+                    "insert": {
+                        "id": "storage.buckets.insert",
+                        "path": "b",
+                        "httpMethod": "POST",
+                        "deprecated": true,
+                        "parameters": {
+                            "project": { "type": "string", "required": true, "location": "query" }
+                        },
+                        "request": { "$ref": "Bucket" },
+                        "response": { "$ref": "Bucket" }
+                    }
+                }
+            }
+        }
+    }"##;
+
+    let parser = DiscoveryParser::from_json(discovery_json, "storage", "v1beta2").unwrap();
+    let service_def = parser.parse().unwrap();
+    let bucket = &service_def.resources[0];
+    let field = |name: &str| bucket.fields.iter().find(|f| f.name == name).unwrap();
+
+    // The API version suffix sets the stability level
+    assert_eq!(bucket.stability, Stability::Beta);
+
+    // A deprecated insert method deprecates the resource
+    assert!(bucket
+        .operations
+        .create
+        .as_ref()
+        .unwrap()
+        .deprecated
+        .is_some());
+    assert!(bucket.deprecated.is_some());
+
+    assert!(field("legacy_acl").deprecated.is_some());
+    assert!(field("name").deprecated.is_none());
+
+    println!("✅ Successfully parsed Discovery deprecation and stability!");
+}
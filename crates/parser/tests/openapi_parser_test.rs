@@ -1,6 +1,8 @@
 //! Integration test for OpenAPI parser
 
+use hemmer_provider_generator_common::{FieldType, OperationMapping, Stability};
 use hemmer_provider_generator_parser::openapi::{OpenApiParser, ProviderHint};
+use hemmer_provider_generator_parser::CrudRules;
 
 #[test]
 fn test_parse_kubernetes_style_openapi() {
@@ -279,3 +281,1527 @@ fn test_parse_kubernetes_style_openapi() {
         pod.operations.delete.is_some()
     );
 }
+
+#[test]
+fn test_parse_openapi_composition_and_discriminator() {
+    // Azure-style spec using allOf inheritance and a discriminated oneOf
+    let openapi_json = r##"{
+        "openapi": "3.0.0",
+        "info": {
+            "title": "Widget API",
+            "version": "2024-01-01"
+        },
+        "paths": {
+            "/projects/{projectId}/widgets": {
+                "post": {
+                    "operationId": "Widgets_Create",
+                    "requestBody": {
+                        "content": {
+                            "application/json": {
+                                "schema": { "$ref": "#/components/schemas/Widget" }
+                            }
+                        }
+                    },
+                    "responses": {
+                        "201": { "description": "Created" }
+                    }
+                }
+            },
+            "/projects/{projectId}/widgets/{widgetId}": {
+                "get": {
+                    "operationId": "Widgets_Get",
+                    "responses": {
+                        "200": {
+                            "description": "OK",
+                            "content": {
+                                "application/json": {
+                                    "schema": { "$ref": "#/components/schemas/Widget" }
+                                }
+                            }
+                        }
+                    }
+                },
+                "delete": {
+                    "operationId": "Widgets_Delete",
+                    "responses": {
+                        "200": { "description": "OK" }
+                    }
+                }
+            }
+        },
+        "components": {
+            "schemas": {
+                "TrackedResource": {
+                    "type": "object",
+                    "properties": {
+                        "id": { "type": "string", "description": "Resource ID" },
+                        "location": { "type": "string" }
+                    },
+                    "required": ["location"]
+                },
+                "Widget": {
+                    "description": "A widget",
+                    "allOf": [
+                        { "$ref": "#/components/schemas/TrackedResource" },
+                        {
+                            "type": "object",
+                            "properties": {
+                                "displayName": { "type": "string" },
+                                "port": {
+                                    "anyOf": [{ "type": "integer" }, { "type": "string" }]
+                                },
+                                "settings": { "$ref": "#/components/schemas/WidgetSettings" }
+                            },
+                            "required": ["displayName"]
+                        }
+                    ]
+                },
+                "WidgetSettings": {
+                    "type": "object",
+                    "properties": {
+                        "retentionDays": { "type": "integer" },
+                        "labels": {
+                            "type": "object",
+                            "additionalProperties": { "type": "string" }
+                        },
+                        "source": { "$ref": "#/components/schemas/Source" }
+                    }
+                },
+                "Source": {
+                    "oneOf": [
+                        { "$ref": "#/components/schemas/UrlSource" },
+                        { "$ref": "#/components/schemas/BlobSource" }
+                    ],
+                    "discriminator": {
+                        "propertyName": "kind",
+                        "mapping": {
+                            "url": "#/components/schemas/UrlSource",
+                            "blob": "#/components/schemas/BlobSource"
+                        }
+                    }
+                },
+                "UrlSource": {
+                    "type": "object",
+                    "properties": {
+                        "kind": { "type": "string" },
+                        "url": { "type": "string" }
+                    },
+                    "required": ["kind", "url"]
+                },
+                "BlobSource": {
+                    "type": "object",
+                    "properties": {
+                        "kind": { "type": "string" },
+                        "container": { "type": "string" },
+                        "blobName": { "type": "string" }
+                    },
+                    "required": ["kind", "container"]
+                }
+            }
+        }
+    }"##;
+
+    let parser = OpenApiParser::from_json(openapi_json, "widgets", "2024-01-01")
+        .unwrap()
+        .with_provider_hint(ProviderHint::Azure);
+    let service_def = parser.parse().unwrap();
+
+    let widget = service_def
+        .resources
+        .iter()
+        .find(|r| r.name == "widget")
+        .expect("Should have widget resource");
+    let field = |name: &str| {
+        widget
+            .fields
+            .iter()
+            .find(|f| f.name == name)
+            .unwrap_or_else(|| panic!("Should have {} field", name))
+    };
+
+    // allOf merges inherited and own properties, including required ones
+    assert!(field("location").required);
+    assert!(!field("id").required);
+    assert_eq!(field("id").description.as_deref(), Some("Resource ID"));
+    assert!(field("display_name").required);
+
+    // anyOf of mixed primitives falls back to string
+    assert_eq!(field("port").field_type, FieldType::String);
+
+    // The oneOf variants become blocks of a one-of block, named by the discriminator
+    let settings = widget
+        .blocks
+        .iter()
+        .find(|b| b.name == "settings")
+        .expect("Should have settings block");
+    let source = settings
+        .blocks
+        .iter()
+        .find(|b| b.name == "source")
+        .expect("Should have source block");
+    assert!(source.one_of);
+    assert!(source.attributes.is_empty());
+    let variants: Vec<&str> = source.blocks.iter().map(|b| b.name.as_str()).collect();
+    assert_eq!(variants, vec!["url", "blob"]);
+
+    let blob = &source.blocks[1];
+    assert_eq!(blob.sdk_type_name.as_deref(), Some("BlobSource"));
+    let attribute = |name: &str| blob.attributes.iter().find(|a| a.name == name);
+    assert!(attribute("container").unwrap().required);
+    assert!(!attribute("blob_name").unwrap().required);
+    assert!(
+        attribute("kind").is_none(),
+        "The discriminator is implied by the variant"
+    );
+
+    // Response schemas are composed the same way
+    assert!(widget.outputs.iter().any(|o| o.name == "location"));
+
+    println!("✅ Successfully parsed OpenAPI allOf/oneOf/anyOf composition!");
+}
+
+#[test]
+fn test_parse_swagger2_polymorphism() {
+    // Azure-style polymorphism: subtypes extend a discriminated base through allOf
+    let swagger_yaml = r##"
+swagger: "2.0"
+info:
+  title: MonitorManagementClient
+  version: "2024-01-01"
+paths:
+  /alertRules/{ruleName}:
+    put:
+      operationId: AlertRules_CreateOrUpdate
+      parameters:
+        - { name: ruleName, in: path, required: true, type: string }
+        - { name: parameters, in: body, required: true, schema: { $ref: "#/definitions/AlertRule" } }
+      responses:
+        200: { description: OK, schema: { $ref: "#/definitions/AlertRule" } }
+  /conditions/{conditionName}:
+    put:
+      operationId: Conditions_CreateOrUpdate
+      parameters:
+        - { name: conditionName, in: path, required: true, type: string }
+        - { name: parameters, in: body, required: true, schema: { $ref: "#/definitions/Condition" } }
+      responses:
+        200: { description: OK }
+  /logConditions/{conditionName}:
+    put:
+      operationId: LogConditions_CreateOrUpdate
+      parameters:
+        - { name: conditionName, in: path, required: true, type: string }
+        - { name: parameters, in: body, required: true, schema: { $ref: "#/definitions/LogCondition" } }
+      responses:
+        200: { description: OK }
+definitions:
+  AlertRule:
+    properties:
+      location: { type: string }
+      condition: { $ref: "#/definitions/Condition" }
+  Condition:
+    discriminator: kind
+    required: [kind]
+    properties:
+      kind: { type: string, description: Kind of condition }
+      enabled: { type: boolean }
+  ThresholdCondition:
+    x-ms-discriminator-value: Threshold
+    allOf:
+      - $ref: "#/definitions/Condition"
+    properties:
+      threshold: { type: number }
+      metricName: { type: string }
+    required: [threshold]
+  LogCondition:
+    allOf:
+      - $ref: "#/definitions/Condition"
+    properties:
+      kind: { type: string, enum: [LogCondition] }
+      query: { type: string }
+"##;
+
+    let parser = OpenApiParser::from_yaml(swagger_yaml, "monitor", "2024-01-01")
+        .unwrap()
+        .with_provider_hint(ProviderHint::Azure);
+    let service_def = parser.parse().unwrap();
+    let resource = |name: &str| {
+        service_def
+            .resources
+            .iter()
+            .find(|r| r.name == name)
+            .unwrap_or_else(|| panic!("Should have {} resource", name))
+    };
+    // The base lists the discriminator values of its subtypes
+    let condition = resource("condition");
+    let kind = condition.fields.iter().find(|f| f.name == "kind").unwrap();
+    assert!(kind.required);
+    assert_eq!(kind.description.as_deref(), Some("Kind of condition"));
+    assert_eq!(
+        kind.field_type,
+        FieldType::Enum(vec!["LogCondition".to_string(), "Threshold".to_string()])
+    );
+
+    // Subtypes inherit the base properties but keep their own discriminator property
+    let log_condition = resource("log_condition");
+    let kind = log_condition
+        .fields
+        .iter()
+        .find(|f| f.name == "kind")
+        .unwrap();
+    assert_eq!(
+        kind.field_type,
+        FieldType::Enum(vec!["LogCondition".to_string()])
+    );
+    assert!(log_condition.fields.iter().any(|f| f.name == "enabled"));
+
+    // Properties of the base type become one-of blocks of the subtypes
+    let block = resource("alert_rule")
+        .blocks
+        .iter()
+        .find(|b| b.name == "condition")
+        .expect("Should have condition block");
+    assert!(block.one_of);
+    let variants: Vec<&str> = block.blocks.iter().map(|b| b.name.as_str()).collect();
+    assert_eq!(variants, vec!["log_condition", "threshold"]);
+    let threshold = &block.blocks[1];
+    assert_eq!(
+        threshold.sdk_type_name.as_deref(),
+        Some("ThresholdCondition")
+    );
+    let attributes: Vec<(&str, bool)> = {
+        let mut attributes: Vec<_> = threshold
+            .attributes
+            .iter()
+            .map(|a| (a.name.as_str(), a.required))
+            .collect();
+        attributes.sort();
+        attributes
+    };
+    assert_eq!(
+        attributes,
+        vec![
+            ("enabled", false),
+            ("metric_name", false),
+            ("threshold", true)
+        ]
+    );
+
+    println!("✅ Successfully parsed Swagger 2.0 polymorphism!");
+}
+
+#[test]
+fn test_parse_swagger2_yaml_file() {
+    // Azure-style Swagger 2.0 spec in YAML
+    let swagger_yaml = r##"
+swagger: "2.0"
+info:
+  title: KeyVaultManagementClient
+  version: "2023-07-01"
+host: management.azure.com
+schemes:
+  - https
+consumes:
+  - application/json
+produces:
+  - application/json
+paths:
+  /subscriptions/{subscriptionId}/vaults/{vaultName}:
+    parameters:
+      - $ref: "#/parameters/SubscriptionIdParameter"
+      - name: vaultName
+        in: path
+        required: true
+        type: string
+        description: Name of the vault
+    put:
+      operationId: Vaults_CreateOrUpdate
+      parameters:
+        - name: parameters
+          in: body
+          required: true
+          schema:
+            $ref: "#/definitions/VaultCreateOrUpdateParameters"
+      responses:
+        200:
+          description: OK
+          schema:
+            $ref: "#/definitions/Vault"
+    get:
+      operationId: Vaults_Get
+      responses:
+        200:
+          description: OK
+          schema:
+            $ref: "#/definitions/Vault"
+    delete:
+      operationId: Vaults_Delete
+      responses:
+        200:
+          description: OK
+parameters:
+  SubscriptionIdParameter:
+    name: subscriptionId
+    in: path
+    required: true
+    type: string
+definitions:
+  VaultCreateOrUpdateParameters:
+    properties:
+      location:
+        type: string
+        description: The supported Azure location
+      tags:
+        type: object
+        additionalProperties:
+          type: string
+    required:
+      - location
+  Vault:
+    properties:
+      id:
+        type: string
+        readOnly: true
+      location:
+        type: string
+"##;
+
+    let dir = tempfile::TempDir::new().unwrap();
+    let path = dir.path().join("keyvault.yaml");
+    std::fs::write(&path, swagger_yaml).unwrap();
+    let parser = OpenApiParser::from_file(&path, "keyvault", "2023-07-01")
+        .unwrap()
+        .with_provider_hint(ProviderHint::Azure);
+
+    assert_eq!(parser.spec().openapi, "3.0.0");
+    assert_eq!(parser.spec().servers[0].url, "https://management.azure.com");
+
+    let service_def = parser.parse().unwrap();
+    let vault = service_def
+        .resources
+        .iter()
+        .find(|r| r.name == "vault")
+        .expect("Should have vault resource");
+
+    // Body parameter became the request body
+    let location = vault
+        .fields
+        .iter()
+        .find(|f| f.name == "location")
+        .expect("Should have location field from body schema");
+    assert!(location.required);
+
+    // Path parameters (inline and referenced) became immutable fields
+    for name in ["vault_name", "subscription_id"] {
+        let field = vault
+            .fields
+            .iter()
+            .find(|f| f.name == name)
+            .unwrap_or_else(|| panic!("Should have {} field", name));
+        assert!(field.required);
+        assert!(field.immutable);
+    }
+
+    assert!(vault.operations.update.is_some());
+    assert!(vault.operations.read.is_some());
+    assert!(vault.operations.delete.is_some());
+    assert!(vault.outputs.iter().any(|o| o.name == "id"));
+
+    println!("✅ Successfully parsed Swagger 2.0 YAML spec!");
+}
+
+#[test]
+fn test_parse_multi_file_openapi_spec() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let dir = temp_dir.path();
+    std::fs::create_dir_all(dir.join("common-types")).unwrap();
+    std::fs::create_dir_all(dir.join("storage")).unwrap();
+
+    // Shared types referenced by the resource provider spec
+    std::fs::write(
+        dir.join("common-types/types.json"),
+        r##"{
+            "swagger": "2.0",
+            "info": { "title": "Common types", "version": "5.0" },
+            "paths": {},
+            "definitions": {
+                "TrackedResource": {
+                    "properties": {
+                        "id": { "type": "string" },
+                        "location": { "type": "string" },
+                        "systemData": { "$ref": "#/definitions/SystemData" }
+                    },
+                    "required": ["location"]
+                },
+                "SystemData": {
+                    "properties": {
+                        "createdBy": { "type": "string" }
+                    }
+                }
+            },
+            "parameters": {
+                "SubscriptionIdParameter": {
+                    "name": "subscriptionId",
+                    "in": "path",
+                    "required": true,
+                    "type": "string"
+                }
+            }
+        }"##,
+    )
+    .unwrap();
+
+    let spec_path = dir.join("storage/storage.json");
+    std::fs::write(
+        &spec_path,
+        r##"{
+            "swagger": "2.0",
+            "info": { "title": "StorageManagementClient", "version": "2023-01-01" },
+            "paths": {
+                "/subscriptions/{subscriptionId}/storageAccounts/{accountName}": {
+                    "put": {
+                        "operationId": "StorageAccounts_Create",
+                        "parameters": [
+                            { "$ref": "../common-types/types.json#/parameters/SubscriptionIdParameter" },
+                            { "name": "accountName", "in": "path", "required": true, "type": "string" },
+                            {
+                                "name": "parameters",
+                                "in": "body",
+                                "schema": { "$ref": "#/definitions/StorageAccount" }
+                            }
+                        ],
+                        "responses": { "200": { "description": "OK" } }
+                    }
+                }
+            },
+            "definitions": {
+                "StorageAccount": {
+                    "allOf": [{ "$ref": "../common-types/types.json#/definitions/TrackedResource" }],
+                    "properties": {
+                        "kind": { "type": "string" }
+                    }
+                }
+            }
+        }"##,
+    )
+    .unwrap();
+
+    let parser = OpenApiParser::from_file(&spec_path, "storage", "2023-01-01")
+        .unwrap()
+        .with_provider_hint(ProviderHint::Azure);
+
+    // Shared schemas were merged into the spec
+    let schemas = &parser.spec().components.as_ref().unwrap().schemas;
+    assert!(schemas.contains_key("TrackedResource"));
+    assert!(schemas.contains_key("SystemData"));
+
+    let service_def = parser.parse().unwrap();
+    let account = service_def
+        .resources
+        .iter()
+        .find(|r| r.name == "storage_account")
+        .expect("Should have storage_account resource");
+
+    // Inherited properties and referenced parameters came through
+    let location = account
+        .fields
+        .iter()
+        .find(|f| f.name == "location")
+        .expect("Should have location field from common types");
+    assert!(location.required);
+    assert!(account.fields.iter().any(|f| f.name == "kind"));
+    assert!(account
+        .fields
+        .iter()
+        .any(|f| f.name == "subscription_id" && f.immutable));
+
+    println!("✅ Successfully resolved multi-file OpenAPI spec!");
+}
+
+#[test]
+fn test_parse_azure_extensions() {
+    let openapi_json = r##"{
+        "swagger": "2.0",
+        "info": { "title": "NetworkManagementClient", "version": "2023-05-01" },
+        "paths": {
+            "/subscriptions/{subscriptionId}/virtualNetworks/{virtualNetworkName}": {
+                "put": {
+                    "operationId": "VirtualNetworks_CreateOrUpdate",
+                    "x-ms-long-running-operation": true,
+                    "x-ms-long-running-operation-options": {
+                        "final-state-via": "azure-async-operation"
+                    },
+                    "parameters": [
+                        { "name": "subscriptionId", "in": "path", "required": true, "type": "string" },
+                        { "name": "virtualNetworkName", "in": "path", "required": true, "type": "string" },
+                        {
+                            "name": "parameters",
+                            "in": "body",
+                            "required": true,
+                            "schema": { "$ref": "#/definitions/VirtualNetwork" }
+                        }
+                    ],
+                    "responses": {
+                        "200": { "description": "OK", "schema": { "$ref": "#/definitions/VirtualNetwork" } }
+                    }
+                },
+                "get": {
+                    "operationId": "VirtualNetworks_Get",
+                    "responses": {
+                        "200": { "description": "OK", "schema": { "$ref": "#/definitions/VirtualNetwork" } }
+                    }
+                },
+                "delete": {
+                    "operationId": "VirtualNetworks_Delete",
+                    "x-ms-long-running-operation": true,
+                    "responses": { "200": { "description": "OK" } }
+                }
+            },
+            "/subscriptions/{subscriptionId}/virtualNetworks/{virtualNetworkName}/subnets": {
+                "get": {
+                    "operationId": "Subnets_List",
+                    "x-ms-pageable": { "nextLinkName": "nextLink" },
+                    "responses": { "200": { "description": "OK" } }
+                }
+            }
+        },
+        "definitions": {
+            "VirtualNetwork": {
+                "properties": {
+                    "id": { "type": "string", "readOnly": true },
+                    "location": {
+                        "type": "string",
+                        "x-ms-mutability": ["create", "read"]
+                    },
+                    "properties": {
+                        "x-ms-client-flatten": true,
+                        "$ref": "#/definitions/VirtualNetworkPropertiesFormat"
+                    }
+                },
+                "required": ["location"]
+            },
+            "VirtualNetworkPropertiesFormat": {
+                "properties": {
+                    "provisioningState": {
+                        "type": "string",
+                        "x-ms-mutability": ["read"]
+                    },
+                    "encryptionKey": {
+                        "type": "string",
+                        "x-ms-secret": true
+                    },
+                    "ddosProtectionMode": {
+                        "type": "string",
+                        "enum": ["Enabled", "Disabled"],
+                        "x-ms-enum": { "name": "DdosProtectionMode", "modelAsString": true }
+                    },
+                    "flowTimeoutInMinutes": {
+                        "type": "integer"
+                    }
+                }
+            }
+        }
+    }"##;
+
+    let parser = OpenApiParser::from_json(openapi_json, "network", "2023-05-01")
+        .unwrap()
+        .with_provider_hint(ProviderHint::Azure);
+    let service_def = parser.parse().unwrap();
+
+    let vnet = service_def
+        .resources
+        .iter()
+        .find(|r| r.name == "virtual_network")
+        .expect("Should have virtual_network resource");
+    let field = |name: &str| vnet.fields.iter().find(|f| f.name == name);
+
+    // x-ms-mutability / readOnly
+    let location = field("location").expect("Should have location field");
+    assert!(location.immutable, "create-only properties are immutable");
+    assert!(field("id").is_none(), "read-only properties are not inputs");
+    assert!(field("provisioning_state").is_none());
+    assert!(vnet.outputs.iter().any(|o| o.name == "provisioning_state"));
+
+    // x-ms-client-flatten lifts the properties envelope
+    assert!(field("properties").is_none());
+    assert!(field("flow_timeout_in_minutes").is_some());
+
+    // x-ms-secret
+    assert!(field("encryption_key").unwrap().sensitive);
+
+    // x-ms-enum
+    let ddos = field("ddos_protection_mode").unwrap();
+    assert_eq!(
+        ddos.field_type,
+        FieldType::Enum(vec!["Enabled".to_string(), "Disabled".to_string()])
+    );
+    assert_eq!(ddos.sdk_type_name.as_deref(), Some("DdosProtectionMode"));
+
+    // x-ms-long-running-operation
+    let update = vnet.operations.update.as_ref().unwrap();
+    assert_eq!(
+        update
+            .long_running
+            .as_ref()
+            .expect("PUT should be long-running")
+            .final_state_via
+            .as_deref(),
+        Some("azure-async-operation")
+    );
+    assert!(vnet
+        .operations
+        .delete
+        .as_ref()
+        .unwrap()
+        .long_running
+        .is_some());
+    assert!(vnet
+        .operations
+        .read
+        .as_ref()
+        .unwrap()
+        .long_running
+        .is_none());
+
+    // x-ms-pageable
+    let subnet = service_def
+        .resources
+        .iter()
+        .find(|r| r.name == "subnet")
+        .expect("Should have subnet resource");
+    let pagination = subnet
+        .operations
+        .read
+        .as_ref()
+        .unwrap()
+        .pagination
+        .as_ref()
+        .expect("List should be pageable");
+    assert_eq!(pagination.output_token.as_deref(), Some("next_link"));
+    assert_eq!(pagination.items.as_deref(), Some("value"));
+
+    println!("✅ Successfully applied Azure x-ms-* extensions!");
+}
+
+#[test]
+fn test_parse_openapi_data_sources() {
+    let openapi_json = r##"{
+        "openapi": "3.0.0",
+        "info": { "title": "NetworkManagementClient", "version": "2023-05-01" },
+        "paths": {
+            "/subscriptions/{subscriptionId}/virtualNetworks": {
+                "get": {
+                    "operationId": "VirtualNetworks_ListAll",
+                    "description": "Gets all virtual networks in a subscription.",
+                    "x-ms-pageable": { "nextLinkName": "nextLink" },
+                    "parameters": [
+                        { "name": "subscriptionId", "in": "path", "required": true, "schema": { "type": "string" } },
+                        { "name": "filter", "in": "query", "schema": { "type": "string" } }
+                    ],
+                    "responses": {
+                        "200": {
+                            "description": "OK",
+                            "content": {
+                                "application/json": {
+                                    "schema": { "$ref": "#/components/schemas/VirtualNetworkListResult" }
+                                }
+                            }
+                        }
+                    }
+                }
+            },
+            "/subscriptions/{subscriptionId}/virtualNetworks/{virtualNetworkName}": {
+                "get": {
+                    "operationId": "VirtualNetworks_Get",
+                    "responses": {
+                        "200": {
+                            "description": "OK",
+                            "content": {
+                                "application/json": {
+                                    "schema": { "$ref": "#/components/schemas/VirtualNetwork" }
+                                }
+                            }
+                        }
+                    }
+                }
+            },
+            "/usages": {
+                "get": {
+                    "operationId": "Usages_Get",
+                    "responses": {
+                        "200": {
+                            "description": "OK",
+                            "content": {
+                                "application/json": {
+                                    "schema": { "type": "object", "properties": { "count": { "type": "integer" } } }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        },
+        "components": {
+            "schemas": {
+                "VirtualNetworkListResult": {
+                    "type": "object",
+                    "properties": {
+                        "value": {
+                            "type": "array",
+                            "items": { "$ref": "#/components/schemas/VirtualNetwork" }
+                        },
+                        "nextLink": { "type": "string" }
+                    }
+                },
+                "VirtualNetwork": {
+                    "type": "object",
+                    "properties": {
+                        "id": { "type": "string", "readOnly": true },
+                        "name": { "type": "string" },
+                        "location": { "type": "string" }
+                    }
+                }
+            }
+        }
+    }"##;
+
+    let parser = OpenApiParser::from_json(openapi_json, "network", "2023-05-01")
+        .unwrap()
+        .with_provider_hint(ProviderHint::Azure);
+    let service_def = parser.parse().unwrap();
+
+    // Collection GETs look up the first item of the response's item array
+    let vnet = service_def
+        .data_sources
+        .iter()
+        .find(|d| d.name == "virtual_network")
+        .expect("Should have virtual_network data source");
+    assert_eq!(
+        vnet.description.as_deref(),
+        Some("Gets all virtual networks in a subscription.")
+    );
+    assert_eq!(
+        vnet.read_operation.sdk_operation,
+        "virtual_networks_list_all"
+    );
+    assert!(vnet.read_operation.readonly);
+    assert!(vnet.read_operation.idempotent);
+    assert_eq!(vnet.items_accessor.as_deref(), Some("value"));
+
+    // Path and query parameters are the lookup's arguments
+    let subscription = vnet
+        .arguments
+        .iter()
+        .find(|a| a.name == "subscription_id")
+        .expect("Should have subscription_id argument");
+    assert!(subscription.required);
+    assert!(vnet
+        .arguments
+        .iter()
+        .any(|a| a.name == "filter" && !a.required));
+
+    // Item properties, read-only ones included, are the attributes
+    let mut attributes: Vec<&str> = vnet.attributes.iter().map(|a| a.name.as_str()).collect();
+    attributes.sort();
+    assert_eq!(attributes, vec!["id", "location", "name"]);
+
+    // Responses without an item array are not lookups
+    assert_eq!(service_def.data_sources.len(), 1);
+
+    println!("✅ Successfully extracted OpenAPI data sources!");
+}
+
+#[test]
+fn test_parse_kubernetes_group_version_kinds() {
+    let openapi_json = r##"{
+        "swagger": "2.0",
+        "info": { "title": "Kubernetes", "version": "v1.29.0" },
+        "paths": {
+            "/api/v1/namespaces/{namespace}/pods": {
+                "get": {
+                    "operationId": "listCoreV1NamespacedPod",
+                    "parameters": [
+                        { "name": "namespace", "in": "path", "required": true, "type": "string" },
+                        { "name": "labelSelector", "in": "query", "type": "string" },
+                        { "name": "fieldSelector", "in": "query", "type": "string" },
+                        { "name": "limit", "in": "query", "type": "integer" }
+                    ],
+                    "responses": { "200": { "description": "OK", "schema": { "$ref": "#/definitions/io.k8s.api.core.v1.PodList" } } },
+                    "x-kubernetes-action": "list",
+                    "x-kubernetes-group-version-kind": { "group": "", "version": "v1", "kind": "Pod" }
+                },
+                "post": {
+                    "operationId": "createCoreV1NamespacedPod",
+                    "parameters": [
+                        { "name": "namespace", "in": "path", "required": true, "type": "string" },
+                        { "name": "body", "in": "body", "required": true, "schema": { "$ref": "#/definitions/io.k8s.api.core.v1.Pod" } }
+                    ],
+                    "responses": { "201": { "description": "Created", "schema": { "$ref": "#/definitions/io.k8s.api.core.v1.Pod" } } },
+                    "x-kubernetes-action": "post",
+                    "x-kubernetes-group-version-kind": { "group": "", "version": "v1", "kind": "Pod" }
+                }
+            },
+            "/api/v1/namespaces/{namespace}/pods/{name}": {
+                "get": {
+                    "operationId": "readCoreV1NamespacedPod",
+                    "responses": { "200": { "description": "OK", "schema": { "$ref": "#/definitions/io.k8s.api.core.v1.Pod" } } },
+                    "x-kubernetes-action": "get",
+                    "x-kubernetes-group-version-kind": { "group": "", "version": "v1", "kind": "Pod" }
+                },
+                "put": {
+                    "operationId": "replaceCoreV1NamespacedPod",
+                    "parameters": [
+                        { "name": "body", "in": "body", "required": true, "schema": { "$ref": "#/definitions/io.k8s.api.core.v1.Pod" } }
+                    ],
+                    "responses": { "200": { "description": "OK", "schema": { "$ref": "#/definitions/io.k8s.api.core.v1.Pod" } } },
+                    "x-kubernetes-action": "put",
+                    "x-kubernetes-group-version-kind": { "group": "", "version": "v1", "kind": "Pod" }
+                },
+                "patch": {
+                    "operationId": "patchCoreV1NamespacedPod",
+                    "responses": { "200": { "description": "OK" } },
+                    "x-kubernetes-action": "patch",
+                    "x-kubernetes-group-version-kind": { "group": "", "version": "v1", "kind": "Pod" }
+                },
+                "delete": {
+                    "operationId": "deleteCoreV1NamespacedPod",
+                    "responses": { "200": { "description": "OK" } },
+                    "x-kubernetes-action": "delete",
+                    "x-kubernetes-group-version-kind": { "group": "", "version": "v1", "kind": "Pod" }
+                }
+            },
+            "/api/v1/namespaces/{namespace}/pods/{name}/status": {
+                "put": {
+                    "operationId": "replaceCoreV1NamespacedPodStatus",
+                    "responses": { "200": { "description": "OK" } },
+                    "x-kubernetes-action": "put",
+                    "x-kubernetes-group-version-kind": { "group": "", "version": "v1", "kind": "Pod" }
+                }
+            },
+            "/api/v1/pods": {
+                "get": {
+                    "operationId": "listCoreV1PodForAllNamespaces",
+                    "responses": { "200": { "description": "OK", "schema": { "$ref": "#/definitions/io.k8s.api.core.v1.PodList" } } },
+                    "x-kubernetes-action": "list",
+                    "x-kubernetes-group-version-kind": { "group": "", "version": "v1", "kind": "Pod" }
+                }
+            },
+            "/api/v1/watch/namespaces/{namespace}/pods": {
+                "get": {
+                    "operationId": "watchCoreV1NamespacedPodList",
+                    "responses": { "200": { "description": "OK" } },
+                    "x-kubernetes-action": "watchlist",
+                    "x-kubernetes-group-version-kind": { "group": "", "version": "v1", "kind": "Pod" }
+                }
+            },
+            "/apis/apps/v1/namespaces/{namespace}/deployments": {
+                "post": {
+                    "operationId": "createAppsV1NamespacedDeployment",
+                    "parameters": [
+                        { "name": "body", "in": "body", "required": true, "schema": { "$ref": "#/definitions/io.k8s.api.apps.v1.Deployment" } }
+                    ],
+                    "responses": { "201": { "description": "Created", "schema": { "$ref": "#/definitions/io.k8s.api.apps.v1.Deployment" } } },
+                    "x-kubernetes-action": "post",
+                    "x-kubernetes-group-version-kind": { "group": "apps", "version": "v1", "kind": "Deployment" }
+                }
+            },
+            "/apis/apps/v1/namespaces/{namespace}/deployments/{name}": {
+                "patch": {
+                    "operationId": "patchAppsV1NamespacedDeployment",
+                    "responses": { "200": { "description": "OK" } },
+                    "x-kubernetes-action": "patch",
+                    "x-kubernetes-group-version-kind": { "group": "apps", "version": "v1", "kind": "Deployment" }
+                }
+            },
+            "/api/v1/namespaces": {
+                "get": {
+                    "operationId": "listCoreV1Namespace",
+                    "responses": { "200": { "description": "OK" } },
+                    "x-kubernetes-action": "list",
+                    "x-kubernetes-group-version-kind": { "group": "", "version": "v1", "kind": "Namespace" }
+                },
+                "post": {
+                    "operationId": "createCoreV1Namespace",
+                    "parameters": [
+                        { "name": "body", "in": "body", "required": true, "schema": { "$ref": "#/definitions/io.k8s.api.core.v1.Namespace" } }
+                    ],
+                    "responses": { "201": { "description": "Created" } },
+                    "x-kubernetes-action": "post",
+                    "x-kubernetes-group-version-kind": { "group": "", "version": "v1", "kind": "Namespace" }
+                }
+            }
+        },
+        "definitions": {
+            "io.k8s.api.core.v1.Pod": {
+                "description": "Pod is a collection of containers that can run on a host.",
+                "type": "object",
+                "properties": {
+                    "apiVersion": { "type": "string" },
+                    "kind": { "type": "string" },
+                    "metadata": { "type": "object", "properties": { "name": { "type": "string" } } },
+                    "spec": { "type": "object", "properties": { "nodeName": { "type": "string" } } },
+                    "status": { "type": "object", "properties": { "phase": { "type": "string" } } }
+                }
+            },
+            "io.k8s.api.core.v1.PodList": {
+                "type": "object",
+                "properties": {
+                    "items": { "type": "array", "items": { "$ref": "#/definitions/io.k8s.api.core.v1.Pod" } }
+                }
+            },
+            "io.k8s.api.apps.v1.Deployment": {
+                "type": "object",
+                "properties": {
+                    "apiVersion": { "type": "string" },
+                    "kind": { "type": "string" },
+                    "spec": { "type": "object", "properties": { "replicas": { "type": "integer" } } }
+                }
+            },
+            "io.k8s.api.core.v1.Namespace": {
+                "type": "object",
+                "properties": {
+                    "apiVersion": { "type": "string" },
+                    "kind": { "type": "string" },
+                    "metadata": { "type": "object", "properties": { "name": { "type": "string" } } }
+                }
+            }
+        }
+    }"##;
+
+    let parser = OpenApiParser::from_json(openapi_json, "kubernetes", "1.29.0")
+        .unwrap()
+        .with_provider_hint(ProviderHint::Kubernetes);
+    let service_def = parser.parse().unwrap();
+
+    let resource = |name: &str| {
+        service_def
+            .resources
+            .iter()
+            .find(|r| r.name == name)
+            .unwrap_or_else(|| panic!("Should have {} resource", name))
+    };
+
+    // Namespaced core/v1 Pod
+    let pod = resource("pod");
+    assert_eq!(
+        pod.description.as_deref(),
+        Some("Pod is a collection of containers that can run on a host.")
+    );
+    let field = |name: &str| pod.fields.iter().find(|f| f.name == name);
+    assert_eq!(
+        field("api_version").unwrap().field_type,
+        FieldType::Enum(vec!["v1".to_string()])
+    );
+    assert_eq!(
+        field("kind").unwrap().field_type,
+        FieldType::Enum(vec!["Pod".to_string()])
+    );
+    // name and namespace identify the object and fill in the path parameters
+    assert_eq!(pod.id_field.as_deref(), Some("name"));
+    for identifier in ["name", "namespace"] {
+        let identifier = field(identifier).unwrap();
+        assert!(identifier.required && identifier.immutable);
+    }
+    assert!(field("status").is_none(), "status is server-managed");
+    assert!(pod.blocks.iter().all(|b| b.name != "status"));
+
+    // Reads come from `get`, not `list`, and subresources are ignored
+    let ops = &pod.operations;
+    assert_eq!(
+        ops.read.as_ref().unwrap().sdk_operation,
+        "read_core_v1_namespaced_pod"
+    );
+    assert_eq!(
+        ops.update.as_ref().unwrap().sdk_operation,
+        "replace_core_v1_namespaced_pod"
+    );
+    assert_eq!(
+        ops.delete.as_ref().unwrap().sdk_operation,
+        "delete_core_v1_namespaced_pod"
+    );
+
+    // apps/v1 Deployment falls back to patch for updates
+    let deployment = resource("deployment");
+    assert_eq!(
+        deployment
+            .fields
+            .iter()
+            .find(|f| f.name == "api_version")
+            .unwrap()
+            .field_type,
+        FieldType::Enum(vec!["apps/v1".to_string()])
+    );
+    assert_eq!(
+        deployment.operations.update.as_ref().unwrap().sdk_operation,
+        "patch_apps_v1_namespaced_deployment"
+    );
+
+    // Cluster-scoped Namespace has no namespace field
+    let namespace = resource("namespace");
+    assert!(namespace.fields.iter().all(|f| f.name != "namespace"));
+    assert_eq!(namespace.id_field.as_deref(), Some("name"));
+    assert!(namespace
+        .fields
+        .iter()
+        .any(|f| f.name == "name" && f.required));
+
+    // Lists become data sources
+    let pod_list = service_def
+        .data_sources
+        .iter()
+        .find(|d| d.name == "pod_list")
+        .expect("Should have pod_list data source");
+    assert_eq!(
+        pod_list.read_operation.sdk_operation,
+        "list_core_v1_namespaced_pod"
+    );
+    assert_eq!(
+        pod_list.read_operation.additional_operations,
+        vec!["list_core_v1_pod_for_all_namespaces".to_string()]
+    );
+    assert!(pod_list.read_operation.readonly);
+    let namespace_arg = pod_list
+        .arguments
+        .iter()
+        .find(|a| a.name == "namespace")
+        .unwrap();
+    assert!(!namespace_arg.required);
+    assert!(pod_list
+        .arguments
+        .iter()
+        .any(|a| a.name == "label_selector"));
+    assert!(pod_list
+        .arguments
+        .iter()
+        .any(|a| a.name == "field_selector"));
+    assert_eq!(
+        pod_list
+            .read_operation
+            .pagination
+            .as_ref()
+            .unwrap()
+            .items
+            .as_deref(),
+        Some("items")
+    );
+
+    let namespace_list = service_def
+        .data_sources
+        .iter()
+        .find(|d| d.name == "namespace_list")
+        .expect("Should have namespace_list data source");
+    assert!(namespace_list
+        .arguments
+        .iter()
+        .all(|a| a.name != "namespace"));
+    assert!(service_def
+        .data_sources
+        .iter()
+        .all(|d| d.name != "deployment_list"));
+
+    println!("✅ Successfully grouped Kubernetes operations by group/version/kind!");
+    println!("   Resources: {}", service_def.resources.len());
+    println!("   Data sources: {}", service_def.data_sources.len());
+}
+
+#[test]
+fn test_parse_openapi_constraints_and_defaults() {
+    let openapi_json = r##"{
+        "openapi": "3.0.0",
+        "info": { "title": "StorageManagementClient", "version": "2023-01-01" },
+        "paths": {
+            "/subscriptions/{subscriptionId}/storageAccounts/{accountName}": {
+                "put": {
+                    "operationId": "StorageAccounts_Create",
+                    "parameters": [
+                        { "name": "subscriptionId", "in": "path", "required": true, "schema": { "type": "string" } },
+                        {
+                            "name": "accountName",
+                            "in": "path",
+                            "required": true,
+                            "schema": { "type": "string", "minLength": 3, "maxLength": 24, "pattern": "^[a-z0-9]+$" }
+                        }
+                    ],
+                    "requestBody": {
+                        "content": {
+                            "application/json": {
+                                "schema": { "$ref": "#/components/schemas/StorageAccount" }
+                            }
+                        }
+                    },
+                    "responses": { "200": { "description": "OK" } }
+                }
+            }
+        },
+        "components": {
+            "schemas": {
+                "StorageAccount": {
+                    "type": "object",
+                    "properties": {
+                        "retentionDays": { "type": "integer", "minimum": 1, "maximum": 365, "default": 7 },
+                        "tags": { "type": "array", "items": { "type": "string" }, "maxItems": 10 },
+                        "httpsOnly": { "type": "boolean", "default": true }
+                    }
+                }
+            }
+        }
+    }"##;
+
+    let parser = OpenApiParser::from_json(openapi_json, "storage", "2023-01-01")
+        .unwrap()
+        .with_provider_hint(ProviderHint::Azure);
+    let service_def = parser.parse().unwrap();
+    let account = service_def
+        .resources
+        .iter()
+        .find(|r| r.name == "storage_account")
+        .expect("Should have storage_account resource");
+    let field = |name: &str| {
+        account
+            .fields
+            .iter()
+            .find(|f| f.name == name)
+            .unwrap_or_else(|| panic!("Should have {} field", name))
+    };
+
+    // Parameter schemas carry their constraints
+    let account_name = field("account_name");
+    assert_eq!(account_name.constraints.min_length, Some(3));
+    assert_eq!(account_name.constraints.max_length, Some(24));
+    assert_eq!(
+        account_name.constraints.pattern.as_deref(),
+        Some("^[a-z0-9]+$")
+    );
+
+    // Property ranges and defaults are kept
+    let retention = field("retention_days");
+    assert_eq!(retention.constraints.minimum, Some(1.0));
+    assert_eq!(retention.constraints.maximum, Some(365.0));
+    assert_eq!(retention.default_value, Some(serde_json::json!(7)));
+
+    // Item counts bound the length of lists
+    assert_eq!(field("tags").constraints.max_length, Some(10));
+    assert_eq!(
+        field("https_only").default_value,
+        Some(serde_json::json!(true))
+    );
+    assert!(field("subscription_id").constraints.is_empty());
+
+    println!("✅ Successfully extracted OpenAPI constraints and defaults!");
+}
+
+#[test]
+fn test_parse_openapi_write_only_properties() {
+    let openapi_json = r##"{
+        "openapi": "3.0.0",
+        "info": { "title": "SqlManagementClient", "version": "2023-01-01" },
+        "paths": {
+            "/servers/{serverName}": {
+                "put": {
+                    "operationId": "Servers_CreateOrUpdate",
+                    "parameters": [
+                        { "name": "serverName", "in": "path", "required": true, "schema": { "type": "string" } }
+                    ],
+                    "requestBody": {
+                        "content": {
+                            "application/json": { "schema": { "$ref": "#/components/schemas/Server" } }
+                        }
+                    },
+                    "responses": { "200": { "description": "OK" } }
+                },
+                "get": {
+                    "operationId": "Servers_Get",
+                    "parameters": [
+                        { "name": "serverName", "in": "path", "required": true, "schema": { "type": "string" } }
+                    ],
+                    "responses": {
+                        "200": {
+                            "description": "OK",
+                            "content": {
+                                "application/json": { "schema": { "$ref": "#/components/schemas/Server" } }
+                            }
+                        }
+                    }
+                }
+            }
+        },
+        "components": {
+            "schemas": {
+                "Server": {
+                    "type": "object",
+                    "properties": {
+                        "administratorLoginPassword": { "type": "string", "writeOnly": true },
+                        "version": { "type": "string" },
+                        "fullyQualifiedDomainName": { "type": "string", "readOnly": true }
+                    }
+                }
+            }
+        }
+    }"##;
+
+    let parser = OpenApiParser::from_json(openapi_json, "sql", "2023-01-01")
+        .unwrap()
+        .with_provider_hint(ProviderHint::Azure);
+    let service_def = parser.parse().unwrap();
+    let server = service_def
+        .resources
+        .iter()
+        .find(|r| r.name == "server")
+        .expect("Should have server resource");
+
+    // writeOnly properties are inputs only
+    let password = server
+        .fields
+        .iter()
+        .find(|f| f.name == "administrator_login_password")
+        .expect("Should have administrator_login_password field");
+    assert!(password.write_only);
+    assert!(!server
+        .outputs
+        .iter()
+        .any(|o| o.name == "administrator_login_password"));
+
+    // Optional properties the read returns are computed when not set
+    let version = server.fields.iter().find(|f| f.name == "version").unwrap();
+    assert!(version.is_optional_computed() && !version.write_only);
+    assert!(server
+        .outputs
+        .iter()
+        .any(|o| o.name == "fully_qualified_domain_name"));
+
+    println!("✅ Successfully parsed OpenAPI write-only properties!");
+}
+
+#[test]
+fn test_parse_openapi_sized_integer_bytes_and_document_types() {
+    let openapi_json = r##"{
+        "openapi": "3.0.0",
+        "info": { "title": "Blob Service", "version": "1.0.0" },
+        "paths": {
+            "/blobs": {
+                "post": {
+                    "operationId": "createBlob",
+                    "requestBody": {
+                        "content": {
+                            "application/json": { "schema": { "$ref": "#/components/schemas/Blob" } }
+                        }
+                    },
+                    "responses": { "201": { "description": "Created" } }
+                }
+            }
+        },
+        "components": {
+            "schemas": {
+                "Blob": {
+                    "type": "object",
+                    "properties": {
+                        "replicas": { "type": "integer", "format": "int32" },
+                        "size": { "type": "integer", "format": "int64" },
+                        "content": { "type": "string", "format": "byte" },
+                        "attributes": { "type": "object" }
+                    }
+                }
+            }
+        }
+    }"##;
+
+    let parser = OpenApiParser::from_json(openapi_json, "storage", "1.0.0").unwrap();
+    let service_def = parser.parse().unwrap();
+    let blob = service_def
+        .resources
+        .iter()
+        .find(|r| r.name == "blob")
+        .expect("Should have blob resource");
+    let field_type = |name: &str| {
+        blob.fields
+            .iter()
+            .find(|f| f.name == name)
+            .unwrap_or_else(|| panic!("Should have {} field", name))
+            .field_type
+            .clone()
+    };
+
+    assert_eq!(field_type("replicas"), FieldType::Int32);
+    assert_eq!(field_type("size"), FieldType::Integer);
+    assert_eq!(field_type("content"), FieldType::Bytes);
+    assert_eq!(field_type("attributes"), FieldType::Document);
+
+    println!("✅ Successfully parsed OpenAPI sized integer, bytes and document types!");
+}
+
+#[test]
+fn test_parse_openapi_deprecation_and_stability() {
+    let openapi_json = r##"{
+        "openapi": "3.0.0",
+        "info": { "title": "Queue Service", "version": "2024-01-01-preview" },
+        "paths": {
+            "/queues": {
+                "post": {
+                    "operationId": "createQueue",
+                    "deprecated": true,
+                    "parameters": [
+                        { "name": "legacyMode", "in": "query", "deprecated": true, "schema": { "type": "boolean" } }
+                    ],
+                    "requestBody": {
+                        "content": {
+                            "application/json": { "schema": { "$ref": "#/components/schemas/Queue" } }
+                        }
+                    },
+                    "responses": { "201": { "description": "Created" } }
+                }
+            },
+            "/queues/{queueName}": {
+                "delete": {
+                    "operationId": "deleteQueue",
+                    "parameters": [
+                        { "name": "queueName", "in": "path", "required": true, "schema": { "type": "string" } }
+                    ],
+                    "responses": { "204": { "description": "Deleted" } }
+                }
+            }
+        },
+        "components": {
+            "schemas": {
+                "Queue": {
+                    "type": "object",
+                    "properties": {
+                        "name": { "type": "string" },
+                        "visibility": { "type": "integer", "deprecated": true }
+                    }
+                }
+            }
+        }
+    }"##;
+
+    let parser = OpenApiParser::from_json(openapi_json, "queue", "2024-01-01-preview").unwrap();
+    let service_def = parser.parse().unwrap();
+    let queue = service_def
+        .resources
+        .iter()
+        .find(|r| r.name == "queue")
+        .expect("Should have queue resource");
+
+    assert!(queue
+        .operations
+        .create
+        .as_ref()
+        .unwrap()
+        .deprecated
+        .is_some());
+    assert!(queue
+        .operations
+        .delete
+        .as_ref()
+        .unwrap()
+        .deprecated
+        .is_none());
+    assert!(queue.deprecated.is_some());
+    assert_eq!(queue.stability, Stability::Beta);
+
+    let field = |name: &str| queue.fields.iter().find(|f| f.name == name).unwrap();
+    assert!(field("visibility").deprecated.is_some());
+    assert!(field("legacy_mode").deprecated.is_some());
+    assert!(field("name").deprecated.is_none());
+
+    println!("✅ Successfully parsed OpenAPI deprecation and stability!");
+}
+
+#[test]
+fn test_parse_openapi_with_crud_rules() {
+    let openapi_json = r##"{
+        "openapi": "3.0.0",
+        "info": { "title": "Widget Service", "version": "2024-01-01" },
+        "paths": {
+            "/widgets": {
+                "get": {
+                    "operationId": "Widgets_List",
+                    "responses": { "200": { "description": "OK" } }
+                }
+            },
+            "/widgets/{widgetName}": {
+                "get": {
+                    "operationId": "Widgets_Get",
+                    "responses": { "200": { "description": "OK" } }
+                },
+                "put": {
+                    "operationId": "Widgets_CreateOrUpdate",
+                    "requestBody": {
+                        "content": {
+                            "application/json": {
+                                "schema": {
+                                    "type": "object",
+                                    "properties": { "color": { "type": "string" } }
+                                }
+                            }
+                        }
+                    },
+                    "responses": { "200": { "description": "OK" } }
+                },
+                "patch": {
+                    "operationId": "Widgets_Update",
+                    "responses": { "200": { "description": "OK" } }
+                },
+                "delete": {
+                    "operationId": "Widgets_Delete",
+                    "responses": { "200": { "description": "OK" } }
+                }
+            }
+        }
+    }"##;
+
+    let operation_id =
+        |op: &Option<OperationMapping>| op.as_ref().map(|op| op.sdk_operation.clone());
+
+    // Built-in rules fall back to the HTTP method: Azure PUTs create, PATCH
+    // updates, and the item GET reads rather than the collection GET
+    let parser = OpenApiParser::from_json(openapi_json, "widgets", "2024-01-01")
+        .unwrap()
+        .with_provider_hint(ProviderHint::Azure);
+    let service_def = parser.parse().unwrap();
+    let widget = &service_def.resources[0];
+    assert_eq!(widget.name, "widget");
+    assert_eq!(
+        operation_id(&widget.operations.create),
+        Some("widgets_create_or_update".to_string())
+    );
+    assert_eq!(
+        operation_id(&widget.operations.read),
+        Some("widgets_get".to_string())
+    );
+    assert_eq!(
+        operation_id(&widget.operations.update),
+        Some("widgets_update".to_string())
+    );
+
+    // A rules file is checked first
+    let rules = CrudRules::from_yaml(
+        r#"
+overrides:
+  Widgets_Get: ignore
+  Widgets_Update: ignore
+"#,
+    )
+    .unwrap();
+    let parser = OpenApiParser::from_json(openapi_json, "widgets", "2024-01-01")
+        .unwrap()
+        .with_provider_hint(ProviderHint::Azure)
+        .with_crud_rules(rules);
+    let service_def = parser.parse().unwrap();
+    let widget = &service_def.resources[0];
+    assert_eq!(
+        operation_id(&widget.operations.create),
+        Some("widgets_create_or_update".to_string())
+    );
+    // Without a PATCH, the PUT that creates also updates
+    assert_eq!(
+        operation_id(&widget.operations.update),
+        Some("widgets_create_or_update".to_string())
+    );
+    // Without a get, the list stands in for the read
+    assert_eq!(
+        operation_id(&widget.operations.read),
+        Some("widgets_list".to_string())
+    );
+    assert!(widget.fields.iter().any(|f| f.name == "color"));
+
+    println!("✅ Successfully classified OpenAPI operations with CRUD rules!");
+}
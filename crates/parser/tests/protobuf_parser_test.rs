@@ -1,5 +1,6 @@
 //! Integration test for Protobuf parser
 
+use hemmer_provider_generator_common::{FieldType, NestingMode};
 use hemmer_provider_generator_parser::ProtobufParser;
 use prost::Message;
 use prost_types::{
@@ -216,3 +217,758 @@ fn test_parse_protobuf_storage_service() {
         bucket.operations.delete.is_some()
     );
 }
+
+#[test]
+fn test_parse_proto_sources_with_imports() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let dir = temp_dir.path();
+    let protos = dir.join("protos");
+    std::fs::create_dir_all(protos.join("example/common")).unwrap();
+    std::fs::create_dir_all(protos.join("example/library/v1")).unwrap();
+
+    std::fs::write(
+        protos.join("example/common/common.proto"),
+        r#"
+syntax = "proto3";
+package example.common;
+
+// Labels shared by all resources
+message Labels {
+  map<string, string> values = 1;
+}
+
+// Imported services are not part of the provider
+service Health {
+  rpc GetStatus(Labels) returns (Labels);
+}
+"#,
+    )
+    .unwrap();
+
+    let library_path = protos.join("example/library/v1/library.proto");
+    std::fs::write(
+        &library_path,
+        r#"
+syntax = "proto3";
+package example.library.v1;
+
+import "google/protobuf/timestamp.proto";
+import "example/common/common.proto";
+
+/* A shelf of books */
+message Shelf {
+  string name = 1;
+  string theme = 2;
+  int64 book_count = 3;
+  google.protobuf.Timestamp create_time = 4;
+  common.Labels labels = 5;
+  optional bool archived = 6;
+  oneof location {
+    string room = 7;
+    int32 floor = 8;
+  }
+}
+
+message CreateShelfRequest {
+  Shelf shelf = 1;
+}
+
+message GetShelfRequest {
+  string name = 1;
+}
+
+message DeleteShelfRequest {
+  string name = 1;
+}
+
+message Empty {}
+
+service Library {
+  rpc CreateShelf(CreateShelfRequest) returns (Shelf);
+  rpc GetShelf(GetShelfRequest) returns (Shelf) {
+    option deprecated = false;
+  }
+  rpc DeleteShelf(DeleteShelfRequest) returns (Empty);
+}
+"#,
+    )
+    .unwrap();
+
+    let parser = ProtobufParser::from_file_with_import_roots(
+        &library_path,
+        std::slice::from_ref(&protos),
+        "library",
+        "v1",
+    )
+    .unwrap();
+
+    // Imports resolve against the root, well-known types are built in
+    let pool = parser.pool();
+    assert!(pool
+        .get_file_by_name("example/library/v1/library.proto")
+        .is_some());
+    assert!(pool
+        .get_file_by_name("google/protobuf/timestamp.proto")
+        .is_some());
+    let shelf = pool
+        .get_message_by_name("example.library.v1.Shelf")
+        .unwrap();
+    assert_eq!(
+        shelf
+            .get_field_by_name("labels")
+            .unwrap()
+            .kind()
+            .as_message()
+            .unwrap()
+            .full_name(),
+        "example.common.Labels"
+    );
+    assert!(shelf
+        .get_field_by_name("archived")
+        .unwrap()
+        .supports_presence());
+
+    let service_def = parser.parse().unwrap();
+    let names: Vec<&str> = service_def
+        .resources
+        .iter()
+        .map(|r| r.name.as_str())
+        .collect();
+    assert_eq!(names, vec!["shelf"]);
+
+    let shelf = &service_def.resources[0];
+    assert_eq!(
+        shelf.operations.create.as_ref().unwrap().sdk_operation,
+        "create_shelf"
+    );
+    assert!(shelf.operations.read.is_some());
+    assert!(shelf.operations.delete.is_some());
+
+    // Without an import root, the file's own directory is used and imports fail
+    let error = ProtobufParser::from_file(&library_path, "library", "v1")
+        .err()
+        .expect("Should fail to resolve imports");
+    assert!(error.to_string().contains("example/common/common.proto"));
+
+    println!("✅ Successfully compiled .proto sources!");
+    println!("   Resources: {}", service_def.resources.len());
+}
+
+/// Trimmed copies of google/api/resource.proto and field_behavior.proto
+const GOOGLE_API_RESOURCE_PROTO: &str = r#"
+syntax = "proto3";
+package google.api;
+import "google/protobuf/descriptor.proto";
+
+extend google.protobuf.FieldOptions {
+  ResourceReference resource_reference = 1055;
+}
+extend google.protobuf.MessageOptions {
+  ResourceDescriptor resource = 1053;
+}
+message ResourceDescriptor {
+  string type = 1;
+  repeated string pattern = 2;
+  string name_field = 3;
+}
+message ResourceReference {
+  string type = 1;
+  string child_type = 2;
+}
+"#;
+
+const GOOGLE_API_FIELD_BEHAVIOR_PROTO: &str = r#"
+syntax = "proto3";
+package google.api;
+import "google/protobuf/descriptor.proto";
+
+extend google.protobuf.FieldOptions {
+  repeated google.api.FieldBehavior field_behavior = 1052 [packed = false];
+}
+enum FieldBehavior {
+  FIELD_BEHAVIOR_UNSPECIFIED = 0;
+  OPTIONAL = 1;
+  REQUIRED = 2;
+  OUTPUT_ONLY = 3;
+  INPUT_ONLY = 4;
+  IMMUTABLE = 5;
+  UNORDERED_LIST = 6;
+  NON_EMPTY_DEFAULT = 7;
+  IDENTIFIER = 8;
+}
+"#;
+
+#[test]
+fn test_parse_aip_resource_annotations() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let dir = temp_dir.path();
+    std::fs::create_dir_all(dir.join("google/api")).unwrap();
+    std::fs::write(
+        dir.join("google/api/resource.proto"),
+        GOOGLE_API_RESOURCE_PROTO,
+    )
+    .unwrap();
+    std::fs::write(
+        dir.join("google/api/field_behavior.proto"),
+        GOOGLE_API_FIELD_BEHAVIOR_PROTO,
+    )
+    .unwrap();
+
+    let storage_path = dir.join("storage.proto");
+    std::fs::write(
+        &storage_path,
+        r#"
+syntax = "proto3";
+package storage.v1;
+
+import "google/api/field_behavior.proto";
+import "google/api/resource.proto";
+import "google/protobuf/timestamp.proto";
+
+message Bucket {
+  option (google.api.resource) = {
+    type: "storage.googleapis.com/Bucket"
+    pattern: "projects/{project}/buckets/{bucket}"
+  };
+
+  string name = 1 [(google.api.field_behavior) = IDENTIFIER];
+  string location = 2 [
+    (google.api.field_behavior) = REQUIRED,
+    (google.api.field_behavior) = IMMUTABLE
+  ];
+  string storage_class = 3;
+  google.protobuf.Timestamp create_time = 4 [(google.api.field_behavior) = OUTPUT_ONLY];
+  string encryption_key = 5 [(google.api.field_behavior) = INPUT_ONLY];
+}
+
+message CreateBucketRequest {
+  string parent = 1 [
+    (google.api.field_behavior) = REQUIRED,
+    (google.api.resource_reference).child_type = "storage.googleapis.com/Bucket"
+  ];
+  Bucket bucket = 2 [(google.api.field_behavior) = REQUIRED];
+  string bucket_id = 3 [(google.api.field_behavior) = REQUIRED];
+}
+
+message GetBucketRequest {
+  string name = 1 [
+    (google.api.field_behavior) = REQUIRED,
+    (google.api.resource_reference) = { type: "storage.googleapis.com/Bucket" }
+  ];
+}
+
+message RemoveBucketRequest {
+  string name = 1 [(google.api.resource_reference).type = "storage.googleapis.com/Bucket"];
+}
+
+message UpdateBucketRequest {
+  Bucket bucket = 1 [(google.api.field_behavior) = REQUIRED];
+}
+
+message Empty {}
+message Policy { string etag = 1; }
+message GetIamPolicyRequest { string resource = 1; }
+
+service Storage {
+  rpc CreateBucket(CreateBucketRequest) returns (Bucket);
+  rpc GetBucket(GetBucketRequest) returns (Bucket);
+  rpc UpdateBucket(UpdateBucketRequest) returns (Bucket);
+  // Non-standard name: matched through the resource reference
+  rpc DeleteBucketNow(RemoveBucketRequest) returns (Empty);
+  rpc GetIamPolicy(GetIamPolicyRequest) returns (Policy);
+}
+"#,
+    )
+    .unwrap();
+
+    let parser = ProtobufParser::from_file(&storage_path, "storage", "v1").unwrap();
+    let service_def = parser.parse().unwrap();
+
+    // Only annotated messages become resources
+    let names: Vec<&str> = service_def
+        .resources
+        .iter()
+        .map(|r| r.name.as_str())
+        .collect();
+    assert_eq!(names, vec!["bucket"]);
+
+    let bucket = &service_def.resources[0];
+    assert_eq!(bucket.id_field.as_deref(), Some("name"));
+    assert_eq!(
+        bucket.operations.delete.as_ref().unwrap().sdk_operation,
+        "delete_bucket_now"
+    );
+    assert!(bucket.operations.update.is_some());
+
+    let field = |name: &str| bucket.fields.iter().find(|f| f.name == name);
+    let name = field("name").unwrap();
+    assert!(name.immutable && !name.required);
+    assert_eq!(
+        name.description.as_deref(),
+        Some("Resource name (projects/{project}/buckets/{bucket})")
+    );
+    let location = field("location").unwrap();
+    assert!(location.required && location.immutable);
+    let storage_class = field("storage_class").unwrap();
+    assert!(!storage_class.required && !storage_class.immutable);
+    assert!(field("encryption_key").unwrap().sensitive);
+    assert!(
+        field("create_time").is_none(),
+        "OUTPUT_ONLY is not an input"
+    );
+
+    // Other create request fields are create-only inputs
+    let bucket_id = field("bucket_id").unwrap();
+    assert!(bucket_id.required && bucket_id.immutable);
+    assert!(field("parent").unwrap().required);
+    assert!(field("bucket").is_none());
+
+    let output = |name: &str| bucket.outputs.iter().find(|o| o.name == name);
+    assert_eq!(
+        output("create_time").unwrap().field_type,
+        FieldType::DateTime
+    );
+    assert!(
+        output("encryption_key").is_none(),
+        "INPUT_ONLY is not returned"
+    );
+    assert!(output("location").unwrap().response_accessor.is_some());
+
+    println!("✅ Successfully parsed AIP resource annotations!");
+    println!("   Inputs: {}", bucket.fields.len());
+    println!("   Outputs: {}", bucket.outputs.len());
+}
+
+#[test]
+fn test_parse_proto_nested_messages_oneofs_and_well_known_types() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let dir = temp_dir.path();
+    let path = dir.join("functions.proto");
+    std::fs::write(
+        &path,
+        r#"
+syntax = "proto3";
+package functions.v1;
+
+import "google/protobuf/duration.proto";
+import "google/protobuf/field_mask.proto";
+import "google/protobuf/struct.proto";
+import "google/protobuf/wrappers.proto";
+
+message Function {
+  string name = 1;
+  map<string, string> labels = 2;
+  google.protobuf.Duration timeout = 3;
+  google.protobuf.Struct config = 4;
+  google.protobuf.Int32Value max_instances = 5;
+  repeated string tags = 6;
+
+  message SourceRepository {
+    string url = 1;
+    string branch = 2;
+  }
+  oneof source {
+    string source_archive_url = 7;
+    SourceRepository source_repository = 8;
+  }
+
+  message EventTrigger {
+    string event_type = 1;
+    RetryPolicy retry_policy = 2;
+  }
+  repeated EventTrigger event_triggers = 9;
+
+  // Recursive through a nested message
+  Node tree = 10;
+}
+
+message RetryPolicy {
+  int32 max_attempts = 1;
+}
+
+message Node {
+  string value = 1;
+  repeated Node children = 2;
+}
+
+message CreateFunctionRequest {
+  Function function = 1;
+}
+
+message GetFunctionRequest {
+  string name = 1;
+}
+
+message UpdateFunctionRequest {
+  Function function = 1;
+  google.protobuf.FieldMask update_mask = 2;
+}
+
+service Functions {
+  rpc CreateFunction(CreateFunctionRequest) returns (Function);
+  rpc GetFunction(GetFunctionRequest) returns (Function);
+  rpc UpdateFunction(UpdateFunctionRequest) returns (Function);
+}
+"#,
+    )
+    .unwrap();
+
+    let parser = ProtobufParser::from_file(&path, "functions", "v1").unwrap();
+    let service_def = parser.parse().unwrap();
+
+    let function = &service_def.resources[0];
+    assert_eq!(function.name, "function");
+
+    // The request's message field is a block with the resource's schema
+    let block = function
+        .blocks
+        .iter()
+        .find(|b| b.name == "function")
+        .expect("Should have function block");
+    assert_eq!(block.sdk_type_name.as_deref(), Some("Function"));
+    assert_eq!(block.sdk_accessor_method.as_deref(), Some("function"));
+
+    let attribute = |name: &str| block.attributes.iter().find(|a| a.name == name).unwrap();
+    assert_eq!(
+        attribute("labels").field_type,
+        FieldType::Map(Box::new(FieldType::String), Box::new(FieldType::String))
+    );
+    assert_eq!(attribute("timeout").field_type, FieldType::String);
+    assert_eq!(attribute("config").field_type, FieldType::Document);
+    assert_eq!(attribute("max_instances").field_type, FieldType::Int32);
+    assert_eq!(
+        attribute("tags").field_type,
+        FieldType::List(Box::new(FieldType::String))
+    );
+    assert!(block.attributes.iter().all(|a| !a.required));
+
+    // oneof → one-of block with the variants
+    let source = block.blocks.iter().find(|b| b.name == "source").unwrap();
+    assert!(source.one_of);
+    assert_eq!(source.sdk_type_name.as_deref(), Some("function::Source"));
+    assert_eq!(source.sdk_accessor_method.as_deref(), Some("source"));
+    assert_eq!(source.attributes[0].name, "source_archive_url");
+    assert_eq!(
+        source.blocks[0].sdk_type_name.as_deref(),
+        Some("function::SourceRepository")
+    );
+
+    // Repeated nested messages → list blocks, recursively
+    let triggers = block
+        .blocks
+        .iter()
+        .find(|b| b.name == "event_triggers")
+        .unwrap();
+    assert_eq!(triggers.nesting_mode, NestingMode::List);
+    assert_eq!(triggers.blocks[0].name, "retry_policy");
+    assert_eq!(triggers.blocks[0].nesting_mode, NestingMode::Single);
+
+    // Recursive messages stop at the first repetition
+    let tree = block.blocks.iter().find(|b| b.name == "tree").unwrap();
+    assert!(tree.blocks.is_empty());
+    assert!(tree.attributes.iter().any(|a| a.name == "children"));
+
+    // Outputs keep messages as objects
+    let output = |name: &str| function.outputs.iter().find(|o| o.name == name).unwrap();
+    assert!(matches!(
+        output("event_triggers").field_type,
+        FieldType::List(ref inner) if matches!(**inner, FieldType::Object(ref props) if props.contains_key("retry_policy"))
+    ));
+
+    println!("✅ Successfully parsed nested protobuf messages!");
+    println!("   Function blocks: {}", block.blocks.len());
+}
+
+#[test]
+fn test_parse_proto_comments_as_descriptions() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let dir = temp_dir.path();
+    let path = dir.join("queues.proto");
+    std::fs::write(
+        &path,
+        r#"
+syntax = "proto3";
+package queues.v1;
+
+// A queue of
+// pending tasks.
+message Queue {
+  // Queue name.
+  string name = 1;
+  int32 max_tasks = 2; // Maximum number of tasks.
+
+  /* Unused detached comment */
+
+  /*
+   * How failed tasks are retried.
+   */
+  RetryConfig retry_config = 3;
+
+  // Where tasks are sent.
+  oneof target {
+    string http_url = 4;
+    string app_engine_service = 5;
+  }
+}
+
+message RetryConfig {
+  int32 max_attempts = 1;
+}
+
+message CreateQueueRequest {
+  Queue queue = 1;
+}
+
+message GetQueueRequest {
+  string name = 1;
+}
+
+service Queues {
+  rpc CreateQueue(CreateQueueRequest) returns (Queue);
+  rpc GetQueue(GetQueueRequest) returns (Queue);
+}
+"#,
+    )
+    .unwrap();
+
+    let parser = ProtobufParser::from_file(&path, "queues", "v1").unwrap();
+    let service_def = parser.parse().unwrap();
+
+    let queue = &service_def.resources[0];
+    assert_eq!(
+        queue.description.as_deref(),
+        Some("A queue of pending tasks.")
+    );
+
+    let output = |name: &str| queue.outputs.iter().find(|o| o.name == name).unwrap();
+    assert_eq!(output("name").description.as_deref(), Some("Queue name."));
+    assert_eq!(
+        output("max_tasks").description.as_deref(),
+        Some("Maximum number of tasks.")
+    );
+
+    let block = queue
+        .blocks
+        .iter()
+        .find(|b| b.name == "queue")
+        .expect("Should have queue block");
+    let retry_config = block
+        .blocks
+        .iter()
+        .find(|b| b.name == "retry_config")
+        .unwrap();
+    assert_eq!(
+        retry_config.description.as_deref(),
+        Some("How failed tasks are retried.")
+    );
+    let target = block.blocks.iter().find(|b| b.name == "target").unwrap();
+    assert!(target.one_of);
+    assert_eq!(target.description.as_deref(), Some("Where tasks are sent."));
+    assert_eq!(
+        block
+            .attributes
+            .iter()
+            .find(|a| a.name == "name")
+            .unwrap()
+            .description
+            .as_deref(),
+        Some("Queue name.")
+    );
+
+    println!("✅ Successfully mapped proto comments to descriptions!");
+}
+
+#[test]
+fn test_parse_proto_list_methods_as_data_sources() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let dir = temp_dir.path();
+    let path = dir.join("queues.proto");
+    std::fs::write(
+        &path,
+        r#"
+syntax = "proto3";
+package queues.v1;
+
+message Queue {
+  string name = 1;
+  int32 max_tasks = 2;
+}
+
+message GetQueueRequest {
+  string name = 1;
+}
+
+message ListQueuesRequest {
+  string parent = 1;
+  string filter = 2;
+  int32 page_size = 3;
+  string page_token = 4;
+}
+
+message ListQueuesResponse {
+  repeated Queue queues = 1;
+  string next_page_token = 2;
+}
+
+service Queues {
+  rpc GetQueue(GetQueueRequest) returns (Queue);
+  // Lists queues.
+  rpc ListQueues(ListQueuesRequest) returns (ListQueuesResponse);
+}
+"#,
+    )
+    .unwrap();
+
+    let parser = ProtobufParser::from_file(&path, "queues", "v1").unwrap();
+    let service_def = parser.parse().unwrap();
+
+    assert_eq!(service_def.data_sources.len(), 1);
+    let queue = &service_def.data_sources[0];
+    assert_eq!(queue.name, "queue");
+    assert_eq!(queue.description.as_deref(), Some("Lists queues."));
+    assert_eq!(queue.read_operation.sdk_operation, "list_queues");
+    assert!(queue.read_operation.readonly);
+    assert_eq!(queue.items_accessor.as_deref(), Some("queues"));
+
+    // AIP-158 pagination fields are driven by the provider
+    let pagination = queue.read_operation.pagination.as_ref().unwrap();
+    assert_eq!(pagination.input_token.as_deref(), Some("page_token"));
+    assert_eq!(pagination.page_size.as_deref(), Some("page_size"));
+    assert_eq!(pagination.items.as_deref(), Some("queues"));
+    let arguments: Vec<&str> = queue.arguments.iter().map(|a| a.name.as_str()).collect();
+    assert_eq!(arguments, vec!["parent", "filter"]);
+
+    // Attributes are the fields of the listed message
+    let attributes: Vec<&str> = queue.attributes.iter().map(|a| a.name.as_str()).collect();
+    assert_eq!(attributes, vec!["name", "max_tasks"]);
+    assert!(queue
+        .attributes
+        .iter()
+        .all(|a| a.response_accessor.is_some()));
+
+    println!("✅ Successfully mapped List RPCs to data sources!");
+}
+
+/// Trimmed copy of google/longrunning/operations.proto
+const GOOGLE_LONGRUNNING_OPERATIONS_PROTO: &str = r#"
+syntax = "proto3";
+package google.longrunning;
+import "google/protobuf/any.proto";
+import "google/protobuf/descriptor.proto";
+
+extend google.protobuf.MethodOptions {
+  google.longrunning.OperationInfo operation_info = 1049;
+}
+message Operation {
+  string name = 1;
+  google.protobuf.Any metadata = 2;
+  bool done = 3;
+  oneof result {
+    google.protobuf.Any error = 4;
+    google.protobuf.Any response = 5;
+  }
+}
+message OperationInfo {
+  string response_type = 1;
+  string metadata_type = 2;
+}
+"#;
+
+#[test]
+fn test_parse_proto_long_running_operations() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let dir = temp_dir.path();
+    std::fs::create_dir_all(dir.join("google/longrunning")).unwrap();
+    std::fs::write(
+        dir.join("google/longrunning/operations.proto"),
+        GOOGLE_LONGRUNNING_OPERATIONS_PROTO,
+    )
+    .unwrap();
+
+    let path = dir.join("instances.proto");
+    std::fs::write(
+        &path,
+        r#"
+syntax = "proto3";
+package example.compute.v1;
+
+import "google/longrunning/operations.proto";
+import "google/protobuf/empty.proto";
+
+message Instance {
+  string name = 1;
+  string machine_type = 2;
+  string state = 3;
+}
+
+message OperationMetadata {
+  string verb = 1;
+}
+
+message CreateInstanceRequest {
+  string instance_id = 1;
+  string machine_type = 2;
+}
+
+message DeleteInstanceRequest {
+  string name = 1;
+}
+
+service Instances {
+  rpc CreateInstance(CreateInstanceRequest) returns (google.longrunning.Operation) {
+    option (google.longrunning.operation_info) = {
+      response_type: "Instance"
+      metadata_type: "OperationMetadata"
+    };
+  }
+  rpc DeleteInstance(DeleteInstanceRequest) returns (google.longrunning.Operation) {
+    option (google.longrunning.operation_info) = {
+      response_type: "google.protobuf.Empty"
+      metadata_type: "example.compute.v1.OperationMetadata"
+    };
+  }
+}
+"#,
+    )
+    .unwrap();
+
+    let parser =
+        ProtobufParser::from_file_with_import_roots(&path, &[dir.to_path_buf()], "compute", "v1")
+            .unwrap();
+    let service_def = parser.parse().unwrap();
+
+    let instance = &service_def.resources[0];
+    assert_eq!(instance.name, "instance");
+
+    // Outputs come from the operation's response type, not Operation itself
+    let outputs: Vec<&str> = instance.outputs.iter().map(|o| o.name.as_str()).collect();
+    assert_eq!(outputs, vec!["name", "machine_type", "state"]);
+
+    let create = instance.operations.create.as_ref().unwrap();
+    let long_running = create
+        .long_running
+        .as_ref()
+        .expect("Create should be long-running");
+    assert_eq!(
+        long_running.response_type.as_deref(),
+        Some("example.compute.v1.Instance")
+    );
+    assert_eq!(
+        long_running.metadata_type.as_deref(),
+        Some("example.compute.v1.OperationMetadata")
+    );
+    assert_eq!(long_running.timeout_secs, None);
+
+    let delete = instance.operations.delete.as_ref().unwrap();
+    assert_eq!(
+        delete
+            .long_running
+            .as_ref()
+            .unwrap()
+            .response_type
+            .as_deref(),
+        Some("google.protobuf.Empty")
+    );
+
+    println!("✅ Successfully parsed long-running operations!");
+}
@@ -1,6 +1,8 @@
 //! Integration test for Smithy parser
 
-use hemmer_provider_generator_parser::smithy::SmithyParser;
+use hemmer_provider_generator_common::{Deprecation, FieldType, Provider, Stability, WaiterState};
+use hemmer_provider_generator_parser::smithy::{SmithyModelPath, SmithyParser};
+use hemmer_provider_generator_parser::CrudRules;
 
 #[test]
 fn test_parse_simple_smithy_model() {
@@ -583,7 +585,7 @@ fn test_parse_smithy_with_recursive_nested_blocks() {
         .expect("Should have days attribute");
     assert_eq!(
         days_attr.field_type,
-        hemmer_provider_generator_common::FieldType::Integer
+        hemmer_provider_generator_common::FieldType::Int32
     );
 
     let storage_class_attr = transitions_block
@@ -611,3 +613,1325 @@ fn test_parse_smithy_with_recursive_nested_blocks() {
         transitions_block.attributes.len()
     );
 }
+
+#[test]
+fn test_parse_smithy_resource_lifecycle_bindings() {
+    // Resource shapes bind lifecycle operations explicitly; operation names
+    // deliberately don't follow the Create/Get/Delete naming convention
+    let smithy_json = r#"{
+        "smithy": "2.0",
+        "shapes": {
+            "com.example.storage#StorageService": {
+                "type": "service",
+                "version": "2023-01-01",
+                "operations": [
+                    { "target": "com.example.storage#GetServiceStatus" }
+                ],
+                "resources": [
+                    { "target": "com.example.storage#Bucket" }
+                ]
+            },
+            "com.example.storage#Bucket": {
+                "type": "resource",
+                "identifiers": {
+                    "bucketName": { "target": "smithy.api#String" }
+                },
+                "create": { "target": "com.example.storage#MakeBucket" },
+                "read": { "target": "com.example.storage#FetchBucket" },
+                "delete": { "target": "com.example.storage#RemoveBucket" },
+                "list": { "target": "com.example.storage#ListBuckets" },
+                "resources": [
+                    { "target": "com.example.storage#Object" }
+                ],
+                "traits": {
+                    "smithy.api#documentation": "A storage bucket"
+                }
+            },
+            "com.example.storage#Object": {
+                "type": "resource",
+                "identifiers": {
+                    "bucketName": { "target": "smithy.api#String" },
+                    "key": { "target": "smithy.api#String" }
+                },
+                "put": { "target": "com.example.storage#PutObject" },
+                "read": { "target": "com.example.storage#GetObject" },
+                "delete": { "target": "com.example.storage#DeleteObject" },
+                "operations": [
+                    { "target": "com.example.storage#RestoreObject" }
+                ]
+            },
+            "com.example.storage#MakeBucket": {
+                "type": "operation",
+                "input": { "target": "com.example.storage#MakeBucketInput" }
+            },
+            "com.example.storage#MakeBucketInput": {
+                "type": "structure",
+                "members": {
+                    "bucketName": {
+                        "target": "smithy.api#String",
+                        "traits": { "smithy.api#required": {} }
+                    },
+                    "region": { "target": "smithy.api#String" }
+                }
+            },
+            "com.example.storage#FetchBucket": {
+                "type": "operation",
+                "output": { "target": "com.example.storage#FetchBucketOutput" }
+            },
+            "com.example.storage#FetchBucketOutput": {
+                "type": "structure",
+                "members": {
+                    "creationDate": { "target": "smithy.api#Timestamp" }
+                }
+            },
+            "com.example.storage#RemoveBucket": { "type": "operation" },
+            "com.example.storage#ListBuckets": { "type": "operation" },
+            "com.example.storage#PutObject": {
+                "type": "operation",
+                "input": { "target": "com.example.storage#PutObjectInput" }
+            },
+            "com.example.storage#PutObjectInput": {
+                "type": "structure",
+                "members": {
+                    "bucketName": {
+                        "target": "smithy.api#String",
+                        "traits": { "smithy.api#required": {} }
+                    },
+                    "key": {
+                        "target": "smithy.api#String",
+                        "traits": { "smithy.api#required": {} }
+                    },
+                    "body": { "target": "smithy.api#String" }
+                }
+            },
+            "com.example.storage#GetObject": { "type": "operation" },
+            "com.example.storage#DeleteObject": { "type": "operation" },
+            "com.example.storage#RestoreObject": { "type": "operation" },
+            "com.example.storage#GetServiceStatus": { "type": "operation" }
+        }
+    }"#;
+
+    let parser = SmithyParser::from_json(smithy_json, "storage", "2023-01-01").unwrap();
+    let service_def = parser.parse().unwrap();
+
+    // Bucket is built from its lifecycle bindings
+    let bucket = service_def
+        .resources
+        .iter()
+        .find(|r| r.name == "bucket")
+        .expect("Should have bucket resource");
+    assert_eq!(bucket.id_field.as_deref(), Some("bucket_name"));
+    assert_eq!(bucket.description.as_deref(), Some("A storage bucket"));
+    assert_eq!(
+        bucket.operations.create.as_ref().unwrap().sdk_operation,
+        "make_bucket"
+    );
+    assert_eq!(
+        bucket.operations.read.as_ref().unwrap().sdk_operation,
+        "fetch_bucket"
+    );
+    assert_eq!(
+        bucket.operations.delete.as_ref().unwrap().sdk_operation,
+        "remove_bucket"
+    );
+    assert!(bucket.operations.update.is_none());
+    let bucket_name = bucket
+        .fields
+        .iter()
+        .find(|f| f.name == "bucket_name")
+        .expect("Should have bucket_name field");
+    assert!(bucket_name.required);
+    assert!(bucket_name.immutable, "Identifiers should be immutable");
+    assert!(bucket.outputs.iter().any(|o| o.name == "creation_date"));
+
+    // Child resource uses its own identifier, and put covers create and update
+    let object = service_def
+        .resources
+        .iter()
+        .find(|r| r.name == "object")
+        .expect("Should have child object resource");
+    assert_eq!(object.id_field.as_deref(), Some("key"));
+    assert_eq!(
+        object.operations.create.as_ref().unwrap().sdk_operation,
+        "put_object"
+    );
+    assert_eq!(
+        object.operations.update.as_ref().unwrap().sdk_operation,
+        "put_object"
+    );
+    assert!(object
+        .fields
+        .iter()
+        .all(|f| f.name != "body" || !f.immutable));
+
+    // Bound operations are not regrouped by name prefix
+    assert_eq!(
+        service_def
+            .resources
+            .iter()
+            .filter(|r| r.name == "object" || r.name == "bucket")
+            .count(),
+        2
+    );
+
+    // Unbound operations still fall back to prefix grouping
+    let status = service_def
+        .resources
+        .iter()
+        .find(|r| r.name == "service_status")
+        .expect("Unbound operation should fall back to prefix grouping");
+    assert!(status.id_field.is_none());
+    assert_eq!(service_def.resources.len(), 3);
+
+    println!("✅ Successfully parsed Smithy resource lifecycle bindings!");
+    println!("   Resources: {}", service_def.resources.len());
+}
+
+#[test]
+fn test_parse_smithy_child_resource_bound_to_two_parents() {
+    // Attachment is bound under both Volume and Snapshot
+    let smithy_json = r#"{
+        "smithy": "2.0",
+        "shapes": {
+            "com.example.disks#DiskService": {
+                "type": "service",
+                "version": "2023-01-01",
+                "resources": [
+                    { "target": "com.example.disks#Volume" },
+                    { "target": "com.example.disks#Snapshot" }
+                ]
+            },
+            "com.example.disks#Volume": {
+                "type": "resource",
+                "identifiers": {
+                    "volumeId": { "target": "smithy.api#String" }
+                },
+                "create": { "target": "com.example.disks#CreateVolume" },
+                "resources": [
+                    { "target": "com.example.disks#Attachment" }
+                ]
+            },
+            "com.example.disks#Snapshot": {
+                "type": "resource",
+                "identifiers": {
+                    "snapshotId": { "target": "smithy.api#String" }
+                },
+                "create": { "target": "com.example.disks#CreateSnapshot" },
+                "resources": [
+                    { "target": "com.example.disks#Attachment" }
+                ]
+            },
+            "com.example.disks#Attachment": {
+                "type": "resource",
+                "identifiers": {
+                    "attachmentId": { "target": "smithy.api#String" }
+                },
+                "create": { "target": "com.example.disks#Attach" },
+                "delete": { "target": "com.example.disks#Detach" }
+            },
+            "com.example.disks#CreateVolume": { "type": "operation" },
+            "com.example.disks#CreateSnapshot": { "type": "operation" },
+            "com.example.disks#Attach": { "type": "operation" },
+            "com.example.disks#Detach": { "type": "operation" }
+        }
+    }"#;
+
+    let service_def = SmithyParser::from_json(smithy_json, "disks", "2023-01-01")
+        .unwrap()
+        .parse()
+        .unwrap();
+
+    let mut names: Vec<&str> = service_def
+        .resources
+        .iter()
+        .map(|r| r.name.as_str())
+        .collect();
+    names.sort();
+    assert_eq!(names, ["attachment", "snapshot", "volume"]);
+
+    println!("✅ Successfully parsed Smithy child resource with two parents!");
+}
+
+#[test]
+fn test_parse_smithy_idl_model() {
+    let smithy_idl = r#"
+$version: "2.0"
+
+namespace com.example.storage
+
+use smithy.waiters#waitable
+
+/// Simple storage service
+service StorageService {
+    version: "2023-01-01"
+    resources: [Bucket]
+}
+
+/// A storage bucket
+resource Bucket {
+    identifiers: { bucketName: BucketName }
+    create: CreateBucket
+    read: GetBucket
+    delete: DeleteBucket
+}
+
+string BucketName
+
+@mixin
+structure BucketNameMixin {
+    /// Name of the bucket
+    @required
+    bucketName: BucketName
+}
+
+operation CreateBucket {
+    input := with [BucketNameMixin] {
+        region: String
+        lifecycleRules: LifecycleRuleList
+    }
+    output := {
+        location: String
+    }
+}
+
+@readonly
+@waitable(
+    BucketExists: {
+        acceptors: [
+            { state: "success", matcher: { success: true } }
+        ]
+    }
+)
+operation GetBucket {
+    input := for Bucket {
+        @required
+        $bucketName
+    }
+    output := {
+        bucketName: BucketName
+        creationDate: Timestamp
+    }
+}
+
+@idempotent
+operation DeleteBucket {
+    input := with [BucketNameMixin] {}
+}
+
+list LifecycleRuleList {
+    member: LifecycleRule
+}
+
+structure LifecycleRule {
+    id: String
+    prefix: String
+    enabled: Boolean = false
+}
+
+apply CreateBucket @documentation("Creates a new bucket")
+"#;
+
+    let parser = SmithyParser::from_idl(smithy_idl, "storage", "2023-01-01").unwrap();
+
+    // The IDL produces the same shapes as the JSON AST
+    let model = parser.model();
+    assert!(model
+        .get_shape("com.example.storage#CreateBucketInput")
+        .is_some());
+    assert!(model
+        .get_shape("com.example.storage#GetBucketOutput")
+        .is_some());
+
+    let service_def = parser.parse().unwrap();
+    let bucket = service_def
+        .resources
+        .iter()
+        .find(|r| r.name == "bucket")
+        .expect("Should have bucket resource");
+
+    assert_eq!(bucket.id_field.as_deref(), Some("bucket_name"));
+    assert_eq!(bucket.description.as_deref(), Some("A storage bucket"));
+    assert_eq!(
+        bucket.operations.create.as_ref().unwrap().sdk_operation,
+        "create_bucket"
+    );
+    assert!(bucket.operations.read.is_some());
+    assert!(bucket.operations.delete.is_some());
+
+    // Mixin members are flattened into the inline input structure
+    let bucket_name = bucket
+        .fields
+        .iter()
+        .find(|f| f.name == "bucket_name")
+        .expect("Should have bucket_name field from mixin");
+    assert!(bucket_name.required);
+    assert_eq!(
+        bucket_name.description.as_deref(),
+        Some("Name of the bucket")
+    );
+    assert!(bucket.fields.iter().any(|f| f.name == "region"));
+
+    // Nested blocks work the same as with the JSON AST
+    let rules = bucket
+        .blocks
+        .iter()
+        .find(|b| b.name == "lifecycle_rules")
+        .expect("Should have lifecycle_rules block");
+    assert_eq!(rules.sdk_type_name.as_deref(), Some("LifecycleRule"));
+    assert_eq!(rules.attributes.len(), 3);
+
+    assert!(bucket.outputs.iter().any(|o| o.name == "creation_date"));
+
+    println!("✅ Successfully parsed Smithy IDL model!");
+    println!("   Resources: {}", service_def.resources.len());
+}
+
+#[test]
+fn test_parse_smithy_idl_model_set() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let shared = dir.path().join("shared");
+    std::fs::create_dir(&shared).unwrap();
+    std::fs::write(
+        dir.path().join("storage.smithy"),
+        r#"
+$version: "2.0"
+
+namespace com.example.storage
+
+use com.example.common#Tags
+
+service StorageService {
+    version: "2023-01-01"
+    resources: [Bucket]
+}
+
+resource Bucket {
+    identifiers: { bucketName: BucketName }
+    create: CreateBucket
+}
+
+operation CreateBucket {
+    input := {
+        @required
+        bucketName: BucketName
+        tags: Tags
+    }
+}
+"#,
+    )
+    .unwrap();
+    std::fs::write(
+        shared.join("common.smithy"),
+        r#"
+$version: "2.0"
+
+namespace com.example.common
+
+list Tags {
+    member: Tag
+}
+
+structure Tag {
+    key: String
+    value: String
+}
+"#,
+    )
+    .unwrap();
+    std::fs::write(
+        shared.join("names.smithy"),
+        "$version: \"2.0\"\nnamespace com.example.storage\nstring BucketName\n",
+    )
+    .unwrap();
+    // Other files next to the spec are not part of its model set
+    std::fs::write(
+        dir.path().join("broken.smithy"),
+        "namespace broken
+string
+",
+    )
+    .unwrap();
+
+    // Shapes from the files of the model path resolve; the spec itself may be
+    // part of the model path
+    let model_path =
+        SmithyModelPath::load(&[shared.clone(), dir.path().join("storage.smithy")]).unwrap();
+    let service_def = SmithyParser::from_file_with_model_path(
+        dir.path().join("storage.smithy"),
+        &model_path,
+        "storage",
+        "2023-01-01",
+    )
+    .unwrap()
+    .parse()
+    .unwrap();
+    let bucket = &service_def.resources[0];
+    assert_eq!(bucket.id_field.as_deref(), Some("bucket_name"));
+    let tags = bucket
+        .blocks
+        .iter()
+        .find(|b| b.name == "tags")
+        .expect("Should have tags block from common.smithy");
+    assert_eq!(tags.sdk_type_name.as_deref(), Some("Tag"));
+
+    // A file without a service of its own has nothing to generate
+    let err = SmithyParser::from_file_with_model_path(
+        shared.join("common.smithy"),
+        &model_path,
+        "common",
+        "1.0",
+    )
+    .unwrap()
+    .parse()
+    .unwrap_err();
+    assert!(
+        err.to_string().contains("No service shape found"),
+        "{}",
+        err
+    );
+
+    // References to shapes outside the model set are reported
+    let err = SmithyParser::from_file(dir.path().join("storage.smithy"), "storage", "2023-01-01")
+        .err()
+        .expect("Missing shapes should be an error");
+    assert!(
+        err.to_string().contains("references unknown shape"),
+        "{}",
+        err
+    );
+    std::fs::remove_file(shared.join("common.smithy")).unwrap();
+    let model_path = SmithyModelPath::load(&[shared]).unwrap();
+    let err = SmithyParser::from_file_with_model_path(
+        dir.path().join("storage.smithy"),
+        &model_path,
+        "storage",
+        "2023-01-01",
+    )
+    .err()
+    .expect("Missing shapes should be an error");
+    assert!(
+        err.to_string()
+            .contains("references unknown shape com.example.common#Tags"),
+        "{}",
+        err
+    );
+
+    println!("✅ Successfully parsed Smithy IDL model set!");
+}
+
+#[test]
+fn test_parse_smithy_operation_traits() {
+    let smithy_idl = r#"
+$version: "2.0"
+
+namespace com.example.queue
+
+use smithy.waiters#waitable
+
+@paginated(inputToken: "NextToken", outputToken: "NextToken", pageSize: "MaxResults")
+service QueueService {
+    version: "2023-01-01"
+    operations: [CreateQueue, DescribeQueue, DeleteQueue, GetQueueEvents]
+}
+
+operation CreateQueue {
+    input := {
+        @required
+        QueueName: String
+
+        @idempotencyToken
+        ClientToken: String
+    }
+}
+
+@readonly
+@waitable(
+    QueueExists: {
+        documentation: "Wait until the queue is active"
+        acceptors: [
+            {
+                state: "success"
+                matcher: { output: { path: "Status", expected: "ACTIVE", comparator: "stringEquals" } }
+            }
+            { state: "retry", matcher: { errorType: "QueueNotFound" } }
+        ]
+        minDelay: 5
+    }
+    QueueDeleted: {
+        acceptors: [
+            { state: "success", matcher: { errorType: "QueueNotFound" } }
+        ]
+    }
+)
+operation DescribeQueue {
+    input := {
+        @required
+        QueueName: String
+    }
+    output := {
+        Status: String
+    }
+}
+
+@idempotent
+operation DeleteQueue {
+    input := {
+        @required
+        QueueName: String
+    }
+}
+
+@readonly
+@paginated(items: "Result.Events")
+operation GetQueueEvents {
+    input := {
+        NextToken: String
+        MaxResults: Integer
+    }
+}
+"#;
+
+    let parser = SmithyParser::from_idl(smithy_idl, "queue", "2023-01-01").unwrap();
+    let service_def = parser.parse().unwrap();
+
+    let queue = service_def
+        .resources
+        .iter()
+        .find(|r| r.name == "queue")
+        .expect("Should have queue resource");
+
+    // Idempotency tokens are filled in by the provider, not by users
+    let create = queue.operations.create.as_ref().unwrap();
+    assert_eq!(create.idempotency_token.as_deref(), Some("client_token"));
+    assert!(queue.fields.iter().all(|f| f.name != "client_token"));
+    assert!(!create.readonly);
+
+    // Readonly implies idempotent
+    let read = queue.operations.read.as_ref().unwrap();
+    assert!(read.readonly);
+    assert!(read.idempotent);
+    let delete = queue.operations.delete.as_ref().unwrap();
+    assert!(delete.idempotent);
+    assert!(!delete.readonly);
+
+    // Waiters are attached to the operations that have to wait for them
+    let ready = create
+        .waiter
+        .as_ref()
+        .expect("Create should wait for queue");
+    assert_eq!(ready.name, "queue_exists");
+    assert_eq!(ready.operation, "describe_queue");
+    assert_eq!(ready.min_delay, Some(5));
+    assert_eq!(ready.acceptors.len(), 2);
+    assert_eq!(ready.acceptors[0].state, WaiterState::Success);
+    assert_eq!(ready.acceptors[1].matcher["errorType"], "QueueNotFound");
+    assert_eq!(
+        delete.waiter.as_ref().map(|w| w.name.as_str()),
+        Some("queue_deleted")
+    );
+
+    // Operation pagination inherits tokens from the service
+    let log_events = service_def
+        .resources
+        .iter()
+        .find(|r| r.name == "queue_events")
+        .expect("Should have queue_events resource");
+    let pagination = log_events
+        .operations
+        .read
+        .as_ref()
+        .and_then(|op| op.pagination.as_ref())
+        .expect("Read should be paginated");
+    assert_eq!(pagination.input_token.as_deref(), Some("next_token"));
+    assert_eq!(pagination.output_token.as_deref(), Some("next_token"));
+    assert_eq!(pagination.page_size.as_deref(), Some("max_results"));
+    assert_eq!(pagination.items.as_deref(), Some("result.events"));
+    assert!(queue.operations.read.as_ref().unwrap().pagination.is_none());
+
+    println!("✅ Successfully carried Smithy operation traits into the IR!");
+}
+
+#[test]
+fn test_parse_smithy_enum_union_and_numeric_shapes() {
+    let smithy_json = r#"{
+        "smithy": "2.0",
+        "shapes": {
+            "com.example.media#MediaService": {
+                "type": "service",
+                "version": "2024-01-01",
+                "resources": [
+                    { "target": "com.example.media#Asset" }
+                ]
+            },
+            "com.example.media#Asset": {
+                "type": "resource",
+                "identifiers": {
+                    "AssetId": { "target": "smithy.api#String" }
+                },
+                "create": { "target": "com.example.media#CreateAsset" },
+                "read": { "target": "com.example.media#GetAsset" }
+            },
+            "com.example.media#CreateAsset": {
+                "type": "operation",
+                "input": { "target": "com.example.media#CreateAssetInput" }
+            },
+            "com.example.media#CreateAssetInput": {
+                "type": "structure",
+                "members": {
+                    "AssetId": { "target": "smithy.api#String" },
+                    "StorageClass": { "target": "com.example.media#StorageClass" },
+                    "Codec": { "target": "com.example.media#Codec" },
+                    "Priority": { "target": "com.example.media#Priority" },
+                    "Source": { "target": "com.example.media#AssetSource" },
+                    "Thumbnail": { "target": "com.example.media#Thumbnail" },
+                    "Channels": { "target": "com.example.media#Channels" },
+                    "SampleBits": { "target": "smithy.api#Short" },
+                    "Gain": { "target": "com.example.media#Gain" },
+                    "ByteCount": { "target": "com.example.media#ByteCount" },
+                    "Checksum": { "target": "com.example.media#Checksum" },
+                    "Metadata": { "target": "com.example.media#Metadata" }
+                }
+            },
+            "com.example.media#GetAsset": {
+                "type": "operation",
+                "input": { "target": "com.example.media#GetAssetInput" },
+                "traits": { "smithy.api#readonly": {} }
+            },
+            "com.example.media#GetAssetInput": {
+                "type": "structure",
+                "members": {
+                    "AssetId": {
+                        "target": "smithy.api#String",
+                        "traits": { "smithy.api#required": {} }
+                    }
+                }
+            },
+            "com.example.media#StorageClass": {
+                "type": "enum",
+                "members": {
+                    "STANDARD": {
+                        "target": "smithy.api#Unit",
+                        "traits": { "smithy.api#enumValue": "standard" }
+                    },
+                    "ARCHIVE": {
+                        "target": "smithy.api#Unit",
+                        "traits": { "smithy.api#enumValue": "archive" }
+                    }
+                }
+            },
+            "com.example.media#Codec": {
+                "type": "string",
+                "traits": {
+                    "smithy.api#enum": [
+                        { "value": "h264", "name": "H264" },
+                        { "value": "av1", "name": "AV1" }
+                    ]
+                }
+            },
+            "com.example.media#Priority": {
+                "type": "intEnum",
+                "members": {
+                    "LOW": {
+                        "target": "smithy.api#Unit",
+                        "traits": { "smithy.api#enumValue": 1 }
+                    },
+                    "HIGH": {
+                        "target": "smithy.api#Unit",
+                        "traits": { "smithy.api#enumValue": 10 }
+                    }
+                }
+            },
+            "com.example.media#AssetSource": {
+                "type": "union",
+                "members": {
+                    "Url": { "target": "smithy.api#String" },
+                    "Upload": { "target": "com.example.media#UploadSource" }
+                }
+            },
+            "com.example.media#UploadSource": {
+                "type": "structure",
+                "members": {
+                    "Bucket": { "target": "smithy.api#String" },
+                    "Key": { "target": "smithy.api#String" },
+                    "VersionId": { "target": "smithy.api#String" }
+                }
+            },
+            "com.example.media#Thumbnail": { "type": "blob" },
+            "com.example.media#Channels": { "type": "byte" },
+            "com.example.media#Gain": { "type": "float" },
+            "com.example.media#ByteCount": { "type": "bigInteger" },
+            "com.example.media#Checksum": { "type": "bigDecimal" },
+            "com.example.media#Metadata": { "type": "document" }
+        }
+    }"#;
+
+    let parser = SmithyParser::from_json(smithy_json, "media", "2024-01-01").unwrap();
+    let service_def = parser.parse().unwrap();
+
+    let asset = service_def
+        .resources
+        .iter()
+        .find(|r| r.name == "asset")
+        .expect("Should have asset resource");
+    let field_type = |name: &str| {
+        asset
+            .fields
+            .iter()
+            .find(|f| f.name == name)
+            .unwrap_or_else(|| panic!("Should have {} field", name))
+            .field_type
+            .clone()
+    };
+
+    // Enums keep their actual values
+    assert_eq!(
+        field_type("storage_class"),
+        FieldType::Enum(vec!["archive".to_string(), "standard".to_string()])
+    );
+    assert_eq!(
+        field_type("codec"),
+        FieldType::Enum(vec!["h264".to_string(), "av1".to_string()])
+    );
+    assert_eq!(field_type("priority"), FieldType::Int32);
+
+    // String enums are converted with the SDK's enum types
+    let sdk_type_name = |name: &str| {
+        asset
+            .fields
+            .iter()
+            .find(|f| f.name == name)
+            .and_then(|f| f.sdk_type_name.clone())
+    };
+    assert_eq!(
+        sdk_type_name("storage_class").as_deref(),
+        Some("StorageClass")
+    );
+    assert_eq!(sdk_type_name("codec").as_deref(), Some("Codec"));
+    assert_eq!(sdk_type_name("priority"), None);
+
+    // Numeric shapes keep their width, arbitrary precision values stay lossless
+    assert_eq!(field_type("channels"), FieldType::Int8);
+    assert_eq!(field_type("sample_bits"), FieldType::Int16);
+    assert_eq!(field_type("gain"), FieldType::Float);
+    assert_eq!(field_type("byte_count"), FieldType::String);
+    assert_eq!(field_type("checksum"), FieldType::String);
+    assert_eq!(field_type("thumbnail"), FieldType::Bytes);
+    assert_eq!(field_type("metadata"), FieldType::Document);
+
+    // Unions become one-of blocks
+    assert!(matches!(field_type("source"), FieldType::Union(ref m) if m.len() == 2));
+    let source = asset
+        .blocks
+        .iter()
+        .find(|b| b.name == "source")
+        .expect("Should have source block");
+    assert!(source.one_of);
+    assert_eq!(source.sdk_type_name.as_deref(), Some("AssetSource"));
+    assert_eq!(source.sdk_accessor_method.as_deref(), Some("source"));
+    assert_eq!(source.max_items, 1);
+    assert_eq!(source.min_items, 0);
+    assert_eq!(source.attributes.len(), 1);
+    assert_eq!(source.attributes[0].name, "url");
+    assert!(!source.attributes[0].required);
+    assert_eq!(source.blocks.len(), 1);
+    assert_eq!(source.blocks[0].name, "upload");
+
+    println!("✅ Successfully parsed Smithy enum, union and numeric shapes!");
+}
+
+#[test]
+fn test_parse_smithy_data_sources() {
+    let smithy_idl = r#"
+$version: "2.0"
+
+namespace com.example.ec2
+
+service Ec2 {
+    version: "2016-11-15"
+    operations: [CreateVpc, DescribeVpcs, DescribeTable]
+}
+
+operation CreateVpc {
+    input := {
+        CidrBlock: String
+    }
+}
+
+/// Describes one or more of your VPCs
+@readonly
+@paginated(inputToken: "NextToken", outputToken: "NextToken", items: "Vpcs", pageSize: "MaxResults")
+operation DescribeVpcs {
+    input := {
+        Filters: FilterList
+        VpcIds: VpcIdStringList
+        NextToken: String
+        MaxResults: Integer
+    }
+    output := {
+        Vpcs: VpcList
+        NextToken: String
+    }
+}
+
+@readonly
+operation DescribeTable {
+    input := {
+        @required
+        TableName: String
+    }
+    output := {
+        TableName: String
+        Indexes: IndexList
+    }
+}
+
+list FilterList {
+    member: Filter
+}
+
+structure Filter {
+    Name: String
+    Values: VpcIdStringList
+}
+
+list VpcIdStringList {
+    member: String
+}
+
+list VpcList {
+    member: Vpc
+}
+
+structure Vpc {
+    VpcId: String
+    CidrBlock: String
+    IsDefault: Boolean
+}
+
+list IndexList {
+    member: Index
+}
+
+structure Index {
+    IndexName: String
+}
+"#;
+
+    let parser = SmithyParser::from_idl(smithy_idl, "ec2", "2016-11-15").unwrap();
+    let service_def = parser.parse().unwrap();
+
+    // List-style operations look up the first listed item
+    let vpc = service_def
+        .data_sources
+        .iter()
+        .find(|ds| ds.name == "vpc")
+        .expect("Should have vpc data source");
+    assert_eq!(
+        vpc.description.as_deref(),
+        Some("Describes one or more of your VPCs")
+    );
+    assert_eq!(vpc.read_operation.sdk_operation, "describe_vpcs");
+    assert!(vpc.read_operation.readonly);
+    assert!(vpc.read_operation.pagination.is_some());
+    assert_eq!(vpc.items_accessor.as_deref(), Some("vpcs"));
+
+    // Filters become arguments; pagination tokens are driven by the provider
+    let mut arguments: Vec<&str> = vpc.arguments.iter().map(|a| a.name.as_str()).collect();
+    arguments.sort();
+    assert_eq!(arguments, vec!["filters", "vpc_ids"]);
+
+    let mut attributes: Vec<&str> = vpc.attributes.iter().map(|a| a.name.as_str()).collect();
+    attributes.sort();
+    assert_eq!(attributes, vec!["cidr_block", "is_default", "vpc_id"]);
+    assert!(vpc
+        .attributes
+        .iter()
+        .all(|a| a.response_accessor.as_deref() == Some(a.name.as_str())));
+
+    // Singular lookups read the output itself, list members included
+    let table = service_def
+        .data_sources
+        .iter()
+        .find(|ds| ds.name == "table")
+        .expect("Should have table data source");
+    assert!(table.items_accessor.is_none());
+    assert_eq!(table.arguments.len(), 1);
+    assert!(table.arguments[0].required);
+    assert!(table.attributes.iter().any(|a| a.name == "indexes"));
+
+    assert_eq!(service_def.data_sources.len(), 2);
+
+    println!("✅ Successfully extracted Smithy data sources!");
+}
+
+#[test]
+fn test_parse_smithy_with_crud_rules() {
+    let smithy_idl = r#"
+$version: "2.0"
+
+namespace com.example.storage
+
+service StorageService {
+    version: "2023-01-01"
+    operations: [CreateBucket, GetBucket, DeleteBucket, PutBucketPolicy, GetBucketPolicy]
+}
+
+operation CreateBucket {
+    input := {
+        @required
+        BucketName: String
+    }
+}
+
+@readonly
+operation GetBucket {
+    input := {
+        @required
+        BucketName: String
+    }
+}
+
+operation DeleteBucket {
+    input := {
+        @required
+        BucketName: String
+    }
+}
+
+operation PutBucketPolicy {
+    input := {
+        @required
+        BucketName: String
+        Policy: String
+    }
+}
+
+@readonly
+operation GetBucketPolicy {
+    input := {
+        @required
+        BucketName: String
+    }
+    output := {
+        Policy: String
+    }
+}
+"#;
+
+    // Built-in rules: AWS Put* operations create
+    let service_def = SmithyParser::from_idl(smithy_idl, "storage", "2023-01-01")
+        .unwrap()
+        .parse()
+        .unwrap();
+    let policy = service_def
+        .resources
+        .iter()
+        .find(|r| r.name == "bucket_policy")
+        .expect("Should have bucket_policy resource");
+    assert_eq!(
+        policy
+            .operations
+            .create
+            .as_ref()
+            .map(|op| op.sdk_operation.as_str()),
+        Some("put_bucket_policy")
+    );
+
+    let rules = CrudRules::from_yaml(
+        r#"
+rules:
+  - prefix: put_bucket_policy
+    role: update
+    providers: [aws]
+overrides:
+  DeleteBucket: ignore
+"#,
+    )
+    .unwrap();
+    let service_def = SmithyParser::from_idl(smithy_idl, "storage", "2023-01-01")
+        .unwrap()
+        .with_crud_rules(rules)
+        .parse()
+        .unwrap();
+
+    let policy = service_def
+        .resources
+        .iter()
+        .find(|r| r.name == "bucket_policy")
+        .expect("Should have bucket_policy resource");
+    assert!(policy.operations.create.is_none());
+    assert_eq!(
+        policy
+            .operations
+            .update
+            .as_ref()
+            .map(|op| op.sdk_operation.as_str()),
+        Some("put_bucket_policy")
+    );
+
+    // Ignored operations are left out of the resource
+    let bucket = service_def
+        .resources
+        .iter()
+        .find(|r| r.name == "bucket")
+        .expect("Should have bucket resource");
+    assert!(bucket.operations.create.is_some());
+    assert!(bucket.operations.read.is_some());
+    assert!(bucket.operations.delete.is_none());
+
+    // Rules are selected by the service's provider: Google `Put*` methods update
+    let service_def = SmithyParser::from_idl(smithy_idl, "storage", "2023-01-01")
+        .unwrap()
+        .with_provider(Provider::Gcp)
+        .parse()
+        .unwrap();
+    assert_eq!(service_def.provider, Provider::Gcp);
+    let policy = service_def
+        .resources
+        .iter()
+        .find(|r| r.name == "bucket_policy")
+        .expect("Should have bucket_policy resource");
+    assert!(policy.operations.create.is_none());
+    assert!(policy.operations.update.is_some());
+
+    println!("✅ Successfully classified Smithy operations with CRUD rules!");
+}
+
+#[test]
+fn test_parse_smithy_constraints_and_defaults() {
+    let smithy_idl = r#"
+$version: "2.0"
+
+namespace com.example.storage
+
+service StorageService {
+    version: "2023-01-01"
+    operations: [CreateBucket]
+}
+
+operation CreateBucket {
+    input := {
+        @required
+        BucketName: BucketName
+
+        @range(min: 1, max: 365)
+        RetentionDays: Integer = 7
+
+        @length(max: 10)
+        Tags: TagList
+
+        @pattern("^[A-Z]+$")
+        @default("EU")
+        Region: String
+    }
+    output := {
+        BucketArn: String
+    }
+}
+
+@length(min: 3, max: 63)
+@pattern("^[a-z0-9.-]+$")
+string BucketName
+
+list TagList {
+    member: String
+}
+"#;
+
+    let service_def = SmithyParser::from_idl(smithy_idl, "storage", "2023-01-01")
+        .unwrap()
+        .parse()
+        .unwrap();
+    let bucket = service_def
+        .resources
+        .iter()
+        .find(|r| r.name == "bucket")
+        .expect("Should have bucket resource");
+    let field = |name: &str| {
+        bucket
+            .fields
+            .iter()
+            .find(|f| f.name == name)
+            .unwrap_or_else(|| panic!("Should have {} field", name))
+    };
+
+    // Constraints on the target shape apply to members
+    let bucket_name = field("bucket_name");
+    assert_eq!(bucket_name.constraints.min_length, Some(3));
+    assert_eq!(bucket_name.constraints.max_length, Some(63));
+    assert_eq!(
+        bucket_name.constraints.pattern.as_deref(),
+        Some("^[a-z0-9.-]+$")
+    );
+
+    // Member traits and default values
+    let retention = field("retention_days");
+    assert_eq!(retention.constraints.minimum, Some(1.0));
+    assert_eq!(retention.constraints.maximum, Some(365.0));
+    assert_eq!(retention.default_value, Some(serde_json::json!(7)));
+
+    let tags = field("tags");
+    assert_eq!(tags.constraints.min_length, None);
+    assert_eq!(tags.constraints.max_length, Some(10));
+
+    let region = field("region");
+    assert_eq!(region.constraints.pattern.as_deref(), Some("^[A-Z]+$"));
+    assert_eq!(region.default_value, Some(serde_json::json!("EU")));
+
+    println!("✅ Successfully extracted Smithy constraints and defaults!");
+}
+
+#[test]
+fn test_parse_smithy_computed_and_write_only_fields() {
+    let smithy_idl = r#"
+$version: "2.0"
+
+namespace com.example.database
+
+service DatabaseService {
+    version: "2023-01-01"
+    operations: [CreateDatabase, GetDatabase]
+}
+
+operation CreateDatabase {
+    input := {
+        @required
+        DatabaseName: String
+
+        StorageClass: String
+
+        @sensitive
+        MasterPassword: String
+
+        Description: String
+    }
+}
+
+@readonly
+operation GetDatabase {
+    input := {
+        @required
+        DatabaseName: String
+    }
+    output := {
+        DatabaseName: String
+        StorageClass: String
+        Endpoint: String
+    }
+}
+"#;
+
+    let service_def = SmithyParser::from_idl(smithy_idl, "database", "2023-01-01")
+        .unwrap()
+        .parse()
+        .unwrap();
+    let database = service_def
+        .resources
+        .iter()
+        .find(|r| r.name == "database")
+        .expect("Should have database resource");
+    let field = |name: &str| {
+        database
+            .fields
+            .iter()
+            .find(|f| f.name == name)
+            .unwrap_or_else(|| panic!("Should have {} field", name))
+    };
+
+    // Optional inputs the read operation returns are computed when not set
+    assert!(field("storage_class").is_optional_computed());
+    assert!(!field("database_name").computed);
+
+    // Sensitive inputs the read operation never returns are write-only
+    let password = field("master_password");
+    assert!(password.write_only && !password.computed);
+
+    // Other inputs are neither
+    let description = field("description");
+    assert!(!description.computed && !description.write_only);
+
+    println!("✅ Successfully inferred computed and write-only Smithy fields!");
+}
+
+#[test]
+fn test_parse_smithy_deprecation_and_stability() {
+    let smithy_idl = r#"
+$version: "2.0"
+
+namespace com.example.queue
+
+service QueueService {
+    version: "2023-01-01"
+    operations: [CreateQueue, GetQueue, UpdateQueue]
+}
+
+@unstable
+@deprecated(message: "Use CreateTopic instead", since: "2024-01-01")
+operation CreateQueue {
+    input := {
+        @required
+        QueueName: String
+
+        @deprecated
+        Visibility: Integer
+
+        RetentionDays: Integer
+    }
+}
+
+@readonly
+operation GetQueue {
+    input := {
+        @required
+        QueueName: String
+    }
+    output := {
+        QueueName: String
+        RetentionDays: Integer
+    }
+}
+
+operation UpdateQueue {
+    input := {
+        @required
+        QueueName: String
+    }
+}
+"#;
+
+    let service_def = SmithyParser::from_idl(smithy_idl, "queue", "2023-01-01")
+        .unwrap()
+        .parse()
+        .unwrap();
+    let queue = service_def
+        .resources
+        .iter()
+        .find(|r| r.name == "queue")
+        .expect("Should have queue resource");
+
+    // A deprecated create operation deprecates the resource
+    let create = queue.operations.create.as_ref().unwrap();
+    assert_eq!(
+        create.deprecated.as_ref().unwrap().message.as_deref(),
+        Some("Use CreateTopic instead")
+    );
+    assert_eq!(queue.deprecated, create.deprecated);
+    assert!(queue
+        .operations
+        .update
+        .as_ref()
+        .unwrap()
+        .deprecated
+        .is_none());
+    assert_eq!(queue.stability, Stability::Beta);
+
+    // Deprecated members without a message
+    let field = |name: &str| queue.fields.iter().find(|f| f.name == name).unwrap();
+    assert_eq!(
+        field("visibility").deprecated,
+        Some(Deprecation { message: None })
+    );
+    assert!(field("retention_days").deprecated.is_none());
+
+    println!("✅ Successfully parsed Smithy deprecation and stability!");
+}